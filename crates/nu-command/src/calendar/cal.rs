@@ -324,10 +324,7 @@ fn add_month_to_table(
 
         if should_show_month_column || should_show_month_names {
             let month_value = if should_show_month_names {
-                Value::String {
-                    val: month_helper.month_name.clone(),
-                    span: tag,
-                }
+                Value::string(month_helper.month_name.clone(), tag)
             } else {
                 Value::Int {
                     val: month_helper.selected_month as i64,
@@ -365,18 +362,7 @@ fn add_month_to_table(
             day_number += 1;
         }
 
-        let cols: Vec<String> = indexmap.keys().map(|f| f.to_string()).collect();
-        let mut vals: Vec<Value> = Vec::new();
-        for c in &cols {
-            if let Some(x) = indexmap.get(c) {
-                vals.push(x.to_owned())
-            }
-        }
-        calendar_vec_deque.push_back(Value::Record {
-            cols,
-            vals,
-            span: tag,
-        })
+        calendar_vec_deque.push_back(Value::record(indexmap.into_iter().collect(), tag))
     }
 
     Ok(())
@@ -2,7 +2,7 @@ use nu_engine::CallExt;
 use nu_protocol::{
     ast::{Call, CellPath},
     engine::{Command, EngineState, Stack},
-    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Value,
+    record, Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Value,
 };
 
 #[derive(Clone)]
@@ -45,32 +45,13 @@ impl Command for SubCommand {
                 example: "echo [[value]; ['false'] ['1'] [0] [1.0] [$true]] | into bool value",
                 result: Some(Value::List {
                     vals: vec![
-                        Value::Record {
-                            cols: vec!["value".to_string()],
-                            vals: vec![Value::boolean(false, span)],
-                            span,
-                        },
-                        Value::Record {
-                            cols: vec!["value".to_string()],
-                            vals: vec![Value::boolean(true, span)],
-                            span,
-                        },
-                        Value::Record {
-                            cols: vec!["value".to_string()],
-                            vals: vec![Value::boolean(false, span)],
-                            span,
-                        },
-                        Value::Record {
-                            cols: vec!["value".to_string()],
-                            vals: vec![Value::boolean(true, span)],
-                            span,
-                        },
-                        Value::Record {
-                            cols: vec!["value".to_string()],
-                            vals: vec![Value::boolean(true, span)],
-                            span,
-                        },
-                    ],
+                        Value::record(record! { "value" => Value::boolean(false, span) }, span),
+                        Value::record(record! { "value" => Value::boolean(true, span) }, span),
+                        Value::record(record! { "value" => Value::boolean(false, span) }, span),
+                        Value::record(record! { "value" => Value::boolean(true, span) }, span),
+                        Value::record(record! { "value" => Value::boolean(true, span) }, span),
+                    ]
+                    .into(),
                     span,
                 }),
             },
@@ -28,10 +28,10 @@ impl Command for Into {
         call: &Call,
         _input: PipelineData,
     ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
-        Ok(Value::String {
-            val: get_full_help(&Into.signature(), &[], engine_state),
-            span: call.head,
-        }
+        Ok(Value::string(
+            get_full_help(&Into.signature(), &[], engine_state),
+            call.head,
+        )
         .into_pipeline_data())
     }
 }
@@ -41,11 +41,11 @@ impl Command for SubCommand {
                 description: "Convert string to integer in table",
                 example: "[[num]; ['5.01']] | into decimal num",
                 result: Some(Value::List {
-                    vals: vec![Value::Record {
-                        cols: vec!["num".to_string()],
-                        vals: vec![Value::test_float(5.01)],
-                        span: Span::test_data(),
-                    }],
+                    vals: vec![Value::test_record(
+                        vec!["num"],
+                        vec![Value::test_float(5.01)],
+                    )]
+                    .into(),
                     span: Span::test_data(),
                 }),
             },
@@ -76,10 +76,10 @@ impl Command for SubCommand {
             Example {
                 description: "Convert bool to integer",
                 example: "[$false, $true] | into int",
-                result: Some(Value::List {
-                    vals: vec![Value::test_int(0), Value::test_int(1)],
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::list(
+                    vec![Value::test_int(0), Value::test_int(1)],
+                    Span::test_data(),
+                )),
             },
             Example {
                 description: "Convert to integer from binary",
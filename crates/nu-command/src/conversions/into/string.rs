@@ -2,7 +2,8 @@ use nu_engine::CallExt;
 use nu_protocol::{
     ast::{Call, CellPath},
     engine::{Command, EngineState, Stack},
-    Category, Config, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Value,
+    Category, Config, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span,
+    SyntaxShape, Value,
 };
 
 // TODO num_format::SystemLocale once platform-specific dependencies are stable (see Cargo.toml)
@@ -51,26 +52,17 @@ impl Command for SubCommand {
             Example {
                 description: "convert decimal to string and round to nearest integer",
                 example: "1.7 | into string -d 0",
-                result: Some(Value::String {
-                    val: "2".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("2", Span::test_data())),
             },
             Example {
                 description: "convert decimal to string",
                 example: "1.7 | into string -d 1",
-                result: Some(Value::String {
-                    val: "1.7".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("1.7", Span::test_data())),
             },
             Example {
                 description: "convert decimal to string and limit to 2 decimals",
                 example: "1.734 | into string -d 2",
-                result: Some(Value::String {
-                    val: "1.73".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("1.73", Span::test_data())),
             },
             Example {
                 description: "try to convert decimal to string and provide negative decimal points",
@@ -87,26 +79,17 @@ impl Command for SubCommand {
             Example {
                 description: "convert decimal to string",
                 example: "4.3 | into string",
-                result: Some(Value::String {
-                    val: "4.3".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("4.3", Span::test_data())),
             },
             Example {
                 description: "convert string to string",
                 example: "'1234' | into string",
-                result: Some(Value::String {
-                    val: "1234".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("1234", Span::test_data())),
             },
             Example {
                 description: "convert boolean to string",
                 example: "$true | into string",
-                result: Some(Value::String {
-                    val: "true".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("true", Span::test_data())),
             },
             Example {
                 description: "convert date to string",
@@ -148,6 +131,12 @@ fn string_helper(
         }
     }
 
+    // The raw output of an external is read as text in one go, so characters split between its
+    // chunks come out whole
+    if let PipelineData::RawStream(stream, ..) = input {
+        return Ok(Value::string(stream.into_string()?, head).into_pipeline_data());
+    }
+
     input.map(
         move |v| {
             if column_paths.is_empty() {
@@ -190,48 +179,25 @@ pub fn action(
                 val.to_string()
             };
 
-            Value::String { val: res, span }
+            Value::string(res, span)
         }
         Value::Float { val, .. } => {
             if decimals {
                 let decimal_value = digits.unwrap_or(2) as usize;
-                Value::String {
-                    val: format!("{:.*}", decimal_value, val),
-                    span,
-                }
+                Value::string(format!("{:.*}", decimal_value, val), span)
             } else {
-                Value::String {
-                    val: val.to_string(),
-                    span,
-                }
+                Value::string(val.to_string(), span)
             }
         }
-        Value::Bool { val, .. } => Value::String {
-            val: val.to_string(),
-            span,
-        },
-        Value::Date { val, .. } => Value::String {
-            val: val.format("%c").to_string(),
-            span,
-        },
-        Value::String { val, .. } => Value::String {
-            val: val.to_string(),
-            span,
-        },
+        Value::Bool { val, .. } => Value::string(val.to_string(), span),
+        Value::Date { val, .. } => Value::string(val.format("%c").to_string(), span),
+        Value::String { val, .. } => Value::string(val.to_string(), span),
 
-        Value::Filesize { val: _, .. } => Value::String {
-            val: input.clone().into_string(", ", config),
-            span,
-        },
-        Value::Nothing { .. } => Value::String {
-            val: "nothing".to_string(),
-            span,
-        },
-        Value::Record {
-            cols: _,
-            vals: _,
-            span: _,
-        } => Value::Error {
+        Value::Filesize { val: _, .. } => {
+            Value::string(input.clone().into_string(", ", config), span)
+        }
+        Value::Nothing { .. } => Value::string("nothing", span),
+        Value::Record { val: _, span: _ } => Value::Error {
             error: ShellError::UnsupportedInput(
                 "Cannot convert Record into string".to_string(),
                 span,
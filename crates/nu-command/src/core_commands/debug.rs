@@ -36,15 +36,9 @@ impl Command for Debug {
         input.map(
             move |x| {
                 if raw {
-                    Value::String {
-                        val: x.debug_value(),
-                        span: head,
-                    }
+                    Value::string(x.debug_value(), head)
                 } else {
-                    Value::String {
-                        val: x.debug_string(", ", &config),
-                        span: head,
-                    }
+                    Value::string(x.debug_string(", ", &config), head)
                 }
             },
             engine_state.ctrlc.clone(),
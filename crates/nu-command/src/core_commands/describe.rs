@@ -27,10 +27,7 @@ impl Command for Describe {
     ) -> Result<PipelineData, ShellError> {
         let head = call.head;
         input.map(
-            move |x| Value::String {
-                val: x.get_type().to_string(),
-                span: head,
-            },
+            move |x| Value::string(x.get_type().to_string(), head),
             engine_state.ctrlc.clone(),
         )
     }
@@ -0,0 +1,141 @@
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, PipelineData, Record, ShellError, Signature, Span, SyntaxShape, Value,
+};
+
+#[derive(Clone)]
+pub struct ErrorMake;
+
+impl Command for ErrorMake {
+    fn name(&self) -> &str {
+        "error make"
+    }
+
+    fn usage(&self) -> &str {
+        "Fail with an error made from a record"
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"The record has the message of the error in msg, and can have a label and a help text. The label points at the span from start to end, or at the record given to error make when they are left out."#
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("error make")
+            .required(
+                "error_struct",
+                SyntaxShape::Any,
+                "the error to make, as {msg: ..., label: {text: ..., start: ..., end: ...}, help: ...}",
+            )
+            .category(Category::Core)
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let arg: Value = call.req(engine_state, stack, 0)?;
+        let arg_span = call.positional[0].span;
+
+        Err(make_error(&arg, arg_span)?)
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Fail with a custom error",
+                example: r#"error make {msg: "my custom error"}"#,
+                result: None,
+            },
+            Example {
+                description: "Point at what caused the error, and explain how to fix it",
+                example: r#"try { [] | first } catch { |err| error make {msg: "no first item", label: {text: "the list was empty here", start: $err.span.start, end: $err.span.end}, help: "check that the list has items"} }"#,
+                result: None,
+            },
+        ]
+    }
+}
+
+// The error the record describes, or the one explaining what's wrong with the record
+fn make_error(arg: &Value, arg_span: Span) -> Result<ShellError, ShellError> {
+    let record = match arg {
+        Value::Record { val, .. } => val,
+        other => {
+            return Err(ShellError::UnsupportedInput(
+                format!(
+                    "expected a record with the error, found {}",
+                    other.get_type()
+                ),
+                arg_span,
+            ))
+        }
+    };
+
+    let msg = match record.get("msg") {
+        Some(msg) => msg.as_string()?,
+        None => {
+            return Err(ShellError::MissingParameter(
+                "msg in the error record".into(),
+                arg_span,
+            ))
+        }
+    };
+    let help = record.get("help").map(Value::as_string).transpose()?;
+
+    let (text, span) = match record.get("label") {
+        Some(Value::Record { val: label, .. }) => label_of(label, arg_span)?,
+        Some(other) => {
+            return Err(ShellError::UnsupportedInput(
+                format!(
+                    "expected the label to be a record, found {}",
+                    other.get_type()
+                ),
+                other.span()?,
+            ))
+        }
+        None => ("originates from here".to_string(), arg_span),
+    };
+
+    Ok(match help {
+        Some(help) => ShellError::SpannedLabeledErrorHelp(msg, text, span, help),
+        None => ShellError::SpannedLabeledError(msg, text, span),
+    })
+}
+
+fn label_of(label: &Record, arg_span: Span) -> Result<(String, Span), ShellError> {
+    let text = match label.get("text") {
+        Some(text) => text.as_string()?,
+        None => {
+            return Err(ShellError::MissingParameter(
+                "text in the label record".into(),
+                arg_span,
+            ))
+        }
+    };
+
+    let span = match (label.get("start"), label.get("end")) {
+        (Some(start), Some(end)) => {
+            let (start, end) = (start.as_integer()?, end.as_integer()?);
+            if start < 0 || end < start {
+                return Err(ShellError::UnsupportedInput(
+                    "the label's start and end should be offsets, with start before end".into(),
+                    arg_span,
+                ));
+            }
+            Span::new(start as usize, end as usize)
+        }
+        (None, None) => arg_span,
+        _ => {
+            return Err(ShellError::MissingParameter(
+                "both start and end in the label record".into(),
+                arg_span,
+            ))
+        }
+    };
+
+    Ok((text, span))
+}
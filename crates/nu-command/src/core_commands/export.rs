@@ -33,7 +33,8 @@ impl Command for ExportCommand {
                 &ExportCommand.signature(),
                 &ExportCommand.examples(),
                 engine_state,
-            ),
+            )
+            .into(),
             span: call.head,
         }
         .into_pipeline_data())
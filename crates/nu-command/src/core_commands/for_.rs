@@ -109,7 +109,8 @@ impl Command for For {
                         Value::Int { val: 1, span },
                         Value::Int { val: 4, span },
                         Value::Int { val: 9, span },
-                    ],
+                    ]
+                    .into(),
                     span,
                 }),
             },
@@ -121,7 +122,8 @@ impl Command for For {
                         Value::Int { val: 1, span },
                         Value::Int { val: 2, span },
                         Value::Int { val: 3, span },
-                    ],
+                    ]
+                    .into(),
                     span,
                 }),
             },
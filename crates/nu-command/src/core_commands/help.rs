@@ -1,7 +1,7 @@
 use nu_protocol::{
     ast::Call,
     engine::{Command, EngineState, Stack},
-    span, Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData,
+    span, Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, Record,
     ShellError, Signature, Spanned, SyntaxShape, Value,
 };
 
@@ -92,8 +92,7 @@ fn help(
         let mut found_cmds_vec = Vec::new();
 
         for (sig, _, is_plugin, is_custom) in full_commands {
-            let mut cols = vec![];
-            let mut vals = vec![];
+            let mut record = Record::new();
 
             let key = sig.name.clone();
             let c = sig.usage.clone();
@@ -102,41 +101,31 @@ fn help(
                 || c.to_lowercase().contains(&search_string)
                 || e.to_lowercase().contains(&search_string)
             {
-                cols.push("name".into());
-                vals.push(Value::String {
-                    val: key,
-                    span: head,
-                });
-
-                cols.push("category".into());
-                vals.push(Value::String {
-                    val: sig.category.to_string(),
-                    span: head,
-                });
-
-                cols.push("is_plugin".into());
-                vals.push(Value::Bool {
-                    val: is_plugin,
-                    span: head,
-                });
-
-                cols.push("is_custom".into());
-                vals.push(Value::Bool {
-                    val: is_custom,
-                    span: head,
-                });
-
-                cols.push("usage".into());
-                vals.push(Value::String { val: c, span: head });
-
-                cols.push("extra_usage".into());
-                vals.push(Value::String { val: e, span: head });
-
-                found_cmds_vec.push(Value::Record {
-                    cols,
-                    vals,
-                    span: head,
-                });
+                record.insert("name", Value::string(key, head));
+
+                record.insert("category", Value::string(sig.category.to_string(), head));
+
+                record.insert(
+                    "is_plugin",
+                    Value::Bool {
+                        val: is_plugin,
+                        span: head,
+                    },
+                );
+
+                record.insert(
+                    "is_custom",
+                    Value::Bool {
+                        val: is_custom,
+                        span: head,
+                    },
+                );
+
+                record.insert("usage", Value::string(c, head));
+
+                record.insert("extra_usage", Value::string(e, head));
+
+                found_cmds_vec.push(Value::record(record, head));
             }
         }
 
@@ -150,48 +139,37 @@ fn help(
 
         if rest[0].item == "commands" {
             for (sig, _, is_plugin, is_custom) in full_commands {
-                let mut cols = vec![];
-                let mut vals = vec![];
+                let mut record = Record::new();
 
                 let key = sig.name.clone();
                 let c = sig.usage.clone();
                 let e = sig.extra_usage.clone();
 
-                cols.push("name".into());
-                vals.push(Value::String {
-                    val: key,
-                    span: head,
-                });
-
-                cols.push("category".into());
-                vals.push(Value::String {
-                    val: sig.category.to_string(),
-                    span: head,
-                });
-
-                cols.push("is_plugin".into());
-                vals.push(Value::Bool {
-                    val: is_plugin,
-                    span: head,
-                });
-
-                cols.push("is_custom".into());
-                vals.push(Value::Bool {
-                    val: is_custom,
-                    span: head,
-                });
-
-                cols.push("usage".into());
-                vals.push(Value::String { val: c, span: head });
-
-                cols.push("extra_usage".into());
-                vals.push(Value::String { val: e, span: head });
-
-                found_cmds_vec.push(Value::Record {
-                    cols,
-                    vals,
-                    span: head,
-                });
+                record.insert("name", Value::string(key, head));
+
+                record.insert("category", Value::string(sig.category.to_string(), head));
+
+                record.insert(
+                    "is_plugin",
+                    Value::Bool {
+                        val: is_plugin,
+                        span: head,
+                    },
+                );
+
+                record.insert(
+                    "is_custom",
+                    Value::Bool {
+                        val: is_custom,
+                        span: head,
+                    },
+                );
+
+                record.insert("usage", Value::string(c, head));
+
+                record.insert("extra_usage", Value::string(e, head));
+
+                found_cmds_vec.push(Value::record(record, head));
             }
 
             Ok(found_cmds_vec
@@ -214,11 +192,10 @@ fn help(
                 .collect::<Vec<String>>();
 
             if !output.is_empty() {
-                Ok(Value::String {
-                    val: output.join("======================\n\n"),
-                    span: call.head,
-                }
-                .into_pipeline_data())
+                Ok(
+                    Value::string(output.join("======================\n\n"), call.head)
+                        .into_pipeline_data(),
+                )
             } else {
                 Err(ShellError::CommandNotFound(span(&[
                     rest[0].span,
@@ -393,11 +370,7 @@ Get the processes on your system actively using CPU:
 
 You can also learn more at https://www.nushell.sh/book/"#;
 
-        Ok(Value::String {
-            val: msg.into(),
-            span: head,
-        }
-        .into_pipeline_data())
+        Ok(Value::string(msg, head).into_pipeline_data())
     }
 }
 
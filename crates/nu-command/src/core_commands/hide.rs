@@ -24,6 +24,10 @@ impl Command for Hide {
         "If there is a definition and an environment variable with the same name in the current scope, first the definition will be hidden, then the environment variable."
     }
 
+    fn changes_env(&self) -> bool {
+        true
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
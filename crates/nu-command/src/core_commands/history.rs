@@ -1,5 +1,6 @@
+use nu_engine::check_path;
 use nu_protocol::ast::Call;
-use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::engine::{Capability, Command, EngineState, Stack};
 use nu_protocol::{
     Category, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, Value,
 };
@@ -22,6 +23,10 @@ impl Command for History {
             .category(Category::Core)
     }
 
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::FilesystemRead, Capability::FilesystemWrite]
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -38,6 +43,20 @@ impl Command for History {
             history_path.push("nushell");
             history_path.push("history.txt");
 
+            // The history file isn't a path argument the engine checks before the command runs
+            let capability = if clear {
+                Capability::FilesystemWrite
+            } else {
+                Capability::FilesystemRead
+            };
+            check_path(
+                &engine_state.permissions,
+                self.name(),
+                capability,
+                &history_path.to_string_lossy(),
+                head,
+            )?;
+
             if clear {
                 let _ = std::fs::remove_file(history_path);
                 Ok(PipelineData::new(head))
@@ -47,10 +66,7 @@ impl Command for History {
                 if let Ok(contents) = contents {
                     Ok(contents
                         .lines()
-                        .map(move |x| Value::String {
-                            val: x.to_string(),
-                            span: head,
-                        })
+                        .map(move |x| Value::string(x.to_string(), head))
                         .collect::<Vec<_>>()
                         .into_iter()
                         .into_pipeline_data(ctrlc))
@@ -4,6 +4,7 @@ mod def;
 mod describe;
 mod do_;
 mod echo;
+mod error_make;
 mod export;
 mod export_def;
 mod export_env;
@@ -15,6 +16,7 @@ mod if_;
 mod let_;
 mod module;
 mod source;
+mod try_;
 mod use_;
 mod version;
 
@@ -24,6 +26,7 @@ pub use def::Def;
 pub use describe::Describe;
 pub use do_::Do;
 pub use echo::Echo;
+pub use error_make::ErrorMake;
 pub use export::ExportCommand;
 pub use export_def::ExportDef;
 pub use export_env::ExportEnv;
@@ -35,6 +38,7 @@ pub use if_::If;
 pub use let_::Let;
 pub use module::Module;
 pub use source::Source;
+pub use try_::Try;
 pub use use_::Use;
 pub use version::Version;
 #[cfg(feature = "plugin")]
@@ -1,5 +1,5 @@
 use nu_protocol::ast::Call;
-use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::engine::{Capability, Command, EngineState, Stack};
 use nu_protocol::{Category, PipelineData, Signature, SyntaxShape};
 
 #[derive(Clone)]
@@ -41,6 +41,10 @@ impl Command for Register {
             .category(Category::Core)
     }
 
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::Plugins]
+    }
+
     fn run(
         &self,
         _engine_state: &EngineState,
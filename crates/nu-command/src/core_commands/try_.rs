@@ -0,0 +1,195 @@
+use nu_engine::eval_block;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    BlockId, Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span,
+    SyntaxShape, Value, ValueStream,
+};
+
+#[derive(Clone)]
+pub struct Try;
+
+impl Command for Try {
+    fn name(&self) -> &str {
+        "try"
+    }
+
+    fn usage(&self) -> &str {
+        "Run a block, and another one if it fails"
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"The catch block is given the error as a record, with its message in msg, which error it is in code (like nu::shell::division_by_zero, also for an error raised inside a custom command), its labels in labels, the span of the first label in span, its help text in help, and the custom command calls it came out of in trace. Without a catch block, the error is ignored. When the block streams its output, an error in the stream is caught once it is reached, after the items before it have gone on, and the output of the catch block takes the place of the rest of the stream."#
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("try")
+            .required(
+                "try_block",
+                SyntaxShape::Block(Some(vec![])),
+                "the block to run",
+            )
+            .optional(
+                "catch_block",
+                SyntaxShape::Keyword(
+                    b"catch".to_vec(),
+                    Box::new(SyntaxShape::Block(Some(vec![SyntaxShape::Any]))),
+                ),
+                "the block to run with the error if the first one fails",
+            )
+            .category(Category::Core)
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let try_block = call.positional[0]
+            .as_block()
+            .expect("internal error: expected block");
+        let catch_block = call.positional.get(1).map(|catch_block| {
+            catch_block
+                .as_keyword()
+                .and_then(|block| block.as_block())
+                .expect("internal error: expected block")
+        });
+        let block = engine_state.get_block(try_block);
+        let mut try_stack = stack.collect_captures(&block.captures);
+
+        match eval_block(engine_state, &mut try_stack, block, input) {
+            Err(error) | Ok(PipelineData::Value(Value::Error { error }, ..)) => {
+                catch(engine_state, stack, catch_block, error, call.head)
+            }
+            // An error among the items of a stream is caught once the stream gets to it, so the
+            // stream isn't held up. The items before the error have gone on by then
+            Ok(PipelineData::Stream(stream, metadata)) => {
+                let ctrlc = stream.ctrlc.clone();
+                let catch = catch_block.map(|catch_block| Catch {
+                    engine_state: engine_state.clone(),
+                    stack: stack.collect_captures(&engine_state.get_block(catch_block).captures),
+                    catch_block,
+                    head: call.head,
+                });
+                let catching = Catching {
+                    stream,
+                    catch,
+                    rest: None,
+                };
+
+                Ok(PipelineData::Stream(
+                    ValueStream::from_stream(catching, ctrlc),
+                    metadata,
+                ))
+            }
+            Ok(output) => Ok(output),
+        }
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Ignore an error",
+                example: "try { 1 / 0 }",
+                result: None,
+            },
+            Example {
+                description: "Handle an error with its message",
+                example: "try { 1 / 0 } catch { |err| $err.msg }",
+                result: Some(Value::test_string("Division by zero.")),
+            },
+        ]
+    }
+}
+
+// Run the catch block with the error, or ignore the error without one
+fn catch(
+    engine_state: &EngineState,
+    stack: &Stack,
+    catch_block: Option<BlockId>,
+    error: ShellError,
+    head: Span,
+) -> Result<PipelineData, ShellError> {
+    let catch_block = match catch_block {
+        Some(catch_block) => catch_block,
+        None => return Ok(PipelineData::new(head)),
+    };
+    let block = engine_state.get_block(catch_block);
+    let mut catch_stack = stack.collect_captures(&block.captures);
+
+    let error = error.into_value(head);
+    if let Some(var_id) = block
+        .signature
+        .get_positional(0)
+        .and_then(|param| param.var_id)
+    {
+        catch_stack.add_var(var_id, error.clone());
+    }
+
+    eval_block(
+        engine_state,
+        &mut catch_stack,
+        block,
+        error.into_pipeline_data(),
+    )
+}
+
+// What a stream needs to run the catch block once it gets to an error
+struct Catch {
+    engine_state: EngineState,
+    stack: Stack,
+    catch_block: BlockId,
+    head: Span,
+}
+
+// The items of a stream up to the first error among them. The output of the catch block takes the
+// place of the error and of the items after it
+struct Catching {
+    stream: ValueStream,
+    catch: Option<Catch>,
+    rest: Option<Box<dyn Iterator<Item = Value> + Send>>,
+}
+
+impl Iterator for Catching {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Value> {
+        if let Some(rest) = &mut self.rest {
+            return rest.next();
+        }
+
+        match self.stream.next()? {
+            Value::Error { error } => {
+                let rest: Box<dyn Iterator<Item = Value> + Send> = match self.catch.take() {
+                    Some(Catch {
+                        engine_state,
+                        stack,
+                        catch_block,
+                        head,
+                    }) => match catch(&engine_state, &stack, Some(catch_block), error, head) {
+                        Ok(output) => Box::new(output.into_iter()),
+                        Err(error) => Box::new(std::iter::once(Value::Error { error })),
+                    },
+                    None => Box::new(std::iter::empty()),
+                };
+
+                self.rest.insert(rest).next()
+            }
+            value => Some(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Try {})
+    }
+}
@@ -21,6 +21,10 @@ impl Command for Use {
             .category(Category::Core)
     }
 
+    fn changes_env(&self) -> bool {
+        true
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
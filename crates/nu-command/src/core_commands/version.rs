@@ -54,20 +54,14 @@ pub fn version(
 
     indexmap.insert(
         "version".to_string(),
-        Value::String {
-            val: env!("CARGO_PKG_VERSION").to_string(),
-            span: tag,
-        },
+        Value::string(env!("CARGO_PKG_VERSION").to_string(), tag),
     );
 
     let branch: Option<&str> = Some(shadow::BRANCH).filter(|x| !x.is_empty());
     if let Some(branch) = branch {
         indexmap.insert(
             "branch".to_string(),
-            Value::String {
-                val: branch.to_string(),
-                span: call.head,
-            },
+            Value::string(branch.to_string(), call.head),
         );
     }
 
@@ -75,30 +69,21 @@ pub fn version(
     if let Some(short_commit) = short_commit {
         indexmap.insert(
             "short_commit".to_string(),
-            Value::String {
-                val: short_commit.to_string(),
-                span: call.head,
-            },
+            Value::string(short_commit.to_string(), call.head),
         );
     }
     let commit_hash: Option<&str> = Some(shadow::COMMIT_HASH).filter(|x| !x.is_empty());
     if let Some(commit_hash) = commit_hash {
         indexmap.insert(
             "commit_hash".to_string(),
-            Value::String {
-                val: commit_hash.to_string(),
-                span: call.head,
-            },
+            Value::string(commit_hash.to_string(), call.head),
         );
     }
     let commit_date: Option<&str> = Some(shadow::COMMIT_DATE).filter(|x| !x.is_empty());
     if let Some(commit_date) = commit_date {
         indexmap.insert(
             "commit_date".to_string(),
-            Value::String {
-                val: commit_date.to_string(),
-                span: call.head,
-            },
+            Value::string(commit_date.to_string(), call.head),
         );
     }
 
@@ -106,10 +91,7 @@ pub fn version(
     if let Some(build_os) = build_os {
         indexmap.insert(
             "build_os".to_string(),
-            Value::String {
-                val: build_os.to_string(),
-                span: call.head,
-            },
+            Value::string(build_os.to_string(), call.head),
         );
     }
 
@@ -117,10 +99,7 @@ pub fn version(
     if let Some(rust_version) = rust_version {
         indexmap.insert(
             "rust_version".to_string(),
-            Value::String {
-                val: rust_version.to_string(),
-                span: call.head,
-            },
+            Value::string(rust_version.to_string(), call.head),
         );
     }
 
@@ -128,10 +107,7 @@ pub fn version(
     if let Some(rust_channel) = rust_channel {
         indexmap.insert(
             "rust_channel".to_string(),
-            Value::String {
-                val: rust_channel.to_string(),
-                span: call.head,
-            },
+            Value::string(rust_channel.to_string(), call.head),
         );
     }
 
@@ -139,10 +115,7 @@ pub fn version(
     if let Some(cargo_version) = cargo_version {
         indexmap.insert(
             "cargo_version".to_string(),
-            Value::String {
-                val: cargo_version.to_string(),
-                span: call.head,
-            },
+            Value::string(cargo_version.to_string(), call.head),
         );
     }
 
@@ -150,10 +123,7 @@ pub fn version(
     if let Some(pkg_version) = pkg_version {
         indexmap.insert(
             "pkg_version".to_string(),
-            Value::String {
-                val: pkg_version.to_string(),
-                span: call.head,
-            },
+            Value::string(pkg_version.to_string(), call.head),
         );
     }
 
@@ -161,10 +131,7 @@ pub fn version(
     if let Some(build_time) = build_time {
         indexmap.insert(
             "build_time".to_string(),
-            Value::String {
-                val: build_time.to_string(),
-                span: call.head,
-            },
+            Value::string(build_time.to_string(), call.head),
         );
     }
 
@@ -173,19 +140,13 @@ pub fn version(
     if let Some(build_rust_channel) = build_rust_channel {
         indexmap.insert(
             "build_rust_channel".to_string(),
-            Value::String {
-                val: build_rust_channel.to_string(),
-                span: call.head,
-            },
+            Value::string(build_rust_channel.to_string(), call.head),
         );
     }
 
     indexmap.insert(
         "features".to_string(),
-        Value::String {
-            val: features_enabled().join(", "),
-            span: call.head,
-        },
+        Value::string(features_enabled().join(", "), call.head),
     );
 
     // Get a list of command names and check for plugins
@@ -198,32 +159,11 @@ pub fn version(
 
     indexmap.insert(
         "installed_plugins".to_string(),
-        Value::String {
-            val: installed_plugins.join(", "),
-            span: call.head,
-        },
+        Value::string(installed_plugins.join(", "), call.head),
     );
 
-    let cols = indexmap.keys().cloned().collect::<Vec<_>>();
-    let vals = indexmap.values().cloned().collect::<Vec<_>>();
-
-    // Ok(Value::List {
-    //     vals: vec![Value::Record {
-    //         cols,
-    //         vals,
-    //         span: call.head,
-    //     }],
-    //     span: call.head,
-    // }
-    // .into_pipeline_data())
-
     // List looks better than table, imo
-    Ok(Value::Record {
-        cols,
-        vals,
-        span: call.head,
-    }
-    .into_pipeline_data())
+    Ok(Value::record(indexmap.into_iter().collect(), call.head).into_pipeline_data())
 }
 
 fn features_enabled() -> Vec<String> {
@@ -28,10 +28,10 @@ impl Command for Dataframe {
         call: &Call,
         _input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        Ok(Value::String {
-            val: get_full_help(&Dataframe.signature(), &Dataframe.examples(), engine_state),
-            span: call.head,
-        }
+        Ok(Value::string(
+            get_full_help(&Dataframe.signature(), &Dataframe.examples(), engine_state),
+            call.head,
+        )
         .into_pipeline_data())
     }
 }
@@ -75,15 +75,9 @@ fn command(
                 .dtype();
 
             let dtype_str = dtype.to_string();
-            dtypes.push(Value::String {
-                val: dtype_str,
-                span: call.head,
-            });
+            dtypes.push(Value::string(dtype_str, call.head));
 
-            Value::String {
-                val: v.to_string(),
-                span: call.head,
-            }
+            Value::string(v.to_string(), call.head)
         })
         .collect();
 
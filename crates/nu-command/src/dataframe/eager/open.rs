@@ -2,7 +2,7 @@ use super::super::values::NuDataFrame;
 use nu_engine::CallExt;
 use nu_protocol::{
     ast::Call,
-    engine::{Command, EngineState, Stack},
+    engine::{Capability, Command, EngineState, Stack},
     Category, Example, PipelineData, ShellError, Signature, Spanned, SyntaxShape,
 };
 use std::{fs::File, path::PathBuf};
@@ -68,6 +68,10 @@ impl Command for OpenDataFrame {
         }]
     }
 
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::FilesystemRead]
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
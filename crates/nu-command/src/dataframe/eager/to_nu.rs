@@ -74,10 +74,7 @@ fn command(
         df.head(rows, call.head)?
     };
 
-    let value = Value::List {
-        vals: values,
-        span: call.head,
-    };
+    let value = Value::list(values, call.head);
 
     Ok(PipelineData::Value(value, None))
 }
@@ -3,7 +3,7 @@ use std::{fs::File, path::PathBuf};
 use nu_engine::CallExt;
 use nu_protocol::{
     ast::Call,
-    engine::{Command, EngineState, Stack},
+    engine::{Capability, Command, EngineState, Stack},
     Category, Example, PipelineData, ShellError, Signature, Spanned, SyntaxShape, Value,
 };
 use polars::prelude::ParquetWriter;
@@ -36,6 +36,10 @@ impl Command for ToParquet {
         }]
     }
 
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::FilesystemWrite]
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -69,16 +73,10 @@ fn command(
         ShellError::SpannedLabeledError("Error saving file".into(), e.to_string(), file_name.span)
     })?;
 
-    let file_value = Value::String {
-        val: format!("saved {:?}", &file_name.item),
-        span: file_name.span,
-    };
+    let file_value = Value::string(format!("saved {:?}", &file_name.item), file_name.span);
 
     Ok(PipelineData::Value(
-        Value::List {
-            vals: vec![file_value],
-            span: call.head,
-        },
+        Value::list(vec![file_value], call.head),
         None,
     ))
 }
@@ -147,7 +147,7 @@ fn command(
             })?;
 
             let res = chunked
-                .set_at_idx(indices, Some(val.as_ref()))
+                .set_at_idx(indices, Some(val.as_str()))
                 .map_err(|e| {
                     ShellError::SpannedLabeledError(
                         "Error setting value".into(),
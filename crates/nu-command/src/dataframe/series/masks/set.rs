@@ -130,7 +130,7 @@ fn command(
                 )
             })?;
 
-            let res = chunked.set(bool_mask, Some(val.as_ref())).map_err(|e| {
+            let res = chunked.set(bool_mask, Some(val.as_str())).map_err(|e| {
                 ShellError::SpannedLabeledError("Error setting value".into(), e.to_string(), span)
             })?;
 
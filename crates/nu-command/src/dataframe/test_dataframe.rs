@@ -2,7 +2,7 @@ use nu_engine::eval_block;
 use nu_parser::parse;
 use nu_protocol::{
     engine::{Command, EngineState, Stack, StateWorkingSet},
-    PipelineData, Span, Value, CONFIG_VARIABLE_ID,
+    PipelineData, Record, Span, Value, CONFIG_VARIABLE_ID,
 };
 
 use super::eager::ToDataFrame;
@@ -59,11 +59,7 @@ pub fn test_dataframe(cmds: Vec<Box<dyn Command + 'static>>) {
         // Set up our initial config to start from
         stack.vars.insert(
             CONFIG_VARIABLE_ID,
-            Value::Record {
-                cols: vec![],
-                vals: vec![],
-                span: Span::test_data(),
-            },
+            Value::record(Record::new(), Span::test_data()),
         );
 
         match eval_block(
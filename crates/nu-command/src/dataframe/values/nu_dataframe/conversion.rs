@@ -2,7 +2,7 @@ use super::{DataFrameValue, NuDataFrame};
 
 use chrono::{DateTime, FixedOffset, NaiveDateTime};
 use indexmap::map::{Entry, IndexMap};
-use nu_protocol::{ShellError, Span, Value};
+use nu_protocol::{Record, ShellError, Span, Value};
 use polars::chunked_array::object::builder::ObjectChunkedBuilder;
 use polars::chunked_array::ChunkedArray;
 use polars::prelude::{
@@ -334,10 +334,7 @@ pub fn create_column(
                 .skip(from_row)
                 .take(size)
                 .map(|v| match v {
-                    Some(a) => Value::String {
-                        val: a.into(),
-                        span,
-                    },
+                    Some(a) => Value::string(a, span),
                     None => Value::Nothing { span },
                 })
                 .collect::<Vec<Value>>();
@@ -459,18 +456,13 @@ pub fn create_column(
 // Adds a separator to the vector of values using the column names from the
 // dataframe to create the Values Row
 pub fn add_separator(values: &mut Vec<Value>, df: &DataFrame, span: Span) {
-    let mut cols = vec![];
-    let mut vals = vec![];
+    let mut record = Record::new();
 
     for name in df.get_column_names() {
-        cols.push(name.to_string());
-        vals.push(Value::String {
-            val: "...".into(),
-            span,
-        })
+        record.insert(name, Value::string("...", span));
     }
 
-    let extra_record = Value::Record { cols, vals, span };
+    let extra_record = Value::record(record, span);
 
     values.push(extra_record);
 }
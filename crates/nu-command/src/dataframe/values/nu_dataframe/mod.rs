@@ -7,7 +7,7 @@ pub use conversion::{Column, ColumnMap};
 pub use operations::Axis;
 
 use indexmap::map::IndexMap;
-use nu_protocol::{did_you_mean, PipelineData, ShellError, Span, Value};
+use nu_protocol::{did_you_mean, PipelineData, Record, ShellError, Span, Value};
 use polars::prelude::{DataFrame, DataType, PolarsObject, Series};
 use serde::{Deserialize, Serialize};
 use std::{cmp::Ordering, fmt::Display, hash::Hasher};
@@ -137,8 +137,8 @@ impl NuDataFrame {
 
                     conversion::insert_record(&mut column_values, &cols, &vals)?
                 }
-                Value::Record { cols, vals, .. } => {
-                    conversion::insert_record(&mut column_values, &cols, &vals)?
+                Value::Record { val, .. } => {
+                    conversion::insert_record(&mut column_values, val.columns(), val.values())?
                 }
                 _ => {
                     let key = "0".to_string();
@@ -326,19 +326,13 @@ impl NuDataFrame {
         let values = (0..size)
             .into_iter()
             .map(|_| {
-                let mut cols = vec![];
-                let mut vals = vec![];
+                let mut record = Record::new();
 
                 for (name, col) in &mut iterators {
-                    cols.push(name.clone());
-
-                    match col.next() {
-                        Some(v) => vals.push(v),
-                        None => vals.push(Value::Nothing { span }),
-                    };
+                    record.insert(name.clone(), col.next().unwrap_or(Value::Nothing { span }));
                 }
 
-                Value::Record { cols, vals, span }
+                Value::record(record, span)
             })
             .collect::<Vec<Value>>();
 
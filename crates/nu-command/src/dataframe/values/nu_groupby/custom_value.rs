@@ -31,7 +31,7 @@ impl CustomValue for NuGroupBy {
     fn to_base_value(&self, span: Span) -> Result<Value, ShellError> {
         let vals = self.print(span)?;
 
-        Ok(Value::List { vals, span })
+        Ok(Value::list(vals, span))
     }
 
     fn to_json(&self) -> nu_json::Value {
@@ -1,6 +1,6 @@
 mod custom_value;
 
-use nu_protocol::{PipelineData, ShellError, Span, Value};
+use nu_protocol::{record, PipelineData, ShellError, Span, Value};
 use polars::frame::groupby::{GroupBy, GroupTuples};
 use polars::prelude::DataFrame;
 use serde::{Deserialize, Serialize};
@@ -68,13 +68,12 @@ impl NuGroupBy {
             .by
             .iter()
             .map(|col| {
-                let cols = vec!["group by".to_string()];
-                let vals = vec![Value::String {
-                    val: col.into(),
+                Value::record(
+                    record! {
+                        "group by" => Value::string(col, span),
+                    },
                     span,
-                }];
-
-                Value::Record { cols, vals, span }
+                )
             })
             .collect::<Vec<Value>>();
 
@@ -26,7 +26,10 @@ pub(crate) fn convert_columns(
         .map(|value| match value {
             Value::String { val, span } => {
                 col_span = span_join(&[col_span, span]);
-                Ok(Spanned { item: val, span })
+                Ok(Spanned {
+                    item: val.into_inner(),
+                    span,
+                })
             }
             _ => Err(ShellError::SpannedLabeledError(
                 "Incorrect column format".into(),
@@ -62,7 +65,7 @@ pub(crate) fn convert_columns_string(
         .map(|value| match value {
             Value::String { val, span } => {
                 col_span = span_join(&[col_span, span]);
-                Ok(val)
+                Ok(val.into_inner())
             }
             _ => Err(ShellError::SpannedLabeledError(
                 "Incorrect column format".into(),
@@ -39,9 +39,9 @@ fn date(
 ) -> Result<PipelineData, ShellError> {
     let head = call.head;
 
-    Ok(Value::String {
-        val: get_full_help(&Date.signature(), &Date.examples(), engine_state),
-        span: head,
-    }
+    Ok(Value::string(
+        get_full_help(&Date.signature(), &Date.examples(), engine_state),
+        head,
+    )
     .into_pipeline_data())
 }
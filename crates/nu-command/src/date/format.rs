@@ -50,26 +50,23 @@ impl Command for SubCommand {
             Example {
                 description: "Format a given date using the given format string.",
                 example: "date format '%Y-%m-%d'",
-                result: Some(Value::String {
-                    val: Local::now().format("%Y-%m-%d").to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string(
+                    Local::now().format("%Y-%m-%d").to_string(),
+                    Span::test_data(),
+                )),
             },
             Example {
                 description: "Format a given date using the given format string.",
                 example: r#"date format "%Y-%m-%d %H:%M:%S""#,
-                result: Some(Value::String {
-                    val: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string(
+                    Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+                    Span::test_data(),
+                )),
             },
             Example {
                 description: "Format a given date using the given format string.",
                 example: r#""2021-10-22 20:00:12 +01:00" | date format "%Y-%m-%d""#,
-                result: Some(Value::String {
-                    val: "2021-10-22".into(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("2021-10-22", Span::test_data())),
             },
         ]
     }
@@ -77,20 +74,16 @@ impl Command for SubCommand {
 
 fn format_helper(value: Value, formatter: &Spanned<String>, span: Span) -> Value {
     match value {
-        Value::Date { val, span: _ } => Value::String {
-            val: val.format(formatter.item.as_str()).to_string(),
-            span,
-        },
+        Value::Date { val, span: _ } => {
+            Value::string(val.format(formatter.item.as_str()).to_string(), span)
+        }
         Value::String {
             val,
             span: val_span,
         } => {
-            let dt = parse_date_from_string(val, val_span);
+            let dt = parse_date_from_string(val.into_inner(), val_span);
             match dt {
-                Ok(x) => Value::String {
-                    val: x.format(formatter.item.as_str()).to_string(),
-                    span,
-                },
+                Ok(x) => Value::string(x.format(formatter.item.as_str()).to_string(), span),
                 Err(e) => e,
             }
         }
@@ -100,7 +93,8 @@ fn format_helper(value: Value, formatter: &Spanned<String>, span: Span) -> Value
                 val: dt
                     .with_timezone(dt.offset())
                     .format(formatter.item.as_str())
-                    .to_string(),
+                    .to_string()
+                    .into(),
                 span,
             }
         }
@@ -36,10 +36,7 @@ impl Command for SubCommand {
             Example {
                 description: "Print a 'humanized' format for the date, relative to now.",
                 example: "date humanize",
-                result: Some(Value::String {
-                    val: "now".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("now", Span::test_data())),
             },
             Example {
                 description: "Print a 'humanized' format for the date, relative to now.",
@@ -54,28 +51,19 @@ fn helper(value: Value, head: Span) -> Value {
     match value {
         Value::Nothing { span: _ } => {
             let dt = Local::now();
-            Value::String {
-                val: humanize_date(dt.with_timezone(dt.offset())),
-                span: head,
-            }
+            Value::string(humanize_date(dt.with_timezone(dt.offset())), head)
         }
         Value::String {
             val,
             span: val_span,
         } => {
-            let dt = parse_date_from_string(val, val_span);
+            let dt = parse_date_from_string(val.into_inner(), val_span);
             match dt {
-                Ok(x) => Value::String {
-                    val: humanize_date(x),
-                    span: head,
-                },
+                Ok(x) => Value::string(humanize_date(x), head),
                 Err(e) => e,
             }
         }
-        Value::Date { val, span: _ } => Value::String {
-            val: humanize_date(val),
-            span: head,
-        },
+        Value::Date { val, span: _ } => Value::string(humanize_date(val), head),
         _ => Value::Error {
             error: ShellError::UnsupportedInput(
                 String::from("Date cannot be parsed / date format is not supported"),
@@ -1,7 +1,9 @@
 use chrono_tz::TZ_VARIANTS;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, IntoInterruptiblePipelineData, PipelineData, Signature, Value};
+use nu_protocol::{
+    record, Category, IntoInterruptiblePipelineData, PipelineData, Signature, Value,
+};
 
 #[derive(Clone)]
 pub struct SubCommand;
@@ -31,12 +33,12 @@ impl Command for SubCommand {
         Ok(TZ_VARIANTS
             .iter()
             .map(move |x| {
-                let cols = vec!["timezone".into()];
-                let vals = vec![Value::String {
-                    val: x.name().to_string(),
+                Value::record(
+                    record! {
+                        "timezone" => Value::string(x.name().to_string(), span),
+                    },
                     span,
-                }];
-                Value::Record { cols, vals, span }
+                )
             })
             .into_iter()
             .into_pipeline_data(engine_state.ctrlc.clone()))
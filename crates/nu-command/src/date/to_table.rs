@@ -2,7 +2,7 @@ use crate::date::utils::{parse_date_from_string, unsupported_input_error};
 use chrono::{DateTime, Datelike, FixedOffset, Local, Timelike};
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, Example, PipelineData, Signature, Span, Value};
+use nu_protocol::{record, Category, Example, PipelineData, Signature, Span, Value};
 
 #[derive(Clone)]
 pub struct SubCommand;
@@ -48,29 +48,20 @@ impl Command for SubCommand {
                 example: " '2020-04-12 22:10:57 +0200' | date to-table",
                 result: {
                     let span = Span::test_data();
-                    let cols = vec![
-                        "year".into(),
-                        "month".into(),
-                        "day".into(),
-                        "hour".into(),
-                        "minute".into(),
-                        "second".into(),
-                        "timezone".into(),
-                    ];
-                    let vals = vec![
-                        Value::Int { val: 2020, span },
-                        Value::Int { val: 4, span },
-                        Value::Int { val: 12, span },
-                        Value::Int { val: 22, span },
-                        Value::Int { val: 10, span },
-                        Value::Int { val: 57, span },
-                        Value::String {
-                            val: "+02:00".to_string(),
-                            span,
-                        },
-                    ];
                     Some(Value::List {
-                        vals: vec![Value::Record { cols, vals, span }],
+                        vals: vec![Value::record(
+                            record! {
+                                "year" => Value::Int { val: 2020, span },
+                                "month" => Value::Int { val: 4, span },
+                                "day" => Value::Int { val: 12, span },
+                                "hour" => Value::Int { val: 22, span },
+                                "minute" => Value::Int { val: 10, span },
+                                "second" => Value::Int { val: 57, span },
+                                "timezone" => Value::string("+02:00", span),
+                            },
+                            span,
+                        )]
+                        .into(),
                         span,
                     })
                 },
@@ -80,56 +71,41 @@ impl Command for SubCommand {
 }
 
 fn parse_date_into_table(date: Result<DateTime<FixedOffset>, Value>, head: Span) -> Value {
-    let cols = vec![
-        "year".into(),
-        "month".into(),
-        "day".into(),
-        "hour".into(),
-        "minute".into(),
-        "second".into(),
-        "timezone".into(),
-    ];
     match date {
-        Ok(x) => {
-            let vals = vec![
-                Value::Int {
-                    val: x.year() as i64,
-                    span: head,
-                },
-                Value::Int {
-                    val: x.month() as i64,
-                    span: head,
-                },
-                Value::Int {
-                    val: x.day() as i64,
-                    span: head,
-                },
-                Value::Int {
-                    val: x.hour() as i64,
-                    span: head,
+        Ok(x) => Value::List {
+            vals: vec![Value::record(
+                record! {
+                    "year" => Value::Int {
+                        val: x.year() as i64,
+                        span: head,
+                    },
+                    "month" => Value::Int {
+                        val: x.month() as i64,
+                        span: head,
+                    },
+                    "day" => Value::Int {
+                        val: x.day() as i64,
+                        span: head,
+                    },
+                    "hour" => Value::Int {
+                        val: x.hour() as i64,
+                        span: head,
+                    },
+                    "minute" => Value::Int {
+                        val: x.minute() as i64,
+                        span: head,
+                    },
+                    "second" => Value::Int {
+                        val: x.second() as i64,
+                        span: head,
+                    },
+                    "timezone" => Value::string(x.offset().to_string(), head),
                 },
-                Value::Int {
-                    val: x.minute() as i64,
-                    span: head,
-                },
-                Value::Int {
-                    val: x.second() as i64,
-                    span: head,
-                },
-                Value::String {
-                    val: x.offset().to_string(),
-                    span: head,
-                },
-            ];
-            Value::List {
-                vals: vec![Value::Record {
-                    cols,
-                    vals,
-                    span: head,
-                }],
-                span: head,
-            }
-        }
+                head,
+            )]
+            .into(),
+            span: head,
+        },
         Err(e) => e,
     }
 }
@@ -140,7 +116,7 @@ fn helper(val: Value, head: Span) -> Value {
             val,
             span: val_span,
         } => {
-            let date = parse_date_from_string(val, val_span);
+            let date = parse_date_from_string(val.into_inner(), val_span);
             parse_date_into_table(date, head)
         }
         Value::Nothing { span: _ } => {
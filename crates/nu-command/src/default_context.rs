@@ -29,6 +29,7 @@ pub fn create_default_context() -> EngineState {
             Describe,
             Do,
             Echo,
+            ErrorMake,
             ExportCommand,
             ExportDef,
             ExportEnv,
@@ -40,6 +41,7 @@ pub fn create_default_context() -> EngineState {
             Let,
             Module,
             Source,
+            Try,
             Use,
             Version,
         };
@@ -100,9 +102,17 @@ pub fn create_default_context() -> EngineState {
         // System
         bind_command! {
             Benchmark,
+            Complete,
             External,
+            Fg,
+            JobCommand,
+            JobKill,
+            Jobs,
+            Profile,
             Ps,
             Sys,
+            Timeout,
+            Which,
         };
 
         // Strings
@@ -147,6 +157,7 @@ pub fn create_default_context() -> EngineState {
             Mkdir,
             Mv,
             Rm,
+            Save,
             Touch,
         };
 
@@ -288,6 +299,13 @@ pub fn create_default_context() -> EngineState {
             HashSha256::default(),
         };
 
+        // Testing
+        bind_command! {
+            Assert,
+            AssertEqual,
+            AssertError,
+        };
+
         #[cfg(feature = "plugin")]
         bind_command!(Register);
 
@@ -301,3 +319,58 @@ pub fn create_default_context() -> EngineState {
 
     engine_state
 }
+
+#[cfg(test)]
+mod tests {
+    use nu_protocol::engine::Capability;
+    use nu_protocol::SyntaxShape;
+
+    use super::create_default_context;
+
+    // These only work with the text of a path, and never look at the filesystem
+    const PATH_TEXT_COMMANDS: &[&str] = &["path join", "path relative-to"];
+
+    #[test]
+    fn commands_taking_paths_declare_a_filesystem_capability() {
+        let engine_state = create_default_context();
+
+        let mut missing = vec![];
+        for decl_id in 0..engine_state.num_decls() {
+            let decl = engine_state.get_decl(decl_id);
+            let signature = decl.signature();
+
+            let takes_paths = signature
+                .required_positional
+                .iter()
+                .chain(signature.optional_positional.iter())
+                .chain(signature.rest_positional.iter())
+                .map(|param| Some(&param.shape))
+                .chain(signature.named.iter().map(|flag| flag.arg.as_ref()))
+                .any(|shape| {
+                    matches!(
+                        shape,
+                        Some(SyntaxShape::Filepath) | Some(SyntaxShape::GlobPattern)
+                    )
+                });
+            // An external can do anything to the filesystem already
+            let touches_filesystem = decl.capabilities().iter().any(|capability| {
+                matches!(
+                    capability,
+                    Capability::FilesystemRead
+                        | Capability::FilesystemWrite
+                        | Capability::Externals
+                )
+            });
+
+            if takes_paths && !touches_filesystem && !PATH_TEXT_COMMANDS.contains(&decl.name()) {
+                missing.push(decl.name().to_string());
+            }
+        }
+
+        assert!(
+            missing.is_empty(),
+            "commands taking paths without a filesystem capability: {}",
+            missing.join(", ")
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use nu_engine::env_to_string;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, IntoPipelineData, PipelineData, Signature, Value};
+use nu_protocol::{Category, IntoPipelineData, PipelineData, Record, Signature, Value};
 
 #[derive(Clone)]
 pub struct Env;
@@ -35,27 +35,22 @@ impl Command for Env {
         let mut values = vec![];
 
         for (name, val) in env_vars {
-            let mut cols = vec![];
-            let mut vals = vec![];
+            let mut record = Record::new();
 
             let raw = env_to_string(&name, val.clone(), engine_state, stack, &config)?;
             let val_type = val.get_type();
 
-            cols.push("name".into());
-            vals.push(Value::string(name, span));
+            record.insert("name", Value::string(name, span));
 
-            cols.push("type".into());
-            vals.push(Value::string(format!("{}", val_type), span));
+            record.insert("type", Value::string(format!("{}", val_type), span));
 
-            cols.push("value".into());
-            vals.push(val);
+            record.insert("value", val);
 
-            cols.push("raw".into());
-            vals.push(Value::string(raw, span));
+            record.insert("raw", Value::string(raw, span));
 
-            values.push(Value::Record { cols, vals, span });
+            values.push(Value::record(record, span));
         }
 
-        Ok(Value::List { vals: values, span }.into_pipeline_data())
+        Ok(Value::list(values, span).into_pipeline_data())
     }
 }
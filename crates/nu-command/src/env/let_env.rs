@@ -26,6 +26,10 @@ impl Command for LetEnv {
             .category(Category::Env)
     }
 
+    fn changes_env(&self) -> bool {
+        true
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -5,7 +5,7 @@ use nu_parser::parse;
 #[cfg(test)]
 use nu_protocol::{
     engine::{Command, EngineState, Stack, StateWorkingSet},
-    PipelineData, Span, Value, CONFIG_VARIABLE_ID,
+    PipelineData, Record, Span, Value, CONFIG_VARIABLE_ID,
 };
 
 #[cfg(test)]
@@ -71,11 +71,7 @@ pub fn test_examples(cmd: impl Command + 'static) {
         // Set up our initial config to start from
         stack.vars.insert(
             CONFIG_VARIABLE_ID,
-            Value::Record {
-                cols: vec![],
-                vals: vec![],
-                span: Span::test_data(),
-            },
+            Value::record(Record::new(), Span::test_data()),
         );
 
         match eval_block(
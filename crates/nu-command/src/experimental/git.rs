@@ -36,11 +36,10 @@ impl Command for Git {
                     Ok(val) => {
                         let result = val.stdout;
 
-                        Ok(Value::String {
-                            val: String::from_utf8_lossy(&result).to_string(),
-                            span: call.head,
-                        }
-                        .into_pipeline_data())
+                        Ok(
+                            Value::string(String::from_utf8_lossy(&result).to_string(), call.head)
+                                .into_pipeline_data(),
+                        )
                     }
                     Err(_err) => {
                         // FIXME: Move this to an external signature and add better error handling
@@ -57,10 +57,7 @@ impl Command for ListGitBranches {
                             Some(x.trim())
                         }
                     })
-                    .map(|x| Value::String {
-                        val: x.into(),
-                        span: call.head,
-                    })
+                    .map(|x| Value::string(x, call.head))
                     .collect();
 
                 Ok(lines
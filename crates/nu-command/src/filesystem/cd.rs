@@ -1,7 +1,7 @@
 use nu_engine::CallExt;
 use nu_protocol::ast::Call;
-use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, PipelineData, Signature, SyntaxShape, Value};
+use nu_protocol::engine::{Capability, Command, EngineState, Stack};
+use nu_protocol::{Category, PipelineData, ShellError, Signature, SyntaxShape, Value};
 
 #[derive(Clone)]
 pub struct Cd;
@@ -17,10 +17,22 @@ impl Command for Cd {
 
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("cd")
-            .optional("path", SyntaxShape::Filepath, "the path to change to")
+            .optional(
+                "path",
+                SyntaxShape::Filepath,
+                "the path to change to, or '-' for the previous directory",
+            )
             .category(Category::FileSystem)
     }
 
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::FilesystemRead]
+    }
+
+    fn changes_env(&self) -> bool {
+        true
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -31,6 +43,17 @@ impl Command for Cd {
         let path_val: Option<Value> = call.opt(engine_state, stack, 0)?;
 
         let (path, span) = match path_val {
+            // The directory we were in before the last change
+            Some(v) if v.as_string()? == "-" => match stack.get_env_var("OLDPWD") {
+                Some(oldpwd) => (oldpwd.as_string()?, v.span()?),
+                None => {
+                    return Err(ShellError::SpannedLabeledError(
+                        "No previous directory".into(),
+                        "there's no directory to go back to yet".into(),
+                        v.span()?,
+                    ))
+                }
+            },
             Some(v) => {
                 let path = nu_path::expand_path(v.as_string()?);
                 (path.to_string_lossy().to_string(), v.span()?)
@@ -40,11 +63,23 @@ impl Command for Cd {
                 (path.to_string_lossy().to_string(), call.head)
             }
         };
-        let _ = std::env::set_current_dir(&path);
+
+        // OLDPWD only changes along with the directory, so a failed `cd` leaves it alone
+        let oldpwd = std::env::current_dir();
+        std::env::set_current_dir(&path).map_err(|err| {
+            ShellError::SpannedLabeledError("Cannot change directory".into(), err.to_string(), span)
+        })?;
+
+        if let Ok(cwd) = oldpwd {
+            stack.add_env_var(
+                "OLDPWD".into(),
+                Value::string(cwd.to_string_lossy().to_string(), span),
+            );
+        }
 
         //FIXME: this only changes the current scope, but instead this environment variable
         //should probably be a block that loads the information from the state in the overlay
-        stack.add_env_var("PWD".into(), Value::String { val: path, span });
+        stack.add_env_var("PWD".into(), Value::string(path, span));
         Ok(PipelineData::new(call.head))
     }
 }
@@ -1,10 +1,10 @@
 use chrono::{DateTime, Utc};
 use nu_engine::eval_expression;
 use nu_protocol::ast::Call;
-use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::engine::{Capability, Command, EngineState, Stack};
 use nu_protocol::{
-    Category, DataSource, IntoInterruptiblePipelineData, PipelineData, PipelineMetadata, Signature,
-    SyntaxShape, Value,
+    record, Category, DataSource, IntoInterruptiblePipelineData, PipelineData, PipelineMetadata,
+    Signature, SyntaxShape, Value,
 };
 
 #[derive(Clone)]
@@ -30,6 +30,10 @@ impl Command for Ls {
             .category(Category::FileSystem)
     }
 
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::FilesystemRead]
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -72,14 +76,9 @@ impl Command for Ls {
                         let is_file = metadata.is_file();
                         let is_dir = metadata.is_dir();
                         let filesize = metadata.len();
-                        let mut cols = vec!["name".into(), "type".into(), "size".into()];
-
-                        let mut vals = vec![
-                            Value::String {
-                                val: path.to_string_lossy().to_string(),
-                                span: call_span,
-                            },
-                            if is_symlink {
+                        let mut record = record! {
+                            "name" => Value::string(path.to_string_lossy().to_string(), call_span),
+                            "type" => if is_symlink {
                                 Value::string("symlink", call_span)
                             } else if is_file {
                                 Value::string("file", call_span)
@@ -88,40 +87,34 @@ impl Command for Ls {
                             } else {
                                 Value::Nothing { span: call_span }
                             },
-                            Value::Filesize {
+                            "size" => Value::Filesize {
                                 val: filesize as i64,
                                 span: call_span,
                             },
-                        ];
+                        };
 
                         if let Ok(date) = metadata.modified() {
                             let utc: DateTime<Utc> = date.into();
 
-                            cols.push("modified".into());
-                            vals.push(Value::Date {
-                                val: utc.into(),
-                                span: call_span,
-                            });
+                            record.insert(
+                                "modified",
+                                Value::Date {
+                                    val: utc.into(),
+                                    span: call_span,
+                                },
+                            );
                         }
 
-                        Value::Record {
-                            cols,
-                            vals,
-                            span: call_span,
-                        }
+                        Value::record(record, call_span)
                     }
-                    Err(_) => Value::Record {
-                        cols: vec!["name".into(), "type".into(), "size".into()],
-                        vals: vec![
-                            Value::String {
-                                val: path.to_string_lossy().to_string(),
-                                span: call_span,
-                            },
-                            Value::Nothing { span: call_span },
-                            Value::Nothing { span: call_span },
-                        ],
-                        span: call_span,
-                    },
+                    Err(_) => Value::record(
+                        record! {
+                            "name" => Value::string(path.to_string_lossy().to_string(), call_span),
+                            "type" => Value::Nothing { span: call_span },
+                            "size" => Value::Nothing { span: call_span },
+                        },
+                        call_span,
+                    ),
                 },
                 _ => Value::Nothing { span: call_span },
             })
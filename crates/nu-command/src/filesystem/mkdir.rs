@@ -3,7 +3,7 @@ use std::env::current_dir;
 
 use nu_engine::CallExt;
 use nu_protocol::ast::Call;
-use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::engine::{Capability, Command, EngineState, Stack};
 use nu_protocol::{
     Category, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, SyntaxShape,
     Value,
@@ -32,6 +32,10 @@ impl Command for Mkdir {
         "Make directories, creates intermediary directories as required."
     }
 
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::FilesystemWrite]
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -69,7 +73,7 @@ impl Command for Mkdir {
 
             if show_created_paths {
                 let val = format!("{:}", dir.to_string_lossy());
-                stream.push_back(Value::String { val, span });
+                stream.push_back(Value::string(val, span));
             }
         }
 
@@ -4,6 +4,7 @@ mod ls;
 mod mkdir;
 mod mv;
 mod rm;
+mod save;
 mod touch;
 mod util;
 
@@ -13,4 +14,5 @@ pub use ls::Ls;
 pub use mkdir::Mkdir;
 pub use mv::Mv;
 pub use rm::Rm;
+pub use save::Save;
 pub use touch::Touch;
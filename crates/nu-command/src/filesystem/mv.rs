@@ -4,7 +4,7 @@ use std::path::{Path, PathBuf};
 use super::util::get_interactive_confirmation;
 use nu_engine::CallExt;
 use nu_protocol::ast::Call;
-use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::engine::{Capability, Command, EngineState, Stack};
 use nu_protocol::{Category, PipelineData, ShellError, Signature, Spanned, SyntaxShape};
 
 #[derive(Clone)]
@@ -37,6 +37,10 @@ impl Command for Mv {
             .category(Category::FileSystem)
     }
 
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::FilesystemWrite]
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -7,7 +7,7 @@ use super::util::get_interactive_confirmation;
 
 use nu_engine::CallExt;
 use nu_protocol::ast::Call;
-use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::engine::{Capability, Command, EngineState, Stack};
 use nu_protocol::{
     Category, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, SyntaxShape,
     Value,
@@ -59,6 +59,10 @@ impl Command for Rm {
             .category(Category::FileSystem)
     }
 
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::FilesystemWrite]
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -268,10 +272,10 @@ fn rm_helper(call: &Call, args: RmArgs) -> Vec<Value> {
                             ),
                         }
                     } else {
-                        Value::String {
-                            val: format!("deleted {:}", f.to_string_lossy()),
-                            span: call.positional[i].span,
-                        }
+                        Value::string(
+                            format!("deleted {:}", f.to_string_lossy()),
+                            call.positional[i].span,
+                        )
                     }
                 } else {
                     Value::Error {
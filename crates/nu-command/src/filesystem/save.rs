@@ -0,0 +1,131 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Capability, Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, PipelineData, ShellError, Signature, Spanned, SyntaxShape, Value,
+};
+
+#[derive(Clone)]
+pub struct Save;
+
+impl Command for Save {
+    fn name(&self) -> &str {
+        "save"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("save")
+            .required("filename", SyntaxShape::Filepath, "the filename to use")
+            .switch(
+                "raw",
+                "save the input as it is, without converting it based on the file extension",
+                Some('r'),
+            )
+            .category(Category::FileSystem)
+    }
+
+    fn usage(&self) -> &str {
+        "Save a file."
+    }
+
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::FilesystemWrite]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let path: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let raw = call.has_flag("raw");
+        let config = stack.get_config().unwrap_or_default();
+
+        let mut file = File::create(&path.item).map_err(|err| {
+            ShellError::CreateNotPossible(format!("Failed to create file: {}", err), path.span)
+        })?;
+
+        let write_error = |err: std::io::Error| ShellError::IOError(err.to_string());
+
+        match input {
+            // The raw output of an external is written chunk by chunk as it comes in
+            PipelineData::RawStream(stream, ..) => {
+                for bytes in stream.stream {
+                    file.write_all(&bytes?).map_err(write_error)?;
+
+                    if let Some(ctrlc) = &stream.ctrlc {
+                        if ctrlc.load(std::sync::atomic::Ordering::SeqCst) {
+                            break;
+                        }
+                    }
+                }
+            }
+            PipelineData::Value(Value::String { val, .. }, ..) => {
+                file.write_all(val.as_bytes()).map_err(write_error)?
+            }
+            PipelineData::Value(Value::Binary { val, .. }, ..) => {
+                file.write_all(&val).map_err(write_error)?
+            }
+            input => {
+                let extension = Path::new(&path.item)
+                    .extension()
+                    .map(|extension| extension.to_string_lossy().to_string());
+
+                // Structured data is converted with the `to` command matching the extension
+                let converter = match extension {
+                    Some(extension) if !raw => {
+                        engine_state.find_decl(format!("to {}", extension).as_bytes())
+                    }
+                    _ => None,
+                };
+
+                let output = match converter {
+                    Some(decl_id) => {
+                        let to_call = Call {
+                            decl_id,
+                            head: call.head,
+                            positional: vec![],
+                            named: vec![],
+                        };
+
+                        engine_state
+                            .get_decl(decl_id)
+                            .run(engine_state, stack, &to_call, input)?
+                            .collect_string("", &config)?
+                    }
+                    None => input.collect_string("\n", &config)?,
+                };
+
+                file.write_all(output.as_bytes()).map_err(write_error)?
+            }
+        }
+
+        Ok(PipelineData::new(call.head))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Save a string to foo.txt in the current directory",
+                example: r#"echo 'save me' | save foo.txt"#,
+                result: None,
+            },
+            Example {
+                description: "Save a record to foo.json, converting it to JSON",
+                example: r#"echo { a: 1, b: 2 } | save foo.json"#,
+                result: None,
+            },
+            Example {
+                description: "Save the output of an external command as it is",
+                example: r#"^cat image.png | save copy.png"#,
+                result: None,
+            },
+        ]
+    }
+}
@@ -36,7 +36,8 @@ impl Command for Append {
                         Value::test_int(2),
                         Value::test_int(3),
                         Value::test_int(4),
-                    ],
+                    ]
+                    .into(),
                     span: Span::test_data(),
                 }),
             },
@@ -50,7 +51,8 @@ impl Command for Append {
                         Value::test_int(2),
                         Value::test_int(3),
                         Value::test_int(4),
-                    ],
+                    ]
+                    .into(),
                     span: Span::test_data(),
                 }),
             },
@@ -65,7 +67,8 @@ impl Command for Append {
                         Value::test_string("nu"),
                         Value::test_int(4),
                         Value::test_string("shell"),
-                    ],
+                    ]
+                    .into(),
                     span: Span::test_data(),
                 }),
             },
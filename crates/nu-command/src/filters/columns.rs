@@ -1,8 +1,8 @@
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, ShellError,
-    Signature, Span, Value,
+    Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, Record,
+    ShellError, Signature, Span, Value,
 };
 
 #[derive(Clone)]
@@ -66,10 +66,10 @@ fn getcol(
             },
             ..,
         ) => {
-            let input_cols = get_input_cols(input_vals);
+            let input_cols = get_input_cols(input_vals.into_inner());
             Ok(input_cols
                 .into_iter()
-                .map(move |x| Value::String { val: x, span })
+                .map(move |x| Value::string(x, span))
                 .into_pipeline_data(engine_state.ctrlc.clone()))
         }
         PipelineData::Stream(stream, ..) => {
@@ -78,13 +78,11 @@ fn getcol(
 
             Ok(input_cols
                 .into_iter()
-                .map(move |x| Value::String { val: x, span })
+                .map(move |x| Value::string(x, span))
                 .into_pipeline_data(engine_state.ctrlc.clone()))
         }
-        PipelineData::Value(_v, ..) => {
-            let cols = vec![];
-            let vals = vec![];
-            Ok(Value::Record { cols, vals, span }.into_pipeline_data())
+        PipelineData::Value(..) | PipelineData::RawStream(..) => {
+            Ok(Value::record(Record::new(), span).into_pipeline_data())
         }
     }
 }
@@ -92,7 +90,7 @@ fn getcol(
 fn get_input_cols(input: Vec<Value>) -> Vec<String> {
     let rec = input.first();
     match rec {
-        Some(Value::Record { cols, vals: _, .. }) => cols.to_vec(),
+        Some(Value::Record { val, .. }) => val.columns().to_vec(),
         _ => vec!["".to_string()],
     }
 }
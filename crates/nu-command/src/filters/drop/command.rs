@@ -34,10 +34,10 @@ impl Command for Drop {
             Example {
                 example: "[0,1,2,3] | drop",
                 description: "Remove the last item of a list/table",
-                result: Some(Value::List {
-                    vals: vec![Value::test_int(0), Value::test_int(1), Value::test_int(2)],
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::list(
+                    vec![Value::test_int(0), Value::test_int(1), Value::test_int(2)],
+                    Span::test_data(),
+                )),
             },
             Example {
                 example: "[0,1,2,3] | drop 0",
@@ -48,17 +48,18 @@ impl Command for Drop {
                         Value::test_int(1),
                         Value::test_int(2),
                         Value::test_int(3),
-                    ],
+                    ]
+                    .into(),
                     span: Span::test_data(),
                 }),
             },
             Example {
                 example: "[0,1,2,3] | drop 2",
                 description: "Remove the last two items of a list/table",
-                result: Some(Value::List {
-                    vals: vec![Value::test_int(0), Value::test_int(1)],
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::list(
+                    vec![Value::test_int(0), Value::test_int(1)],
+                    Span::test_data(),
+                )),
             },
         ]
     }
@@ -2,8 +2,8 @@ use nu_engine::eval_block;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, Signature,
-    Span, SyntaxShape, Value,
+    record, Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData,
+    Record, Signature, Span, SyntaxShape, Value,
 };
 
 #[derive(Clone)]
@@ -48,10 +48,7 @@ impl Command for Each {
         vec![Example {
             example: "[1 2 3] | each { 2 * $it }",
             description: "Multiplies elements in list",
-            result: Some(Value::List {
-                vals: stream_test_1,
-                span: Span::test_data(),
-            }),
+            result: Some(Value::list(stream_test_1, Span::test_data())),
         }]
     }
 
@@ -85,17 +82,16 @@ impl Command for Each {
                             if numbered {
                                 stack.add_var(
                                     *var_id,
-                                    Value::Record {
-                                        cols: vec!["index".into(), "item".into()],
-                                        vals: vec![
-                                            Value::Int {
+                                    Value::record(
+                                        record! {
+                                            "index" => Value::Int {
                                                 val: idx as i64,
                                                 span,
                                             },
-                                            x,
-                                        ],
+                                            "item" => x,
+                                        },
                                         span,
-                                    },
+                                    ),
                                 );
                             } else {
                                 stack.add_var(*var_id, x);
@@ -109,11 +105,10 @@ impl Command for Each {
                     }
                 })
                 .into_pipeline_data(ctrlc)),
-            PipelineData::Value(Value::Record { cols, vals, .. }, ..) => {
-                let mut output_cols = vec![];
-                let mut output_vals = vec![];
+            PipelineData::Value(Value::Record { val: record, .. }, ..) => {
+                let mut output = Record::new();
 
-                for (col, val) in cols.into_iter().zip(vals.into_iter()) {
+                for (col, val) in record {
                     let block = engine_state.get_block(block_id);
 
                     let mut stack = stack.clone();
@@ -122,46 +117,36 @@ impl Command for Each {
                         if let Some(var_id) = &var.var_id {
                             stack.add_var(
                                 *var_id,
-                                Value::Record {
-                                    cols: vec!["column".into(), "value".into()],
-                                    vals: vec![
-                                        Value::String {
-                                            val: col.clone(),
-                                            span: call.head,
-                                        },
-                                        val,
-                                    ],
-                                    span: call.head,
-                                },
+                                Value::record(
+                                    record! {
+                                        "column" => Value::string(col.clone(), call.head),
+                                        "value" => val,
+                                    },
+                                    call.head,
+                                ),
                             );
                         }
                     }
 
                     match eval_block(&engine_state, &mut stack, block, PipelineData::new(span))? {
-                        PipelineData::Value(
-                            Value::Record {
-                                mut cols, mut vals, ..
-                            },
-                            ..,
-                        ) => {
-                            // TODO check that the lengths match when traversing record
-                            output_cols.append(&mut cols);
-                            output_vals.append(&mut vals);
+                        PipelineData::Value(Value::Record { val, .. }, ..) => {
+                            output.extend(val);
                         }
                         x => {
-                            output_cols.push(col);
-                            output_vals.push(x.into_value(span));
+                            output.insert(col, x.into_value(span));
                         }
                     }
                 }
 
-                Ok(Value::Record {
-                    cols: output_cols,
-                    vals: output_vals,
-                    span: call.head,
-                }
-                .into_pipeline_data())
+                Ok(Value::record(output, call.head).into_pipeline_data())
             }
+            // The raw output of an external is handed to the block a line at a time, as it's read
+            PipelineData::RawStream(stream, metadata) => self.run(
+                &engine_state,
+                &mut stack,
+                call,
+                PipelineData::Stream(stream.into_line_stream(), metadata),
+            ),
             PipelineData::Value(x, ..) => {
                 let block = engine_state.get_block(block_id);
 
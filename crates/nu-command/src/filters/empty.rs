@@ -59,15 +59,9 @@ impl Command for Empty {
                 result: Some(
                     Value::List {
                         vals: vec![
-                            Value::Record{cols: vec!["meal".to_string(), "size".to_string()], vals: vec![
-                                Value::Bool{val: false, span: Span::test_data()},
-                                Value::Bool{val: false, span: Span::test_data()}
-                            ], span: Span::test_data()},
-                            Value::Record{cols: vec!["meal".to_string(), "size".to_string()], vals: vec![
-                                Value::Bool{val: false, span: Span::test_data()},
-                                Value::Bool{val: true, span: Span::test_data()}
-                            ], span: Span::test_data()}
-                        ], span: Span::test_data()
+                            Value::test_record(vec!["meal", "size"], vec![Value::Bool{val: false, span: Span::test_data()}, Value::Bool{val: false, span: Span::test_data()}]),
+                            Value::test_record(vec!["meal", "size"], vec![Value::Bool{val: false, span: Span::test_data()}, Value::Bool{val: true, span: Span::test_data()}])
+                        ].into(), span: Span::test_data()
                     })
             },
             Example {
@@ -76,21 +70,15 @@ impl Command for Empty {
                 result: Some(
                     Value::List {
                         vals: vec![
-                            Value::Record{
-                            cols: vec!["2020/04/16".to_string(), "2020/07/10".to_string(), "2020/11/16".to_string()], 
-                            vals: vec![
-                                Value::List{vals: vec![
+                            Value::test_record(vec!["2020/04/16", "2020/07/10", "2020/11/16"], vec![Value::List{vals: vec![
                                     Value::Int{val: 33, span: Span::test_data()},
                                     Value::Int{val: 37, span: Span::test_data()}
-                                ], span: Span::test_data()},
-                                Value::List{vals: vec![
+                                ].into(), span: Span::test_data()}, Value::List{vals: vec![
                                     Value::Int{val: 27, span: Span::test_data()},
-                                ], span: Span::test_data()},
-                                Value::List{vals: vec![
+                                ].into(), span: Span::test_data()}, Value::List{vals: vec![
                                     Value::Int{val: 37, span: Span::test_data()},
-                                ], span: Span::test_data()},
-                            ], span: Span::test_data()}
-                        ], span: Span::test_data()
+                                ].into(), span: Span::test_data()}])
+                        ].into(), span: Span::test_data()
                     }
                 )
             }
@@ -141,13 +129,9 @@ fn process_row(
     head: Span,
 ) -> Value {
     match input {
-        Value::Record {
-            cols: _,
-            ref vals,
-            span,
-        } => {
+        Value::Record { ref val, span } => {
             if column_paths.is_empty() {
-                let is_empty = vals.iter().all(|v| v.clone().is_empty());
+                let is_empty = val.values().iter().all(|v| v.clone().is_empty());
                 if default_block.is_some() {
                     if is_empty {
                         Value::Bool { val: true, span }
@@ -103,12 +103,8 @@ fn flat_value(columns: &[CellPath], item: &Value, _name_tag: Span) -> Vec<Value>
             let mut a_table = None;
             let mut tables_explicitly_flattened = 0;
 
-            let records = match item {
-                Value::Record {
-                    cols,
-                    vals,
-                    span: _,
-                } => (cols, vals),
+            let record = match item {
+                Value::Record { val, span: _ } => val,
                 x => {
                     return vec![Value::Error {
                         error: ShellError::UnsupportedInput(
@@ -124,18 +120,16 @@ fn flat_value(columns: &[CellPath], item: &Value, _name_tag: Span) -> Vec<Value>
                 Err(e) => return vec![Value::Error { error: e }],
             };
 
-            for (column, value) in records.0.iter().zip(records.1.iter()) {
+            for (column, value) in record.iter() {
                 let column_requested = columns.iter().find(|c| c.into_string() == *column);
 
                 match value {
                     Value::List { vals, span: _ } if vals.iter().all(|f| f.as_record().is_ok()) => {
-                        let mut cs = vec![];
-                        let mut vs = vec![];
+                        let mut records = vec![];
 
                         for v in vals {
                             if let Ok(r) = v.as_record() {
-                                cs.push(r.0);
-                                vs.push(r.1)
+                                records.push(r)
                             }
                         }
 
@@ -148,14 +142,11 @@ fn flat_value(columns: &[CellPath], item: &Value, _name_tag: Span) -> Vec<Value>
                             continue;
                         }
 
-                        let cols = cs.into_iter().flat_map(|f| f.to_vec());
-                        let vals = vs.into_iter().flat_map(|f| f.to_vec());
-
-                        for (k, v) in cols.into_iter().zip(vals.into_iter()) {
-                            if out.contains_key(&k) {
+                        for (k, v) in records.into_iter().flat_map(|r| r.iter()) {
+                            if out.contains_key(k) {
                                 out.insert(format!("{}_{}", column.to_string(), k), v.clone());
                             } else {
-                                out.insert(k, v.clone());
+                                out.insert(k.clone(), v.clone());
                             }
                         }
                     }
@@ -217,19 +208,11 @@ fn flat_value(columns: &[CellPath], item: &Value, _name_tag: Span) -> Vec<Value>
                 for entry in entries {
                     let mut base = out.clone();
                     base.insert(column.to_string(), entry.clone());
-                    let r = Value::Record {
-                        cols: base.keys().map(|f| f.to_string()).collect::<Vec<_>>(),
-                        vals: base.values().cloned().collect(),
-                        span: tag,
-                    };
+                    let r = Value::record(base.into_iter().collect(), tag);
                     expanded.push(r);
                 }
             } else {
-                let r = Value::Record {
-                    cols: out.keys().map(|f| f.to_string()).collect::<Vec<_>>(),
-                    vals: out.values().cloned().collect(),
-                    span: tag,
-                };
+                let r = Value::record(out.into_iter().collect(), tag);
                 expanded.push(r);
             }
             expanded
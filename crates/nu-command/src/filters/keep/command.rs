@@ -33,27 +33,17 @@ impl Command for Keep {
                 example: "echo [[editions]; [2015] [2018] [2021]] | keep 2",
                 result: Some(Value::List {
                     vals: vec![
-                        Value::Record {
-                            cols: vec!["editions".to_owned()],
-                            vals: vec![Value::test_int(2015)],
-                            span: Span::test_data(),
-                        },
-                        Value::Record {
-                            cols: vec!["editions".to_owned()],
-                            vals: vec![Value::test_int(2018)],
-                            span: Span::test_data(),
-                        },
-                    ],
+                        Value::test_record(vec!["editions"], vec![Value::test_int(2015)]),
+                        Value::test_record(vec!["editions"], vec![Value::test_int(2018)]),
+                    ]
+                    .into(),
                     span: Span::test_data(),
                 }),
             },
             Example {
                 description: "Keep the first value",
                 example: "echo [2 4 6 8] | keep",
-                result: Some(Value::List {
-                    vals: vec![Value::test_int(2)],
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::list(vec![Value::test_int(2)], Span::test_data())),
             },
         ]
     }
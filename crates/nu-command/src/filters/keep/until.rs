@@ -32,10 +32,10 @@ impl Command for KeepUntil {
         vec![Example {
             description: "Keep until the element is positive",
             example: "echo [-1 -2 9 1] | keep until $it > 0",
-            result: Some(Value::List {
-                vals: vec![Value::test_int(-1), Value::test_int(-2)],
-                span: Span::test_data(),
-            }),
+            result: Some(Value::list(
+                vec![Value::test_int(-1), Value::test_int(-2)],
+                Span::test_data(),
+            )),
         }]
     }
 
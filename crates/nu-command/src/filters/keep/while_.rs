@@ -32,10 +32,10 @@ impl Command for KeepWhile {
         vec![Example {
             description: "Keep while the element is negative",
             example: "echo [-1 -2 9 1] | keep while $it < 0",
-            result: Some(Value::List {
-                vals: vec![Value::test_int(-1), Value::test_int(-2)],
-                span: Span::test_data(),
-            }),
+            result: Some(Value::list(
+                vec![Value::test_int(-1), Value::test_int(-2)],
+                Span::test_data(),
+            )),
         }]
     }
 
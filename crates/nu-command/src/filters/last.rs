@@ -33,10 +33,10 @@ impl Command for Last {
         vec![Example {
             example: "[1,2,3] | last 2",
             description: "Get the last 2 items",
-            result: Some(Value::List {
-                vals: vec![Value::test_int(2), Value::test_int(3)],
-                span: Span::test_data(),
-            }),
+            result: Some(Value::list(
+                vec![Value::test_int(2), Value::test_int(3)],
+                Span::test_data(),
+            )),
         }]
     }
 
@@ -64,10 +64,7 @@ impl Command for Lines {
                                     if skip_empty && s.is_empty() {
                                         None
                                     } else {
-                                        Some(Value::String {
-                                            val: s.into(),
-                                            span,
-                                        })
+                                        Some(Value::string(s, span))
                                     }
                                 })
                                 .collect::<Vec<Value>>();
@@ -81,6 +78,16 @@ impl Command for Lines {
 
                 Ok(iter.into_pipeline_data(engine_state.ctrlc.clone()))
             }
+            PipelineData::RawStream(stream, ..) => {
+                let span = call.head;
+                let iter = stream.lines().filter_map(move |line| match line {
+                    Ok(line) if skip_empty && line.is_empty() => None,
+                    Ok(line) => Some(Value::string(line, span)),
+                    Err(error) => Some(Value::Error { error }),
+                });
+
+                Ok(iter.into_pipeline_data(engine_state.ctrlc.clone()))
+            }
             PipelineData::Value(val, ..) => Err(ShellError::UnsupportedInput(
                 format!("Not supported input: {}", val.as_string()?),
                 call.head,
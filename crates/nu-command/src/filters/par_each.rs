@@ -1,11 +1,29 @@
-use nu_engine::eval_block;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Condvar, Mutex};
+use std::time::Duration;
+
+use lazy_static::lazy_static;
+
+use nu_engine::{eval_block, CallExt};
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, Signature,
-    SyntaxShape, Value,
+    record, Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData,
+    Record, ShellError, Signature, Span, SyntaxShape, Value, EVAL_STACK_SIZE,
 };
 use rayon::prelude::*;
+use rayon::ThreadPool;
+
+// How many results can wait to be picked up before the workers pause
+const OUTPUT_BUFFER_SIZE: usize = 64;
+
+lazy_static! {
+    // The last thread pool given back, with its number of threads, to be reused by the next call.
+    // Only one is kept, so that calls with many different numbers of threads don't leave a pool
+    // of idle threads behind for each
+    static ref IDLE_POOL: Mutex<Option<(usize, ThreadPool)>> = Mutex::new(None);
+}
 
 #[derive(Clone)]
 pub struct ParEach;
@@ -19,6 +37,10 @@ impl Command for ParEach {
         "Run a block on each element of input in parallel"
     }
 
+    fn extra_usage(&self) -> &str {
+        "The results are streamed out in the order of the input, unless --unordered is given, in which case they come out as soon as they're ready."
+    }
+
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("par-each")
             .required(
@@ -27,15 +49,41 @@ impl Command for ParEach {
                 "the block to run",
             )
             .switch("numbered", "iterate with an index", Some('n'))
+            .switch(
+                "unordered",
+                "output the results as they finish, not in input order",
+                Some('u'),
+            )
+            .named(
+                "threads",
+                SyntaxShape::Int,
+                "the number of threads to run the block on",
+                Some('t'),
+            )
             .category(Category::Filters)
     }
 
     fn examples(&self) -> Vec<Example> {
-        vec![Example {
-            example: "[1 2 3] | par-each { 2 * $it }",
-            description: "Multiplies elements in list",
-            result: None,
-        }]
+        vec![
+            Example {
+                example: "[1 2 3] | par-each { 2 * $it }",
+                description: "Multiplies elements in list",
+                result: Some(Value::list(
+                    vec![Value::test_int(2), Value::test_int(4), Value::test_int(6)],
+                    Span::test_data(),
+                )),
+            },
+            Example {
+                example: "ls | par-each --unordered { |it| open $it.name | size }",
+                description: "Get the size of each file, in the order they finish",
+                result: None,
+            },
+            Example {
+                example: "1..100 | par-each --threads 4 { 2 * $it }",
+                description: "Multiplies numbers using at most four threads",
+                result: None,
+            },
+        ]
     }
 
     fn run(
@@ -50,140 +98,36 @@ impl Command for ParEach {
             .expect("internal error: expected block");
 
         let numbered = call.has_flag("numbered");
+        let unordered = call.has_flag("unordered");
+        let threads: Option<i64> = call.get_flag(engine_state, stack, "threads")?;
         let ctrlc = engine_state.ctrlc.clone();
         let engine_state = engine_state.clone();
         let block = engine_state.get_block(block_id);
         let mut stack = stack.collect_captures(&block.captures);
         let span = call.head;
 
-        match input {
-            PipelineData::Value(Value::Range { val, .. }, ..) => Ok(val
-                .into_range_iter()?
-                .enumerate()
-                .par_bridge()
-                .map(move |(idx, x)| {
-                    let block = engine_state.get_block(block_id);
-
-                    let mut stack = stack.clone();
-
-                    if let Some(var) = block.signature.get_positional(0) {
-                        if let Some(var_id) = &var.var_id {
-                            if numbered {
-                                stack.add_var(
-                                    *var_id,
-                                    Value::Record {
-                                        cols: vec!["index".into(), "item".into()],
-                                        vals: vec![
-                                            Value::Int {
-                                                val: idx as i64,
-                                                span,
-                                            },
-                                            x,
-                                        ],
-                                        span,
-                                    },
-                                );
-                            } else {
-                                stack.add_var(*var_id, x);
-                            }
-                        }
-                    }
-
-                    match eval_block(&engine_state, &mut stack, block, PipelineData::new(span)) {
-                        Ok(v) => v,
-                        Err(error) => Value::Error { error }.into_pipeline_data(),
-                    }
-                })
-                .collect::<Vec<_>>()
-                .into_iter()
-                .flatten()
-                .into_pipeline_data(ctrlc)),
-            PipelineData::Value(Value::List { vals: val, .. }, ..) => Ok(val
-                .into_iter()
-                .enumerate()
-                .par_bridge()
-                .map(move |(idx, x)| {
-                    let block = engine_state.get_block(block_id);
-
-                    let mut stack = stack.clone();
-
-                    if let Some(var) = block.signature.get_positional(0) {
-                        if let Some(var_id) = &var.var_id {
-                            if numbered {
-                                stack.add_var(
-                                    *var_id,
-                                    Value::Record {
-                                        cols: vec!["index".into(), "item".into()],
-                                        vals: vec![
-                                            Value::Int {
-                                                val: idx as i64,
-                                                span,
-                                            },
-                                            x,
-                                        ],
-                                        span,
-                                    },
-                                );
-                            } else {
-                                stack.add_var(*var_id, x);
-                            }
-                        }
-                    }
-
-                    match eval_block(&engine_state, &mut stack, block, PipelineData::new(span)) {
-                        Ok(v) => v,
-                        Err(error) => Value::Error { error }.into_pipeline_data(),
-                    }
-                })
-                .collect::<Vec<_>>()
-                .into_iter()
-                .flatten()
-                .into_pipeline_data(ctrlc)),
-            PipelineData::Stream(stream, ..) => Ok(stream
-                .enumerate()
-                .par_bridge()
-                .map(move |(idx, x)| {
-                    let block = engine_state.get_block(block_id);
-
-                    let mut stack = stack.clone();
-
-                    if let Some(var) = block.signature.get_positional(0) {
-                        if let Some(var_id) = &var.var_id {
-                            if numbered {
-                                stack.add_var(
-                                    *var_id,
-                                    Value::Record {
-                                        cols: vec!["index".into(), "item".into()],
-                                        vals: vec![
-                                            Value::Int {
-                                                val: idx as i64,
-                                                span,
-                                            },
-                                            x,
-                                        ],
-                                        span,
-                                    },
-                                );
-                            } else {
-                                stack.add_var(*var_id, x);
-                            }
-                        }
-                    }
+        let threads = match threads {
+            Some(threads) if threads < 1 => {
+                return Err(ShellError::UnsupportedInput(
+                    "the number of threads has to be positive".into(),
+                    call.get_named_arg("threads")
+                        .map(|arg| arg.span)
+                        .unwrap_or(span),
+                ))
+            }
+            Some(threads) => threads as usize,
+            // Let rayon pick, based on the number of CPUs
+            None => 0,
+        };
 
-                    match eval_block(&engine_state, &mut stack, block, PipelineData::new(span)) {
-                        Ok(v) => v,
-                        Err(error) => Value::Error { error }.into_pipeline_data(),
-                    }
-                })
-                .collect::<Vec<_>>()
-                .into_iter()
-                .flatten()
-                .into_pipeline_data(ctrlc)),
-            PipelineData::Value(Value::Record { cols, vals, .. }, ..) => {
-                let mut output_cols = vec![];
-                let mut output_vals = vec![];
+        let items: Box<dyn Iterator<Item = Value> + Send> = match input {
+            PipelineData::Value(Value::Range { val, .. }, ..) => Box::new(val.into_range_iter()?),
+            PipelineData::Value(Value::List { vals, .. }, ..) => Box::new(vals.into_iter()),
+            PipelineData::Stream(stream, ..) => Box::new(stream),
+            PipelineData::Value(Value::Record { val: record, .. }, ..) => {
+                let mut output = Record::new();
 
-                for (col, val) in cols.into_iter().zip(vals.into_iter()) {
+                for (col, val) in record {
                     let block = engine_state.get_block(block_id);
 
                     let mut stack = stack.clone();
@@ -192,45 +136,37 @@ impl Command for ParEach {
                         if let Some(var_id) = &var.var_id {
                             stack.add_var(
                                 *var_id,
-                                Value::Record {
-                                    cols: vec!["column".into(), "value".into()],
-                                    vals: vec![
-                                        Value::String {
-                                            val: col.clone(),
-                                            span: call.head,
-                                        },
-                                        val,
-                                    ],
-                                    span: call.head,
-                                },
+                                Value::record(
+                                    record! {
+                                        "column" => Value::string(col.clone(), call.head),
+                                        "value" => val,
+                                    },
+                                    call.head,
+                                ),
                             );
                         }
                     }
 
                     match eval_block(&engine_state, &mut stack, block, PipelineData::new(span))? {
-                        PipelineData::Value(
-                            Value::Record {
-                                mut cols, mut vals, ..
-                            },
-                            ..,
-                        ) => {
-                            // TODO check that the lengths match when traversing record
-                            output_cols.append(&mut cols);
-                            output_vals.append(&mut vals);
+                        PipelineData::Value(Value::Record { val, .. }, ..) => {
+                            output.extend(val);
                         }
                         x => {
-                            output_cols.push(col);
-                            output_vals.push(x.into_value(span));
+                            output.insert(col, x.into_value(span));
                         }
                     }
                 }
 
-                Ok(Value::Record {
-                    cols: output_cols,
-                    vals: output_vals,
-                    span: call.head,
-                }
-                .into_pipeline_data())
+                return Ok(Value::record(output, call.head).into_pipeline_data());
+            }
+            // The raw output of an external is handed to the block a line at a time, as it's read
+            PipelineData::RawStream(stream, metadata) => {
+                return self.run(
+                    &engine_state,
+                    &mut stack,
+                    call,
+                    PipelineData::Stream(stream.into_line_stream(), metadata),
+                )
             }
             PipelineData::Value(x, ..) => {
                 let block = engine_state.get_block(block_id);
@@ -241,9 +177,215 @@ impl Command for ParEach {
                     }
                 }
 
-                eval_block(&engine_state, &mut stack, block, PipelineData::new(span))
+                return eval_block(&engine_state, &mut stack, block, PipelineData::new(span));
+            }
+        };
+
+        let pool = take_pool(threads)?;
+
+        // The workers stop picking up new items once ctrl-c is pressed, or once nobody is
+        // listening for the results anymore
+        let (tx, rx) = mpsc::sync_channel(OUTPUT_BUFFER_SIZE);
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let worker_ctrlc = ctrlc.clone();
+        let window = Arc::new(Window {
+            next: Mutex::new(0),
+            moved: Condvar::new(),
+        });
+        let worker_window = (!unordered).then(|| window.clone());
+
+        std::thread::spawn(move || {
+            let stopped = || {
+                cancelled.load(Ordering::SeqCst)
+                    || matches!(&worker_ctrlc, Some(ctrlc) if ctrlc.load(Ordering::SeqCst))
+            };
+
+            pool.install(|| {
+                items
+                    .enumerate()
+                    .take_while(|_| !stopped())
+                    .par_bridge()
+                    .for_each(|(idx, x)| {
+                        if stopped() {
+                            return;
+                        }
+
+                        if let Some(window) = &worker_window {
+                            if !window.wait_for(idx, &stopped) {
+                                return;
+                            }
+                        }
+
+                        let block = engine_state.get_block(block_id);
+
+                        let mut stack = stack.clone();
+
+                        if let Some(var) = block.signature.get_positional(0) {
+                            if let Some(var_id) = &var.var_id {
+                                if numbered {
+                                    stack.add_var(
+                                        *var_id,
+                                        Value::record(
+                                            record! {
+                                                "index" => Value::Int {
+                                                    val: idx as i64,
+                                                    span,
+                                                },
+                                                "item" => x,
+                                            },
+                                            span,
+                                        ),
+                                    );
+                                } else {
+                                    stack.add_var(*var_id, x);
+                                }
+                            }
+                        }
+
+                        let output = match eval_block(
+                            &engine_state,
+                            &mut stack,
+                            block,
+                            PipelineData::new(span),
+                        ) {
+                            Ok(v) => v.into_iter().collect(),
+                            Err(error) => vec![Value::Error { error }],
+                        };
+
+                        if tx.send((idx, output)).is_err() {
+                            cancelled.store(true, Ordering::SeqCst);
+                        }
+                    })
+            });
+
+            give_back_pool(threads, pool);
+        });
+
+        if unordered {
+            Ok(rx
+                .into_iter()
+                .flat_map(|(_, output)| output)
+                .into_pipeline_data(ctrlc))
+        } else {
+            Ok(InOrder {
+                results: rx,
+                pending: BTreeMap::new(),
+                next: 0,
+                current: vec![].into_iter(),
+                window,
+            }
+            .into_pipeline_data(ctrlc))
+        }
+    }
+}
+
+// A pool with the number of threads, 0 for as many as rayon picks. Calls running at the same time
+// each get a pool of their own: their workers wait on each other, as in a par-each inside the block
+// of another, or piped into another, so sharing threads could leave none to make progress
+fn take_pool(threads: usize) -> Result<ThreadPool, ShellError> {
+    if let Ok(mut idle) = IDLE_POOL.lock() {
+        if matches!(*idle, Some((idle_threads, _)) if idle_threads == threads) {
+            if let Some((_, pool)) = idle.take() {
+                return Ok(pool);
+            }
+        }
+    }
+
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .stack_size(EVAL_STACK_SIZE)
+        .build()
+        .map_err(|err| ShellError::NushellFailed(format!("{}", err)))
+}
+
+// The pool that was idle before is dropped, which lets its threads finish
+fn give_back_pool(threads: usize, pool: ThreadPool) {
+    if let Ok(mut idle) = IDLE_POOL.lock() {
+        *idle = Some((threads, pool));
+    }
+}
+
+// How far ahead of the results that went out the workers may get, so that the results waiting to
+// be put back in order stay within the output buffer, however long one item takes
+struct Window {
+    next: Mutex<usize>,
+    moved: Condvar,
+}
+
+impl Window {
+    // Waits until the item is within the window. Returns false if the workers were stopped meanwhile
+    fn wait_for(&self, idx: usize, stopped: &dyn Fn() -> bool) -> bool {
+        let mut next = match self.next.lock() {
+            Ok(next) => next,
+            Err(_) => return false,
+        };
+
+        while idx >= next.saturating_add(OUTPUT_BUFFER_SIZE) {
+            if stopped() {
+                return false;
             }
+            next = match self.moved.wait_timeout(next, Duration::from_millis(100)) {
+                Ok((next, _)) => next,
+                Err(_) => return false,
+            };
         }
+
+        true
+    }
+
+    fn move_to(&self, next: usize) {
+        if let Ok(mut current) = self.next.lock() {
+            *current = next;
+        }
+        self.moved.notify_all();
+    }
+}
+
+// Puts the results of the workers back in the order of the input. A result that finished early
+// waits until the ones before it are out
+struct InOrder {
+    results: mpsc::Receiver<(usize, Vec<Value>)>,
+    pending: BTreeMap<usize, Vec<Value>>,
+    next: usize,
+    current: std::vec::IntoIter<Value>,
+    window: Arc<Window>,
+}
+
+impl Iterator for InOrder {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(value) = self.current.next() {
+                return Some(value);
+            }
+
+            if let Some(output) = self.pending.remove(&self.next) {
+                self.next += 1;
+                self.window.move_to(self.next);
+                self.current = output.into_iter();
+                continue;
+            }
+
+            match self.results.recv() {
+                Ok((idx, output)) => {
+                    self.pending.insert(idx, output);
+                }
+                // The workers are done. If they were stopped early, the results we still hold
+                // may have gaps between them
+                Err(_) => {
+                    self.next = *self.pending.keys().next()?;
+                }
+            }
+        }
+    }
+}
+
+// Without anyone to take the results, the workers waiting for the window are let go, to find out
+// that nobody is listening
+impl Drop for InOrder {
+    fn drop(&mut self) {
+        self.window.move_to(usize::MAX);
     }
 }
 
@@ -257,4 +399,31 @@ mod test {
 
         test_examples(ParEach {})
     }
+
+    #[test]
+    fn workers_wait_for_the_window() {
+        let window = Arc::new(Window {
+            next: Mutex::new(0),
+            moved: Condvar::new(),
+        });
+
+        let waiting = window.clone();
+        let worker = std::thread::spawn(move || waiting.wait_for(OUTPUT_BUFFER_SIZE, &|| false));
+
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(!worker.is_finished());
+
+        window.move_to(1);
+        assert!(worker.join().expect("the worker shouldn't panic"));
+    }
+
+    #[test]
+    fn stopped_workers_stop_waiting_for_the_window() {
+        let window = Window {
+            next: Mutex::new(0),
+            moved: Condvar::new(),
+        };
+
+        assert!(!window.wait_for(OUTPUT_BUFFER_SIZE, &|| true));
+    }
 }
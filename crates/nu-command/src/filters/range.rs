@@ -34,26 +34,26 @@ impl Command for Range {
             Example {
                 example: "[0,1,2,3,4,5] | range 4..5",
                 description: "Get the last 2 items",
-                result: Some(Value::List {
-                    vals: vec![Value::test_int(4), Value::test_int(5)],
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::list(
+                    vec![Value::test_int(4), Value::test_int(5)],
+                    Span::test_data(),
+                )),
             },
             Example {
                 example: "[0,1,2,3,4,5] | range (-2)..",
                 description: "Get the last 2 items",
-                result: Some(Value::List {
-                    vals: vec![Value::test_int(4), Value::test_int(5)],
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::list(
+                    vec![Value::test_int(4), Value::test_int(5)],
+                    Span::test_data(),
+                )),
             },
             Example {
                 example: "[0,1,2,3,4,5] | range (-3)..-2",
                 description: "Get the next to last 2 items",
-                result: Some(Value::List {
-                    vals: vec![Value::test_int(3), Value::test_int(4)],
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::list(
+                    vec![Value::test_int(3), Value::test_int(4)],
+                    Span::test_data(),
+                )),
             },
         ]
     }
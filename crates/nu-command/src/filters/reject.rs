@@ -2,8 +2,8 @@ use nu_engine::CallExt;
 use nu_protocol::ast::{Call, CellPath};
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, FromValue, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, ShellError,
-    Signature, Span, SyntaxShape, Value,
+    Category, FromValue, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, Record,
+    ShellError, Signature, Span, SyntaxShape, Value,
 };
 
 #[derive(Clone)]
@@ -62,20 +62,18 @@ fn reject(
             ..,
         ) => {
             let mut output = vec![];
-            let input_cols = get_input_cols(input_vals.clone());
+            let input_cols = get_input_cols(input_vals.to_vec());
             let kc = get_keep_columns(input_cols, columns);
             keep_columns = get_cellpath_columns(kc, span);
 
             for input_val in input_vals {
-                let mut cols = vec![];
-                let mut vals = vec![];
+                let mut record = Record::new();
 
                 for path in &keep_columns {
                     let fetcher = input_val.clone().follow_cell_path(&path.members)?;
-                    cols.push(path.into_string());
-                    vals.push(fetcher);
+                    record.insert(path.into_string(), fetcher);
                 }
-                output.push(Value::Record { cols, vals, span })
+                output.push(Value::record(record, span))
             }
 
             Ok(output
@@ -91,33 +89,35 @@ fn reject(
             keep_columns = get_cellpath_columns(kc, span);
 
             for input_val in v {
-                let mut cols = vec![];
-                let mut vals = vec![];
+                let mut record = Record::new();
 
                 for path in &keep_columns {
                     let fetcher = input_val.clone().follow_cell_path(&path.members)?;
-                    cols.push(path.into_string());
-                    vals.push(fetcher);
+                    record.insert(path.into_string(), fetcher);
                 }
-                output.push(Value::Record { cols, vals, span })
+                output.push(Value::record(record, span))
             }
 
             Ok(output
                 .into_iter()
                 .into_pipeline_data(engine_state.ctrlc.clone()))
         }
+        PipelineData::RawStream(stream, metadata) => reject(
+            engine_state,
+            span,
+            PipelineData::Value(stream.into_value(), metadata),
+            columns,
+        ),
         PipelineData::Value(v, ..) => {
-            let mut cols = vec![];
-            let mut vals = vec![];
+            let mut record = Record::new();
 
             for cell_path in &keep_columns {
                 let result = v.clone().follow_cell_path(&cell_path.members)?;
 
-                cols.push(cell_path.into_string());
-                vals.push(result);
+                record.insert(cell_path.into_string(), result);
             }
 
-            Ok(Value::Record { cols, vals, span }.into_pipeline_data())
+            Ok(Value::record(record, span).into_pipeline_data())
         }
     }
 }
@@ -125,7 +125,7 @@ fn reject(
 fn get_input_cols(input: Vec<Value>) -> Vec<String> {
     let rec = input.first();
     match rec {
-        Some(Value::Record { cols, vals: _, .. }) => cols.to_vec(),
+        Some(Value::Record { val, .. }) => val.columns().to_vec(),
         _ => vec!["".to_string()],
     }
 }
@@ -133,10 +133,7 @@ fn get_input_cols(input: Vec<Value>) -> Vec<String> {
 fn get_cellpath_columns(keep_cols: Vec<String>, span: Span) -> Vec<CellPath> {
     let mut output = vec![];
     for keep_col in keep_cols {
-        let val = Value::String {
-            val: keep_col,
-            span,
-        };
+        let val = Value::string(keep_col, span);
         let cell_path = match CellPath::from_value(&val) {
             Ok(v) => v,
             Err(_) => return vec![],
@@ -31,7 +31,8 @@ impl Command for Reverse {
                     Value::test_int(2),
                     Value::test_int(1),
                     Value::test_int(0),
-                ],
+                ]
+                .into(),
                 span: Span::test_data(),
             }),
         }]
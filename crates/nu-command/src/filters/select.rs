@@ -2,8 +2,8 @@ use nu_engine::CallExt;
 use nu_protocol::ast::{Call, CellPath};
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, ShellError,
-    Signature, Span, SyntaxShape, Value,
+    Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, Record,
+    ShellError, Signature, Span, SyntaxShape, Value,
 };
 
 #[derive(Clone)]
@@ -38,6 +38,20 @@ impl Command for Select {
         let columns: Vec<CellPath> = call.rest(engine_state, stack, 0)?;
         let span = call.head;
 
+        // Checked once here, so the rows can't have a column twice
+        for (idx, column) in columns.iter().enumerate() {
+            let name = column.into_string();
+            if columns[..idx]
+                .iter()
+                .any(|other| other.into_string() == name)
+            {
+                return Err(ShellError::ColumnDefinedTwice(
+                    name,
+                    call.positional[idx].span,
+                ));
+            }
+        }
+
         select(engine_state, span, columns, input)
     }
 
@@ -78,17 +92,15 @@ fn select(
             let mut output = vec![];
 
             for input_val in input_vals {
-                let mut cols = vec![];
-                let mut vals = vec![];
+                let mut record = Record::new();
                 for path in &columns {
                     //FIXME: improve implementation to not clone
                     let fetcher = input_val.clone().follow_cell_path(&path.members)?;
 
-                    cols.push(path.into_string());
-                    vals.push(fetcher);
+                    record.insert(path.into_string(), fetcher);
                 }
 
-                output.push(Value::Record { cols, vals, span })
+                output.push(Value::record(record, span))
             }
 
             Ok(output
@@ -97,38 +109,39 @@ fn select(
         }
         PipelineData::Stream(stream, ..) => Ok(stream
             .map(move |x| {
-                let mut cols = vec![];
-                let mut vals = vec![];
+                let mut record = Record::new();
                 for path in &columns {
                     //FIXME: improve implementation to not clone
                     match x.clone().follow_cell_path(&path.members) {
                         Ok(value) => {
-                            cols.push(path.into_string());
-                            vals.push(value);
+                            record.insert(path.into_string(), value);
                         }
                         Err(error) => {
-                            cols.push(path.into_string());
-                            vals.push(Value::Error { error });
+                            record.insert(path.into_string(), Value::Error { error });
                         }
                     }
                 }
 
-                Value::Record { cols, vals, span }
+                Value::record(record, span)
             })
             .into_pipeline_data(engine_state.ctrlc.clone())),
+        PipelineData::RawStream(stream, metadata) => select(
+            engine_state,
+            span,
+            columns,
+            PipelineData::Stream(stream.into_line_stream(), metadata),
+        ),
         PipelineData::Value(v, ..) => {
-            let mut cols = vec![];
-            let mut vals = vec![];
+            let mut record = Record::new();
 
             for cell_path in columns {
                 // FIXME: remove clone
                 let result = v.clone().follow_cell_path(&cell_path.members)?;
 
-                cols.push(cell_path.into_string());
-                vals.push(result);
+                record.insert(cell_path.into_string(), result);
             }
 
-            Ok(Value::Record { cols, vals, span }.into_pipeline_data())
+            Ok(Value::record(record, span).into_pipeline_data())
         }
     }
 }
@@ -32,21 +32,21 @@ impl Command for Skip {
                 description: "Skip two elements",
                 example: "echo [[editions]; [2015] [2018] [2021]] | skip 2",
                 result: Some(Value::List {
-                    vals: vec![Value::Record {
-                        cols: vec!["editions".to_owned()],
-                        vals: vec![Value::test_int(2021)],
-                        span: Span::test_data(),
-                    }],
+                    vals: vec![Value::test_record(
+                        vec!["editions"],
+                        vec![Value::test_int(2021)],
+                    )]
+                    .into(),
                     span: Span::test_data(),
                 }),
             },
             Example {
                 description: "Skip the first value",
                 example: "echo [2 4 6 8] | skip",
-                result: Some(Value::List {
-                    vals: vec![Value::test_int(4), Value::test_int(6), Value::test_int(8)],
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::list(
+                    vec![Value::test_int(4), Value::test_int(6), Value::test_int(8)],
+                    Span::test_data(),
+                )),
             },
         ]
     }
@@ -32,10 +32,10 @@ impl Command for SkipUntil {
         vec![Example {
             description: "Skip until the element is positive",
             example: "echo [-2 0 2 -1] | skip until $it > 0",
-            result: Some(Value::List {
-                vals: vec![Value::test_int(2), Value::test_int(-1)],
-                span: Span::test_data(),
-            }),
+            result: Some(Value::list(
+                vec![Value::test_int(2), Value::test_int(-1)],
+                Span::test_data(),
+            )),
         }]
     }
 
@@ -32,10 +32,10 @@ impl Command for SkipWhile {
         vec![Example {
             description: "Skip while the element is negative",
             example: "echo [-2 0 2 -1] | skip while $it < 0",
-            result: Some(Value::List {
-                vals: vec![Value::test_int(0), Value::test_int(2), Value::test_int(-1)],
-                span: Span::test_data(),
-            }),
+            result: Some(Value::list(
+                vec![Value::test_int(0), Value::test_int(2), Value::test_int(-1)],
+                Span::test_data(),
+            )),
         }]
     }
 
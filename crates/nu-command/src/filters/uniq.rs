@@ -3,7 +3,7 @@ use std::collections::VecDeque;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Value,
+    record, Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Value,
 };
 
 #[derive(Clone)]
@@ -50,10 +50,10 @@ impl Command for Uniq {
             Example {
                 description: "Remove duplicate rows of a list/table",
                 example: "[2 3 3 4] | uniq",
-                result: Some(Value::List {
-                    vals: vec![Value::test_int(2), Value::test_int(3), Value::test_int(4)],
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::list(
+                    vec![Value::test_int(2), Value::test_int(3), Value::test_int(4)],
+                    Span::test_data(),
+                )),
             },
             Example {
                 description: "Only print duplicate lines, one for each group",
@@ -68,27 +68,26 @@ impl Command for Uniq {
             Example {
                 description: "Ignore differences in case when comparing",
                 example: "['hello' 'goodbye' 'Hello'] | uniq -i",
-                result: Some(Value::List {
-                    vals: vec![Value::test_string("hello"), Value::test_string("goodbye")],
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::list(
+                    vec![Value::test_string("hello"), Value::test_string("goodbye")],
+                    Span::test_data(),
+                )),
             },
             Example {
                 description: "Remove duplicate rows and show counts of a list/table",
                 example: "[1 2 2] | uniq -c",
                 result: Some(Value::List {
                     vals: vec![
-                        Value::Record {
-                            cols: vec!["value".to_string(), "count".to_string()],
-                            vals: vec![Value::test_int(1), Value::test_int(1)],
-                            span: Span::test_data(),
-                        },
-                        Value::Record {
-                            cols: vec!["value".to_string(), "count".to_string()],
-                            vals: vec![Value::test_int(2), Value::test_int(2)],
-                            span: Span::test_data(),
-                        },
-                    ],
+                        Value::test_record(
+                            vec!["value", "count"],
+                            vec![Value::test_int(1), Value::test_int(1)],
+                        ),
+                        Value::test_record(
+                            vec!["value", "count"],
+                            vec![Value::test_int(2), Value::test_int(2)],
+                        ),
+                    ]
+                    .into(),
                     span: Span::test_data(),
                 }),
             },
@@ -98,10 +97,7 @@ impl Command for Uniq {
 
 fn to_lowercase(value: nu_protocol::Value) -> nu_protocol::Value {
     match value {
-        Value::String { val: s, span } => Value::String {
-            val: s.to_lowercase(),
-            span,
-        },
+        Value::String { val: s, span } => Value::string(s.to_lowercase(), span),
         other => other,
     }
 }
@@ -156,19 +152,16 @@ fn uniq(
     if should_show_count {
         for item in values {
             values_vec_deque.push_back({
-                let cols = vec!["value".to_string(), "count".to_string()];
-                let vals = vec![
-                    item.0,
-                    Value::Int {
-                        val: item.1,
-                        span: head,
+                Value::record(
+                    record! {
+                        "value" => item.0,
+                        "count" => Value::Int {
+                            val: item.1,
+                            span: head,
+                        },
                     },
-                ];
-                Value::Record {
-                    cols,
-                    vals,
-                    span: head,
-                }
+                    head,
+                )
             });
         }
     } else {
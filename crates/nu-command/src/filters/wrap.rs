@@ -2,7 +2,7 @@ use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, Signature,
+    record, Category, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, Signature,
     SyntaxShape, Value,
 };
 
@@ -37,25 +37,18 @@ impl Command for Wrap {
         match input {
             PipelineData::Value(Value::List { vals, .. }, ..) => Ok(vals
                 .into_iter()
-                .map(move |x| Value::Record {
-                    cols: vec![name.clone()],
-                    vals: vec![x],
-                    span,
-                })
+                .map(move |x| Value::record(record! { name.clone() => x }, span))
                 .into_pipeline_data(engine_state.ctrlc.clone())),
             PipelineData::Stream(stream, ..) => Ok(stream
-                .map(move |x| Value::Record {
-                    cols: vec![name.clone()],
-                    vals: vec![x],
-                    span,
-                })
+                .map(move |x| Value::record(record! { name.clone() => x }, span))
                 .into_pipeline_data(engine_state.ctrlc.clone())),
-            PipelineData::Value(input, ..) => Ok(Value::Record {
-                cols: vec![name],
-                vals: vec![input],
-                span,
+            PipelineData::RawStream(stream, ..) => {
+                Ok(Value::record(record! { name => stream.into_value() }, span)
+                    .into_pipeline_data())
+            }
+            PipelineData::Value(input, ..) => {
+                Ok(Value::record(record! { name => input }, span).into_pipeline_data())
             }
-            .into_pipeline_data()),
         }
     }
 }
@@ -46,10 +46,7 @@ impl Command for Zip {
         Ok(input
             .into_iter()
             .zip(other.into_pipeline_data().into_iter())
-            .map(move |(x, y)| Value::List {
-                vals: vec![x, y],
-                span: head,
-            })
+            .map(move |(x, y)| Value::list(vec![x, y], head))
             .into_pipeline_data(ctrlc))
     }
 }
@@ -1,48 +1,70 @@
+use std::io::Read;
+
 use csv::ReaderBuilder;
-use nu_protocol::{Config, IntoPipelineData, PipelineData, ShellError, Span, Value};
+use nu_protocol::{
+    Config, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, Record, ShellError,
+    Span, Value,
+};
 
-fn from_delimited_string_to_value(
-    s: String,
+// The rows of the delimited data, parsed as they're read
+fn from_delimited_reader_to_rows(
+    reader: impl Read,
     noheaders: bool,
     separator: char,
     span: Span,
-) -> Result<Value, csv::Error> {
+) -> Result<impl Iterator<Item = Result<Value, csv::Error>>, ShellError> {
     let mut reader = ReaderBuilder::new()
         .has_headers(!noheaders)
         .delimiter(separator as u8)
-        .from_reader(s.as_bytes());
+        .from_reader(reader);
+
+    let headers = reader
+        .headers()
+        .map_err(|x| delimiter_error(x, span))?
+        .iter()
+        .map(String::from)
+        .collect::<Vec<String>>();
 
     let headers = if noheaders {
-        (1..=reader.headers()?.len())
+        (1..=headers.len())
             .map(|i| format!("Column{}", i))
             .collect::<Vec<String>>()
     } else {
-        reader.headers()?.iter().map(String::from).collect()
+        // Checked once here, so the rows can't have a column twice
+        for (idx, header) in headers.iter().enumerate() {
+            if headers[..idx].contains(header) {
+                return Err(ShellError::ColumnDefinedTwice(header.clone(), span));
+            }
+        }
+        headers
     };
 
-    let mut rows = vec![];
-    for row in reader.records() {
-        let mut output_row = vec![];
-        for value in row?.iter() {
-            if let Ok(i) = value.parse::<i64>() {
-                output_row.push(Value::Int { val: i, span });
+    Ok(reader.into_records().map(move |row| {
+        let row = row?;
+        let mut output_row = Record::with_capacity(headers.len());
+        for (header, value) in headers.iter().zip(row.iter()) {
+            let value = if let Ok(i) = value.parse::<i64>() {
+                Value::Int { val: i, span }
             } else if let Ok(f) = value.parse::<f64>() {
-                output_row.push(Value::Float { val: f, span });
+                Value::Float { val: f, span }
             } else {
-                output_row.push(Value::String {
-                    val: value.into(),
-                    span,
-                });
-            }
+                Value::string(value, span)
+            };
+            output_row.insert(header.clone(), value);
         }
-        rows.push(Value::Record {
-            cols: headers.clone(),
-            vals: output_row,
-            span,
-        });
-    }
 
-    Ok(Value::List { vals: rows, span })
+        Ok(Value::record(output_row, span))
+    }))
+}
+
+fn delimiter_error(error: csv::Error, span: Span) -> ShellError {
+    let message = error.to_string();
+
+    match error.into_kind() {
+        // Failing to read the input is the input's error, not one in the delimited data
+        csv::ErrorKind::Io(error) => error.into(),
+        _ => ShellError::DelimiterError(message, span),
+    }
 }
 
 pub fn from_delimited_data(
@@ -52,11 +74,24 @@ pub fn from_delimited_data(
     name: Span,
     config: &Config,
 ) -> Result<PipelineData, ShellError> {
-    let concat_string = input.collect_string("", config);
+    // The raw output of an external is parsed a row at a time as it comes in
+    if let PipelineData::RawStream(stream, ..) = input {
+        let ctrlc = stream.ctrlc.clone();
+        let rows = from_delimited_reader_to_rows(stream, noheaders, sep, name)?;
+
+        return Ok(rows
+            .map(move |row| {
+                row.unwrap_or_else(|x| Value::Error {
+                    error: delimiter_error(x, name),
+                })
+            })
+            .into_pipeline_data(ctrlc));
+    }
+
+    let concat_string = input.collect_string("", config)?;
+    let rows = from_delimited_reader_to_rows(concat_string.as_bytes(), noheaders, sep, name)?
+        .collect::<Result<Vec<Value>, csv::Error>>()
+        .map_err(|x| delimiter_error(x, name))?;
 
-    Ok(
-        from_delimited_string_to_value(concat_string, noheaders, sep, name)
-            .map_err(|x| ShellError::DelimiterError(x.to_string(), name))?
-            .into_pipeline_data(),
-    )
+    Ok(Value::list(rows, name).into_pipeline_data())
 }
@@ -7,7 +7,7 @@ use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::Category;
 use nu_protocol::Config;
 use nu_protocol::{
-    Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Value,
+    record, Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Value,
 };
 
 #[derive(Clone)]
@@ -58,35 +58,27 @@ Subject: Welcome
 To: someone@somewhere.com
 
 Test' | from eml",
-                result: Some(Value::Record {
-                    cols: vec![
-                        "Subject".to_string(),
-                        "From".to_string(),
-                        "To".to_string(),
-                        "Body".to_string(),
-                    ],
-                    vals: vec![
+                result: Some(Value::test_record(
+                    vec!["Subject", "From", "To", "Body"],
+                    vec![
                         Value::test_string("Welcome"),
-                        Value::Record {
-                            cols: vec!["Name".to_string(), "Address".to_string()],
-                            vals: vec![
+                        Value::test_record(
+                            vec!["Name", "Address"],
+                            vec![
                                 Value::nothing(Span::test_data()),
                                 Value::test_string("test@email.com"),
                             ],
-                            span: Span::test_data(),
-                        },
-                        Value::Record {
-                            cols: vec!["Name".to_string(), "Address".to_string()],
-                            vals: vec![
+                        ),
+                        Value::test_record(
+                            vec!["Name", "Address"],
+                            vec![
                                 Value::nothing(Span::test_data()),
                                 Value::test_string("someone@somewhere.com"),
                             ],
-                            span: Span::test_data(),
-                        },
+                        ),
                         Value::test_string("Test"),
                     ],
-                    span: Span::test_data(),
-                }),
+                )),
             },
             Example {
                 description: "Convert eml structured data into table",
@@ -95,35 +87,27 @@ Subject: Welcome
 To: someone@somewhere.com
 
 Test' | from eml -b 1",
-                result: Some(Value::Record {
-                    cols: vec![
-                        "Subject".to_string(),
-                        "From".to_string(),
-                        "To".to_string(),
-                        "Body".to_string(),
-                    ],
-                    vals: vec![
+                result: Some(Value::test_record(
+                    vec!["Subject", "From", "To", "Body"],
+                    vec![
                         Value::test_string("Welcome"),
-                        Value::Record {
-                            cols: vec!["Name".to_string(), "Address".to_string()],
-                            vals: vec![
+                        Value::test_record(
+                            vec!["Name", "Address"],
+                            vec![
                                 Value::nothing(Span::test_data()),
                                 Value::test_string("test@email.com"),
                             ],
-                            span: Span::test_data(),
-                        },
-                        Value::Record {
-                            cols: vec!["Name".to_string(), "Address".to_string()],
-                            vals: vec![
+                        ),
+                        Value::test_record(
+                            vec!["Name", "Address"],
+                            vec![
                                 Value::nothing(Span::test_data()),
                                 Value::test_string("someone@somewhere.com"),
                             ],
-                            span: Span::test_data(),
-                        },
+                        ),
                         Value::test_string("T"),
                     ],
-                    span: Span::test_data(),
-                }),
+                )),
             },
         ]
     }
@@ -133,28 +117,21 @@ fn emailaddress_to_value(span: Span, email_address: &EmailAddress) -> Value {
     let (n, a) = match email_address {
         EmailAddress::AddressOnly { address } => (
             Value::nothing(span),
-            Value::String {
-                val: address.to_string(),
-                span,
-            },
+            Value::string(address.to_string(), span),
         ),
         EmailAddress::NameAndEmailAddress { name, address } => (
-            Value::String {
-                val: name.to_string(),
-                span,
-            },
-            Value::String {
-                val: address.to_string(),
-                span,
-            },
+            Value::string(name.to_string(), span),
+            Value::string(address.to_string(), span),
         ),
     };
 
-    Value::Record {
-        cols: vec!["Name".to_string(), "Address".to_string()],
-        vals: vec![n, a],
+    Value::record(
+        record! {
+            "Name" => n,
+            "Address" => a,
+        },
         span,
-    }
+    )
 }
 
 fn headerfieldvalue_to_value(head: Span, value: &HeaderFieldValue) -> Value {
@@ -169,10 +146,7 @@ fn headerfieldvalue_to_value(head: Span, value: &HeaderFieldValue) -> Value {
                 .collect(),
             span: head,
         },
-        Unstructured(s) => Value::String {
-            val: s.to_string(),
-            span: head,
-        },
+        Unstructured(s) => Value::string(s.to_string(), head),
         Empty => Value::nothing(head),
     }
 }
@@ -183,7 +157,7 @@ fn from_eml(
     head: Span,
     config: &Config,
 ) -> Result<PipelineData, ShellError> {
-    let value = input.collect_string("", config);
+    let value = input.collect_string("", config)?;
 
     let body_preview = preview_body
         .map(|b| b.item as usize)
@@ -199,13 +173,7 @@ fn from_eml(
     let mut collected = IndexMap::new();
 
     if let Some(subj) = eml.subject {
-        collected.insert(
-            "Subject".to_string(),
-            Value::String {
-                val: subj,
-                span: head,
-            },
-        );
+        collected.insert("Subject".to_string(), Value::string(subj, head));
     }
 
     if let Some(from) = eml.from {
@@ -216,18 +184,25 @@ fn from_eml(
         collected.insert("To".to_string(), headerfieldvalue_to_value(head, &to));
     }
 
+    // A header can be given more than once, like Received is. Its values are kept as a list then
+    let mut headers: IndexMap<&str, Vec<Value>> = IndexMap::new();
     for HeaderField { name, value } in &eml.headers {
-        collected.insert(name.to_string(), headerfieldvalue_to_value(head, value));
+        headers
+            .entry(name.as_str())
+            .or_default()
+            .push(headerfieldvalue_to_value(head, value));
+    }
+    for (name, mut values) in headers {
+        let value = if values.len() == 1 {
+            values.remove(0)
+        } else {
+            Value::list(values, head)
+        };
+        collected.insert(name.to_string(), value);
     }
 
     if let Some(body) = eml.body {
-        collected.insert(
-            "Body".to_string(),
-            Value::String {
-                val: body,
-                span: head,
-            },
-        );
+        collected.insert("Body".to_string(), Value::string(body, head));
     }
 
     Ok(PipelineData::Value(
@@ -44,48 +44,27 @@ impl Command for FromIcs {
 END:VCALENDAR' | from ics",
             description: "Converts ics formatted string to table",
             result: Some(Value::List {
-                vals: vec![Value::Record {
-                    cols: vec![
-                        "properties".to_string(),
-                        "events".to_string(),
-                        "alarms".to_string(),
-                        "to-Dos".to_string(),
-                        "journals".to_string(),
-                        "free-busys".to_string(),
-                        "timezones".to_string(),
+                vals: vec![Value::test_record(
+                    vec![
+                        "properties",
+                        "events",
+                        "alarms",
+                        "to-Dos",
+                        "journals",
+                        "free-busys",
+                        "timezones",
                     ],
-                    vals: vec![
-                        Value::List {
-                            vals: vec![],
-                            span: Span::test_data(),
-                        },
-                        Value::List {
-                            vals: vec![],
-                            span: Span::test_data(),
-                        },
-                        Value::List {
-                            vals: vec![],
-                            span: Span::test_data(),
-                        },
-                        Value::List {
-                            vals: vec![],
-                            span: Span::test_data(),
-                        },
-                        Value::List {
-                            vals: vec![],
-                            span: Span::test_data(),
-                        },
-                        Value::List {
-                            vals: vec![],
-                            span: Span::test_data(),
-                        },
-                        Value::List {
-                            vals: vec![],
-                            span: Span::test_data(),
-                        },
+                    vec![
+                        Value::list(vec![], Span::test_data()),
+                        Value::list(vec![], Span::test_data()),
+                        Value::list(vec![], Span::test_data()),
+                        Value::list(vec![], Span::test_data()),
+                        Value::list(vec![], Span::test_data()),
+                        Value::list(vec![], Span::test_data()),
+                        Value::list(vec![], Span::test_data()),
                     ],
-                    span: Span::test_data(),
-                }],
+                )]
+                .into(),
                 span: Span::test_data(),
             }),
         }]
@@ -93,7 +72,7 @@ END:VCALENDAR' | from ics",
 }
 
 fn from_ics(input: PipelineData, head: Span, config: &Config) -> Result<PipelineData, ShellError> {
-    let input_string = input.collect_string("", config);
+    let input_string = input.collect_string("", config)?;
     let input_bytes = input_string.as_bytes();
     let buf_reader = BufReader::new(input_bytes);
     let parser = ical::IcalParser::new(buf_reader);
@@ -111,11 +90,7 @@ fn from_ics(input: PipelineData, head: Span, config: &Config) -> Result<Pipeline
             }),
         }
     }
-    Ok(Value::List {
-        vals: output,
-        span: head,
-    }
-    .into_pipeline_data())
+    Ok(Value::list(output, head).into_pipeline_data())
 }
 
 fn calendar_to_value(calendar: IcalCalendar, span: Span) -> Value {
@@ -157,7 +132,7 @@ fn events_to_value(events: Vec<IcalEvent>, span: Span) -> Value {
                 row.insert("alarms".to_string(), alarms_to_value(event.alarms, span));
                 Value::from(Spanned { item: row, span })
             })
-            .collect::<Vec<Value>>(),
+            .collect(),
         span,
     }
 }
@@ -174,7 +149,7 @@ fn alarms_to_value(alarms: Vec<IcalAlarm>, span: Span) -> Value {
                 );
                 Value::from(Spanned { item: row, span })
             })
-            .collect::<Vec<Value>>(),
+            .collect(),
         span,
     }
 }
@@ -192,7 +167,7 @@ fn todos_to_value(todos: Vec<IcalTodo>, span: Span) -> Value {
                 row.insert("alarms".to_string(), alarms_to_value(todo.alarms, span));
                 Value::from(Spanned { item: row, span })
             })
-            .collect::<Vec<Value>>(),
+            .collect(),
         span,
     }
 }
@@ -209,7 +184,7 @@ fn journals_to_value(journals: Vec<IcalJournal>, span: Span) -> Value {
                 );
                 Value::from(Spanned { item: row, span })
             })
-            .collect::<Vec<Value>>(),
+            .collect(),
         span,
     }
 }
@@ -226,7 +201,7 @@ fn free_busys_to_value(free_busys: Vec<IcalFreeBusy>, span: Span) -> Value {
                 );
                 Value::from(Spanned { item: row, span })
             })
-            .collect::<Vec<Value>>(),
+            .collect(),
         span,
     }
 }
@@ -247,7 +222,7 @@ fn timezones_to_value(timezones: Vec<IcalTimeZone>, span: Span) -> Value {
                 );
                 Value::from(Spanned { item: row, span })
             })
-            .collect::<Vec<Value>>(),
+            .collect(),
         span,
     }
 }
@@ -264,7 +239,7 @@ fn timezone_transitions_to_value(transitions: Vec<IcalTimeZoneTransition>, span:
                 );
                 Value::from(Spanned { item: row, span })
             })
-            .collect::<Vec<Value>>(),
+            .collect(),
         span,
     }
 }
@@ -276,12 +251,9 @@ fn properties_to_value(properties: Vec<Property>, span: Span) -> Value {
             .map(|prop| {
                 let mut row = IndexMap::new();
 
-                let name = Value::String {
-                    val: prop.name,
-                    span,
-                };
+                let name = Value::string(prop.name, span);
                 let value = match prop.value {
-                    Some(val) => Value::String { val, span },
+                    Some(val) => Value::string(val, span),
                     None => Value::nothing(span),
                 };
                 let params = match prop.params {
@@ -294,24 +266,27 @@ fn properties_to_value(properties: Vec<Property>, span: Span) -> Value {
                 row.insert("params".to_string(), params);
                 Value::from(Spanned { item: row, span })
             })
-            .collect::<Vec<Value>>(),
+            .collect(),
         span,
     }
 }
 
+// A param given more than once has the values of each time it's given
 fn params_to_value(params: Vec<(String, Vec<String>)>, span: Span) -> Value {
-    let mut row = IndexMap::new();
+    let mut row: IndexMap<String, Vec<Value>> = IndexMap::new();
 
     for (param_name, param_values) in params {
-        let values: Vec<Value> = param_values
-            .into_iter()
-            .map(|val| Value::string(val, span))
-            .collect();
-        let values = Value::List { vals: values, span };
-        row.insert(param_name, values);
+        row.entry(param_name)
+            .or_default()
+            .extend(param_values.into_iter().map(|val| Value::string(val, span)));
     }
 
-    Value::from(Spanned { item: row, span })
+    Value::record(
+        row.into_iter()
+            .map(|(param_name, values)| (param_name, Value::list(values, span)))
+            .collect(),
+        span,
+    )
 }
 
 #[cfg(test)]
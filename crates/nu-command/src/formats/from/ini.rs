@@ -27,24 +27,16 @@ impl Command for FromIni {
 a=1
 b=2' | from ini",
             description: "Converts ini formatted string to table",
-            result: Some(Value::Record {
-                cols: vec!["foo".to_string()],
-                vals: vec![Value::Record {
-                    cols: vec!["a".to_string(), "b".to_string()],
-                    vals: vec![
-                        Value::String {
-                            val: "1".to_string(),
-                            span: Span::test_data(),
-                        },
-                        Value::String {
-                            val: "2".to_string(),
-                            span: Span::test_data(),
-                        },
+            result: Some(Value::test_record(
+                vec!["foo"],
+                vec![Value::test_record(
+                    vec!["a", "b"],
+                    vec![
+                        Value::string("1", Span::test_data()),
+                        Value::string("2", Span::test_data()),
                     ],
-                    span: Span::test_data(),
-                }],
-                span: Span::test_data(),
-            }),
+                )],
+            )),
         }]
     }
 
@@ -66,19 +58,17 @@ pub fn from_ini_string_to_value(s: String, span: Span) -> Result<Value, ShellErr
         serde_ini::from_str(&s);
     match v {
         Ok(index_map) => {
-            let (cols, vals) = index_map
+            let record = index_map
                 .into_iter()
-                .fold((vec![], vec![]), |mut acc, (k, v)| {
-                    let (cols, vals) = v.into_iter().fold((vec![], vec![]), |mut acc, (k, v)| {
-                        acc.0.push(k);
-                        acc.1.push(Value::String { val: v, span });
-                        acc
-                    });
-                    acc.0.push(k);
-                    acc.1.push(Value::Record { cols, vals, span });
-                    acc
-                });
-            Ok(Value::Record { cols, vals, span })
+                .map(|(section, entries)| {
+                    let entries = entries
+                        .into_iter()
+                        .map(|(k, v)| (k, Value::string(v, span)))
+                        .collect();
+                    (section, Value::record(entries, span))
+                })
+                .collect();
+            Ok(Value::record(record, span))
         }
         Err(err) => Err(ShellError::UnsupportedInput(
             format!("Could not load ini: {}", err),
@@ -88,7 +78,7 @@ pub fn from_ini_string_to_value(s: String, span: Span) -> Result<Value, ShellErr
 }
 
 fn from_ini(input: PipelineData, head: Span, config: &Config) -> Result<PipelineData, ShellError> {
-    let concat_string = input.collect_string("", config);
+    let concat_string = input.collect_string("", config)?;
 
     match from_ini_string_to_value(concat_string, head) {
         Ok(x) => Ok(x.into_pipeline_data()),
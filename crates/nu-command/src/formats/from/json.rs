@@ -1,8 +1,8 @@
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, ShellError,
-    Signature, Span, Value,
+    Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, Record,
+    ShellError, Signature, Span, Value,
 };
 
 #[derive(Clone)]
@@ -28,21 +28,20 @@ impl Command for FromJson {
             Example {
                 example: "'{ a:1 }' | from json",
                 description: "Converts json formatted string to table",
-                result: Some(Value::Record {
-                    cols: vec!["a".to_string()],
-                    vals: vec![Value::Int {
+                result: Some(Value::test_record(
+                    vec!["a"],
+                    vec![Value::Int {
                         val: 1,
                         span: Span::test_data(),
                     }],
-                    span: Span::test_data(),
-                }),
+                )),
             },
             Example {
                 example: "'{ a:1, b: [1, 2] }' | from json",
                 description: "Converts json formatted string to table",
-                result: Some(Value::Record {
-                    cols: vec!["a".to_string(), "b".to_string()],
-                    vals: vec![
+                result: Some(Value::test_record(
+                    vec!["a", "b"],
+                    vec![
                         Value::Int {
                             val: 1,
                             span: Span::test_data(),
@@ -57,12 +56,12 @@ impl Command for FromJson {
                                     val: 2,
                                     span: Span::test_data(),
                                 },
-                            ],
+                            ]
+                            .into(),
                             span: Span::test_data(),
                         },
                     ],
-                    span: Span::test_data(),
-                }),
+                )),
             },
         ]
     }
@@ -76,7 +75,28 @@ impl Command for FromJson {
     ) -> Result<nu_protocol::PipelineData, ShellError> {
         let span = call.head;
         let config = stack.get_config().unwrap_or_default();
-        let mut string_input = input.collect_string("", &config);
+
+        // Objects in the raw output of an external are parsed a line at a time as it comes in
+        if call.has_flag("objects") {
+            if let PipelineData::RawStream(stream, ..) = input {
+                let ctrlc = stream.ctrlc.clone();
+                return Ok(stream
+                    .lines()
+                    .filter(|line| !matches!(line, Ok(line) if line.trim().is_empty()))
+                    .map(move |line| {
+                        match line.and_then(|mut line| {
+                            line.push('\n');
+                            convert_string_to_value(line, span)
+                        }) {
+                            Ok(v) => v,
+                            Err(error) => Value::Error { error },
+                        }
+                    })
+                    .into_pipeline_data(ctrlc));
+            }
+        }
+
+        let mut string_input = input.collect_string("", &config)?;
         string_input.push('\n');
 
         // TODO: turn this into a structured underline of the nu_json error
@@ -107,22 +127,20 @@ fn convert_nujson_to_value(value: &nu_json::Value, span: Span) -> Value {
                 .map(|x| convert_nujson_to_value(x, span))
                 .collect();
 
-            Value::List { vals: v, span }
+            Value::list(v, span)
         }
         nu_json::Value::Bool(b) => Value::Bool { val: *b, span },
         nu_json::Value::F64(f) => Value::Float { val: *f, span },
         nu_json::Value::I64(i) => Value::Int { val: *i, span },
         nu_json::Value::Null => Value::Nothing { span },
         nu_json::Value::Object(k) => {
-            let mut cols = vec![];
-            let mut vals = vec![];
+            let mut record = Record::new();
 
             for item in k {
-                cols.push(item.0.clone());
-                vals.push(convert_nujson_to_value(item.1, span));
+                record.insert(item.0.clone(), convert_nujson_to_value(item.1, span));
             }
 
-            Value::Record { cols, vals, span }
+            Value::record(record, span)
         }
         nu_json::Value::U64(u) => {
             if *u > i64::MAX as u64 {
@@ -140,10 +158,7 @@ fn convert_nujson_to_value(value: &nu_json::Value, span: Span) -> Value {
                 }
             }
         }
-        nu_json::Value::String(s) => Value::String {
-            val: s.clone(),
-            span,
-        },
+        nu_json::Value::String(s) => Value::string(s.clone(), span),
     }
 }
 
@@ -152,9 +167,8 @@ fn convert_string_to_value(string_input: String, span: Span) -> Result<Value, Sh
     match result {
         Ok(value) => Ok(convert_nujson_to_value(&value, span)),
 
-        Err(_x) => Err(ShellError::CantConvert(
-            "structured data from json".into(),
-            "string".into(),
+        Err(err) => Err(ShellError::UnsupportedInput(
+            format!("Could not load json: {}", err),
             span,
         )),
     }
@@ -1,10 +1,9 @@
-use indexmap::map::IndexMap;
 use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Spanned,
-    SyntaxShape, Value,
+    Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, RawStream,
+    Record, ShellError, Signature, Span, Spanned, SyntaxShape, Value,
 };
 
 #[derive(Clone)]
@@ -43,13 +42,13 @@ impl Command for FromSsv {
             example: r#"'FOO   BAR
 1   2' | from ssv"#,
             description: "Converts ssv formatted string to table",
-            result: Some(Value::List { vals: vec![Value::Record { cols: vec!["FOO".to_string(), "BAR".to_string()], vals: vec![Value::String { val: "1".to_string(), span: Span::test_data() }, Value::String { val: "2".to_string(), span: Span::test_data() }], span: Span::test_data() }], span: Span::test_data() }),
+            result: Some(Value::List { vals: vec![Value::test_record(vec!["FOO", "BAR"], vec![Value::String { val: "1".into(), span: Span::test_data() }, Value::String { val: "2".into(), span: Span::test_data() }])].into(), span: Span::test_data() }),
         }, Example {
             example: r#"'FOO   BAR
 1   2' | from ssv -n"#,
             description: "Converts ssv formatted string to table but not treating the first row as column names",
             result: Some(
-                Value::List { vals: vec![Value::Record { cols: vec!["Column1".to_string(), "Column2".to_string()], vals: vec![Value::String { val: "FOO".to_string(), span: Span::test_data() }, Value::String { val: "BAR".to_string(), span: Span::test_data() }], span: Span::test_data() }, Value::Record { cols: vec!["Column1".to_string(), "Column2".to_string()], vals: vec![Value::String { val: "1".to_string(), span: Span::test_data() }, Value::String { val: "2".to_string(), span: Span::test_data() }], span: Span::test_data() }], span: Span::test_data() }),
+                Value::List { vals: vec![Value::test_record(vec!["Column1", "Column2"], vec![Value::String { val: "FOO".into(), span: Span::test_data() }, Value::String { val: "BAR".into(), span: Span::test_data() }]), Value::test_record(vec!["Column1", "Column2"], vec![Value::String { val: "1".into(), span: Span::test_data() }, Value::String { val: "2".into(), span: Span::test_data() }])].into(), span: Span::test_data() }),
         }]
     }
 
@@ -234,31 +233,71 @@ fn string_to_table(
     f(ls, header_options, &separator)
 }
 
+fn ssv_row_to_value(row: &[(String, String)], span: Span) -> Result<Value, ShellError> {
+    let mut record = Record::with_capacity(row.len());
+    for (col, entry) in row {
+        record.try_insert(col, Value::string(entry, span), span)?;
+    }
+    Ok(Value::record(record, span))
+}
+
 fn from_ssv_string_to_value(
     s: &str,
     noheaders: bool,
     aligned_columns: bool,
     split_at: usize,
     span: Span,
-) -> Value {
+) -> Result<Value, ShellError> {
     let rows = string_to_table(s, noheaders, aligned_columns, split_at)
         .iter()
-        .map(|row| {
-            let mut dict = IndexMap::new();
-            for (col, entry) in row {
-                dict.insert(
-                    col.to_string(),
-                    Value::String {
-                        val: entry.to_string(),
-                        span,
-                    },
-                );
-            }
-            Value::from(Spanned { item: dict, span })
-        })
-        .collect();
+        .map(|row| ssv_row_to_value(row, span))
+        .collect::<Result<_, _>>()?;
+
+    Ok(Value::List { vals: rows, span })
+}
+
+// Each row only needs the header line to be parsed, so the raw output of an external is parsed a
+// line at a time as it comes in
+fn from_ssv_stream_with_headers(
+    stream: RawStream,
+    aligned_columns: bool,
+    split_at: usize,
+    span: Span,
+) -> Result<PipelineData, ShellError> {
+    let ctrlc = stream.ctrlc.clone();
+    let mut lines = stream
+        .lines()
+        .map(|line| line.map(|line| line.trim_end_matches('\r').to_string()))
+        .filter(|line| !matches!(line, Ok(line) if line.trim().is_empty()));
+
+    let header = match lines.next() {
+        Some(header) => header?,
+        None => return Ok(Value::list(vec![], span).into_pipeline_data()),
+    };
+    let separator = " ".repeat(std::cmp::max(split_at, 1));
 
-    Value::List { vals: rows, span }
+    Ok(lines
+        .flat_map(move |line| {
+            let line = match line {
+                Ok(line) => line,
+                Err(error) => return vec![Value::Error { error }],
+            };
+            let rows = std::iter::once(line.as_str());
+            let headers = HeaderOptions::WithHeaders(&header);
+
+            let table = if aligned_columns {
+                parse_aligned_columns(rows, headers, &separator)
+            } else {
+                parse_separated_columns(rows, headers, &separator)
+            };
+            table
+                .iter()
+                .map(|row| {
+                    ssv_row_to_value(row, span).unwrap_or_else(|error| Value::Error { error })
+                })
+                .collect()
+        })
+        .into_pipeline_data(ctrlc))
 }
 
 fn from_ssv(
@@ -275,14 +314,23 @@ fn from_ssv(
     let minimum_spaces: Option<Spanned<usize>> =
         call.get_flag(engine_state, stack, "minimum-spaces")?;
 
-    let concat_string = input.collect_string("", &config);
     let split_at = match minimum_spaces {
         Some(number) => number.item,
         None => DEFAULT_MINIMUM_SPACES,
     };
 
+    // Without headers, the columns depend on every row, so all of them are read first
+    let input = match input {
+        PipelineData::RawStream(stream, ..) if !noheaders => {
+            return from_ssv_stream_with_headers(stream, aligned_columns, split_at, name)
+        }
+        input => input,
+    };
+
+    let concat_string = input.collect_string("", &config)?;
+
     Ok(
-        from_ssv_string_to_value(&concat_string, noheaders, aligned_columns, split_at, name)
+        from_ssv_string_to_value(&concat_string, noheaders, aligned_columns, split_at, name)?
             .into_pipeline_data(),
     )
 }
@@ -1,7 +1,7 @@
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Value,
+    Category, Example, IntoPipelineData, PipelineData, Record, ShellError, Signature, Span, Value,
 };
 
 #[derive(Clone)]
@@ -25,22 +25,21 @@ impl Command for FromToml {
             Example {
                 example: "'a = 1' | from toml",
                 description: "Converts toml formatted string to table",
-                result: Some(Value::Record {
-                    cols: vec!["a".to_string()],
-                    vals: vec![Value::Int {
+                result: Some(Value::test_record(
+                    vec!["a"],
+                    vec![Value::Int {
                         val: 1,
                         span: Span::test_data(),
                     }],
-                    span: Span::test_data(),
-                }),
+                )),
             },
             Example {
                 example: "'a = 1
 b = [1, 2]' | from toml",
                 description: "Converts toml formatted string to table",
-                result: Some(Value::Record {
-                    cols: vec!["a".to_string(), "b".to_string()],
-                    vals: vec![
+                result: Some(Value::test_record(
+                    vec!["a", "b"],
+                    vec![
                         Value::Int {
                             val: 1,
                             span: Span::test_data(),
@@ -55,12 +54,12 @@ b = [1, 2]' | from toml",
                                     val: 2,
                                     span: Span::test_data(),
                                 },
-                            ],
+                            ]
+                            .into(),
                             span: Span::test_data(),
                         },
                     ],
-                    span: Span::test_data(),
-                }),
+                )),
             },
         ]
     }
@@ -74,7 +73,7 @@ b = [1, 2]' | from toml",
     ) -> Result<nu_protocol::PipelineData, ShellError> {
         let span = call.head;
         let config = stack.get_config().unwrap_or_default();
-        let mut string_input = input.collect_string("", &config);
+        let mut string_input = input.collect_string("", &config)?;
         string_input.push('\n');
         Ok(convert_string_to_value(string_input, span)?.into_pipeline_data())
     }
@@ -88,30 +87,22 @@ fn convert_toml_to_value(value: &toml::Value, span: Span) -> Value {
                 .map(|x| convert_toml_to_value(x, span))
                 .collect();
 
-            Value::List { vals: v, span }
+            Value::list(v, span)
         }
         toml::Value::Boolean(b) => Value::Bool { val: *b, span },
         toml::Value::Float(f) => Value::Float { val: *f, span },
         toml::Value::Integer(i) => Value::Int { val: *i, span },
         toml::Value::Table(k) => {
-            let mut cols = vec![];
-            let mut vals = vec![];
+            let mut record = Record::new();
 
             for item in k {
-                cols.push(item.0.clone());
-                vals.push(convert_toml_to_value(item.1, span));
+                record.insert(item.0.clone(), convert_toml_to_value(item.1, span));
             }
 
-            Value::Record { cols, vals, span }
+            Value::record(record, span)
         }
-        toml::Value::String(s) => Value::String {
-            val: s.clone(),
-            span,
-        },
-        toml::Value::Datetime(d) => Value::String {
-            val: d.to_string(),
-            span,
-        },
+        toml::Value::String(s) => Value::string(s.clone(), span),
+        toml::Value::Datetime(d) => Value::string(d.to_string(), span),
     }
 }
 
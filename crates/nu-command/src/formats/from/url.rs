@@ -1,6 +1,8 @@
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, Config, Example, PipelineData, ShellError, Signature, Span, Value};
+use nu_protocol::{
+    Category, Config, Example, PipelineData, Record, ShellError, Signature, Span, Value,
+};
 
 #[derive(Clone)]
 pub struct FromUrl;
@@ -34,47 +36,32 @@ impl Command for FromUrl {
         vec![Example {
             example: "'bread=baguette&cheese=comt%C3%A9&meat=ham&fat=butter' | from url",
             description: "Convert url encoded string into a table",
-            result: Some(Value::Record {
-                cols: vec![
-                    "bread".to_string(),
-                    "cheese".to_string(),
-                    "meat".to_string(),
-                    "fat".to_string(),
-                ],
-                vals: vec![
+            result: Some(Value::test_record(
+                vec!["bread", "cheese", "meat", "fat"],
+                vec![
                     Value::test_string("baguette"),
                     Value::test_string("comté"),
                     Value::test_string("ham"),
                     Value::test_string("butter"),
                 ],
-                span: Span::test_data(),
-            }),
+            )),
         }]
     }
 }
 
 fn from_url(input: PipelineData, head: Span, config: &Config) -> Result<PipelineData, ShellError> {
-    let concat_string = input.collect_string("", config);
+    let concat_string = input.collect_string("", config)?;
 
     let result = serde_urlencoded::from_str::<Vec<(String, String)>>(&concat_string);
 
     match result {
         Ok(result) => {
-            let mut cols = vec![];
-            let mut vals = vec![];
+            let mut record = Record::with_capacity(result.len());
             for (k, v) in result {
-                cols.push(k);
-                vals.push(Value::String { val: v, span: head })
+                record.try_insert(k, Value::string(v, head), head)?;
             }
 
-            Ok(PipelineData::Value(
-                Value::Record {
-                    cols,
-                    vals,
-                    span: head,
-                },
-                None,
-            ))
+            Ok(PipelineData::Value(Value::record(record, head), None))
         }
         _ => Err(ShellError::UnsupportedInput(
             "String not compatible with url-encoding".to_string(),
@@ -45,78 +45,46 @@ EMAIL:foo@bar.com
 END:VCARD' | from vcf",
             description: "Converts ics formatted string to table",
             result: Some(Value::List {
-                vals: vec![Value::Record {
-                    cols: vec!["properties".to_string()],
-                    vals: vec![Value::List {
+                vals: vec![Value::test_record(
+                    vec!["properties"],
+                    vec![Value::List {
                         vals: vec![
-                            Value::Record {
-                                cols: vec![
-                                    "name".to_string(),
-                                    "value".to_string(),
-                                    "params".to_string(),
-                                ],
-                                vals: vec![
-                                    Value::String {
-                                        val: "N".to_string(),
-                                        span: Span::test_data(),
-                                    },
-                                    Value::String {
-                                        val: "Foo".to_string(),
-                                        span: Span::test_data(),
-                                    },
+                            Value::test_record(
+                                vec!["name", "value", "params"],
+                                vec![
+                                    Value::string("N", Span::test_data()),
+                                    Value::string("Foo", Span::test_data()),
                                     Value::Nothing {
                                         span: Span::test_data(),
                                     },
                                 ],
-                                span: Span::test_data(),
-                            },
-                            Value::Record {
-                                cols: vec![
-                                    "name".to_string(),
-                                    "value".to_string(),
-                                    "params".to_string(),
-                                ],
-                                vals: vec![
-                                    Value::String {
-                                        val: "FN".to_string(),
-                                        span: Span::test_data(),
-                                    },
-                                    Value::String {
-                                        val: "Bar".to_string(),
-                                        span: Span::test_data(),
-                                    },
+                            ),
+                            Value::test_record(
+                                vec!["name", "value", "params"],
+                                vec![
+                                    Value::string("FN", Span::test_data()),
+                                    Value::string("Bar", Span::test_data()),
                                     Value::Nothing {
                                         span: Span::test_data(),
                                     },
                                 ],
-                                span: Span::test_data(),
-                            },
-                            Value::Record {
-                                cols: vec![
-                                    "name".to_string(),
-                                    "value".to_string(),
-                                    "params".to_string(),
-                                ],
-                                vals: vec![
-                                    Value::String {
-                                        val: "EMAIL".to_string(),
-                                        span: Span::test_data(),
-                                    },
-                                    Value::String {
-                                        val: "foo@bar.com".to_string(),
-                                        span: Span::test_data(),
-                                    },
+                            ),
+                            Value::test_record(
+                                vec!["name", "value", "params"],
+                                vec![
+                                    Value::string("EMAIL", Span::test_data()),
+                                    Value::string("foo@bar.com", Span::test_data()),
                                     Value::Nothing {
                                         span: Span::test_data(),
                                     },
                                 ],
-                                span: Span::test_data(),
-                            },
-                        ],
+                            ),
+                        ]
+                        .into(),
                         span: Span::test_data(),
                     }],
-                    span: Span::test_data(),
-                }],
+                )]
+                .into(),
                 span: Span::test_data(),
             }),
         }]
@@ -124,7 +92,7 @@ END:VCARD' | from vcf",
 }
 
 fn from_vcf(input: PipelineData, head: Span, config: &Config) -> Result<PipelineData, ShellError> {
-    let input_string = input.collect_string("", config);
+    let input_string = input.collect_string("", config)?;
     let input_bytes = input_string.as_bytes();
     let cursor = std::io::Cursor::new(input_bytes);
     let parser = ical::VcardParser::new(cursor);
@@ -137,11 +105,7 @@ fn from_vcf(input: PipelineData, head: Span, config: &Config) -> Result<Pipeline
     });
 
     let collected: Vec<_> = iter.collect();
-    Ok(Value::List {
-        vals: collected,
-        span: head,
-    }
-    .into_pipeline_data())
+    Ok(Value::list(collected, head).into_pipeline_data())
 }
 
 fn contact_to_value(contact: VcardContact, span: Span) -> Value {
@@ -160,12 +124,9 @@ fn properties_to_value(properties: Vec<Property>, span: Span) -> Value {
             .map(|prop| {
                 let mut row = IndexMap::new();
 
-                let name = Value::String {
-                    val: prop.name,
-                    span,
-                };
+                let name = Value::string(prop.name, span);
                 let value = match prop.value {
-                    Some(val) => Value::String { val, span },
+                    Some(val) => Value::string(val, span),
                     None => Value::Nothing { span },
                 };
                 let params = match prop.params {
@@ -178,24 +139,27 @@ fn properties_to_value(properties: Vec<Property>, span: Span) -> Value {
                 row.insert("params".to_string(), params);
                 Value::from(Spanned { item: row, span })
             })
-            .collect::<Vec<Value>>(),
+            .collect(),
         span,
     }
 }
 
+// A param given more than once has the values of each time it's given
 fn params_to_value(params: Vec<(String, Vec<String>)>, span: Span) -> Value {
-    let mut row = IndexMap::new();
+    let mut row: IndexMap<String, Vec<Value>> = IndexMap::new();
 
     for (param_name, param_values) in params {
-        let values: Vec<Value> = param_values
-            .into_iter()
-            .map(|val| Value::string(val, span))
-            .collect();
-        let values = Value::List { vals: values, span };
-        row.insert(param_name, values);
+        row.entry(param_name)
+            .or_default()
+            .extend(param_values.into_iter().map(|val| Value::string(val, span)));
     }
 
-    Value::from(Spanned { item: row, span })
+    Value::record(
+        row.into_iter()
+            .map(|(param_name, values)| (param_name, Value::list(values, span)))
+            .collect(),
+        span,
+    )
 }
 
 #[cfg(test)]
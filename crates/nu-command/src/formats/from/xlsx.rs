@@ -1,10 +1,9 @@
 use calamine::*;
-use indexmap::map::IndexMap;
 use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Value,
+    Category, Example, PipelineData, Record, ShellError, Signature, Span, SyntaxShape, Value,
 };
 use std::io::Cursor;
 
@@ -71,7 +70,7 @@ fn convert_columns(columns: &[Value], span: Span) -> Result<Vec<String>, ShellEr
     let res = columns
         .iter()
         .map(|value| match &value {
-            Value::String { val: s, .. } => Ok(s.clone()),
+            Value::String { val: s, .. } => Ok(s.to_string()),
             _ => Err(ShellError::IncompatibleParametersSingle(
                 "Incorrect column format, Only string as column name".to_string(),
                 value.span().unwrap_or(span),
@@ -83,6 +82,11 @@ fn convert_columns(columns: &[Value], span: Span) -> Result<Vec<String>, ShellEr
 }
 
 fn collect_binary(input: PipelineData, span: Span) -> Result<Vec<u8>, ShellError> {
+    // The raw output of an external is taken as it is, chunks and all
+    if let PipelineData::RawStream(stream, ..) = input {
+        return stream.into_bytes();
+    }
+
     let mut bytes = vec![];
     let mut values = input.into_iter();
 
@@ -114,7 +118,7 @@ fn from_xlsx(
     let mut xlsx = Xlsx::<_>::new(buf)
         .map_err(|_| ShellError::UnsupportedInput("Could not load xlsx file".to_string(), head))?;
 
-    let mut dict = IndexMap::new();
+    let mut dict = Record::new();
 
     let mut sheet_names = xlsx.sheet_names().to_owned();
     if !sel_sheets.is_empty() {
@@ -126,7 +130,7 @@ fn from_xlsx(
 
         if let Some(Ok(current_sheet)) = xlsx.worksheet_range(sheet_name) {
             for row in current_sheet.rows() {
-                let mut row_output = IndexMap::new();
+                let mut row_output = Record::new();
                 for (i, cell) in row.iter().enumerate() {
                     let value = match cell {
                         DataType::Empty => Value::nothing(head),
@@ -149,31 +153,10 @@ fn from_xlsx(
                     row_output.insert(format!("Column{}", i), value);
                 }
 
-                let (cols, vals) =
-                    row_output
-                        .into_iter()
-                        .fold((vec![], vec![]), |mut acc, (k, v)| {
-                            acc.0.push(k);
-                            acc.1.push(v);
-                            acc
-                        });
-
-                let record = Value::Record {
-                    cols,
-                    vals,
-                    span: head,
-                };
-
-                sheet_output.push(record);
+                sheet_output.push(Value::record(row_output, head));
             }
 
-            dict.insert(
-                sheet_name,
-                Value::List {
-                    vals: sheet_output,
-                    span: head,
-                },
-            );
+            dict.insert(sheet_name, Value::list(sheet_output, head));
         } else {
             return Err(ShellError::UnsupportedInput(
                 "Could not load sheet".to_string(),
@@ -182,19 +165,7 @@ fn from_xlsx(
         }
     }
 
-    let (cols, vals) = dict.into_iter().fold((vec![], vec![]), |mut acc, (k, v)| {
-        acc.0.push(k.clone());
-        acc.1.push(v);
-        acc
-    });
-
-    let record = Value::Record {
-        cols,
-        vals,
-        span: head,
-    };
-
-    Ok(PipelineData::Value(record, None))
+    Ok(PipelineData::Value(Value::record(dict, head), None))
 }
 
 #[cfg(test)]
@@ -2,7 +2,7 @@ use indexmap::map::IndexMap;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Config, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span,
+    Category, Config, Example, IntoPipelineData, PipelineData, Record, ShellError, Signature, Span,
     Spanned, Value,
 };
 
@@ -41,65 +41,44 @@ impl Command for FromXml {
   <remember>Event</remember>
 </note>' | from xml"#,
             description: "Converts xml formatted string to table",
-            result: Some(Value::Record {
-                cols: vec!["note".to_string()],
-                vals: vec![Value::Record {
-                    cols: vec!["children".to_string(), "attributes".to_string()],
-                    vals: vec![
+            result: Some(Value::test_record(
+                vec!["note"],
+                vec![Value::test_record(
+                    vec!["children", "attributes"],
+                    vec![
                         Value::List {
-                            vals: vec![Value::Record {
-                                cols: vec!["remember".to_string()],
-                                vals: vec![Value::Record {
-                                    cols: vec!["children".to_string(), "attributes".to_string()],
-                                    vals: vec![
+                            vals: vec![Value::test_record(
+                                vec!["remember"],
+                                vec![Value::test_record(
+                                    vec!["children", "attributes"],
+                                    vec![
                                         Value::List {
-                                            vals: vec![Value::String {
-                                                val: "Event".to_string(),
-                                                span: Span::test_data(),
-                                            }],
-                                            span: Span::test_data(),
-                                        },
-                                        Value::Record {
-                                            cols: vec![],
-                                            vals: vec![],
+                                            vals: vec![Value::string("Event", Span::test_data())]
+                                                .into(),
                                             span: Span::test_data(),
                                         },
+                                        Value::record(Record::new(), Span::test_data()),
                                     ],
-                                    span: Span::test_data(),
-                                }],
-                                span: Span::test_data(),
-                            }],
-                            span: Span::test_data(),
-                        },
-                        Value::Record {
-                            cols: vec![],
-                            vals: vec![],
+                                )],
+                            )]
+                            .into(),
                             span: Span::test_data(),
                         },
+                        Value::record(Record::new(), Span::test_data()),
                     ],
-                    span: Span::test_data(),
-                }],
-                span: Span::test_data(),
-            }),
+                )],
+            )),
         }]
     }
 }
 
 fn from_attributes_to_value(attributes: &[roxmltree::Attribute], span: Span) -> Value {
-    let mut collected = IndexMap::new();
+    let mut collected = Record::new();
     for a in attributes {
-        collected.insert(String::from(a.name()), Value::string(a.value(), span));
+        collected.insert(a.name(), Value::string(a.value(), span));
     }
 
-    let (cols, vals) = collected
-        .into_iter()
-        .fold((vec![], vec![]), |mut acc, (k, v)| {
-            acc.0.push(k);
-            acc.1.push(v);
-            acc
-        });
-
-    Value::Record { cols, vals, span }
+    Value::record(collected, span)
 }
 
 fn from_node_to_value(n: &roxmltree::Node, span: Span) -> Value {
@@ -126,13 +105,7 @@ fn from_node_to_value(n: &roxmltree::Node, span: Span) -> Value {
         let attribute_value: Value = from_attributes_to_value(n.attributes(), span);
 
         let mut row = IndexMap::new();
-        row.insert(
-            String::from("children"),
-            Value::List {
-                vals: children_values,
-                span,
-            },
-        );
+        row.insert(String::from("children"), Value::list(children_values, span));
         row.insert(String::from("attributes"), attribute_value);
         collected.insert(name, Value::from(Spanned { item: row, span }));
 
@@ -141,31 +114,16 @@ fn from_node_to_value(n: &roxmltree::Node, span: Span) -> Value {
             span,
         })
     } else if n.is_comment() {
-        Value::String {
-            val: "<comment>".to_string(),
-            span,
-        }
+        Value::string("<comment>", span)
     } else if n.is_pi() {
-        Value::String {
-            val: "<processing_instruction>".to_string(),
-            span,
-        }
+        Value::string("<processing_instruction>", span)
     } else if n.is_text() {
         match n.text() {
-            Some(text) => Value::String {
-                val: text.to_string(),
-                span,
-            },
-            None => Value::String {
-                val: "<error>".to_string(),
-                span,
-            },
+            Some(text) => Value::string(text.to_string(), span),
+            None => Value::string("<error>", span),
         }
     } else {
-        Value::String {
-            val: "<unknown>".to_string(),
-            span,
-        }
+        Value::string("<unknown>", span)
     }
 }
 
@@ -179,7 +137,7 @@ pub fn from_xml_string_to_value(s: String, span: Span) -> Result<Value, roxmltre
 }
 
 fn from_xml(input: PipelineData, head: Span, config: &Config) -> Result<PipelineData, ShellError> {
-    let concat_string = input.collect_string("", config);
+    let concat_string = input.collect_string("", config)?;
 
     match from_xml_string_to_value(concat_string, head) {
         Ok(x) => Ok(x.into_pipeline_data()),
@@ -199,10 +157,7 @@ mod tests {
     use nu_protocol::{Spanned, Value};
 
     fn string(input: impl Into<String>) -> Value {
-        Value::String {
-            val: input.into(),
-            span: Span::test_data(),
-        }
+        Value::string(input, Span::test_data())
     }
 
     fn row(entries: IndexMap<String, Value>) -> Value {
@@ -213,10 +168,7 @@ mod tests {
     }
 
     fn table(list: &[Value]) -> Value {
-        Value::List {
-            vals: list.to_vec(),
-            span: Span::test_data(),
-        }
+        Value::list(list.to_vec(), Span::test_data())
     }
 
     fn parse(xml: &str) -> Result<Value, roxmltree::Error> {
@@ -29,34 +29,29 @@ impl Command for FromYaml {
             Example {
                 example: "'a: 1' | from yaml",
                 description: "Converts yaml formatted string to table",
-                result: Some(Value::Record {
-                    cols: vec!["a".to_string()],
-                    vals: vec![Value::Int {
+                result: Some(Value::test_record(
+                    vec!["a"],
+                    vec![Value::Int {
                         val: 1,
                         span: Span::test_data(),
                     }],
-                    span: Span::test_data(),
-                }),
+                )),
             },
             Example {
                 example: "'[ a: 1, b: [1, 2] ]' | from yaml",
                 description: "Converts yaml formatted string to table",
                 result: Some(Value::List {
                     vals: vec![
-                        Value::Record {
-                            cols: vec!["a".to_string()],
-                            vals: vec![Value::test_int(1)],
-                            span: Span::test_data(),
-                        },
-                        Value::Record {
-                            cols: vec!["b".to_string()],
-                            vals: vec![Value::List {
-                                vals: vec![Value::test_int(1), Value::test_int(2)],
-                                span: Span::test_data(),
-                            }],
-                            span: Span::test_data(),
-                        },
-                    ],
+                        Value::test_record(vec!["a"], vec![Value::test_int(1)]),
+                        Value::test_record(
+                            vec!["b"],
+                            vec![Value::list(
+                                vec![Value::test_int(1), Value::test_int(2)],
+                                Span::test_data(),
+                            )],
+                        ),
+                    ]
+                    .into(),
                     span: Span::test_data(),
                 }),
             },
@@ -118,19 +113,13 @@ fn convert_yaml_value_to_nu_value(v: &serde_yaml::Value, span: Span) -> Result<V
             val: n.as_f64().ok_or(err_not_compatible_number)?,
             span,
         },
-        serde_yaml::Value::String(s) => Value::String {
-            val: s.to_string(),
-            span,
-        },
+        serde_yaml::Value::String(s) => Value::string(s.to_string(), span),
         serde_yaml::Value::Sequence(a) => {
             let result: Result<Vec<Value>, ShellError> = a
                 .iter()
                 .map(|x| convert_yaml_value_to_nu_value(x, span))
                 .collect();
-            Value::List {
-                vals: result?,
-                span,
-            }
+            Value::list(result?, span)
         }
         serde_yaml::Value::Mapping(t) => {
             let mut collected = Spanned {
@@ -163,10 +152,7 @@ fn convert_yaml_value_to_nu_value(v: &serde_yaml::Value, span: Span) -> Result<V
                             .first()
                             .and_then(|e| match e {
                                 (serde_yaml::Value::String(s), serde_yaml::Value::Null) => {
-                                    Some(Value::String {
-                                        val: "{{ ".to_owned() + s + " }}",
-                                        span,
-                                    })
+                                    Some(Value::string("{{ ".to_owned() + s + " }}", span))
                                 }
                                 _ => None,
                             })
@@ -198,15 +184,12 @@ pub fn from_yaml_string_to_value(s: String, span: Span) -> Result<Value, ShellEr
     match documents.len() {
         0 => Ok(Value::nothing(span)),
         1 => Ok(documents.remove(0)),
-        _ => Ok(Value::List {
-            vals: documents,
-            span,
-        }),
+        _ => Ok(Value::list(documents, span)),
     }
 }
 
 fn from_yaml(input: PipelineData, head: Span, config: &Config) -> Result<PipelineData, ShellError> {
-    let concat_string = input.collect_string("", config);
+    let concat_string = input.collect_string("", config)?;
 
     match from_yaml_string_to_value(concat_string, head) {
         Ok(x) => Ok(x.into_pipeline_data()),
@@ -229,26 +212,18 @@ mod test {
             TestCase {
                 description: "Double Curly Braces With Quotes",
                 input: r#"value: "{{ something }}""#,
-                expected: Ok(Value::Record {
-                    cols: vec!["value".to_string()],
-                    vals: vec![Value::String {
-                        val: "{{ something }}".to_string(),
-                        span: Span::test_data(),
-                    }],
-                    span: Span::test_data(),
-                }),
+                expected: Ok(Value::test_record(
+                    vec!["value"],
+                    vec![Value::string("{{ something }}", Span::test_data())],
+                )),
             },
             TestCase {
                 description: "Double Curly Braces Without Quotes",
                 input: r#"value: {{ something }}"#,
-                expected: Ok(Value::Record {
-                    cols: vec!["value".to_string()],
-                    vals: vec![Value::String {
-                        val: "{{ something }}".to_string(),
-                        span: Span::test_data(),
-                    }],
-                    span: Span::test_data(),
-                }),
+                expected: Ok(Value::test_record(
+                    vec!["value"],
+                    vec![Value::string("{{ something }}", Span::test_data())],
+                )),
             },
         ];
         let config = Config::default();
@@ -10,14 +10,14 @@ fn from_value_to_delimited_string(
     head: Span,
 ) -> Result<String, ShellError> {
     match value {
-        Value::Record { cols, vals, span } => {
+        Value::Record { val, span } => {
             let mut wtr = WriterBuilder::new()
                 .delimiter(separator as u8)
                 .from_writer(vec![]);
             let mut fields: VecDeque<String> = VecDeque::new();
             let mut values: VecDeque<String> = VecDeque::new();
 
-            for (k, v) in cols.iter().zip(vals.iter()) {
+            for (k, v) in val.iter() {
                 fields.push_back(k.clone());
 
                 values.push_back(to_string_tagged_value(v, config, *span)?);
@@ -106,7 +106,7 @@ pub fn merge_descriptors(values: &[Value]) -> Vec<String> {
     let mut seen: IndexSet<String> = indexset! {};
     for value in values {
         let data_descriptors = match value {
-            Value::Record { cols, .. } => cols.to_owned(),
+            Value::Record { val, .. } => val.columns().to_vec(),
             _ => vec!["".to_string()],
         };
         for desc in data_descriptors {
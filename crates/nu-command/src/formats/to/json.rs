@@ -73,9 +73,9 @@ pub fn value_to_json_value(v: &Value) -> Result<nu_json::Value, ShellError> {
         Value::Binary { val, .. } => {
             nu_json::Value::Array(val.iter().map(|x| nu_json::Value::U64(*x as u64)).collect())
         }
-        Value::Record { cols, vals, .. } => {
+        Value::Record { val, .. } => {
             let mut m = nu_json::Map::new();
-            for (k, v) in cols.iter().zip(vals) {
+            for (k, v) in val.iter() {
                 m.insert(k.clone(), value_to_json_value(v)?);
             }
             nu_json::Value::Object(m)
@@ -101,11 +101,7 @@ fn to_json(call: &Call, input: PipelineData) -> Result<PipelineData, ShellError>
 
     let json_value = value_to_json_value(&value)?;
     match nu_json::to_string(&json_value) {
-        Ok(serde_json_string) => Ok(Value::String {
-            val: serde_json_string,
-            span,
-        }
-        .into_pipeline_data()),
+        Ok(serde_json_string) => Ok(Value::string(serde_json_string, span).into_pipeline_data()),
         _ => Ok(Value::Error {
             error: ShellError::CantConvert("JSON".into(), value.get_type().to_string(), span),
         }
@@ -120,11 +116,7 @@ fn to_json_raw(call: &Call, input: PipelineData) -> Result<PipelineData, ShellEr
 
     let json_value = value_to_json_value(&value)?;
     match nu_json::to_string_raw(&json_value) {
-        Ok(serde_json_string) => Ok(Value::String {
-            val: serde_json_string,
-            span,
-        }
-        .into_pipeline_data()),
+        Ok(serde_json_string) => Ok(Value::string(serde_json_string, span).into_pipeline_data()),
         _ => Ok(Value::Error {
             error: ShellError::CantConvert("JSON".into(), value.get_type().to_string(), span),
         }
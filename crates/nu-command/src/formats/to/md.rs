@@ -99,7 +99,7 @@ fn to_md(
 
 fn fragment(input: Value, pretty: bool, config: &Config) -> String {
     let headers = match input {
-        Value::Record { ref cols, .. } => cols.to_owned(),
+        Value::Record { ref val, .. } => val.columns().to_vec(),
         _ => vec![],
     };
     let mut out = String::new();
@@ -200,9 +200,12 @@ pub fn group_by(values: PipelineData, head: Span, config: &Config) -> (PipelineD
     let mut lists = IndexMap::new();
     let mut single_list = false;
     for val in values {
-        if let Value::Record { ref cols, .. } = val {
+        if let Value::Record {
+            val: ref record, ..
+        } = val
+        {
             lists
-                .entry(cols.concat())
+                .entry(record.columns().concat())
                 .and_modify(|v: &mut Vec<Value>| v.push(val.clone()))
                 .or_insert_with(|| vec![val.clone()]);
         } else {
@@ -217,23 +220,13 @@ pub fn group_by(values: PipelineData, head: Span, config: &Config) -> (PipelineD
         if value.len() == 1 {
             output.push(value.pop().unwrap_or_else(|| Value::nothing(head)))
         } else {
-            output.push(Value::List {
-                vals: value.to_vec(),
-                span: head,
-            })
+            output.push(Value::list(value.to_vec(), head))
         }
     }
     if output.len() == 1 {
         single_list = true;
     }
-    (
-        Value::List {
-            vals: output,
-            span: head,
-        }
-        .into_pipeline_data(),
-        single_list,
-    )
+    (Value::list(output, head).into_pipeline_data(), single_list)
 }
 
 fn get_output_string(
@@ -349,44 +342,28 @@ mod tests {
 
     #[test]
     fn render_h1() {
-        let value = Value::Record {
-            cols: vec!["H1".to_string()],
-            vals: vec![Value::test_string("Ecuador")],
-            span: Span::test_data(),
-        };
+        let value = Value::test_record(vec!["H1"], vec![Value::test_string("Ecuador")]);
 
         assert_eq!(fragment(value, false, &Config::default()), "# Ecuador\n");
     }
 
     #[test]
     fn render_h2() {
-        let value = Value::Record {
-            cols: vec!["H2".to_string()],
-            vals: vec![Value::test_string("Ecuador")],
-            span: Span::test_data(),
-        };
+        let value = Value::test_record(vec!["H2"], vec![Value::test_string("Ecuador")]);
 
         assert_eq!(fragment(value, false, &Config::default()), "## Ecuador\n");
     }
 
     #[test]
     fn render_h3() {
-        let value = Value::Record {
-            cols: vec!["H3".to_string()],
-            vals: vec![Value::test_string("Ecuador")],
-            span: Span::test_data(),
-        };
+        let value = Value::test_record(vec!["H3"], vec![Value::test_string("Ecuador")]);
 
         assert_eq!(fragment(value, false, &Config::default()), "### Ecuador\n");
     }
 
     #[test]
     fn render_blockquote() {
-        let value = Value::Record {
-            cols: vec!["BLOCKQUOTE".to_string()],
-            vals: vec![Value::test_string("Ecuador")],
-            span: Span::test_data(),
-        };
+        let value = Value::test_record(vec!["BLOCKQUOTE"], vec![Value::test_string("Ecuador")]);
 
         assert_eq!(fragment(value, false, &Config::default()), "> Ecuador\n");
     }
@@ -395,22 +372,11 @@ mod tests {
     fn render_table() {
         let value = Value::List {
             vals: vec![
-                Value::Record {
-                    cols: vec!["country".to_string()],
-                    vals: vec![Value::test_string("Ecuador")],
-                    span: Span::test_data(),
-                },
-                Value::Record {
-                    cols: vec!["country".to_string()],
-                    vals: vec![Value::test_string("New Zealand")],
-                    span: Span::test_data(),
-                },
-                Value::Record {
-                    cols: vec!["country".to_string()],
-                    vals: vec![Value::test_string("USA")],
-                    span: Span::test_data(),
-                },
-            ],
+                Value::test_record(vec!["country"], vec![Value::test_string("Ecuador")]),
+                Value::test_record(vec!["country"], vec![Value::test_string("New Zealand")]),
+                Value::test_record(vec!["country"], vec![Value::test_string("USA")]),
+            ]
+            .into(),
             span: Span::test_data(),
         };
 
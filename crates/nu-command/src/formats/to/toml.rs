@@ -51,10 +51,10 @@ fn helper(v: &Value) -> Result<toml::Value, ShellError> {
         Value::Date { val, .. } => toml::Value::String(val.to_string()),
         Value::Range { .. } => toml::Value::String("<Range>".to_string()),
         Value::Float { val, .. } => toml::Value::Float(*val),
-        Value::String { val, .. } => toml::Value::String(val.clone()),
-        Value::Record { cols, vals, .. } => {
+        Value::String { val, .. } => toml::Value::String(val.to_string()),
+        Value::Record { val, .. } => {
             let mut m = toml::map::Map::new();
-            for (k, v) in cols.iter().zip(vals.iter()) {
+            for (k, v) in val.iter() {
                 m.insert(k.clone(), helper(v)?);
             }
             toml::Value::Table(m)
@@ -97,11 +97,7 @@ fn toml_into_pipeline_data(
     span: Span,
 ) -> Result<PipelineData, ShellError> {
     match toml::to_string(&toml_value) {
-        Ok(serde_toml_string) => Ok(Value::String {
-            val: serde_toml_string,
-            span,
-        }
-        .into_pipeline_data()),
+        Ok(serde_toml_string) => Ok(Value::string(serde_toml_string, span).into_pipeline_data()),
         _ => Ok(Value::Error {
             error: ShellError::CantConvert("TOML".into(), value_type.to_string(), span),
         }
@@ -175,10 +171,10 @@ mod tests {
         m.insert("is".to_owned(), Value::nothing(Span::test_data()));
         m.insert(
             "features".to_owned(),
-            Value::List {
-                vals: vec![Value::test_string("hello"), Value::test_string("array")],
-                span: Span::test_data(),
-            },
+            Value::list(
+                vec![Value::test_string("hello"), Value::test_string("array")],
+                Span::test_data(),
+            ),
         );
         let tv = value_to_toml_value(
             &Value::from(Spanned {
@@ -224,10 +220,7 @@ mod tests {
         value_to_toml_value(&Value::test_string("not_valid"), Span::test_data())
             .expect_err("Expected non-valid toml (String) to cause error!");
         value_to_toml_value(
-            &Value::List {
-                vals: vec![Value::test_string("1")],
-                span: Span::test_data(),
-            },
+            &Value::list(vec![Value::test_string("1")], Span::test_data()),
             Span::test_data(),
         )
         .expect_err("Expected non-valid toml (Table) to cause error!");
@@ -44,11 +44,9 @@ fn to_url(input: PipelineData, head: Span) -> Result<PipelineData, ShellError> {
     let output: Result<String, ShellError> = input
         .into_iter()
         .map(move |value| match value {
-            Value::Record {
-                ref cols, ref vals, ..
-            } => {
+            Value::Record { ref val, .. } => {
                 let mut row_vec = vec![];
-                for (k, v) in cols.iter().zip(vals.iter()) {
+                for (k, v) in val.iter() {
                     match v.as_string() {
                         Ok(s) => {
                             row_vec.push((k.clone(), s.to_string()));
@@ -7,7 +7,6 @@ use nu_protocol::{
     Spanned, SyntaxShape, Value,
 };
 use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
-use std::collections::HashSet;
 use std::io::Cursor;
 use std::io::Write;
 
@@ -78,9 +77,9 @@ pub fn add_attributes<'a>(
 
 pub fn get_attributes(row: &Value, config: &Config) -> Option<IndexMap<String, String>> {
     if let Value::Record { .. } = row {
-        if let Some(Value::Record { cols, vals, .. }) = row.get_data_by_key("attributes") {
+        if let Some(Value::Record { val, .. }) = row.get_data_by_key("attributes") {
             let mut h = IndexMap::new();
-            for (k, v) in cols.iter().zip(vals.iter()) {
+            for (k, v) in val.iter() {
                 h.insert(k.clone(), v.clone().into_abbreviated_string(config));
             }
             return Some(h);
@@ -92,18 +91,15 @@ pub fn get_attributes(row: &Value, config: &Config) -> Option<IndexMap<String, S
 pub fn get_children(row: &Value) -> Option<Vec<Value>> {
     if let Value::Record { .. } = row {
         if let Some(Value::List { vals, .. }) = row.get_data_by_key("children") {
-            return Some(vals);
+            return Some(vals.into_inner());
         }
     }
     None
 }
 
 pub fn is_xml_row(row: &Value) -> bool {
-    if let Value::Record { cols, .. } = &row {
-        let keys: HashSet<&String> = cols.iter().collect();
-        let children: String = "children".to_string();
-        let attributes: String = "attributes".to_string();
-        return keys.contains(&children) && keys.contains(&attributes) && keys.len() == 2;
+    if let Value::Record { val, .. } = &row {
+        return val.contains("children") && val.contains("attributes") && val.len() == 2;
     }
     false
 }
@@ -114,8 +110,8 @@ pub fn write_xml_events<W: Write>(
     config: &Config,
 ) -> Result<(), ShellError> {
     match current {
-        Value::Record { cols, vals, span } => {
-            for (k, v) in cols.iter().zip(vals.iter()) {
+        Value::Record { val, span } => {
+            for (k, v) in val.iter() {
                 let mut e = BytesStart::owned(k.as_bytes(), k.len());
                 if !is_xml_row(v) {
                     return Err(ShellError::SpannedLabeledError(
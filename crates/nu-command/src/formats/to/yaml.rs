@@ -49,10 +49,10 @@ pub fn value_to_yaml_value(v: &Value) -> Result<serde_yaml::Value, ShellError> {
         Value::Date { val, .. } => serde_yaml::Value::String(val.to_string()),
         Value::Range { .. } => serde_yaml::Value::Null,
         Value::Float { val, .. } => serde_yaml::Value::Number(serde_yaml::Number::from(*val)),
-        Value::String { val, .. } => serde_yaml::Value::String(val.clone()),
-        Value::Record { cols, vals, .. } => {
+        Value::String { val, .. } => serde_yaml::Value::String(val.to_string()),
+        Value::Record { val, .. } => {
             let mut m = serde_yaml::Mapping::new();
-            for (k, v) in cols.iter().zip(vals.iter()) {
+            for (k, v) in val.iter() {
                 m.insert(
                     serde_yaml::Value::String(k.clone()),
                     value_to_yaml_value(v)?,
@@ -97,11 +97,7 @@ fn to_yaml(input: PipelineData, head: Span) -> Result<PipelineData, ShellError>
 
     let yaml_value = value_to_yaml_value(&value)?;
     match serde_yaml::to_string(&yaml_value) {
-        Ok(serde_yaml_string) => Ok(Value::String {
-            val: serde_yaml_string,
-            span: head,
-        }
-        .into_pipeline_data()),
+        Ok(serde_yaml_string) => Ok(Value::string(serde_yaml_string, head).into_pipeline_data()),
         _ => Ok(Value::Error {
             error: ShellError::CantConvert("YAML".into(), value.get_type().to_string(), head),
         }
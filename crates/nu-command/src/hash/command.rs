@@ -26,10 +26,10 @@ impl Command for Hash {
         call: &Call,
         _input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        Ok(Value::String {
-            val: get_full_help(&Self.signature(), &Self.examples(), engine_state),
-            span: call.head,
-        }
+        Ok(Value::string(
+            get_full_help(&Self.signature(), &Self.examples(), engine_state),
+            call.head,
+        )
         .into_pipeline_data())
     }
 }
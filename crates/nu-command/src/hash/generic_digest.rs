@@ -109,5 +109,5 @@ where
     };
 
     let val = format!("{:x}", D::digest(bytes));
-    Value::String { val, span }
+    Value::string(val, span)
 }
@@ -14,10 +14,10 @@ impl HashDigest for Md5 {
             Example {
                 description: "md5 encode a string",
                 example: "echo 'abcdefghijklmnopqrstuvwxyz' | hash md5",
-                result: Some(Value::String {
-                    val: "c3fcd3d76192e4007dfb496cca67e13b".to_owned(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string(
+                    "c3fcd3d76192e4007dfb496cca67e13b".to_owned(),
+                    Span::test_data(),
+                )),
             },
             Example {
                 description: "md5 encode a file",
@@ -40,14 +40,11 @@ mod tests {
 
     #[test]
     fn hash_string() {
-        let binary = Value::String {
-            val: "abcdefghijklmnopqrstuvwxyz".to_owned(),
-            span: Span::test_data(),
-        };
-        let expected = Value::String {
-            val: "c3fcd3d76192e4007dfb496cca67e13b".to_owned(),
-            span: Span::test_data(),
-        };
+        let binary = Value::string("abcdefghijklmnopqrstuvwxyz".to_owned(), Span::test_data());
+        let expected = Value::string(
+            "c3fcd3d76192e4007dfb496cca67e13b".to_owned(),
+            Span::test_data(),
+        );
         let actual = generic_digest::action::<Md5>(&binary);
         assert_eq!(actual, expected);
     }
@@ -58,10 +55,10 @@ mod tests {
             val: vec![0xC0, 0xFF, 0xEE],
             span: Span::test_data(),
         };
-        let expected = Value::String {
-            val: "5f80e231382769b0102b1164cf722d83".to_owned(),
-            span: Span::test_data(),
-        };
+        let expected = Value::string(
+            "5f80e231382769b0102b1164cf722d83".to_owned(),
+            Span::test_data(),
+        );
         let actual = generic_digest::action::<Md5>(&binary);
         assert_eq!(actual, expected);
     }
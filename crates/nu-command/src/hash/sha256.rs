@@ -16,7 +16,8 @@ impl HashDigest for Sha256 {
                 example: "echo 'abcdefghijklmnopqrstuvwxyz' | hash sha256",
                 result: Some(Value::String {
                     val: "71c480df93d6ae2f1efad1447c66c9525e316218cf51fc8d9ed832f2daf18b73"
-                        .to_owned(),
+                        .to_owned()
+                        .into(),
                     span: Span::test_data(),
                 }),
             },
@@ -41,12 +42,11 @@ mod tests {
 
     #[test]
     fn hash_string() {
-        let binary = Value::String {
-            val: "abcdefghijklmnopqrstuvwxyz".to_owned(),
-            span: Span::test_data(),
-        };
+        let binary = Value::string("abcdefghijklmnopqrstuvwxyz".to_owned(), Span::test_data());
         let expected = Value::String {
-            val: "71c480df93d6ae2f1efad1447c66c9525e316218cf51fc8d9ed832f2daf18b73".to_owned(),
+            val: "71c480df93d6ae2f1efad1447c66c9525e316218cf51fc8d9ed832f2daf18b73"
+                .to_owned()
+                .into(),
             span: Span::test_data(),
         };
         let actual = generic_digest::action::<Sha256>(&binary);
@@ -60,7 +60,9 @@ mod tests {
             span: Span::test_data(),
         };
         let expected = Value::String {
-            val: "c47a10dc272b1221f0380a2ae0f7d7fa830b3e378f2f5309bbf13f61ad211913".to_owned(),
+            val: "c47a10dc272b1221f0380a2ae0f7d7fa830b3e378f2f5309bbf13f61ad211913"
+                .to_owned()
+                .into(),
             span: Span::test_data(),
         };
         let actual = generic_digest::action::<Sha256>(&binary);
@@ -18,6 +18,7 @@ mod random;
 mod shells;
 mod strings;
 mod system;
+mod testing;
 mod viewers;
 
 pub use calendar::*;
@@ -41,6 +42,7 @@ pub use random::*;
 pub use shells::*;
 pub use strings::*;
 pub use system::*;
+pub use testing::*;
 pub use viewers::*;
 
 #[cfg(feature = "dataframe")]
@@ -44,7 +44,8 @@ impl Command for SubCommand {
                         span: Span::test_data(),
                     },
                     Value::test_int(25),
-                ],
+                ]
+                .into(),
                 span: Span::test_data(),
             }),
         }]
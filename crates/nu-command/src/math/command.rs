@@ -33,7 +33,8 @@ impl Command for MathCommand {
                 &MathCommand.signature(),
                 &MathCommand.examples(),
                 engine_state,
-            ),
+            )
+            .into(),
             span: call.head,
         }
         .into_pipeline_data())
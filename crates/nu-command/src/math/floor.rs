@@ -36,10 +36,10 @@ impl Command for SubCommand {
         vec![Example {
             description: "Apply the floor function to a list of numbers",
             example: "[1.5 2.3 -3.1] | math floor",
-            result: Some(Value::List {
-                vals: vec![Value::test_int(1), Value::test_int(2), Value::test_int(-4)],
-                span: Span::test_data(),
-            }),
+            result: Some(Value::list(
+                vec![Value::test_int(1), Value::test_int(2), Value::test_int(-4)],
+                Span::test_data(),
+            )),
         }]
     }
 }
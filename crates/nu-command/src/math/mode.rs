@@ -57,10 +57,10 @@ impl Command for SubCommand {
         vec![Example {
             description: "Get the mode(s) of a list of numbers",
             example: "[3 3 9 12 12 15] | math mode",
-            result: Some(Value::List {
-                vals: vec![Value::test_int(3), Value::test_int(12)],
-                span: Span::test_data(),
-            }),
+            result: Some(Value::list(
+                vec![Value::test_int(3), Value::test_int(12)],
+                Span::test_data(),
+            )),
         }]
     }
 }
@@ -130,10 +130,7 @@ pub fn mode(values: &[Value], head: &Span) -> Result<Value, ShellError> {
     }
 
     modes.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
-    Ok(Value::List {
-        vals: modes,
-        span: *head,
-    })
+    Ok(Value::list(modes, *head))
 }
 
 fn recreate_value(hashable_value: &HashableType, head: Span) -> Value {
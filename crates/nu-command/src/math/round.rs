@@ -48,10 +48,10 @@ impl Command for SubCommand {
             Example {
                 description: "Apply the round function to a list of numbers",
                 example: "[1.5 2.3 -3.1] | math round",
-                result: Some(Value::List {
-                    vals: vec![Value::test_int(2), Value::test_int(2), Value::test_int(-3)],
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::list(
+                    vec![Value::test_int(2), Value::test_int(2), Value::test_int(-3)],
+                    Span::test_data(),
+                )),
             },
             Example {
                 description: "Apply the round function with precision specified",
@@ -70,7 +70,8 @@ impl Command for SubCommand {
                             val: -3.11,
                             span: Span::test_data(),
                         },
-                    ],
+                    ]
+                    .into(),
                     span: Span::test_data(),
                 }),
             },
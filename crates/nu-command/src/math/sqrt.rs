@@ -36,10 +36,10 @@ impl Command for SubCommand {
         vec![Example {
             description: "Apply the square root function to a list of numbers",
             example: "[9 16] | math sqrt",
-            result: Some(Value::List {
-                vals: vec![Value::test_int(3), Value::test_int(4)],
-                span: Span::test_data(),
-            }),
+            result: Some(Value::list(
+                vec![Value::test_int(3), Value::test_int(4)],
+                Span::test_data(),
+            )),
         }]
     }
 }
@@ -1,6 +1,6 @@
 use indexmap::map::IndexMap;
 use nu_protocol::ast::Call;
-use nu_protocol::{IntoPipelineData, PipelineData, ShellError, Span, Spanned, Value};
+use nu_protocol::{IntoPipelineData, PipelineData, Record, ShellError, Span, Spanned, Value};
 
 pub fn run_with_function(
     call: &Call,
@@ -24,8 +24,8 @@ fn helper_for_tables(
     // Create a key for each column name
     let mut column_values = IndexMap::new();
     for val in values {
-        if let Value::Record { cols, vals, .. } = val {
-            for (key, value) in cols.iter().zip(vals.iter()) {
+        if let Value::Record { val: record, .. } = val {
+            for (key, value) in record.iter() {
                 column_values
                     .entry(key.clone())
                     .and_modify(|v: &mut Vec<Value>| v.push(value.clone()))
@@ -67,15 +67,13 @@ pub fn calculate(
             [Value::Record { .. }, _end @ ..] => helper_for_tables(vals, name, mf),
             _ => mf(vals, &name),
         },
-        PipelineData::Value(Value::Record { vals, cols, span }, ..) => {
-            let new_vals: Result<Vec<Value>, ShellError> =
-                vals.into_iter().map(|val| mf(&[val], &name)).collect();
+        PipelineData::Value(Value::Record { val, span }, ..) => {
+            let new_vals: Result<Record, ShellError> = val
+                .into_iter()
+                .map(|(col, val)| Ok((col, mf(&[val], &name)?)))
+                .collect();
             match new_vals {
-                Ok(vec) => Ok(Value::Record {
-                    cols,
-                    vals: vec,
-                    span,
-                }),
+                Ok(record) => Ok(Value::record(record, span)),
                 Err(err) => Err(err),
             }
         }
@@ -87,6 +85,7 @@ pub fn calculate(
 
             mf(&new_vals?, &name)
         }
+        PipelineData::RawStream(stream, ..) => mf(&[stream.into_value()], &name),
         PipelineData::Value(val, ..) => mf(&[val], &name),
     }
 }
@@ -28,10 +28,10 @@ impl Command for Url {
         call: &Call,
         _input: PipelineData,
     ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
-        Ok(Value::String {
-            val: get_full_help(&Url.signature(), &Url.examples(), engine_state),
-            span: call.head,
-        }
+        Ok(Value::string(
+            get_full_help(&Url.signature(), &Url.examples(), engine_state),
+            call.head,
+        )
         .into_pipeline_data())
     }
 }
@@ -40,10 +40,7 @@ impl Command for SubCommand {
         vec![Example {
             description: "Get host of a url",
             example: "echo 'http://www.example.com/foo/bar' | url host",
-            result: Some(Value::String {
-                val: "www.example.com".to_string(),
-                span,
-            }),
+            result: Some(Value::string("www.example.com", span)),
         }]
     }
 }
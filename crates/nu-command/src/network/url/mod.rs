@@ -22,10 +22,7 @@ fn handle_value<F>(action: &F, v: &Value, span: Span) -> Value
 where
     F: Fn(&url::Url) -> &str + Send + 'static,
 {
-    let a = |url| Value::String {
-        val: action(url).to_string(),
-        span,
-    };
+    let a = |url| Value::string(action(url).to_string(), span);
 
     match v {
         Value::String { val: s, .. } => {
@@ -33,10 +30,7 @@ where
 
             match url::Url::parse(s) {
                 Ok(url) => a(&url),
-                Err(_) => Value::String {
-                    val: "".to_string(),
-                    span,
-                },
+                Err(_) => Value::string("", span),
             }
         }
         other => {
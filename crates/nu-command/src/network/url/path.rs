@@ -41,18 +41,12 @@ impl Command for SubCommand {
             Example {
                 description: "Get path of a url",
                 example: "echo 'http://www.example.com/foo/bar' | url path",
-                result: Some(Value::String {
-                    val: "/foo/bar".to_string(),
-                    span,
-                }),
+                result: Some(Value::string("/foo/bar", span)),
             },
             Example {
                 description: "A trailing slash will be reflected in the path",
                 example: "echo 'http://www.example.com' | url path",
-                result: Some(Value::String {
-                    val: "/".to_string(),
-                    span,
-                }),
+                result: Some(Value::string("/", span)),
             },
         ]
     }
@@ -41,18 +41,12 @@ impl Command for SubCommand {
             Example {
                 description: "Get query of a url",
                 example: "echo 'http://www.example.com/?foo=bar&baz=quux' | url query",
-                result: Some(Value::String {
-                    val: "foo=bar&baz=quux".to_string(),
-                    span,
-                }),
+                result: Some(Value::string("foo=bar&baz=quux", span)),
             },
             Example {
                 description: "No query gives the empty string",
                 example: "echo 'http://www.example.com/' | url query",
-                result: Some(Value::String {
-                    val: "".to_string(),
-                    span,
-                }),
+                result: Some(Value::string("", span)),
             },
         ]
     }
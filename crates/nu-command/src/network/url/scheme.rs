@@ -41,18 +41,12 @@ impl Command for SubCommand {
             Example {
                 description: "Get scheme of a url",
                 example: "echo 'http://www.example.com' | url scheme",
-                result: Some(Value::String {
-                    val: "http".to_string(),
-                    span,
-                }),
+                result: Some(Value::string("http", span)),
             },
             Example {
                 description: "You get an empty string if there is no scheme",
                 example: "echo 'test' | url scheme",
-                result: Some(Value::String {
-                    val: "".to_string(),
-                    span,
-                }),
+                result: Some(Value::string("", span)),
             },
         ]
     }
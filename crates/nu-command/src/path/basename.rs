@@ -80,11 +80,11 @@ impl Command for SubCommand {
                 description: "Get basename of a path in a column",
                 example: "[[name];[C:\\Users\\Joe]] | path basename -c [ name ]",
                 result: Some(Value::List {
-                    vals: vec![Value::Record {
-                        cols: vec!["name".to_string()],
-                        vals: vec![Value::test_string("Joe")],
-                        span: Span::test_data(),
-                    }],
+                    vals: vec![Value::test_record(
+                        vec!["name"],
+                        vec![Value::test_string("Joe")],
+                    )]
+                    .into(),
                     span: Span::test_data(),
                 }),
             },
@@ -108,11 +108,11 @@ impl Command for SubCommand {
                 description: "Get basename of a path by column",
                 example: "[[name];[/home/joe]] | path basename -c [ name ]",
                 result: Some(Value::List {
-                    vals: vec![Value::Record {
-                        cols: vec!["name".to_string()],
-                        vals: vec![Value::test_string("joe")],
-                        span: Span::test_data(),
-                    }],
+                    vals: vec![Value::test_record(
+                        vec!["name"],
+                        vec![Value::test_string("joe")],
+                    )]
+                    .into(),
                     span: Span::test_data(),
                 }),
             },
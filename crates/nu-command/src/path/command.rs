@@ -48,7 +48,8 @@ the path literal."#
                 &PathCommand.signature(),
                 &PathCommand.examples(),
                 engine_state,
-            ),
+            )
+            .into(),
             span: call.head,
         }
         .into_pipeline_data())
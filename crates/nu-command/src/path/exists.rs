@@ -1,11 +1,15 @@
 use std::path::Path;
 
 use nu_engine::CallExt;
-use nu_protocol::{engine::Command, Example, Signature, Span, SyntaxShape, Value};
+use nu_protocol::{
+    engine::{Capability, Command, Permissions},
+    Example, Signature, Span, SyntaxShape, Value,
+};
 
 use super::PathSubcommandArguments;
 
 struct Arguments {
+    permissions: Permissions,
     columns: Option<Vec<String>>,
 }
 
@@ -36,6 +40,10 @@ impl Command for SubCommand {
         "Check whether a path exists"
     }
 
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::FilesystemRead]
+    }
+
     fn run(
         &self,
         engine_state: &nu_protocol::engine::EngineState,
@@ -45,6 +53,7 @@ impl Command for SubCommand {
     ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
         let head = call.head;
         let args = Arguments {
+            permissions: engine_state.permissions.clone(),
             columns: call.get_flag(engine_state, stack, "columns")?,
         };
 
@@ -93,7 +102,11 @@ impl Command for SubCommand {
     }
 }
 
-fn exists(path: &Path, span: Span, _args: &Arguments) -> Value {
+fn exists(path: &Path, span: Span, args: &Arguments) -> Value {
+    if let Err(error) = super::check_read(&args.permissions, "path exists", path, span) {
+        return error;
+    }
+
     Value::Bool {
         val: path.exists(),
         span,
@@ -2,11 +2,15 @@ use std::path::Path;
 
 use nu_engine::CallExt;
 use nu_path::{canonicalize, expand_path};
-use nu_protocol::{engine::Command, Example, ShellError, Signature, Span, SyntaxShape, Value};
+use nu_protocol::{
+    engine::{Capability, Command, Permissions},
+    Example, ShellError, Signature, Span, SyntaxShape, Value,
+};
 
 use super::PathSubcommandArguments;
 
 struct Arguments {
+    permissions: Permissions,
     strict: bool,
     columns: Option<Vec<String>>,
 }
@@ -44,6 +48,10 @@ impl Command for SubCommand {
         "Try to expand a path to its absolute form"
     }
 
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::FilesystemRead]
+    }
+
     fn run(
         &self,
         engine_state: &nu_protocol::engine::EngineState,
@@ -53,6 +61,7 @@ impl Command for SubCommand {
     ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
         let head = call.head;
         let args = Arguments {
+            permissions: engine_state.permissions.clone(),
             strict: call.has_flag("strict"),
             columns: call.get_flag(engine_state, stack, "columns")?,
         };
@@ -107,6 +116,10 @@ impl Command for SubCommand {
 }
 
 fn expand(path: &Path, span: Span, args: &Arguments) -> Value {
+    if let Err(error) = super::check_read(&args.permissions, "path expand", path, span) {
+        return error;
+    }
+
     if let Ok(p) = canonicalize(path) {
         Value::string(p.to_string_lossy(), span)
     } else if args.strict {
@@ -1,12 +1,9 @@
-use std::{
-    collections::HashMap,
-    path::{Path, PathBuf},
-};
+use std::path::{Path, PathBuf};
 
 use nu_engine::CallExt;
 use nu_protocol::{
-    engine::Command, Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape,
-    Value, ValueStream,
+    engine::Command, Example, PipelineData, Record, ShellError, Signature, Span, Spanned,
+    SyntaxShape, Value, ValueStream,
 };
 
 use super::PathSubcommandArguments;
@@ -71,6 +68,10 @@ the output of 'path parse' and 'path split' subcommands."#
             PipelineData::Value(val, md) => {
                 Ok(PipelineData::Value(handle_value(val, &args, head), md))
             }
+            PipelineData::RawStream(stream, md) => Ok(PipelineData::Value(
+                handle_value(stream.into_value(), &args, head),
+                md,
+            )),
             PipelineData::Stream(stream, md) => Ok(PipelineData::Stream(
                 ValueStream::from_stream(
                     stream.map(move |val| handle_value(val, &args, head)),
@@ -102,10 +103,10 @@ the output of 'path parse' and 'path split' subcommands."#
             Example {
                 description: "Join a structured path into a path",
                 example: r"[ [parent stem extension]; ['C:\Users\viking' 'spam' 'txt']] | path join",
-                result: Some(Value::List {
-                    vals: vec![Value::test_string(r"C:\Users\viking\spam.txt")],
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::list(
+                    vec![Value::test_string(r"C:\Users\viking\spam.txt")],
+                    Span::test_data(),
+                )),
             },
         ]
     }
@@ -131,10 +132,10 @@ the output of 'path parse' and 'path split' subcommands."#
             Example {
                 description: "Join a structured path into a path",
                 example: r"[[ parent stem extension ]; [ '/home/viking' 'spam' 'txt' ]] | path join",
-                result: Some(Value::List {
-                    vals: vec![Value::test_string(r"/home/viking/spam.txt")],
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::list(
+                    vec![Value::test_string(r"/home/viking/spam.txt")],
+                    Span::test_data(),
+                )),
             },
         ]
     }
@@ -143,7 +144,7 @@ the output of 'path parse' and 'path split' subcommands."#
 fn handle_value(v: Value, args: &Arguments, head: Span) -> Value {
     match v {
         Value::String { ref val, span } => join_single(Path::new(val), span, args),
-        Value::Record { cols, vals, span } => join_record(&cols, &vals, span, args),
+        Value::Record { val, span } => join_record(&val, span, args),
         Value::List { vals, span } => join_list(&vals, span, args),
 
         _ => super::handle_invalid_values(v, head),
@@ -171,7 +172,7 @@ fn join_list(parts: &[Value], span: Span, args: &Arguments) -> Value {
                 Ok(vals) => {
                     let vals = vals
                         .iter()
-                        .map(|(k, v)| join_record(k, v, span, args))
+                        .map(|record| join_record(record, span, args))
                         .collect();
 
                     Value::List { vals, span }
@@ -184,28 +185,24 @@ fn join_list(parts: &[Value], span: Span, args: &Arguments) -> Value {
     }
 }
 
-fn join_record(cols: &[String], vals: &[Value], span: Span, args: &Arguments) -> Value {
+fn join_record(record: &Record, span: Span, args: &Arguments) -> Value {
     if args.columns.is_some() {
         super::operate(
             &join_single,
             args,
-            Value::Record {
-                cols: cols.to_vec(),
-                vals: vals.to_vec(),
-                span,
-            },
+            Value::record(record.clone(), span),
             span,
         )
     } else {
-        match merge_record(cols, vals, span) {
+        match merge_record(record, span) {
             Ok(p) => join_single(p.as_path(), span, args),
             Err(error) => Value::Error { error },
         }
     }
 }
 
-fn merge_record(cols: &[String], vals: &[Value], span: Span) -> Result<PathBuf, ShellError> {
-    for key in cols {
+fn merge_record(record: &Record, span: Span) -> Result<PathBuf, ShellError> {
+    for key in record.columns() {
         if !super::ALLOWED_COLUMNS.contains(&key.as_str()) {
             let allowed_cols = super::ALLOWED_COLUMNS.join(", ");
             let msg = format!(
@@ -216,18 +213,17 @@ fn merge_record(cols: &[String], vals: &[Value], span: Span) -> Result<PathBuf,
         }
     }
 
-    let entries: HashMap<&str, &Value> = cols.iter().map(String::as_str).zip(vals).collect();
     let mut result = PathBuf::new();
 
     #[cfg(windows)]
-    if let Some(val) = entries.get("prefix") {
+    if let Some(val) = record.get("prefix") {
         let p = val.as_string()?;
         if !p.is_empty() {
             result.push(p);
         }
     }
 
-    if let Some(val) = entries.get("parent") {
+    if let Some(val) = record.get("parent") {
         let p = val.as_string()?;
         if !p.is_empty() {
             result.push(p);
@@ -235,14 +231,14 @@ fn merge_record(cols: &[String], vals: &[Value], span: Span) -> Result<PathBuf,
     }
 
     let mut basename = String::new();
-    if let Some(val) = entries.get("stem") {
+    if let Some(val) = record.get("stem") {
         let p = val.as_string()?;
         if !p.is_empty() {
             basename.push_str(&p);
         }
     }
 
-    if let Some(val) = entries.get("extension") {
+    if let Some(val) = record.get("extension") {
         let p = val.as_string()?;
         if !p.is_empty() {
             basename.push('.');
@@ -22,7 +22,9 @@ pub use r#type::SubCommand as PathType;
 pub use relative_to::SubCommand as PathRelativeTo;
 pub use split::SubCommand as PathSplit;
 
-use nu_protocol::{ShellError, Span, Value};
+use nu_engine::check_path;
+use nu_protocol::engine::{Capability, Permissions};
+use nu_protocol::{Record, ShellError, Span, Value};
 
 #[cfg(windows)]
 const ALLOWED_COLUMNS: [&str; 4] = ["prefix", "parent", "stem", "extension"];
@@ -40,7 +42,7 @@ where
 {
     match v {
         Value::String { val, span } => cmd(StdPath::new(&val), span, args),
-        Value::Record { cols, vals, span } => {
+        Value::Record { val: record, span } => {
             let col = if let Some(col) = args.get_columns() {
                 col
             } else {
@@ -55,32 +57,44 @@ where
                 };
             }
 
-            let mut output_cols = vec![];
-            let mut output_vals = vec![];
+            let mut output = Record::new();
 
-            for (k, v) in cols.iter().zip(vals) {
-                output_cols.push(k.clone());
-                if col.contains(k) {
+            for (k, v) in record {
+                if col.contains(&k) {
                     let new_val = match v {
                         Value::String { val, span } => cmd(StdPath::new(&val), span, args),
                         _ => return handle_invalid_values(v, name),
                     };
-                    output_vals.push(new_val);
+                    output.insert(k, new_val);
                 } else {
-                    output_vals.push(v);
+                    output.insert(k, v);
                 }
             }
 
-            Value::Record {
-                cols: output_cols,
-                vals: output_vals,
-                span,
-            }
+            Value::record(output, span)
         }
         _ => handle_invalid_values(v, name),
     }
 }
 
+// The path commands that look at the filesystem check the paths in their input with this, as only
+// the arguments of a command are checked by the engine before it runs
+fn check_read(
+    permissions: &Permissions,
+    command: &str,
+    path: &StdPath,
+    span: Span,
+) -> Result<(), Value> {
+    check_path(
+        permissions,
+        command,
+        Capability::FilesystemRead,
+        &path.to_string_lossy(),
+        span,
+    )
+    .map_err(|error| Value::Error { error })
+}
+
 fn handle_invalid_values(rest: Value, name: Span) -> Value {
     Value::Error {
         error: err_from_value(&rest, name),
@@ -67,7 +67,8 @@ impl Command for SubCommand {
                         Value::test_string("Users"),
                         Value::test_string("viking"),
                         Value::test_string("spam.txt"),
-                    ],
+                    ]
+                    .into(),
                     span: Span::test_data(),
                 }),
             },
@@ -91,7 +92,8 @@ impl Command for SubCommand {
                         Value::test_string("home"),
                         Value::test_string("viking"),
                         Value::test_string("spam.txt"),
-                    ],
+                    ]
+                    .into(),
                     span: Span::test_data(),
                 }),
             },
@@ -1,11 +1,15 @@
 use std::path::Path;
 
 use nu_engine::CallExt;
-use nu_protocol::{engine::Command, Example, ShellError, Signature, Span, SyntaxShape, Value};
+use nu_protocol::{
+    engine::{Capability, Command, Permissions},
+    Example, ShellError, Signature, Span, SyntaxShape, Value,
+};
 
 use super::PathSubcommandArguments;
 
 struct Arguments {
+    permissions: Permissions,
     columns: Option<Vec<String>>,
 }
 
@@ -36,6 +40,10 @@ impl Command for SubCommand {
         "Get the type of the object a path refers to (e.g., file, dir, symlink)"
     }
 
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::FilesystemRead]
+    }
+
     fn run(
         &self,
         engine_state: &nu_protocol::engine::EngineState,
@@ -45,6 +53,7 @@ impl Command for SubCommand {
     ) -> Result<nu_protocol::PipelineData, ShellError> {
         let head = call.head;
         let args = Arguments {
+            permissions: engine_state.permissions.clone(),
             columns: call.get_flag(engine_state, stack, "columns")?,
         };
 
@@ -70,7 +79,11 @@ impl Command for SubCommand {
     }
 }
 
-fn r#type(path: &Path, span: Span, _: &Arguments) -> Value {
+fn r#type(path: &Path, span: Span, args: &Arguments) -> Value {
+    if let Err(error) = super::check_read(&args.permissions, "path type", path, span) {
+        return error;
+    }
+
     let meta = std::fs::symlink_metadata(path);
 
     Value::string(
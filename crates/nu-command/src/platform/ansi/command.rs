@@ -2,8 +2,8 @@ use lazy_static::lazy_static;
 use nu_ansi_term::*;
 use nu_engine::CallExt;
 use nu_protocol::{
-    ast::Call, engine::Command, Category, Example, IntoInterruptiblePipelineData, IntoPipelineData,
-    PipelineData, ShellError, Signature, Span, SyntaxShape, Value,
+    ast::Call, engine::Command, record, Category, Example, IntoInterruptiblePipelineData,
+    IntoPipelineData, PipelineData, ShellError, Signature, Span, SyntaxShape, Value,
 };
 use std::collections::HashMap;
 
@@ -365,17 +365,18 @@ fn generate_ansi_code_list(
     return Ok(CODE_LIST
         .iter()
         .map(move |ansi_code| {
-            let cols = vec!["name".into(), "short name".into(), "code".into()];
             let name: Value = Value::string(String::from(ansi_code.long_name), call_span);
             let short_name = Value::string(ansi_code.short_name.unwrap_or(""), call_span);
             let code_string = String::from(&ansi_code.code.replace("\u{1b}", ""));
             let code = Value::string(code_string, call_span);
-            let vals = vec![name, short_name, code];
-            Value::Record {
-                cols,
-                vals,
-                span: call_span,
-            }
+            Value::record(
+                record! {
+                    "name" => name,
+                    "short name" => short_name,
+                    "code" => code,
+                },
+                call_span,
+            )
         })
         .into_pipeline_data(engine_state.ctrlc.clone()));
 }
@@ -1,5 +1,5 @@
 use nu_engine::CallExt;
-use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::engine::{Capability, Command, EngineState, Stack};
 use nu_protocol::{ast::Call, span};
 use nu_protocol::{
     Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Spanned, SyntaxShape,
@@ -43,6 +43,10 @@ impl Command for Kill {
         )
     }
 
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::Externals]
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
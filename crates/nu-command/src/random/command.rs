@@ -33,7 +33,8 @@ impl Command for RandomCommand {
                 &RandomCommand.signature(),
                 &RandomCommand.examples(),
                 engine_state,
-            ),
+            )
+            .into(),
             span: call.head,
         }
         .into_pipeline_data())
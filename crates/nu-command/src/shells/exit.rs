@@ -1,8 +1,9 @@
+use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, PipelineData, ShellError, Signature};
+use nu_protocol::{Category, PipelineData, ShellError, Signature, SyntaxShape};
 
-/// Source a file for environment variables.
+/// Exit the shell, optionally with an exit code.
 #[derive(Clone)]
 pub struct Exit;
 
@@ -12,22 +13,30 @@ impl Command for Exit {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("exit").category(Category::Shells)
+        Signature::build("exit")
+            .optional(
+                "exit_code",
+                SyntaxShape::Int,
+                "Exit code to return immediately with",
+            )
+            .category(Category::Shells)
     }
 
     fn usage(&self) -> &str {
-        "Runs a script file in the current context."
+        "Exit a Nu shell with a given exit code."
     }
 
     fn run(
         &self,
-        _engine_state: &EngineState,
-        _stack: &mut Stack,
-        _call: &Call,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
         _input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
         //TODO: add more shell support
 
-        std::process::exit(0);
+        let exit_code: Option<i64> = call.opt(engine_state, stack, 0)?;
+
+        std::process::exit(exit_code.unwrap_or(0) as i32);
     }
 }
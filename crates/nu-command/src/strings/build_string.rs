@@ -29,18 +29,12 @@ impl Command for BuildString {
             Example {
                 example: "build-string a b c",
                 description: "Builds a string from letters a b c",
-                result: Some(Value::String {
-                    val: "abc".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("abc", Span::test_data())),
             },
             Example {
                 example: "build-string (1 + 2) = one ' ' plus ' ' two",
                 description: "Builds a string from letters a b c",
-                result: Some(Value::String {
-                    val: "3=one plus two".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("3=one plus two", Span::test_data())),
             },
         ]
     }
@@ -61,11 +55,7 @@ impl Command for BuildString {
             })
             .collect::<Result<Vec<String>, ShellError>>()?;
 
-        Ok(Value::String {
-            val: output.join(""),
-            span: call.head,
-        }
-        .into_pipeline_data())
+        Ok(Value::string(output.join(""), call.head).into_pipeline_data())
     }
 }
 
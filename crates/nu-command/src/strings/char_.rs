@@ -3,8 +3,8 @@ use indexmap::map::IndexMap;
 use lazy_static::lazy_static;
 use nu_engine::CallExt;
 use nu_protocol::{
-    ast::Call, engine::Command, Category, Example, IntoInterruptiblePipelineData, IntoPipelineData,
-    PipelineData, ShellError, Signature, Span, SyntaxShape, Value,
+    ast::Call, engine::Command, record, Category, Example, IntoInterruptiblePipelineData,
+    IntoPipelineData, PipelineData, ShellError, Signature, Span, SyntaxShape, Value,
 };
 
 // Character used to separate directories in a Path Environment variable on windows is ";"
@@ -181,7 +181,6 @@ impl Command for Char {
             return Ok(CHAR_MAP
                 .iter()
                 .map(move |(name, s)| {
-                    let cols = vec!["name".into(), "character".into(), "unicode".into()];
                     let name: Value = Value::string(String::from(*name), call_span);
                     let character = Value::string(s, call_span);
                     let unicode = Value::string(
@@ -191,12 +190,14 @@ impl Command for Char {
                             .join(" "),
                         call_span,
                     );
-                    let vals = vec![name, character, unicode];
-                    Value::Record {
-                        cols,
-                        vals,
-                        span: call_span,
-                    }
+                    Value::record(
+                        record! {
+                            "name" => name,
+                            "character" => character,
+                            "unicode" => unicode,
+                        },
+                        call_span,
+                    )
                 })
                 .into_pipeline_data(engine_state.ctrlc.clone()));
         }
@@ -55,10 +55,10 @@ impl Command for Format {
             Example {
                 description: "Print elements from some columns of a table",
                 example: "echo [[col1, col2]; [v1, v2] [v3, v4]] | format '{col2}'",
-                result: Some(Value::List {
-                    vals: vec![Value::test_string("v2"), Value::test_string("v4")],
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::list(
+                    vec![Value::test_string("v2"), Value::test_string("v4")],
+                    Span::test_data(),
+                )),
             },
         ]
     }
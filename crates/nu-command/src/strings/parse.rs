@@ -2,8 +2,8 @@ use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Value,
-    ValueStream,
+    Category, Example, PipelineData, Record, ShellError, Signature, Span, Spanned, SyntaxShape,
+    Value, ValueStream,
 };
 use regex::Regex;
 
@@ -32,11 +32,11 @@ impl Command for Parse {
 
     fn examples(&self) -> Vec<Example> {
         let result = Value::List {
-            vals: vec![Value::Record {
-                cols: vec!["foo".to_string(), "bar".to_string()],
-                vals: vec![Value::test_string("hi"), Value::test_string("there")],
-                span: Span::test_data(),
-            }],
+            vals: vec![Value::test_record(
+                vec!["foo", "bar"],
+                vec![Value::test_string("hi"), Value::test_string("there")],
+            )]
+            .into(),
             span: Span::test_data(),
         };
 
@@ -97,23 +97,14 @@ fn operate(
                 let results = regex_pattern.captures_iter(&s);
 
                 for c in results {
-                    let mut cols = Vec::with_capacity(columns.len());
-                    let mut vals = Vec::with_capacity(c.len());
+                    let mut record = Record::new();
 
                     for (column_name, cap) in columns.iter().zip(c.iter().skip(1)) {
                         let cap_string = cap.map(|v| v.as_str()).unwrap_or("").to_string();
-                        cols.push(column_name.clone());
-                        vals.push(Value::String {
-                            val: cap_string,
-                            span: v.span()?,
-                        });
+                        record.insert(column_name.clone(), Value::string(cap_string, v.span()?));
                     }
 
-                    parsed.push(Value::Record {
-                        cols,
-                        vals,
-                        span: head,
-                    });
+                    parsed.push(Value::record(record, head));
                 }
             }
             Err(_) => {
@@ -4,7 +4,7 @@ use unicode_segmentation::UnicodeSegmentation;
 
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Span, Value};
+use nu_protocol::{Category, Example, PipelineData, Record, ShellError, Signature, Span, Value};
 
 #[derive(Clone)]
 pub struct Size;
@@ -37,14 +37,9 @@ impl Command for Size {
             Example {
                 description: "Count the number of words in a string",
                 example: r#""There are seven words in this sentence" | size"#,
-                result: Some(Value::Record {
-                    cols: vec![
-                        "lines".into(),
-                        "words".into(),
-                        "chars".into(),
-                        "bytes".into(),
-                    ],
-                    vals: vec![
+                result: Some(Value::test_record(
+                    vec!["lines", "words", "chars", "bytes"],
+                    vec![
                         Value::Int {
                             val: 0,
                             span: Span::test_data(),
@@ -62,20 +57,14 @@ impl Command for Size {
                             span: Span::test_data(),
                         },
                     ],
-                    span: Span::test_data(),
-                }),
+                )),
             },
             Example {
                 description: "Counts Unicode characters correctly in a string",
                 example: r#""Amélie Amelie" | size"#,
-                result: Some(Value::Record {
-                    cols: vec![
-                        "lines".into(),
-                        "words".into(),
-                        "chars".into(),
-                        "bytes".into(),
-                    ],
-                    vals: vec![
+                result: Some(Value::test_record(
+                    vec!["lines", "words", "chars", "bytes"],
+                    vec![
                         Value::Int {
                             val: 0,
                             span: Span::test_data(),
@@ -93,8 +82,7 @@ impl Command for Size {
                             span: Span::test_data(),
                         },
                     ],
-                    span: Span::test_data(),
-                }),
+                )),
             },
         ]
     }
@@ -142,22 +130,17 @@ fn count(contents: &str, span: Span) -> Value {
         }
     }
 
-    let mut cols = vec![];
-    let mut vals = vec![];
+    let mut record = Record::new();
 
-    cols.push("lines".into());
-    vals.push(Value::Int { val: lines, span });
+    record.insert("lines", Value::Int { val: lines, span });
 
-    cols.push("words".into());
-    vals.push(Value::Int { val: words, span });
+    record.insert("words", Value::Int { val: words, span });
 
-    cols.push("chars".into());
-    vals.push(Value::Int { val: chars, span });
+    record.insert("chars", Value::Int { val: chars, span });
 
-    cols.push("bytes".into());
-    vals.push(Value::Int { val: bytes, span });
+    record.insert("bytes", Value::Int { val: bytes, span });
 
-    Value::Record { cols, vals, span }
+    Value::record(record, span)
 }
 
 #[cfg(test)]
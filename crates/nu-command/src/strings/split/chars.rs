@@ -31,7 +31,8 @@ impl Command for SubCommand {
                     Value::test_string("l"),
                     Value::test_string("l"),
                     Value::test_string("o"),
-                ],
+                ]
+                .into(),
                 span: Span::test_data(),
             }),
         }]
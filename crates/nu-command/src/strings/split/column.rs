@@ -2,7 +2,7 @@ use nu_engine::CallExt;
 use nu_protocol::{
     ast::Call,
     engine::{Command, EngineState, Stack},
-    Category, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Value,
+    Category, PipelineData, Record, ShellError, Signature, Span, Spanned, SyntaxShape, Value,
 };
 
 #[derive(Clone)]
@@ -77,33 +77,28 @@ fn split_column_helper(
             s.split(&splitter).collect()
         };
 
-        let positional: Vec<_> = rest.iter().map(|f| f.item.clone()).collect();
-
         // If they didn't provide column names, make up our own
 
-        let mut cols = vec![];
-        let mut vals = vec![];
-        if positional.is_empty() {
+        let mut record = Record::new();
+        if rest.is_empty() {
             let mut gen_columns = vec![];
             for i in 0..split_result.len() {
                 gen_columns.push(format!("Column{}", i + 1));
             }
 
             for (&k, v) in split_result.iter().zip(&gen_columns) {
-                cols.push(v.to_string());
-                vals.push(Value::string(k, head));
+                record.insert(v.to_string(), Value::string(k, head));
             }
         } else {
-            for (&k, v) in split_result.iter().zip(&positional) {
-                cols.push(v.into());
-                vals.push(Value::string(k, head));
+            for (&k, v) in split_result.iter().zip(rest) {
+                if let Err(error) =
+                    record.try_insert(v.item.clone(), Value::string(k, head), v.span)
+                {
+                    return vec![Value::Error { error }];
+                }
             }
         }
-        vec![Value::Record {
-            cols,
-            vals,
-            span: head,
-        }]
+        vec![Value::record(record, head)]
     } else {
         match v.span() {
             Ok(span) => vec![Value::Error {
@@ -33,7 +33,8 @@ impl Command for SplitCommand {
                 &SplitCommand.signature(),
                 &SplitCommand.examples(),
                 engine_state,
-            ),
+            )
+            .into(),
             span: call.head,
         }
         .into_pipeline_data())
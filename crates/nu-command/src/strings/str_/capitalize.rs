@@ -42,34 +42,25 @@ impl Command for SubCommand {
             Example {
                 description: "Capitalize contents",
                 example: "'good day' | str capitalize",
-                result: Some(Value::String {
-                    val: "Good day".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("Good day", Span::test_data())),
             },
             Example {
                 description: "Capitalize contents",
                 example: "'anton' | str capitalize",
-                result: Some(Value::String {
-                    val: "Anton".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("Anton", Span::test_data())),
             },
             Example {
                 description: "Capitalize a column in a table",
                 example: "[[lang, gems]; [nu_test, 100]] | str capitalize lang",
                 result: Some(Value::List {
-                    vals: vec![Value::Record {
-                        span: Span::test_data(),
-                        cols: vec!["lang".to_string(), "gems".to_string()],
-                        vals: vec![
-                            Value::String {
-                                val: "Nu_test".to_string(),
-                                span: Span::test_data(),
-                            },
+                    vals: vec![Value::test_record(
+                        vec!["lang", "gems"],
+                        vec![
+                            Value::string("Nu_test", Span::test_data()),
                             Value::test_int(100),
                         ],
-                    }],
+                    )]
+                    .into(),
                     span: Span::test_data(),
                 }),
             },
@@ -107,10 +98,7 @@ fn operate(
 
 fn action(input: &Value, head: Span) -> Value {
     match input {
-        Value::String { val, .. } => Value::String {
-            val: uppercase_helper(val),
-            span: head,
-        },
+        Value::String { val, .. } => Value::string(uppercase_helper(val), head),
         other => Value::Error {
             error: ShellError::UnsupportedInput(
                 format!(
@@ -44,42 +44,30 @@ impl Command for SubCommand {
             Example {
                 description: "convert a string to kebab-case",
                 example: "'NuShell' | str kebab-case",
-                result: Some(Value::String {
-                    val: "nu-shell".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("nu-shell", Span::test_data())),
             },
             Example {
                 description: "convert a string to kebab-case",
                 example: "'thisIsTheFirstCase' | str kebab-case",
-                result: Some(Value::String {
-                    val: "this-is-the-first-case".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("this-is-the-first-case", Span::test_data())),
             },
             Example {
                 description: "convert a string to kebab-case",
                 example: "'THIS_IS_THE_SECOND_CASE' | str kebab-case",
-                result: Some(Value::String {
-                    val: "this-is-the-second-case".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("this-is-the-second-case", Span::test_data())),
             },
             Example {
                 description: "convert a column from a table to kebab-case",
                 example: r#"[[lang, gems]; [nuTest, 100]] | str kebab-case lang"#,
                 result: Some(Value::List {
-                    vals: vec![Value::Record {
-                        span: Span::test_data(),
-                        cols: vec!["lang".to_string(), "gems".to_string()],
-                        vals: vec![
-                            Value::String {
-                                val: "nu-test".to_string(),
-                                span: Span::test_data(),
-                            },
+                    vals: vec![Value::test_record(
+                        vec!["lang", "gems"],
+                        vec![
+                            Value::string("nu-test", Span::test_data()),
                             Value::test_int(100),
                         ],
-                    }],
+                    )]
+                    .into(),
                     span: Span::test_data(),
                 }),
             },
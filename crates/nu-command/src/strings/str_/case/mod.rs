@@ -58,10 +58,7 @@ where
     F: Fn(&str) -> String + Send + Sync + 'static,
 {
     match input {
-        Value::String { val, .. } => Value::String {
-            val: case_operation(val),
-            span: head,
-        },
+        Value::String { val, .. } => Value::string(case_operation(val), head),
         other => Value::Error {
             error: ShellError::UnsupportedInput(
                 format!(
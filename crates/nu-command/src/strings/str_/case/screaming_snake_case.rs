@@ -43,42 +43,30 @@ impl Command for SubCommand {
             Example {
                 description: "convert a string to camelCase",
                 example: r#" "NuShell" | str screaming-snake-case"#,
-                result: Some(Value::String {
-                    val: "NU_SHELL".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("NU_SHELL", Span::test_data())),
             },
             Example {
                 description: "convert a string to camelCase",
                 example: r#" "this_is_the_second_case" | str screaming-snake-case"#,
-                result: Some(Value::String {
-                    val: "THIS_IS_THE_SECOND_CASE".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("THIS_IS_THE_SECOND_CASE", Span::test_data())),
             },
             Example {
                 description: "convert a string to camelCase",
                 example: r#""this-is-the-first-case" | str screaming-snake-case"#,
-                result: Some(Value::String {
-                    val: "THIS_IS_THE_FIRST_CASE".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("THIS_IS_THE_FIRST_CASE", Span::test_data())),
             },
             Example {
                 description: "convert a column from a table to SCREAMING_SNAKE_CASE",
                 example: r#"[[lang, gems]; [nu_test, 100]] | str screaming-snake-case lang"#,
                 result: Some(Value::List {
-                    vals: vec![Value::Record {
-                        span: Span::test_data(),
-                        cols: vec!["lang".to_string(), "gems".to_string()],
-                        vals: vec![
-                            Value::String {
-                                val: "NU_TEST".to_string(),
-                                span: Span::test_data(),
-                            },
+                    vals: vec![Value::test_record(
+                        vec!["lang", "gems"],
+                        vec![
+                            Value::string("NU_TEST", Span::test_data()),
                             Value::test_int(100),
                         ],
-                    }],
+                    )]
+                    .into(),
                     span: Span::test_data(),
                 }),
             },
@@ -42,42 +42,30 @@ impl Command for SubCommand {
             Example {
                 description: "convert a string to camelCase",
                 example: r#" "NuShell" | str snake-case"#,
-                result: Some(Value::String {
-                    val: "nu_shell".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("nu_shell", Span::test_data())),
             },
             Example {
                 description: "convert a string to camelCase",
                 example: r#" "this_is_the_second_case" | str snake-case"#,
-                result: Some(Value::String {
-                    val: "this_is_the_second_case".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("this_is_the_second_case", Span::test_data())),
             },
             Example {
                 description: "convert a string to camelCase",
                 example: r#""this-is-the-first-case" | str snake-case"#,
-                result: Some(Value::String {
-                    val: "this_is_the_first_case".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("this_is_the_first_case", Span::test_data())),
             },
             Example {
                 description: "convert a column from a table to snake-case",
                 example: r#"[[lang, gems]; [nuTest, 100]] | str snake-case lang"#,
                 result: Some(Value::List {
-                    vals: vec![Value::Record {
-                        span: Span::test_data(),
-                        cols: vec!["lang".to_string(), "gems".to_string()],
-                        vals: vec![
-                            Value::String {
-                                val: "nu_test".to_string(),
-                                span: Span::test_data(),
-                            },
+                    vals: vec![Value::test_record(
+                        vec!["lang", "gems"],
+                        vec![
+                            Value::string("nu_test", Span::test_data()),
                             Value::test_int(100),
                         ],
-                    }],
+                    )]
+                    .into(),
                     span: Span::test_data(),
                 }),
             },
@@ -53,11 +53,7 @@ impl Command for StrCollect {
             strings.join("")
         };
 
-        Ok(Value::String {
-            val: output,
-            span: call.head,
-        }
-        .into_pipeline_data())
+        Ok(Value::string(output, call.head).into_pipeline_data())
     }
 
     fn examples(&self) -> Vec<Example> {
@@ -65,18 +61,12 @@ impl Command for StrCollect {
             Example {
                 description: "Create a string from input",
                 example: "['nu', 'shell'] | str collect",
-                result: Some(Value::String {
-                    val: "nushell".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("nushell", Span::test_data())),
             },
             Example {
                 description: "Create a string from input with a separator",
                 example: "['nu', 'shell'] | str collect '-'",
-                result: Some(Value::String {
-                    val: "nu-shell".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("nu-shell", Span::test_data())),
             },
         ]
     }
@@ -63,17 +63,17 @@ impl Command for SubCommand {
                 description: "Check if string contains pattern in a table",
                 example: " [[ColA ColB]; [test 100]] | str contains 'e' ColA",
                 result: Some(Value::List {
-                    vals: vec![Value::Record {
-                        cols: vec!["ColA".to_string(), "ColB".to_string()],
-                        vals: vec![
+                    vals: vec![Value::test_record(
+                        vec!["ColA", "ColB"],
+                        vec![
                             Value::Bool {
                                 val: true,
                                 span: Span::test_data(),
                             },
                             Value::test_int(100),
                         ],
-                        span: Span::test_data(),
-                    }],
+                    )]
+                    .into(),
                     span: Span::test_data(),
                 }),
             },
@@ -81,17 +81,17 @@ impl Command for SubCommand {
                 description: "Check if string contains pattern in a table",
                 example: " [[ColA ColB]; [test 100]] | str contains -i 'E' ColA",
                 result: Some(Value::List {
-                    vals: vec![Value::Record {
-                        cols: vec!["ColA".to_string(), "ColB".to_string()],
-                        vals: vec![
+                    vals: vec![Value::test_record(
+                        vec!["ColA", "ColB"],
+                        vec![
                             Value::Bool {
                                 val: true,
                                 span: Span::test_data(),
                             },
                             Value::test_int(100),
                         ],
-                        span: Span::test_data(),
-                    }],
+                    )]
+                    .into(),
                     span: Span::test_data(),
                 }),
             },
@@ -99,9 +99,9 @@ impl Command for SubCommand {
                 description: "Check if string contains pattern in a table",
                 example: " [[ColA ColB]; [test hello]] | str contains 'e' ColA ColB",
                 result: Some(Value::List {
-                    vals: vec![Value::Record {
-                        cols: vec!["ColA".to_string(), "ColB".to_string()],
-                        vals: vec![
+                    vals: vec![Value::test_record(
+                        vec!["ColA", "ColB"],
+                        vec![
                             Value::Bool {
                                 val: true,
                                 span: Span::test_data(),
@@ -111,8 +111,8 @@ impl Command for SubCommand {
                                 span: Span::test_data(),
                             },
                         ],
-                        span: Span::test_data(),
-                    }],
+                    )]
+                    .into(),
                     span: Span::test_data(),
                 }),
             },
@@ -42,37 +42,25 @@ impl Command for SubCommand {
             Example {
                 description: "Downcase contents",
                 example: "'NU' | str downcase",
-                result: Some(Value::String {
-                    val: "nu".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("nu", Span::test_data())),
             },
             Example {
                 description: "Downcase contents",
                 example: "'TESTa' | str downcase",
-                result: Some(Value::String {
-                    val: "testa".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("testa", Span::test_data())),
             },
             Example {
                 description: "Downcase contents",
                 example: "[[ColA ColB]; [Test ABC]] | str downcase ColA",
                 result: Some(Value::List {
-                    vals: vec![Value::Record {
-                        cols: vec!["ColA".to_string(), "ColB".to_string()],
-                        vals: vec![
-                            Value::String {
-                                val: "test".to_string(),
-                                span: Span::test_data(),
-                            },
-                            Value::String {
-                                val: "ABC".to_string(),
-                                span: Span::test_data(),
-                            },
+                    vals: vec![Value::test_record(
+                        vec!["ColA", "ColB"],
+                        vec![
+                            Value::string("test", Span::test_data()),
+                            Value::string("ABC", Span::test_data()),
                         ],
-                        span: Span::test_data(),
-                    }],
+                    )]
+                    .into(),
                     span: Span::test_data(),
                 }),
             },
@@ -80,20 +68,14 @@ impl Command for SubCommand {
                 description: "Downcase contents",
                 example: "[[ColA ColB]; [Test ABC]] | str downcase ColA ColB",
                 result: Some(Value::List {
-                    vals: vec![Value::Record {
-                        cols: vec!["ColA".to_string(), "ColB".to_string()],
-                        vals: vec![
-                            Value::String {
-                                val: "test".to_string(),
-                                span: Span::test_data(),
-                            },
-                            Value::String {
-                                val: "abc".to_string(),
-                                span: Span::test_data(),
-                            },
+                    vals: vec![Value::test_record(
+                        vec!["ColA", "ColB"],
+                        vec![
+                            Value::string("test", Span::test_data()),
+                            Value::string("abc", Span::test_data()),
                         ],
-                        span: Span::test_data(),
-                    }],
+                    )]
+                    .into(),
                     span: Span::test_data(),
                 }),
             },
@@ -131,10 +113,7 @@ fn operate(
 
 fn action(input: &Value, head: Span) -> Value {
     match input {
-        Value::String { val, .. } => Value::String {
-            val: val.to_ascii_lowercase(),
-            span: head,
-        },
+        Value::String { val, .. } => Value::string(val.to_ascii_lowercase(), head),
         other => Value::Error {
             error: ShellError::UnsupportedInput(
                 format!(
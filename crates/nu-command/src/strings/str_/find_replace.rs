@@ -55,42 +55,27 @@ impl Command for SubCommand {
             Example {
                 description: "Find and replace contents with capture group",
                 example: "'my_library.rb' | str find-replace '(.+).rb' '$1.nu'",
-                result: Some(Value::String {
-                    val: "my_library.nu".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("my_library.nu", Span::test_data())),
             },
             Example {
                 description: "Find and replace all occurrences of find string",
                 example: "'abc abc abc' | str find-replace -a 'b' 'z'",
-                result: Some(Value::String {
-                    val: "azc azc azc".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("azc azc azc", Span::test_data())),
             },
             Example {
                 description: "Find and replace all occurrences of find string in table",
                 example:
                     "[[ColA ColB ColC]; [abc abc ads]] | str find-replace -a 'b' 'z' ColA ColC",
                 result: Some(Value::List {
-                    vals: vec![Value::Record {
-                        cols: vec!["ColA".to_string(), "ColB".to_string(), "ColC".to_string()],
-                        vals: vec![
-                            Value::String {
-                                val: "azc".to_string(),
-                                span: Span::test_data(),
-                            },
-                            Value::String {
-                                val: "abc".to_string(),
-                                span: Span::test_data(),
-                            },
-                            Value::String {
-                                val: "ads".to_string(),
-                                span: Span::test_data(),
-                            },
+                    vals: vec![Value::test_record(
+                        vec!["ColA", "ColB", "ColC"],
+                        vec![
+                            Value::string("azc", Span::test_data()),
+                            Value::string("abc", Span::test_data()),
+                            Value::string("ads", Span::test_data()),
                         ],
-                        span: Span::test_data(),
-                    }],
+                    )]
+                    .into(),
                     span: Span::test_data(),
                 }),
             },
@@ -154,21 +139,12 @@ fn action(
             match regex {
                 Ok(re) => {
                     if *all {
-                        Value::String {
-                            val: re.replace_all(val, replacement).to_string(),
-                            span: head,
-                        }
+                        Value::string(re.replace_all(val, replacement).to_string(), head)
                     } else {
-                        Value::String {
-                            val: re.replace(val, replacement).to_string(),
-                            span: head,
-                        }
+                        Value::string(re.replace(val, replacement).to_string(), head)
                     }
                 }
-                Err(_) => Value::String {
-                    val: val.to_string(),
-                    span: head,
-                },
+                Err(_) => Value::string(val.to_string(), head),
             }
         }
         other => Value::Error {
@@ -197,10 +173,7 @@ mod tests {
 
     #[test]
     fn can_have_capture_groups() {
-        let word = Value::String {
-            val: "Cargo.toml".to_string(),
-            span: Span::test_data(),
-        };
+        let word = Value::string("Cargo.toml", Span::test_data());
 
         let options = Arguments {
             find: String::from("Cargo.(.+)"),
@@ -213,7 +186,7 @@ mod tests {
         assert_eq!(
             actual,
             Value::String {
-                val: "Carga.toml".to_string(),
+                val: "Carga.toml".into(),
                 span: Span::test_data()
             }
         );
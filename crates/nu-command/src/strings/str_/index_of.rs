@@ -147,10 +147,7 @@ fn action(
 ) -> Value {
     let range = match range {
         Some(range) => range.clone(),
-        None => Value::String {
-            val: "".to_string(),
-            span: head,
-        },
+        None => Value::string("", head),
     };
 
     let r = process_range(input, &range, head);
@@ -282,18 +279,12 @@ mod tests {
 
     #[test]
     fn returns_index_of_substring() {
-        let word = Value::String {
-            val: String::from("Cargo.tomL"),
-            span: Span::test_data(),
-        };
+        let word = Value::string(String::from("Cargo.tomL"), Span::test_data());
 
         let options = Arguments {
             pattern: String::from(".tomL"),
 
-            range: Some(Value::String {
-                val: String::from(""),
-                span: Span::test_data(),
-            }),
+            range: Some(Value::string(String::from(""), Span::test_data())),
             column_paths: vec![],
             end: false,
         };
@@ -304,18 +295,12 @@ mod tests {
     }
     #[test]
     fn index_of_does_not_exist_in_string() {
-        let word = Value::String {
-            val: String::from("Cargo.tomL"),
-            span: Span::test_data(),
-        };
+        let word = Value::string(String::from("Cargo.tomL"), Span::test_data());
 
         let options = Arguments {
             pattern: String::from("Lm"),
 
-            range: Some(Value::String {
-                val: String::from(""),
-                span: Span::test_data(),
-            }),
+            range: Some(Value::string(String::from(""), Span::test_data())),
             column_paths: vec![],
             end: false,
         };
@@ -327,18 +312,12 @@ mod tests {
 
     #[test]
     fn returns_index_of_next_substring() {
-        let word = Value::String {
-            val: String::from("Cargo.Cargo"),
-            span: Span::test_data(),
-        };
+        let word = Value::string(String::from("Cargo.Cargo"), Span::test_data());
 
         let options = Arguments {
             pattern: String::from("Cargo"),
 
-            range: Some(Value::String {
-                val: String::from("1"),
-                span: Span::test_data(),
-            }),
+            range: Some(Value::string(String::from("1"), Span::test_data())),
             column_paths: vec![],
             end: false,
         };
@@ -349,18 +328,12 @@ mod tests {
 
     #[test]
     fn index_does_not_exist_due_to_end_index() {
-        let word = Value::String {
-            val: String::from("Cargo.Banana"),
-            span: Span::test_data(),
-        };
+        let word = Value::string(String::from("Cargo.Banana"), Span::test_data());
 
         let options = Arguments {
             pattern: String::from("Banana"),
 
-            range: Some(Value::String {
-                val: String::from(",5"),
-                span: Span::test_data(),
-            }),
+            range: Some(Value::string(String::from(",5"), Span::test_data())),
             column_paths: vec![],
             end: false,
         };
@@ -371,18 +344,12 @@ mod tests {
 
     #[test]
     fn returns_index_of_nums_in_middle_due_to_index_limit_from_both_ends() {
-        let word = Value::String {
-            val: String::from("123123123"),
-            span: Span::test_data(),
-        };
+        let word = Value::string(String::from("123123123"), Span::test_data());
 
         let options = Arguments {
             pattern: String::from("123"),
 
-            range: Some(Value::String {
-                val: String::from("2,6"),
-                span: Span::test_data(),
-            }),
+            range: Some(Value::string(String::from("2,6"), Span::test_data())),
             column_paths: vec![],
             end: false,
         };
@@ -393,18 +360,12 @@ mod tests {
 
     #[test]
     fn index_does_not_exists_due_to_strict_bounds() {
-        let word = Value::String {
-            val: String::from("123456"),
-            span: Span::test_data(),
-        };
+        let word = Value::string(String::from("123456"), Span::test_data());
 
         let options = Arguments {
             pattern: String::from("1"),
 
-            range: Some(Value::String {
-                val: String::from("2,4"),
-                span: Span::test_data(),
-            }),
+            range: Some(Value::string(String::from("2,4"), Span::test_data())),
             column_paths: vec![],
             end: false,
         };
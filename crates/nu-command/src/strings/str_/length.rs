@@ -47,10 +47,10 @@ impl Command for SubCommand {
             Example {
                 description: "Return the lengths of multiple strings",
                 example: "['hi' 'there'] | str length",
-                result: Some(Value::List {
-                    vals: vec![Value::test_int(2), Value::test_int(5)],
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::list(
+                    vec![Value::test_int(2), Value::test_int(5)],
+                    Span::test_data(),
+                )),
             },
         ]
     }
@@ -41,10 +41,7 @@ impl Command for SubCommand {
         vec![Example {
             description: "Return the reversals of multiple strings",
             example: "'Nushell' | str reverse",
-            result: Some(Value::String {
-                val: "llehsuN".to_string(),
-                span: Span::test_data(),
-            }),
+            result: Some(Value::string("llehsuN", Span::test_data())),
         }]
     }
 }
@@ -79,10 +76,7 @@ fn operate(
 
 fn action(input: &Value, head: Span) -> Value {
     match input {
-        Value::String { val, .. } => Value::String {
-            val: val.chars().rev().collect::<String>(),
-            span: head,
-        },
+        Value::String { val, .. } => Value::string(val.chars().rev().collect::<String>(), head),
 
         other => Value::Error {
             error: ShellError::UnsupportedInput(
@@ -56,34 +56,22 @@ impl Command for SubCommand {
             Example {
                 description: "Right pad a string with a character a number of places",
                 example: "'nushell' | str rpad -l 10 -c '*'",
-                result: Some(Value::String {
-                    val: "nushell***".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("nushell***", Span::test_data())),
             },
             Example {
                 description: "Right pad a string with a character a number of places",
                 example: "'123' | str rpad -l 10 -c '0'",
-                result: Some(Value::String {
-                    val: "1230000000".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("1230000000", Span::test_data())),
             },
             Example {
                 description: "Use rpad to truncate a string",
                 example: "'123456789' | str rpad -l 3 -c '0'",
-                result: Some(Value::String {
-                    val: "123".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("123", Span::test_data())),
             },
             Example {
                 description: "Use rpad to pad Unicode",
                 example: "'▉' | str rpad -l 10 -c '▉'",
-                result: Some(Value::String {
-                    val: "▉▉▉▉▉▉▉▉▉▉".to_string(),
-                    span: Span::test_data(),
-                }),
+                result: Some(Value::string("▉▉▉▉▉▉▉▉▉▉", Span::test_data())),
             },
         ]
     }
@@ -137,17 +125,11 @@ fn action(
             Some(x) => {
                 let s = *x as usize;
                 if s < val.len() {
-                    Value::String {
-                        val: val.chars().take(s).collect::<String>(),
-                        span: head,
-                    }
+                    Value::string(val.chars().take(s).collect::<String>(), head)
                 } else {
                     let mut res = val.to_string();
                     res += &character.as_ref().expect("we already know this flag needs to exist because the command is type checked before we call the action function").repeat(s - val.chars().count());
-                    Value::String {
-                        val: res,
-                        span: head,
-                    }
+                    Value::string(res, head)
                 }
             }
             None => Value::Error {
@@ -144,10 +144,7 @@ fn action(input: &Value, options: &Substring, head: Span) -> Value {
 
             if start < len && end >= 0 {
                 match start.cmp(&end) {
-                    Ordering::Equal => Value::String {
-                        val: "".to_string(),
-                        span: head,
-                    },
+                    Ordering::Equal => Value::string("", head),
                     Ordering::Greater => Value::Error {
                         error: ShellError::UnsupportedInput(
                             "End must be greater than or equal to Start".to_string(),
@@ -161,6 +158,7 @@ fn action(input: &Value, options: &Substring, head: Span) -> Value {
                                     &s.bytes().skip(start as usize).collect::<Vec<_>>(),
                                 )
                                 .to_string()
+                                .into()
                             } else {
                                 String::from_utf8_lossy(
                                     &s.bytes()
@@ -169,16 +167,14 @@ fn action(input: &Value, options: &Substring, head: Span) -> Value {
                                         .collect::<Vec<_>>(),
                                 )
                                 .to_string()
+                                .into()
                             }
                         },
                         span: head,
                     },
                 }
             } else {
-                Value::String {
-                    val: "".to_string(),
-                    span: head,
-                }
+                Value::string("", head)
             }
         }
         other => Value::Error {
@@ -309,10 +305,7 @@ mod tests {
 
     #[test]
     fn substrings_indexes() {
-        let word = Value::String {
-            val: "andres".to_string(),
-            span: Span::test_data(),
-        };
+        let word = Value::string("andres", Span::test_data());
 
         let cases = vec![
             expectation("a", (0, 1)),
@@ -347,7 +340,7 @@ mod tests {
             assert_eq!(
                 actual,
                 Value::String {
-                    val: expected.to_string(),
+                    val: expected.to_string().into(),
                     span: Span::test_data()
                 }
             );
@@ -203,23 +203,21 @@ where
     F: Fn(&str, Option<char>, &ClosureFlags) -> String + Send + Sync + 'static,
 {
     match input {
-        Value::String { val: s, .. } => Value::String {
-            val: trim_operation(s, char_, closure_flags),
-            span: head,
-        },
+        Value::String { val: s, .. } => {
+            Value::string(trim_operation(s, char_, closure_flags), head)
+        }
         other => match mode {
             ActionMode::Global => match other {
-                Value::Record { cols, vals, span } => {
-                    let new_vals = vals
+                Value::Record { val, span } => {
+                    let new_record = val
                         .iter()
-                        .map(|v| action(v, head, char_, closure_flags, trim_operation, mode))
+                        .map(|(col, v)| {
+                            let v = action(v, head, char_, closure_flags, trim_operation, mode);
+                            (col.clone(), v)
+                        })
                         .collect();
 
-                    Value::Record {
-                        cols: cols.to_vec(),
-                        vals: new_vals,
-                        span: *span,
-                    }
+                    Value::record(new_record, *span)
                 }
                 Value::List { vals, span } => {
                     let new_vals = vals
@@ -322,27 +320,14 @@ mod tests {
     }
 
     fn make_record(cols: Vec<&str>, vals: Vec<&str>) -> Value {
-        Value::Record {
-            cols: cols.iter().map(|x| x.to_string()).collect(),
-            vals: vals
-                .iter()
-                .map(|x| Value::String {
-                    val: x.to_string(),
-                    span: Span::test_data(),
-                })
-                .collect(),
-            span: Span::test_data(),
-        }
+        Value::test_record(cols, vals.iter().map(|x| Value::test_string(*x)).collect())
     }
 
     fn make_list(vals: Vec<&str>) -> Value {
         Value::List {
             vals: vals
                 .iter()
-                .map(|x| Value::String {
-                    val: x.to_string(),
-                    span: Span::test_data(),
-                })
+                .map(|x| Value::string(x.to_string(), Span::test_data()))
                 .collect(),
             span: Span::test_data(),
         }
@@ -516,36 +501,26 @@ mod tests {
     fn global_trims_table_all_white_space() {
         let row = Value::List {
             vals: vec![
-                Value::String {
-                    val: "  nu      shell   ".to_string(),
-                    span: Span::test_data(),
-                },
+                Value::string("  nu      shell   ", Span::test_data()),
                 Value::Int {
                     val: 65,
                     span: Span::test_data(),
                 },
-                Value::String {
-                    val: "  d".to_string(),
-                    span: Span::test_data(),
-                },
-            ],
+                Value::string("  d", Span::test_data()),
+            ]
+            .into(),
             span: Span::test_data(),
         };
         let expected = Value::List {
             vals: vec![
-                Value::String {
-                    val: "nushell".to_string(),
-                    span: Span::test_data(),
-                },
+                Value::string("nushell", Span::test_data()),
                 Value::Int {
                     val: 65,
                     span: Span::test_data(),
                 },
-                Value::String {
-                    val: "d".to_string(),
-                    span: Span::test_data(),
-                },
-            ],
+                Value::string("d", Span::test_data()),
+            ]
+            .into(),
             span: Span::test_data(),
         };
 
@@ -609,36 +584,26 @@ mod tests {
     fn global_trims_table_all_custom_character() {
         let row = Value::List {
             vals: vec![
-                Value::String {
-                    val: "##nu####shell##".to_string(),
-                    span: Span::test_data(),
-                },
+                Value::string("##nu####shell##", Span::test_data()),
                 Value::Int {
                     val: 65,
                     span: Span::test_data(),
                 },
-                Value::String {
-                    val: "#d".to_string(),
-                    span: Span::test_data(),
-                },
-            ],
+                Value::string("#d", Span::test_data()),
+            ]
+            .into(),
             span: Span::test_data(),
         };
         let expected = Value::List {
             vals: vec![
-                Value::String {
-                    val: "nushell".to_string(),
-                    span: Span::test_data(),
-                },
+                Value::string("nushell", Span::test_data()),
                 Value::Int {
                     val: 65,
                     span: Span::test_data(),
                 },
-                Value::String {
-                    val: "d".to_string(),
-                    span: Span::test_data(),
-                },
-            ],
+                Value::string("d", Span::test_data()),
+            ]
+            .into(),
             span: Span::test_data(),
         };
 
@@ -742,36 +707,26 @@ mod tests {
     fn global_trim_left_table() {
         let row = Value::List {
             vals: vec![
-                Value::String {
-                    val: "  a  ".to_string(),
-                    span: Span::test_data(),
-                },
+                Value::string("  a  ", Span::test_data()),
                 Value::Int {
                     val: 65,
                     span: Span::test_data(),
                 },
-                Value::String {
-                    val: " d".to_string(),
-                    span: Span::test_data(),
-                },
-            ],
+                Value::string(" d", Span::test_data()),
+            ]
+            .into(),
             span: Span::test_data(),
         };
         let expected = Value::List {
             vals: vec![
-                Value::String {
-                    val: "a  ".to_string(),
-                    span: Span::test_data(),
-                },
+                Value::string("a  ", Span::test_data()),
                 Value::Int {
                     val: 65,
                     span: Span::test_data(),
                 },
-                Value::String {
-                    val: "d".to_string(),
-                    span: Span::test_data(),
-                },
-            ],
+                Value::string("d", Span::test_data()),
+            ]
+            .into(),
             span: Span::test_data(),
         };
 
@@ -894,36 +849,26 @@ mod tests {
     fn global_trim_right_table() {
         let row = Value::List {
             vals: vec![
-                Value::String {
-                    val: "  a  ".to_string(),
-                    span: Span::test_data(),
-                },
+                Value::string("  a  ", Span::test_data()),
                 Value::Int {
                     val: 65,
                     span: Span::test_data(),
                 },
-                Value::String {
-                    val: " d".to_string(),
-                    span: Span::test_data(),
-                },
-            ],
+                Value::string(" d", Span::test_data()),
+            ]
+            .into(),
             span: Span::test_data(),
         };
         let expected = Value::List {
             vals: vec![
-                Value::String {
-                    val: "  a".to_string(),
-                    span: Span::test_data(),
-                },
+                Value::string("  a", Span::test_data()),
                 Value::Int {
                     val: 65,
                     span: Span::test_data(),
                 },
-                Value::String {
-                    val: " d".to_string(),
-                    span: Span::test_data(),
-                },
-            ],
+                Value::string(" d", Span::test_data()),
+            ]
+            .into(),
             span: Span::test_data(),
         };
 
@@ -1046,36 +991,26 @@ mod tests {
     fn global_trim_format_flag_table() {
         let row = Value::List {
             vals: vec![
-                Value::String {
-                    val: "  a    b     c    d  ".to_string(),
-                    span: Span::test_data(),
-                },
+                Value::string("  a    b     c    d  ", Span::test_data()),
                 Value::Int {
                     val: 65,
                     span: Span::test_data(),
                 },
-                Value::String {
-                    val: " b c  d e   f".to_string(),
-                    span: Span::test_data(),
-                },
-            ],
+                Value::string(" b c  d e   f", Span::test_data()),
+            ]
+            .into(),
             span: Span::test_data(),
         };
         let expected = Value::List {
             vals: vec![
-                Value::String {
-                    val: "a b c d".to_string(),
-                    span: Span::test_data(),
-                },
+                Value::string("a b c d", Span::test_data()),
                 Value::Int {
                     val: 65,
                     span: Span::test_data(),
                 },
-                Value::String {
-                    val: "b c d e f".to_string(),
-                    span: Span::test_data(),
-                },
-            ],
+                Value::string("b c d e f", Span::test_data()),
+            ]
+            .into(),
             span: Span::test_data(),
         };
 
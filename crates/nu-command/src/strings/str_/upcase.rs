@@ -73,10 +73,7 @@ fn operate(
 
 fn action(input: &Value, head: Span) -> Value {
     match input {
-        Value::String { val: s, .. } => Value::String {
-            val: s.to_uppercase(),
-            span: head,
-        },
+        Value::String { val: s, .. } => Value::string(s.to_uppercase(), head),
         other => {
             let got = format!("Expected string but got {}", other.get_type());
             Value::Error {
@@ -1,9 +1,13 @@
+use std::sync::atomic::Ordering;
 use std::time::Instant;
 
-use nu_engine::eval_block;
+use nu_engine::{eval_block, CallExt};
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, IntoPipelineData, PipelineData, Signature, SyntaxShape, Value};
+use nu_protocol::{
+    record, BlockId, Category, Example, IntoPipelineData, PipelineData, Record, ShellError,
+    Signature, Span, SyntaxShape, Value,
+};
 
 #[derive(Clone)]
 pub struct Benchmark;
@@ -17,12 +21,30 @@ impl Command for Benchmark {
         "Time the running time of a block"
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"Without --rounds or --warmup, the block is run once and the output is how long it took.
+With them, it's run as many times as --rounds asks for, after the --warmup runs that aren't timed, and the output is the mean, median, minimum, maximum and standard deviation of the times.
+Given a record of named blocks, the output is a table comparing them, with how many times slower than the fastest one each block is."#
+    }
+
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("benchmark")
             .required(
                 "block",
-                SyntaxShape::Block(Some(vec![])),
-                "the block to run",
+                SyntaxShape::Any,
+                "the block to run, or a record of named blocks to compare",
+            )
+            .named(
+                "rounds",
+                SyntaxShape::Int,
+                "the number of timed runs of each block (defaults to 1)",
+                Some('r'),
+            )
+            .named(
+                "warmup",
+                SyntaxShape::Int,
+                "the number of runs of each block before the timed ones (defaults to 0)",
+                Some('w'),
             )
             .category(Category::System)
     }
@@ -34,28 +56,193 @@ impl Command for Benchmark {
         call: &Call,
         _input: PipelineData,
     ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
-        let block = call.positional[0]
-            .as_block()
-            .expect("internal error: expected block");
-        let block = engine_state.get_block(block);
+        let blocks: Value = call.req(engine_state, stack, 0)?;
+        let repeated = call.has_flag("rounds") || call.has_flag("warmup");
+        let rounds = run_count(engine_state, stack, call, "rounds", 1)?;
+        let warmup = run_count(engine_state, stack, call, "warmup", 0)?;
+
+        if rounds == 0 {
+            return Err(ShellError::UnsupportedInput(
+                "at least one round has to be timed".into(),
+                flag_span(call, "rounds"),
+            ));
+        }
+
+        let named_blocks = match blocks {
+            Value::Block { val, .. } => {
+                let mut times = time_block(engine_state, stack, val, warmup, rounds, call.head)?;
+
+                if !repeated {
+                    return Ok(Value::Duration {
+                        val: times[0],
+                        span: call.head,
+                    }
+                    .into_pipeline_data());
+                }
+
+                times.sort_unstable();
+                return Ok(
+                    Value::record(summary(&times, call.head), call.head).into_pipeline_data()
+                );
+            }
+            Value::Record { val, .. } => val,
+            x => {
+                return Err(ShellError::CantConvert(
+                    "block or record of blocks".into(),
+                    x.get_type().to_string(),
+                    x.span()?,
+                ))
+            }
+        };
+
+        let mut timings = vec![];
+        for (name, block) in named_blocks.iter() {
+            let block_id = block.as_block()?;
+            let mut times = time_block(engine_state, stack, block_id, warmup, rounds, call.head)?;
+            times.sort_unstable();
+
+            timings.push((name, times));
+        }
+
+        let fastest = timings
+            .iter()
+            .map(|(_, times)| mean(times))
+            .fold(f64::INFINITY, f64::min);
+
+        let rows = timings
+            .into_iter()
+            .map(|(name, times)| {
+                let mut row = record! {
+                    "name" => Value::string(name, call.head),
+                };
+                row.extend(summary(&times, call.head));
+                row.insert(
+                    "relative",
+                    Value::Float {
+                        val: if fastest > 0.0 {
+                            mean(&times) / fastest
+                        } else {
+                            1.0
+                        },
+                        span: call.head,
+                    },
+                );
+
+                Value::record(row, call.head)
+            })
+            .collect::<Vec<_>>();
+
+        Ok(Value::list(rows, call.head).into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Time a block",
+                example: "benchmark { 1..1000 | math sum }",
+                result: None,
+            },
+            Example {
+                description: "Time a block 100 times, after running it 10 times to warm up",
+                example: "benchmark --rounds 100 --warmup 10 { 1..1000 | math sum }",
+                result: None,
+            },
+            Example {
+                description: "Compare two ways of doing the same thing",
+                example: "benchmark --rounds 20 { sum: { 1..1000 | math sum }, each: { 1..1000 | each { $it } | math sum } }",
+                result: None,
+            },
+        ]
+    }
+}
+
+fn run_count(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &Call,
+    flag: &str,
+    default: usize,
+) -> Result<usize, ShellError> {
+    match call.get_flag::<i64>(engine_state, stack, flag)? {
+        Some(count) if count < 0 => Err(ShellError::UnsupportedInput(
+            format!("the number of {} can't be negative", flag),
+            flag_span(call, flag),
+        )),
+        Some(count) => Ok(count as usize),
+        None => Ok(default),
+    }
+}
+
+fn flag_span(call: &Call, flag: &str) -> Span {
+    call.get_named_arg(flag)
+        .map(|arg| arg.span)
+        .unwrap_or(call.head)
+}
+
+// The time each timed run took, in nanoseconds. The output of each run is drained, so that the
+// work of the commands streaming it is counted as well
+fn time_block(
+    engine_state: &EngineState,
+    stack: &Stack,
+    block_id: BlockId,
+    warmup: usize,
+    rounds: usize,
+    span: Span,
+) -> Result<Vec<i64>, ShellError> {
+    let block = engine_state.get_block(block_id);
+    let mut times = Vec::with_capacity(rounds);
+
+    for round in 0..warmup + rounds {
+        // A block that calls no commands would never see ctrl-c, so each round checks for it
+        if let Some(ctrlc) = &engine_state.ctrlc {
+            if ctrlc.load(Ordering::SeqCst) {
+                return Err(ShellError::Interrupted(span));
+            }
+        }
 
         let mut stack = stack.collect_captures(&block.captures);
+
         let start_time = Instant::now();
-        eval_block(
-            engine_state,
-            &mut stack,
-            block,
-            PipelineData::new(call.head),
-        )?
-        .into_value(call.head);
-
-        let end_time = Instant::now();
-
-        let output = Value::Duration {
-            val: (end_time - start_time).as_nanos() as i64,
-            span: call.head,
-        };
+        eval_block(engine_state, &mut stack, block, PipelineData::new(span))?.into_value(span);
+        let elapsed = start_time.elapsed();
 
-        Ok(output.into_pipeline_data())
+        if round >= warmup {
+            times.push(elapsed.as_nanos() as i64);
+        }
     }
+
+    Ok(times)
+}
+
+// `times` is sorted and not empty
+fn summary(times: &[i64], span: Span) -> Record {
+    let duration = |val: f64| Value::Duration {
+        val: val.round() as i64,
+        span,
+    };
+
+    let mean = mean(times);
+    let middle = times.len() / 2;
+    let median = if times.len() % 2 == 1 {
+        times[middle] as f64
+    } else {
+        (times[middle - 1] as f64 + times[middle] as f64) / 2.0
+    };
+    let variance = times
+        .iter()
+        .map(|&time| (time as f64 - mean).powi(2))
+        .sum::<f64>()
+        / times.len() as f64;
+
+    record! {
+        "mean" => duration(mean),
+        "median" => duration(median),
+        "min" => duration(times[0] as f64),
+        "max" => duration(times[times.len() - 1] as f64),
+        "stddev" => duration(variance.sqrt()),
+    }
+}
+
+fn mean(times: &[i64]) -> f64 {
+    times.iter().map(|&time| time as f64).sum::<f64>() / times.len() as f64
 }
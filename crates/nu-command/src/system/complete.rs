@@ -0,0 +1,70 @@
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, DataSource, ExternalOutput, IntoPipelineData, PipelineData, PipelineMetadata, Record,
+    ShellError, Signature, Value,
+};
+
+#[derive(Clone)]
+pub struct Complete;
+
+impl Command for Complete {
+    fn name(&self) -> &str {
+        "complete"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("complete").category(Category::System)
+    }
+
+    fn usage(&self) -> &str {
+        "Complete the external piped in, collecting outputs and exit code"
+    }
+
+    fn collects_stderr(&self) -> bool {
+        true
+    }
+
+    fn changes_env(&self) -> bool {
+        true
+    }
+
+    fn run(
+        &self,
+        _engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        match input {
+            PipelineData::RawStream(
+                stdout,
+                Some(PipelineMetadata {
+                    data_source: DataSource::External(ExternalOutput { stderr, exit_code }),
+                }),
+            ) => {
+                let mut record = Record::new();
+                record.insert("stdout", stdout.into_value());
+
+                if let Some(stderr) = stderr {
+                    record.insert("stderr", stderr.into_value());
+                }
+
+                // The exit code is only sent once the command finished, after its outputs closed
+                let exit_code = exit_code
+                    .last()
+                    .unwrap_or(Value::Nothing { span: call.head });
+
+                stack.add_env_var("LAST_EXIT_CODE".into(), exit_code.clone());
+
+                record.insert("exit_code", exit_code);
+
+                Ok(Value::record(record, call.head).into_pipeline_data())
+            }
+            _ => Err(ShellError::UnsupportedInput(
+                "complete only works with an external command piped in".into(),
+                call.head,
+            )),
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use nu_engine::get_full_help;
+use nu_protocol::{
+    ast::Call,
+    engine::{Command, EngineState, Stack},
+    Category, IntoPipelineData, PipelineData, Signature, Value,
+};
+
+#[derive(Clone)]
+pub struct JobCommand;
+
+impl Command for JobCommand {
+    fn name(&self) -> &str {
+        "job"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("job").category(Category::System)
+    }
+
+    fn usage(&self) -> &str {
+        "Manage the jobs running in the background."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"An external command followed by '&' is started in the background, as a job. The
+id of the new job is returned, and 'jobs' lists all of them."#
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, nu_protocol::ShellError> {
+        Ok(Value::String {
+            val: get_full_help(
+                &JobCommand.signature(),
+                &JobCommand.examples(),
+                engine_state,
+            )
+            .into(),
+            span: call.head,
+        }
+        .into_pipeline_data())
+    }
+}
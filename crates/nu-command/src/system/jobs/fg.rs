@@ -0,0 +1,138 @@
+use std::process::{Child, ExitStatus};
+
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, PipelineData, ShellError, Signature, Spanned, SyntaxShape, Value,
+};
+
+use super::super::run_external::exit_code;
+use super::job_table;
+
+#[derive(Clone)]
+pub struct Fg;
+
+impl Command for Fg {
+    fn name(&self) -> &str {
+        "fg"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("fg")
+            .required("id", SyntaxShape::Int, "the id of the job")
+            .category(Category::System)
+    }
+
+    fn usage(&self) -> &str {
+        "Bring a job running in the background to the foreground, waiting for it to finish."
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"The job gets the terminal while it's in the foreground, so it reads the input and a
+ctrl-c goes to it. If it's stopped again, with ctrl-z, it goes back to the background."#
+    }
+
+    fn changes_env(&self) -> bool {
+        true
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let id: Spanned<i64> = call.req(engine_state, stack, 0)?;
+
+        let mut job = job_table(engine_state)?
+            .remove(id.item as usize)
+            .ok_or(ShellError::JobNotFound(id.span))?;
+
+        let status = wait_in_foreground(&mut job.child)
+            .map_err(|err| ShellError::ExternalCommand(format!("{}", err), id.span))?;
+
+        match status {
+            Some(status) => stack.add_env_var(
+                "LAST_EXIT_CODE".into(),
+                Value::Int {
+                    val: exit_code(status),
+                    span: call.head,
+                },
+            ),
+            None => job_table(engine_state)?.put_back(job),
+        }
+
+        Ok(PipelineData::new(call.head))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example {
+            description: "Wait for the job with id 1",
+            example: "fg 1",
+            result: None,
+        }]
+    }
+}
+
+// Continues the job and waits for it, with the terminal handed to its process group and given back
+// to the shell's after. Returns `None` if the job was stopped again instead of finishing
+#[cfg(unix)]
+fn wait_in_foreground(child: &mut Child) -> std::io::Result<Option<ExitStatus>> {
+    use std::os::unix::process::ExitStatusExt;
+
+    let group = child.id() as libc::pid_t;
+
+    // Without a terminal, as in a script run from another program, there's none to hand over
+    // SAFETY: isatty and getpgrp only look at the descriptor and the shell's own process
+    let terminal = unsafe { libc::isatty(libc::STDIN_FILENO) } == 1;
+    let shell_group = unsafe { libc::getpgrp() };
+
+    // SAFETY: tcsetpgrp only changes which group the terminal belongs to, and kill only sends a
+    // signal. The job may have been stopped for reading from the terminal while in the background,
+    // so it's continued once it has the terminal
+    unsafe {
+        if terminal {
+            libc::tcsetpgrp(libc::STDIN_FILENO, group);
+        }
+        libc::kill(-group, libc::SIGCONT);
+    }
+
+    let mut status = 0;
+    let waited = loop {
+        // SAFETY: status is a valid place for waitpid to write the status to
+        if unsafe { libc::waitpid(group, &mut status, libc::WUNTRACED) } != -1 {
+            break Ok(());
+        }
+
+        let err = std::io::Error::last_os_error();
+        if err.kind() != std::io::ErrorKind::Interrupted {
+            break Err(err);
+        }
+    };
+
+    if terminal {
+        // The shell is in the background until it has the terminal back, and a group in the
+        // background that takes the terminal is stopped, unless it ignores SIGTTOU meanwhile
+        // SAFETY: the previous handler is put back right after, and tcsetpgrp is as above
+        unsafe {
+            let previous = libc::signal(libc::SIGTTOU, libc::SIG_IGN);
+            libc::tcsetpgrp(libc::STDIN_FILENO, shell_group);
+            libc::signal(libc::SIGTTOU, previous);
+        }
+    }
+    waited?;
+
+    if libc::WIFSTOPPED(status) {
+        Ok(None)
+    } else {
+        Ok(Some(ExitStatus::from_raw(status)))
+    }
+}
+
+// The job shares the console, so a ctrl-c already reaches it
+#[cfg(not(unix))]
+fn wait_in_foreground(child: &mut Child) -> std::io::Result<Option<ExitStatus>> {
+    child.wait().map(Some)
+}
@@ -0,0 +1,130 @@
+#[cfg(unix)]
+use std::process::Child;
+#[cfg(unix)]
+use std::sync::mpsc;
+#[cfg(unix)]
+use std::time::Duration;
+
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Spanned, SyntaxShape};
+
+use super::job_table;
+
+#[derive(Clone)]
+pub struct JobKill;
+
+impl Command for JobKill {
+    fn name(&self) -> &str {
+        "job kill"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("job kill")
+            .required("id", SyntaxShape::Int, "the id of the job to kill")
+            .category(Category::System)
+    }
+
+    fn usage(&self) -> &str {
+        "Kill a job running in the background."
+    }
+
+    fn extra_usage(&self) -> &str {
+        "The job and whatever it started are asked to terminate, and killed if they're still running a second later."
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let id: Spanned<i64> = call.req(engine_state, stack, 0)?;
+
+        let job = job_table(engine_state)?
+            .remove(id.item as usize)
+            .ok_or(ShellError::JobNotFound(id.span))?;
+
+        // The job may have finished on its own already, which is just as good
+        #[cfg(unix)]
+        kill_group(job.child);
+
+        #[cfg(not(unix))]
+        {
+            let mut child = job.child;
+            let _ = child.kill();
+            child
+                .wait()
+                .map_err(|err| ShellError::ExternalCommand(format!("{}", err), id.span))?;
+        }
+
+        Ok(PipelineData::new(call.head))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example {
+            description: "Kill the job with id 1",
+            example: "job kill 1",
+            result: None,
+        }]
+    }
+}
+
+// How long the job gets to exit after being asked to, before it's killed
+#[cfg(unix)]
+const GRACE_PERIOD: Duration = Duration::from_secs(1);
+
+// The job leads a process group of its own, which whatever it started is in too, so the whole
+// group is asked to terminate. What's still running after the grace period is killed. The grace
+// period runs on a thread of its own, so the caller doesn't wait it out
+#[cfg(unix)]
+fn kill_group(mut child: Child) {
+    // Once the leader is reaped, say by `jobs` asking how it is doing, its pid can go to anything
+    // else, and so can the group's id. It is only signalled while that can't have happened
+    if !matches!(child.try_wait(), Ok(None)) {
+        return;
+    }
+    let group = child.id() as libc::pid_t;
+
+    // SAFETY: kill only sends a signal, and a negative pid names the job's own group
+    unsafe {
+        libc::kill(-group, libc::SIGTERM);
+    }
+
+    std::thread::spawn(move || {
+        let (exited_tx, exited_rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            wait_without_reaping(group);
+            let _ = exited_tx.send(());
+        });
+        let _ = exited_rx.recv_timeout(GRACE_PERIOD);
+
+        // The leader isn't reaped until after this, so its pid, and the group's id with it, can't
+        // have gone to anything else yet. What's left of the group is killed
+        // SAFETY: as above
+        unsafe {
+            libc::kill(-group, libc::SIGKILL);
+        }
+
+        let _ = child.wait();
+    });
+}
+
+// Block until the process exits, leaving it to be reaped by whoever waits on it next
+#[cfg(unix)]
+fn wait_without_reaping(pid: libc::pid_t) {
+    // SAFETY: waitid only writes to the siginfo it is given, and WNOWAIT leaves the process as it is
+    unsafe {
+        let mut info: libc::siginfo_t = std::mem::zeroed();
+        while libc::waitid(
+            libc::P_PID,
+            pid as libc::id_t,
+            &mut info,
+            libc::WEXITED | libc::WNOWAIT,
+        ) == -1
+            && std::io::Error::last_os_error().kind() == std::io::ErrorKind::Interrupted
+        {}
+    }
+}
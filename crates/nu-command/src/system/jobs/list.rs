@@ -0,0 +1,72 @@
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    record, Category, Example, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature,
+    Value,
+};
+
+use super::job_table;
+
+#[derive(Clone)]
+pub struct Jobs;
+
+impl Command for Jobs {
+    fn name(&self) -> &str {
+        "jobs"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("jobs").category(Category::System)
+    }
+
+    fn usage(&self) -> &str {
+        "List the jobs running in the background."
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let span = call.head;
+        let mut jobs = job_table(engine_state)?;
+
+        let mut output = vec![];
+        for job in jobs.iter_mut() {
+            let status = job.status();
+
+            output.push(Value::record(
+                record! {
+                    "id" => Value::Int {
+                        val: job.id as i64,
+                        span,
+                    },
+                    "pid" => Value::Int {
+                        val: job.child.id() as i64,
+                        span,
+                    },
+                    "command" => Value::string(job.command.clone(), span),
+                    "status" => Value::string(status, span),
+                },
+                span,
+            ));
+        }
+
+        // Finished jobs are listed one last time, then forgotten
+        jobs.take_finished();
+
+        Ok(output
+            .into_iter()
+            .into_pipeline_data(engine_state.ctrlc.clone()))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example {
+            description: "Start a job and list it",
+            example: "^sleep 10 &; jobs",
+            result: None,
+        }]
+    }
+}
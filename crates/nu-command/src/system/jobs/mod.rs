@@ -0,0 +1,19 @@
+mod command;
+mod fg;
+mod kill;
+mod list;
+
+pub use command::JobCommand;
+pub use fg::Fg;
+pub use kill::JobKill;
+pub use list::Jobs;
+
+use nu_protocol::{engine::EngineState, engine::JobTable, ShellError};
+use std::sync::MutexGuard;
+
+fn job_table(engine_state: &EngineState) -> Result<MutexGuard<'_, JobTable>, ShellError> {
+    engine_state
+        .jobs
+        .lock()
+        .map_err(|_| ShellError::NushellFailed("job table is poisoned".into()))
+}
@@ -1,9 +1,19 @@
 mod benchmark;
+mod complete;
+mod jobs;
+mod profile;
 mod ps;
 mod run_external;
 mod sys;
+mod timeout;
+mod which_;
 
 pub use benchmark::Benchmark;
+pub use complete::Complete;
+pub use jobs::{Fg, JobCommand, JobKill, Jobs};
+pub use profile::Profile;
 pub use ps::Ps;
 pub use run_external::{External, ExternalCommand};
 pub use sys::Sys;
+pub use timeout::Timeout;
+pub use which_::Which;
@@ -0,0 +1,73 @@
+use nu_engine::eval_block;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Profiler, Stack};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, SyntaxShape,
+};
+
+#[derive(Clone)]
+pub struct Profile;
+
+impl Command for Profile {
+    fn name(&self) -> &str {
+        "profile"
+    }
+
+    fn usage(&self) -> &str {
+        "Time each pipeline element of a block"
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"The table has a row for each element that ran, with how many times it ran, the time it took in total and the time it took outside of the elements nested in it.
+The elements run by a custom command, or by a block given to a command, are nested in the children column of the element that ran them.
+An element that streams its output is timed while the stream is drained too, but not for the time spent waiting on the element before it."#
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("profile")
+            .required(
+                "block",
+                SyntaxShape::Block(Some(vec![])),
+                "the block to run",
+            )
+            .category(Category::System)
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let block = call.positional[0]
+            .as_block()
+            .expect("internal error: expected block");
+        let block = engine_state.get_block(block);
+
+        let profiler = Profiler::new();
+        let mut stack = stack.collect_captures(&block.captures);
+        stack.profiler = Some(profiler.clone());
+
+        // The output is drained so that elements streaming it are timed too
+        eval_block(
+            engine_state,
+            &mut stack,
+            block,
+            PipelineData::new(call.head),
+        )?
+        .into_value(call.head);
+
+        Ok(profiler
+            .report(engine_state, call.head)
+            .into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example {
+            description: "Find out which step of a pipeline is the slow one",
+            example: "profile { ls | sort-by size | first 3 }",
+            result: None,
+        }]
+    }
+}
@@ -1,7 +1,8 @@
 use nu_protocol::{
     ast::Call,
     engine::{Command, EngineState, Stack},
-    Category, Example, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, Value,
+    Category, Example, IntoInterruptiblePipelineData, PipelineData, Record, ShellError, Signature,
+    Value,
 };
 use sysinfo::{ProcessExt, System, SystemExt};
 
@@ -64,70 +65,69 @@ fn run_ps(engine_state: &EngineState, call: &Call) -> Result<PipelineData, Shell
     for pid in result {
         sys.refresh_process(pid);
         if let Some(result) = sys.process(pid) {
-            let mut cols = vec![];
-            let mut vals = vec![];
-
-            cols.push("pid".into());
-            vals.push(Value::Int {
-                val: pid as i64,
-                span,
-            });
-
-            cols.push("name".into());
-            vals.push(Value::String {
-                val: result.name().into(),
-                span,
-            });
-
-            cols.push("status".into());
-            vals.push(Value::String {
-                val: format!("{:?}", result.status()),
-                span,
-            });
-
-            cols.push("cpu".into());
-            vals.push(Value::Float {
-                val: result.cpu_usage() as f64,
-                span,
-            });
-
-            cols.push("mem".into());
-            vals.push(Value::Filesize {
-                val: result.memory() as i64 * 1000,
-                span,
-            });
-
-            cols.push("virtual".into());
-            vals.push(Value::Filesize {
-                val: result.virtual_memory() as i64 * 1000,
-                span,
-            });
+            let mut record = Record::new();
+
+            record.insert(
+                "pid",
+                Value::Int {
+                    val: pid as i64,
+                    span,
+                },
+            );
+
+            record.insert("name", Value::string(result.name(), span));
+
+            record.insert(
+                "status",
+                Value::string(format!("{:?}", result.status()), span),
+            );
+
+            record.insert(
+                "cpu",
+                Value::Float {
+                    val: result.cpu_usage() as f64,
+                    span,
+                },
+            );
+
+            record.insert(
+                "mem",
+                Value::Filesize {
+                    val: result.memory() as i64 * 1000,
+                    span,
+                },
+            );
+
+            record.insert(
+                "virtual",
+                Value::Filesize {
+                    val: result.virtual_memory() as i64 * 1000,
+                    span,
+                },
+            );
 
             if long {
-                cols.push("parent".into());
                 if let Some(parent) = result.parent() {
-                    vals.push(Value::Int {
-                        val: parent as i64,
-                        span,
-                    });
+                    record.insert(
+                        "parent",
+                        Value::Int {
+                            val: parent as i64,
+                            span,
+                        },
+                    );
                 } else {
-                    vals.push(Value::Nothing { span });
+                    record.insert("parent", Value::Nothing { span });
                 }
 
-                cols.push("exe".into());
-                vals.push(Value::String {
-                    val: result.exe().to_string_lossy().to_string(),
-                    span,
-                });
+                record.insert(
+                    "exe",
+                    Value::string(result.exe().to_string_lossy().to_string(), span),
+                );
 
-                cols.push("command".into());
-                vals.push(Value::String {
-                    val: result.cmd().join(" "),
-                    span,
-                });
+                record.insert("command", Value::string(result.cmd().join(" "), span));
             }
 
-            output.push(Value::Record { cols, vals, span });
+            output.push(Value::record(record, span));
         }
     }
 
@@ -1,16 +1,19 @@
-use std::borrow::Cow;
 use std::collections::HashMap;
 use std::env;
-use std::io::{BufRead, BufReader, Write};
-use std::path::Path;
-use std::process::{Command as CommandSys, Stdio};
-use std::sync::atomic::Ordering;
-use std::sync::mpsc;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::process::{Child, Command as CommandSys, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
 
 use nu_engine::env_to_strings;
-use nu_protocol::engine::{EngineState, Stack};
+use nu_protocol::engine::{Capability, EngineState, Stack};
 use nu_protocol::{ast::Call, engine::Command, ShellError, Signature, SyntaxShape, Value};
-use nu_protocol::{Category, Config, IntoInterruptiblePipelineData, PipelineData, Span, Spanned};
+use nu_protocol::{
+    Category, Config, DataSource, ExternalOutput, PipelineData, PipelineMetadata, RawStream,
+    Spanned, ValueStream,
+};
 
 use itertools::Itertools;
 
@@ -18,6 +21,8 @@ use nu_engine::CallExt;
 use regex::Regex;
 
 const OUTPUT_BUFFER_SIZE: usize = 8192;
+// The longest an external that finished goes unnoticed while it's waited on
+const WAIT_INTERVAL: Duration = Duration::from_millis(10);
 
 #[derive(Clone)]
 pub struct External;
@@ -38,10 +43,25 @@ impl Command for External {
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("run_external")
             .switch("last_expression", "last_expression", None)
+            .switch("redirect_stderr", "redirect_stderr", None)
+            .switch("stderr_to_pipeline", "stderr_to_pipeline", None)
+            .switch("background", "background", None)
+            .named("stdout_file", SyntaxShape::Filepath, "stdout_file", None)
+            .named("stderr_file", SyntaxShape::Filepath, "stderr_file", None)
+            .named(
+                "stdout_and_stderr_file",
+                SyntaxShape::Filepath,
+                "stdout_and_stderr_file",
+                None,
+            )
             .rest("rest", SyntaxShape::Any, "external command to run")
             .category(Category::System)
     }
 
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::Externals]
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -52,39 +72,26 @@ impl Command for External {
         let mut name: Spanned<String> = call.req(engine_state, stack, 0)?;
         let args: Vec<Value> = call.rest(engine_state, stack, 1)?;
         let last_expression = call.has_flag("last_expression");
+        let redirect_stderr = call.has_flag("redirect_stderr");
+        let stderr_to_pipeline = call.has_flag("stderr_to_pipeline");
+        let background = call.has_flag("background");
+
+        let stdout_and_stderr_file: Option<Spanned<String>> =
+            call.get_flag(engine_state, stack, "stdout_and_stderr_file")?;
+        let stdout_file = call
+            .get_flag(engine_state, stack, "stdout_file")?
+            .or_else(|| stdout_and_stderr_file.clone());
+        let stderr_file = call
+            .get_flag(engine_state, stack, "stderr_file")?
+            .or(stdout_and_stderr_file);
 
         // Translate environment variables from Values to Strings
         let config = stack.get_config().unwrap_or_default();
         let env_vars_str = env_to_strings(engine_state, stack, &config)?;
 
-        // Check if this is a single call to a directory, if so auto-cd
         let path = nu_path::expand_path(&name.item);
-        let orig = name.item.clone();
         name.item = path.to_string_lossy().to_string();
 
-        let path = Path::new(&name.item);
-        if (orig.starts_with('.')
-            || orig.starts_with('~')
-            || orig.starts_with('/')
-            || orig.starts_with('\\'))
-            && path.is_dir()
-            && args.is_empty()
-        {
-            // We have an auto-cd
-            let _ = std::env::set_current_dir(&path);
-
-            //FIXME: this only changes the current scope, but instead this environment variable
-            //should probably be a block that loads the information from the state in the overlay
-            stack.add_env_var(
-                "PWD".into(),
-                Value::String {
-                    val: name.item.clone(),
-                    span: call.head,
-                },
-            );
-            return Ok(PipelineData::new(call.head));
-        }
-
         let mut args_strs = vec![];
 
         for arg in args {
@@ -114,10 +121,15 @@ impl Command for External {
             name,
             args: args_strs,
             last_expression,
+            redirect_stderr,
+            stderr_to_pipeline,
+            background,
+            stdout_file,
+            stderr_file,
             env_vars: env_vars_str,
             call,
         };
-        command.run_with_input(engine_state, input, config)
+        command.run_with_input(engine_state, stack, input, config)
     }
 }
 
@@ -125,6 +137,11 @@ pub struct ExternalCommand<'call> {
     pub name: Spanned<String>,
     pub args: Vec<String>,
     pub last_expression: bool,
+    pub redirect_stderr: bool,
+    pub stderr_to_pipeline: bool,
+    pub background: bool,
+    pub stdout_file: Option<Spanned<String>>,
+    pub stderr_file: Option<Spanned<String>>,
     pub env_vars: HashMap<String, String>,
     pub call: &'call Call,
 }
@@ -133,6 +150,7 @@ impl<'call> ExternalCommand<'call> {
     pub fn run_with_input(
         &self,
         engine_state: &EngineState,
+        stack: &mut Stack,
         input: PipelineData,
         config: Config,
     ) -> Result<PipelineData, ShellError> {
@@ -148,12 +166,37 @@ impl<'call> ExternalCommand<'call> {
 
         process.envs(&self.env_vars);
 
+        // Outputs redirected to files are written there by the command itself, so they never
+        // pass through us
+        let (stdout_file, stderr_file) = self.open_redirection_files()?;
+
+        if self.background {
+            if let Some(file) = stdout_file {
+                process.stdout(file);
+            }
+            if let Some(file) = stderr_file {
+                process.stderr(file);
+            }
+
+            return self.spawn_background(engine_state, process);
+        }
+
         // If the external is not the last command, its output will get piped
         // either as a string or binary
-        if !self.last_expression {
+        if let Some(file) = stdout_file {
+            process.stdout(file);
+        } else if !self.last_expression {
             process.stdout(Stdio::piped());
         }
 
+        // Stderr goes to the terminal unless it's redirected or the next command asked for it
+        let merge_stderr = self.stderr_to_pipeline && !self.last_expression;
+        if let Some(file) = stderr_file {
+            process.stderr(file);
+        } else if merge_stderr || self.redirect_stderr {
+            process.stderr(Stdio::piped());
+        }
+
         // If there is an input from the pipeline. The stdin from the process
         // is piped so it can be used to send the input information
         if !matches!(input, PipelineData::Value(Value::Nothing { .. }, ..)) {
@@ -169,6 +212,21 @@ impl<'call> ExternalCommand<'call> {
                 // if there is a string or a stream, that is sent to the pipe std
                 if let Some(mut stdin_write) = child.stdin.take() {
                     std::thread::spawn(move || {
+                        // The raw output of another external is passed on as it is
+                        if let PipelineData::RawStream(stream, ..) = input {
+                            for bytes in stream.stream {
+                                match bytes {
+                                    Ok(bytes) => {
+                                        if stdin_write.write(&bytes).is_err() {
+                                            return Ok(());
+                                        }
+                                    }
+                                    Err(_) => return Err(()),
+                                }
+                            }
+                            return Ok(());
+                        }
+
                         for value in input.into_iter() {
                             match value {
                                 Value::String { val, span: _ } => {
@@ -195,67 +253,183 @@ impl<'call> ExternalCommand<'call> {
                     });
                 }
 
-                let last_expression = self.last_expression;
                 let span = self.name.span;
+
+                // The output of the last expression goes straight to the terminal, so we can
+                // wait for the command here and record how it exited
+                if self.last_expression {
+                    let status = wait_or_kill(&mut child, &ctrlc)
+                        .map_err(|err| ShellError::ExternalCommand(format!("{}", err), span))?;
+
+                    stack.add_env_var(
+                        "LAST_EXIT_CODE".into(),
+                        Value::Int {
+                            val: exit_code(status),
+                            span,
+                        },
+                    );
+
+                    return Ok(PipelineData::new(span));
+                }
+
                 let output_ctrlc = ctrlc.clone();
-                let (tx, rx) = mpsc::channel();
+                let (stdout_tx, stdout_rx) = mpsc::channel();
+                let (exit_code_tx, exit_code_rx) = mpsc::channel();
+
+                // A piped stderr is read on a thread of its own, so a command filling up its
+                // stderr can't block us reading its stdout. It's either merged into the stdout
+                // stream or, when asked for, sent over a second channel
+                let mut stderr = None;
+                if let Some(stderr_read) = child.stderr.take() {
+                    let stderr_ctrlc = ctrlc.clone();
+
+                    if merge_stderr {
+                        let stderr_tx = stdout_tx.clone();
+                        std::thread::spawn(move || {
+                            read_to_channel(stderr_read, stderr_tx, stderr_ctrlc)
+                        });
+                    } else {
+                        let (stderr_tx, stderr_rx) = mpsc::channel();
+                        std::thread::spawn(move || {
+                            read_to_channel(stderr_read, stderr_tx, stderr_ctrlc)
+                        });
+
+                        stderr = Some(RawStream::new(
+                            Box::new(stderr_rx.into_iter().map(Ok)),
+                            output_ctrlc.clone(),
+                            span,
+                        ));
+                    }
+                }
 
+                let exit_code_slot = stack.streamed_exit_code_slot();
                 std::thread::spawn(move || {
-                    // If this external is not the last expression, then its output is piped to a channel
-                    // and we create a ValueStream that can be consumed
-                    if !last_expression {
-                        let stdout = child.stdout.take().ok_or_else(|| {
-                            ShellError::ExternalCommand(
-                                "Error taking stdout from external".to_string(),
-                                span,
-                            )
-                        })?;
-
-                        // Stdout is read using the Buffer reader. It will do so until there is an
-                        // error or there are no more bytes to read
-                        let mut buf_read = BufReader::with_capacity(OUTPUT_BUFFER_SIZE, stdout);
-                        while let Ok(bytes) = buf_read.fill_buf() {
-                            if bytes.is_empty() {
-                                break;
-                            }
+                    // This external is not the last expression, so its output is piped to a channel
+                    // and we create a RawStream that can be consumed. Stdout is missing here
+                    // only when it was redirected to a file. It's read on a thread of its own, so
+                    // that ctrl-c stops an external that isn't writing anything
+                    if let Some(stdout) = child.stdout.take() {
+                        let stdout_tx = stdout_tx.clone();
+                        let stdout_ctrlc = ctrlc.clone();
+                        std::thread::spawn(move || {
+                            read_to_channel(stdout, stdout_tx, stdout_ctrlc)
+                        });
+                    }
 
-                            // The Cow generated from the function represents the conversion
-                            // from bytes to String. If no replacements are required, then the
-                            // borrowed value is a proper UTF-8 string. The Owned option represents
-                            // a string where the values had to be replaced, thus marking it as bytes
-                            let data = match String::from_utf8_lossy(bytes) {
-                                Cow::Borrowed(s) => Data::String(s.into()),
-                                Cow::Owned(_) => Data::Bytes(bytes.to_vec()),
+                    // The stream only ends once the exit code is recorded, so whatever read all of
+                    // the output sees it in LAST_EXIT_CODE
+                    let result = match wait_or_kill(&mut child, &ctrlc) {
+                        Err(err) => Err(ShellError::ExternalCommand(format!("{}", err), span)),
+                        Ok(status) => {
+                            let exit_code = Value::Int {
+                                val: exit_code(status),
+                                span,
                             };
+                            exit_code_slot.record(exit_code.clone());
+                            let _ = exit_code_tx.send(exit_code);
+                            Ok(())
+                        }
+                    };
+                    drop(stdout_tx);
 
-                            let length = bytes.len();
-                            buf_read.consume(length);
+                    result
+                });
 
-                            if let Some(ctrlc) = &ctrlc {
-                                if ctrlc.load(Ordering::SeqCst) {
-                                    break;
-                                }
-                            }
+                // The RawStream is consumed by the next expression in the pipeline
+                let value = PipelineData::RawStream(
+                    RawStream::new(
+                        Box::new(stdout_rx.into_iter().map(Ok)),
+                        output_ctrlc.clone(),
+                        span,
+                    ),
+                    Some(PipelineMetadata {
+                        data_source: DataSource::External(ExternalOutput {
+                            stderr,
+                            exit_code: ValueStream::from_stream(exit_code_rx.into_iter(), None),
+                        }),
+                    }),
+                );
 
-                            match tx.send(data) {
-                                Ok(_) => continue,
-                                Err(_) => break,
-                            }
-                        }
-                    }
+                Ok(value)
+            }
+        }
+    }
 
-                    match child.wait() {
-                        Err(err) => Err(ShellError::ExternalCommand(format!("{}", err), span)),
-                        Ok(_) => Ok(()),
+    // A background job shares the terminal, but only reads from it once `fg` brings it to the
+    // foreground: before that, the terminal stops it when it tries. Its outputs go there too unless
+    // redirected. On unix, it gets a process group of its own so that the ctrl-c meant for the
+    // foreground doesn't reach it, and so that `fg` can hand the terminal to it
+    fn spawn_background(
+        &self,
+        engine_state: &EngineState,
+        mut process: CommandSys,
+    ) -> Result<PipelineData, ShellError> {
+        #[cfg(not(unix))]
+        process.stdin(Stdio::null());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+
+            // Without a terminal, there's nothing the job could get its input from later on
+            // SAFETY: isatty only looks at the file descriptor
+            if unsafe { libc::isatty(libc::STDIN_FILENO) } == 0 {
+                process.stdin(Stdio::null());
+            }
+
+            // SAFETY: setpgid is async-signal-safe, so it can be called between fork and exec
+            unsafe {
+                process.pre_exec(|| {
+                    if libc::setpgid(0, 0) == 0 {
+                        Ok(())
+                    } else {
+                        Err(std::io::Error::last_os_error())
                     }
                 });
-                // The ValueStream is consumed by the next expression in the pipeline
-                let value =
-                    ChannelReceiver::new(rx, self.name.span).into_pipeline_data(output_ctrlc);
-
-                Ok(value)
             }
         }
+
+        let child = process
+            .spawn()
+            .map_err(|err| ShellError::ExternalCommand(format!("{}", err), self.name.span))?;
+
+        let command = std::iter::once(&self.name.item)
+            .chain(self.args.iter())
+            .join(" ");
+
+        let id = engine_state
+            .jobs
+            .lock()
+            .map_err(|_| ShellError::NushellFailed("job table is poisoned".into()))?
+            .add(command, child);
+
+        Ok(PipelineData::Value(
+            Value::Int {
+                val: id as i64,
+                span: self.name.span,
+            },
+            None,
+        ))
+    }
+
+    fn open_redirection_files(&self) -> Result<(Option<File>, Option<File>), ShellError> {
+        let stdout_file = match &self.stdout_file {
+            Some(path) => Some(create_file(path)?),
+            None => None,
+        };
+
+        let stderr_file = match (&self.stderr_file, &self.stdout_file, &stdout_file) {
+            // `out+err>` writes both outputs through the same file handle, so they don't
+            // overwrite each other
+            (Some(path), Some(stdout_path), Some(file)) if path.item == stdout_path.item => Some(
+                file.try_clone()
+                    .map_err(|err| ShellError::CreateNotPossible(format!("{}", err), path.span))?,
+            ),
+            (Some(path), ..) => Some(create_file(path)?),
+            (None, ..) => None,
+        };
+
+        Ok((stdout_file, stderr_file))
     }
 
     fn create_command(&self) -> CommandSys {
@@ -318,6 +492,77 @@ impl<'call> ExternalCommand<'call> {
     }
 }
 
+fn create_file(path: &Spanned<String>) -> Result<File, ShellError> {
+    File::create(nu_path::expand_path(&path.item))
+        .map_err(|err| ShellError::CreateNotPossible(format!("{}", err), path.span))
+}
+
+// Stdout and stderr are read using the Buffer reader. It will do so until there is an
+// error, there are no more bytes to read or nobody is listening anymore
+fn read_to_channel(reader: impl Read, tx: mpsc::Sender<Vec<u8>>, ctrlc: Option<Arc<AtomicBool>>) {
+    let mut buf_read = BufReader::with_capacity(OUTPUT_BUFFER_SIZE, reader);
+    while let Ok(bytes) = buf_read.fill_buf() {
+        if bytes.is_empty() {
+            break;
+        }
+
+        // The bytes are sent as they come. Making sense of them is left to the RawStream
+        let data = bytes.to_vec();
+
+        let length = bytes.len();
+        buf_read.consume(length);
+
+        if let Some(ctrlc) = &ctrlc {
+            if ctrlc.load(Ordering::SeqCst) {
+                break;
+            }
+        }
+
+        if tx.send(data).is_err() {
+            break;
+        }
+    }
+}
+
+// Wait for an external to finish, killing it on ctrl-c. A `timeout` around the external stops it
+// the same way, so the flag is checked while it runs rather than only once it's done
+fn wait_or_kill(child: &mut Child, ctrlc: &Option<Arc<AtomicBool>>) -> io::Result<ExitStatus> {
+    let ctrlc = match ctrlc {
+        Some(ctrlc) => ctrlc,
+        None => return child.wait(),
+    };
+
+    let mut interval = Duration::from_millis(1);
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+        if ctrlc.load(Ordering::SeqCst) {
+            // Killing fails only when it finished in the meantime, and then waiting says how
+            let _ = child.kill();
+            return child.wait();
+        }
+
+        std::thread::sleep(interval);
+        interval = (interval * 2).min(WAIT_INTERVAL);
+    }
+}
+
+// The exit code of a finished external. On unix, a command killed by a signal reports
+// 128 + the signal number, like other shells do
+pub(crate) fn exit_code(status: ExitStatus) -> i64 {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+
+        if let Some(signal) = status.signal() {
+            return 128 + signal as i64;
+        }
+    }
+
+    status.code().unwrap_or(-1) as i64
+}
+
 fn has_unsafe_shell_characters(arg: &str) -> bool {
     let re: Regex = Regex::new(r"[^\w@%+=:,./-]").expect("regex to be valid");
 
@@ -344,44 +589,3 @@ fn trim_enclosing_quotes(input: &str) -> String {
         _ => input.to_string(),
     }
 }
-
-// The piped data from stdout from the external command can be either String
-// or binary. We use this enum to pass the data from the spawned process
-#[derive(Debug)]
-enum Data {
-    String(String),
-    Bytes(Vec<u8>),
-}
-
-// Receiver used for the ValueStream
-// It implements iterator so it can be used as a ValueStream
-struct ChannelReceiver {
-    rx: mpsc::Receiver<Data>,
-    span: Span,
-}
-
-impl ChannelReceiver {
-    pub fn new(rx: mpsc::Receiver<Data>, span: Span) -> Self {
-        Self { rx, span }
-    }
-}
-
-impl Iterator for ChannelReceiver {
-    type Item = Value;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        match self.rx.recv() {
-            Ok(v) => match v {
-                Data::String(s) => Some(Value::String {
-                    val: s,
-                    span: self.span,
-                }),
-                Data::Bytes(b) => Some(Value::Binary {
-                    val: b,
-                    span: self.span,
-                }),
-            },
-            Err(_) => None,
-        }
-    }
-}
@@ -0,0 +1,143 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use nu_engine::{eval_block, CallExt};
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Spanned, SyntaxShape,
+    Value,
+};
+
+const CTRL_C_CHECK_INTERVAL: Duration = Duration::from_millis(10);
+
+#[derive(Clone)]
+pub struct Timeout;
+
+impl Command for Timeout {
+    fn name(&self) -> &str {
+        "timeout"
+    }
+
+    fn usage(&self) -> &str {
+        "Run a block, failing if it doesn't finish in time"
+    }
+
+    fn extra_usage(&self) -> &str {
+        r#"When the time runs out, the block is stopped like it is by ctrl-c. The output of the block is collected before it's returned, so that streams are done within the time too."#
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("timeout")
+            .required(
+                "duration",
+                SyntaxShape::Duration,
+                "how long the block can run for",
+            )
+            .required(
+                "block",
+                SyntaxShape::Block(Some(vec![])),
+                "the block to run",
+            )
+            .category(Category::System)
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let duration: Spanned<i64> = call.req(engine_state, stack, 0)?;
+        let block: Value = call.req(engine_state, stack, 1)?;
+        let block = engine_state.get_block(block.as_block()?);
+
+        if duration.item < 0 {
+            return Err(ShellError::UnsupportedInput(
+                "the duration can't be negative".into(),
+                duration.span,
+            ));
+        }
+        let limit = Duration::from_nanos(duration.item as u64);
+
+        // The block runs with its own ctrl-c flag, which is set when the time runs out or when
+        // the one of the engine is
+        let interrupted = Arc::new(AtomicBool::new(false));
+        let timed_out = Arc::new(AtomicBool::new(false));
+        let mut engine_state = engine_state.clone();
+        let parent_ctrlc = engine_state.ctrlc.replace(interrupted.clone());
+
+        let (done_tx, done_rx) = mpsc::channel::<()>();
+        let watcher = {
+            let timed_out = timed_out.clone();
+            thread::spawn(move || {
+                let start = Instant::now();
+                loop {
+                    let remaining = limit.saturating_sub(start.elapsed());
+                    if remaining.is_zero() {
+                        timed_out.store(true, Ordering::SeqCst);
+                        break;
+                    }
+                    if let Some(ctrlc) = &parent_ctrlc {
+                        if ctrlc.load(Ordering::SeqCst) {
+                            break;
+                        }
+                    }
+
+                    match done_rx.recv_timeout(remaining.min(CTRL_C_CHECK_INTERVAL)) {
+                        Err(RecvTimeoutError::Timeout) => continue,
+                        _ => return,
+                    }
+                }
+                interrupted.store(true, Ordering::SeqCst);
+            })
+        };
+
+        let mut stack = stack.collect_captures(&block.captures);
+        let result = eval_block(&engine_state, &mut stack, block, input)
+            .map(|output| output.into_value(call.head));
+
+        let _ = done_tx.send(());
+        let _ = watcher.join();
+
+        if timed_out.load(Ordering::SeqCst) {
+            return Err(ShellError::TimedOut(
+                String::from_utf8_lossy(engine_state.get_span_contents(&duration.span)).to_string(),
+                call.head,
+            ));
+        }
+
+        result.map(|value| value.into_pipeline_data())
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Give up on a block that takes too long",
+                example: "timeout 1sec { sleep 10sec }",
+                result: None,
+            },
+            Example {
+                description: "Run a block that finishes in time",
+                example: "timeout 1sec { 1 + 2 }",
+                result: Some(Value::test_int(3)),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Timeout {})
+    }
+}
@@ -0,0 +1,244 @@
+use std::path::{Path, PathBuf};
+
+use nu_engine::{check_path, env_to_string, CallExt};
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Capability, Command, EngineState, Stack};
+use nu_protocol::{
+    record, Category, Example, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature,
+    Span, Spanned, SyntaxShape, Value,
+};
+
+#[derive(Clone)]
+pub struct Which;
+
+impl Command for Which {
+    fn name(&self) -> &str {
+        "which"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("which")
+            .required("application", SyntaxShape::String, "application")
+            .rest("rest", SyntaxShape::String, "additional applications")
+            .switch(
+                "all",
+                "list all definitions, not just the one that's used",
+                Some('a'),
+            )
+            .category(Category::System)
+    }
+
+    fn usage(&self) -> &str {
+        "Finds a program file, alias or custom command."
+    }
+
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::FilesystemRead]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let application: Spanned<String> = call.req(engine_state, stack, 0)?;
+        let rest: Vec<Spanned<String>> = call.rest(engine_state, stack, 1)?;
+        let all = call.has_flag("all");
+
+        let paths = search_paths(engine_state, stack)?;
+
+        let mut output = vec![];
+        for application in std::iter::once(application).chain(rest) {
+            // A name with a directory in it is a path, which isn't a path argument the engine
+            // checks before the command runs
+            if is_path(&application.item) {
+                check_path(
+                    &engine_state.permissions,
+                    self.name(),
+                    Capability::FilesystemRead,
+                    &application.item,
+                    application.span,
+                )?;
+            }
+
+            let mut found = which(engine_state, &application, &paths);
+
+            if !all {
+                found.truncate(1);
+            }
+
+            output.extend(found);
+        }
+
+        Ok(output
+            .into_iter()
+            .into_pipeline_data(engine_state.ctrlc.clone()))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Find out what runs when typing 'ls'",
+                example: "which ls",
+                result: None,
+            },
+            Example {
+                description: "List every definition of 'ls', in the order they're looked up",
+                example: "which ls --all",
+                result: None,
+            },
+        ]
+    }
+}
+
+// Everything a name can refer to, in the order the names are looked up. Aliases are expanded
+// first, then the commands known to nushell are tried, and the externals come last
+fn which(
+    engine_state: &EngineState,
+    application: &Spanned<String>,
+    paths: &[PathBuf],
+) -> Vec<Value> {
+    let name = &application.item;
+    let span = application.span;
+    let mut output = vec![];
+
+    if let Some(replacement) = engine_state.find_alias(name.as_bytes()) {
+        let path = replacement
+            .first()
+            .and_then(|first| defining_file(engine_state, first));
+
+        output.push(entry(name, "alias", path, span));
+    }
+
+    for decl_id in engine_state.find_decls(name.as_bytes()) {
+        let decl = engine_state.get_decl(decl_id);
+
+        if let Some((path, ..)) = decl.is_plugin() {
+            output.push(entry(
+                name,
+                "plugin",
+                Some(path.to_string_lossy().to_string()),
+                span,
+            ));
+        } else if let Some(block_id) = decl.get_block_id() {
+            let path = engine_state
+                .get_block(block_id)
+                .span
+                .and_then(|block_span| defining_file(engine_state, &block_span));
+
+            output.push(entry(name, "custom", path, span));
+        } else {
+            output.push(entry(name, "builtin", None, span));
+        }
+    }
+
+    for path in executables(name, paths) {
+        output.push(entry(
+            name,
+            "external",
+            Some(path.to_string_lossy().to_string()),
+            span,
+        ));
+    }
+
+    output
+}
+
+fn entry(name: &str, kind: &str, path: Option<String>, span: Span) -> Value {
+    Value::record(
+        record! {
+            "arg" => Value::string(name, span),
+            "kind" => Value::string(kind, span),
+            "path" => match path {
+                Some(path) => Value::string(path, span),
+                None => Value::Nothing { span },
+            },
+        },
+        span,
+    )
+}
+
+fn defining_file(engine_state: &EngineState, span: &Span) -> Option<String> {
+    engine_state
+        .files()
+        .find(|(_, start, end)| span.start >= *start && span.start < *end)
+        .map(|(name, ..)| name.clone())
+}
+
+// The directories of PATH, read the same way externals get to see them, so that a PATH kept as a
+// list is converted with its env conversion
+fn search_paths(engine_state: &EngineState, stack: &mut Stack) -> Result<Vec<PathBuf>, ShellError> {
+    let config = stack.get_config().unwrap_or_default();
+
+    let (name, value) = match stack.get_env_var("PATH") {
+        Some(value) => ("PATH", value),
+        None => match stack.get_env_var("Path") {
+            Some(value) => ("Path", value),
+            None => return Ok(vec![]),
+        },
+    };
+
+    let path = env_to_string(name, value, engine_state, stack, &config)?;
+
+    Ok(std::env::split_paths(&path).collect())
+}
+
+fn executables(name: &str, paths: &[PathBuf]) -> Vec<PathBuf> {
+    // A name with a directory in it is not looked up in PATH
+    if is_path(name) {
+        let path = PathBuf::from(name);
+
+        return if is_executable(&path) {
+            vec![path]
+        } else {
+            vec![]
+        };
+    }
+
+    paths
+        .iter()
+        .flat_map(|dir| candidates(dir, name))
+        .filter(|path| is_executable(path))
+        .collect()
+}
+
+fn is_path(name: &str) -> bool {
+    name.contains(std::path::MAIN_SEPARATOR) || name.contains('/')
+}
+
+#[cfg(not(windows))]
+fn candidates(dir: &Path, name: &str) -> Vec<PathBuf> {
+    vec![dir.join(name)]
+}
+
+#[cfg(windows)]
+fn candidates(dir: &Path, name: &str) -> Vec<PathBuf> {
+    if Path::new(name).extension().is_some() {
+        return vec![dir.join(name)];
+    }
+
+    let extensions = std::env::var("PATHEXT").unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".into());
+
+    extensions
+        .split(';')
+        .filter(|extension| !extension.is_empty())
+        .map(|extension| dir.join(format!("{}{}", name, extension)))
+        .collect()
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    match std::fs::metadata(path) {
+        Ok(metadata) => metadata.is_file() && metadata.permissions().mode() & 0o111 != 0,
+        Err(_) => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
@@ -0,0 +1,68 @@
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, SyntaxShape, Value};
+
+#[derive(Clone)]
+pub struct Assert;
+
+impl Command for Assert {
+    fn name(&self) -> &str {
+        "assert"
+    }
+
+    fn usage(&self) -> &str {
+        "Fail with an error unless the condition is true"
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("assert")
+            .required("condition", SyntaxShape::Any, "the condition to check")
+            .optional(
+                "message",
+                SyntaxShape::String,
+                "what the error says when the condition is false",
+            )
+            .category(Category::Testing)
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let condition: Value = call.req(engine_state, stack, 0)?;
+        let message: Option<String> = call.opt(engine_state, stack, 1)?;
+        let span = call.positional[0].span;
+
+        match condition {
+            Value::Bool { val: true, .. } => Ok(PipelineData::new(call.head)),
+            Value::Bool { val: false, .. } => Err(ShellError::AssertionFailed(
+                message.unwrap_or_else(|| "this is false".into()),
+                span,
+            )),
+            Value::Error { error } => Err(error),
+            other => Err(ShellError::TypeMismatch(
+                format!("expected a boolean condition, got {}", other.get_type()),
+                span,
+            )),
+        }
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Check that a list isn't empty",
+                example: "assert (([1 2 3] | length) > 0)",
+                result: None,
+            },
+            Example {
+                description: "Check a condition, with a message for when it's false",
+                example: r#"assert ("foo" | str starts-with "f") "the name has to start with f""#,
+                result: None,
+            },
+        ]
+    }
+}
@@ -0,0 +1,89 @@
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Config, Example, PipelineData, ShellError, Signature, SyntaxShape, Value,
+};
+
+#[derive(Clone)]
+pub struct AssertEqual;
+
+impl Command for AssertEqual {
+    fn name(&self) -> &str {
+        "assert equal"
+    }
+
+    fn usage(&self) -> &str {
+        "Fail with an error showing both values unless they are equal"
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("assert equal")
+            .required("left", SyntaxShape::Any, "the value to check")
+            .required("right", SyntaxShape::Any, "the value it should be equal to")
+            .optional(
+                "message",
+                SyntaxShape::String,
+                "what the error says when the values differ",
+            )
+            .category(Category::Testing)
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let left: Value = call.req(engine_state, stack, 0)?;
+        let right: Value = call.req(engine_state, stack, 1)?;
+        let message: Option<String> = call.opt(engine_state, stack, 2)?;
+
+        if left == right {
+            return Ok(PipelineData::new(call.head));
+        }
+
+        let config = stack.get_config().unwrap_or_default();
+
+        Err(ShellError::AssertionNotEqual {
+            message: message.unwrap_or_else(|| "Assertion failed: the values are not equal".into()),
+            left: describe(left, &config),
+            left_span: call.positional[0].span,
+            right: describe(right, &config),
+            right_span: call.positional[1].span,
+        })
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Check the result of a pipeline",
+                example: "assert equal ([1 2 3] | math sum) 6",
+                result: None,
+            },
+            Example {
+                description: "Compare two records, with a message for when they differ",
+                example: r#"assert equal ([[a b]; [1 2]] | get 0) {a: 1, b: 2} "the first row is wrong""#,
+                result: None,
+            },
+        ]
+    }
+}
+
+// The types are shown as well, so that values that look the same, like `1` and "1", can be told
+// apart. Long values are cut short, to keep the labels of the error readable
+fn describe(value: Value, config: &Config) -> String {
+    let ty = value.get_type();
+    let shown = match value {
+        Value::String { val, .. } => format!("{:?}", val.as_str()),
+        value => value.into_string(", ", config),
+    };
+
+    let mut shown: String = shown.replace('\n', " ");
+    if shown.chars().count() > 80 {
+        shown = shown.chars().take(77).collect::<String>() + "...";
+    }
+
+    format!("{} ({})", shown, ty)
+}
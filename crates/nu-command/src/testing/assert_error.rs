@@ -0,0 +1,72 @@
+use nu_engine::eval_block;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, SyntaxShape, Value};
+
+#[derive(Clone)]
+pub struct AssertError;
+
+impl Command for AssertError {
+    fn name(&self) -> &str {
+        "assert error"
+    }
+
+    fn usage(&self) -> &str {
+        "Fail with an error unless running the block fails"
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("assert error")
+            .required(
+                "block",
+                SyntaxShape::Block(Some(vec![])),
+                "the block that should fail",
+            )
+            .category(Category::Testing)
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let block_id = call.positional[0]
+            .as_block()
+            .expect("internal error: expected block");
+        let block = engine_state.get_block(block_id);
+        let mut stack = stack.collect_captures(&block.captures);
+
+        // An error in the output of the block counts as the block failing, like it would for a
+        // script, so the output is drained to find one
+        let failed = match eval_block(
+            engine_state,
+            &mut stack,
+            block,
+            PipelineData::new(call.head),
+        ) {
+            Ok(output) => output
+                .into_iter()
+                .any(|value| matches!(value, Value::Error { .. })),
+            Err(_) => true,
+        };
+
+        if failed {
+            Ok(PipelineData::new(call.head))
+        } else {
+            Err(ShellError::AssertionFailed(
+                "this didn't fail".into(),
+                call.positional[0].span,
+            ))
+        }
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example {
+            description: "Check that dividing by zero is an error",
+            example: "assert error { 1 / 0 }",
+            result: None,
+        }]
+    }
+}
@@ -0,0 +1,7 @@
+mod assert;
+mod assert_equal;
+mod assert_error;
+
+pub use assert::Assert;
+pub use assert_equal::AssertEqual;
+pub use assert_error::AssertError;
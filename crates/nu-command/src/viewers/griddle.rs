@@ -104,11 +104,11 @@ prints out the list properly."#
                     Ok(PipelineData::new(call.head))
                 }
             }
-            PipelineData::Value(Value::Record { cols, vals, .. }, ..) => {
+            PipelineData::Value(Value::Record { val, .. }, ..) => {
                 // dbg!("value::record");
                 let mut items = vec![];
 
-                for (i, (c, v)) in cols.into_iter().zip(vals.into_iter()).enumerate() {
+                for (i, (c, v)) in val.into_iter().enumerate() {
                     items.push((i, c, v.into_string(", ", &config)))
                 }
 
@@ -219,15 +219,12 @@ fn create_grid_output(
 
     Ok(
         if let Some(grid_display) = grid.fit_into_width(cols as usize) {
-            Value::String {
-                val: grid_display.to_string(),
-                span: call.head,
-            }
+            Value::string(grid_display.to_string(), call.head)
         } else {
-            Value::String {
-                val: format!("Couldn't fit grid into {} columns!", cols),
-                span: call.head,
-            }
+            Value::string(
+                format!("Couldn't fit grid into {} columns!", cols),
+                call.head,
+            )
         }
         .into_pipeline_data(),
     )
@@ -57,11 +57,7 @@ impl Command for Table {
                 if let Some(table) = table {
                     let result = nu_table::draw_table(&table, term_width, &color_hm, &config);
 
-                    Ok(Value::String {
-                        val: result,
-                        span: call.head,
-                    }
-                    .into_pipeline_data())
+                    Ok(Value::string(result, call.head).into_pipeline_data())
                 } else {
                     Ok(PipelineData::new(call.head))
                 }
@@ -87,57 +83,25 @@ impl Command for Table {
 
                         ValueStream::from_stream(
                             stream.map(move |mut x| match &mut x {
-                                Value::Record { cols, vals, .. } => {
-                                    let mut idx = 0;
-
-                                    while idx < cols.len() {
-                                        if cols[idx] == "name" {
-                                            if let Some(Value::String { val: path, span }) =
-                                                vals.get(idx)
-                                            {
-                                                match std::fs::symlink_metadata(&path) {
-                                                    Ok(metadata) => {
-                                                        let style = ls_colors
-                                                            .style_for_path_with_metadata(
-                                                                path.clone(),
-                                                                Some(&metadata),
-                                                            );
-                                                        let ansi_style = style
-                                                            .map(Style::to_crossterm_style)
-                                                            .unwrap_or_default();
-                                                        let use_ls_colors = config.use_ls_colors;
-
-                                                        if use_ls_colors {
-                                                            vals[idx] = Value::String {
-                                                                val: ansi_style
-                                                                    .apply(path)
-                                                                    .to_string(),
-                                                                span: *span,
-                                                            };
-                                                        }
-                                                    }
-                                                    Err(_) => {
-                                                        let style =
-                                                            ls_colors.style_for_path(path.clone());
-                                                        let ansi_style = style
-                                                            .map(Style::to_crossterm_style)
-                                                            .unwrap_or_default();
-                                                        let use_ls_colors = config.use_ls_colors;
-
-                                                        if use_ls_colors {
-                                                            vals[idx] = Value::String {
-                                                                val: ansi_style
-                                                                    .apply(path)
-                                                                    .to_string(),
-                                                                span: *span,
-                                                            };
-                                                        }
-                                                    }
-                                                }
-                                            }
+                                Value::Record { val: record, .. } => {
+                                    if let Some(Value::String { val: path, .. }) =
+                                        record.get_mut("name")
+                                    {
+                                        let style = match std::fs::symlink_metadata(path.as_str()) {
+                                            Ok(metadata) => ls_colors.style_for_path_with_metadata(
+                                                path.as_str(),
+                                                Some(&metadata),
+                                            ),
+                                            Err(_) => ls_colors.style_for_path(path.as_str()),
+                                        };
+                                        let ansi_style = style
+                                            .map(Style::to_crossterm_style)
+                                            .unwrap_or_default();
+
+                                        if config.use_ls_colors {
+                                            *path =
+                                                ansi_style.apply(path.as_str()).to_string().into();
                                         }
-
-                                        idx += 1;
                                     }
 
                                     x
@@ -175,10 +139,10 @@ impl Command for Table {
                 //     Ok(PipelineData::new(call.head))
                 // }
             }
-            PipelineData::Value(Value::Record { cols, vals, .. }, ..) => {
+            PipelineData::Value(Value::Record { val, .. }, ..) => {
                 let mut output = vec![];
 
-                for (c, v) in cols.into_iter().zip(vals.into_iter()) {
+                for (c, v) in val {
                     output.push(vec![
                         StyledString {
                             contents: c,
@@ -199,11 +163,7 @@ impl Command for Table {
 
                 let result = nu_table::draw_table(&table, term_width, &color_hm, &config);
 
-                Ok(Value::String {
-                    val: result,
-                    span: call.head,
-                }
-                .into_pipeline_data())
+                Ok(Value::string(result, call.head).into_pipeline_data())
             }
             PipelineData::Value(Value::Error { error }, ..) => Err(error),
             PipelineData::Value(Value::CustomValue { val, span }, ..) => {
@@ -219,8 +179,8 @@ fn get_columns(input: &[Value]) -> Vec<String> {
     let mut columns = vec![];
 
     for item in input {
-        if let Value::Record { cols, vals: _, .. } = item {
-            for col in cols {
+        if let Value::Record { val, .. } = item {
+            for col in val.columns() {
                 if !columns.contains(col) {
                     columns.push(col.to_string());
                 }
@@ -423,10 +383,7 @@ impl Iterator for PagingTableCreator {
             Ok(Some(table)) => {
                 let result = nu_table::draw_table(&table, term_width, &color_hm, &self.config);
 
-                Some(Value::String {
-                    val: result,
-                    span: self.head,
-                })
+                Some(Value::string(result, self.head))
             }
             Err(err) => Some(Value::Error { error: err }),
             _ => None,
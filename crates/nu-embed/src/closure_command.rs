@@ -0,0 +1,128 @@
+use std::sync::Arc;
+
+use nu_engine::eval_expression;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Capability, Command, EngineState, Stack};
+use nu_protocol::{IntoPipelineData, PipelineData, ShellError, Signature, Span, Value};
+
+type Closure = dyn Fn(CommandArgs) -> Result<Value, ShellError> + Send + Sync;
+
+/// A command backed by a Rust closure, for hosts that only need the evaluated arguments and input
+///
+/// The signature gives the command its name, usage and parameters, which the parser checks calls
+/// against like it does for any other command.
+#[derive(Clone)]
+pub struct ClosureCommand {
+    signature: Signature,
+    capabilities: Vec<Capability>,
+    closure: Arc<Closure>,
+}
+
+impl ClosureCommand {
+    pub fn new(
+        signature: Signature,
+        closure: impl Fn(CommandArgs) -> Result<Value, ShellError> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            signature,
+            capabilities: vec![],
+            closure: Arc::new(closure),
+        }
+    }
+
+    /// Declare what the closure does outside of the engine, such as using the network, so that
+    /// the engine's permissions can stop it
+    pub fn with_capabilities(mut self, capabilities: Vec<Capability>) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+}
+
+/// What a closure command was called with
+#[derive(Debug, Clone)]
+pub struct CommandArgs {
+    /// The span of the command's name at the call site, for errors and the values it returns
+    pub head: Span,
+    pub positional: Vec<Value>,
+    /// The flags given, by their long name. Switches don't have a value
+    pub named: Vec<(String, Option<Value>)>,
+    pub input: Value,
+}
+
+impl CommandArgs {
+    /// The positional argument at `pos`, or an error pointing at the call if it wasn't given
+    pub fn req(&self, pos: usize) -> Result<&Value, ShellError> {
+        self.positional.get(pos).ok_or_else(|| {
+            ShellError::AccessBeyondEnd(self.positional.len().saturating_sub(1), self.head)
+        })
+    }
+
+    pub fn opt(&self, pos: usize) -> Option<&Value> {
+        self.positional.get(pos)
+    }
+
+    /// The value of a named flag, if it was given one
+    pub fn get_flag(&self, name: &str) -> Option<&Value> {
+        self.named
+            .iter()
+            .find(|(flag, _)| flag == name)
+            .and_then(|(_, value)| value.as_ref())
+    }
+
+    pub fn has_flag(&self, name: &str) -> bool {
+        self.named.iter().any(|(flag, _)| flag == name)
+    }
+}
+
+impl Command for ClosureCommand {
+    fn name(&self) -> &str {
+        &self.signature.name
+    }
+
+    fn signature(&self) -> Signature {
+        self.signature.clone()
+    }
+
+    fn usage(&self) -> &str {
+        &self.signature.usage
+    }
+
+    fn capabilities(&self) -> Vec<Capability> {
+        self.capabilities.clone()
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let positional = call
+            .positional
+            .iter()
+            .map(|expr| eval_expression(engine_state, stack, expr))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let named = call
+            .named
+            .iter()
+            .map(|(flag, expr)| {
+                let value = match expr {
+                    Some(expr) => Some(eval_expression(engine_state, stack, expr)?),
+                    None => None,
+                };
+                Ok((flag.item.clone(), value))
+            })
+            .collect::<Result<Vec<_>, ShellError>>()?;
+
+        let args = CommandArgs {
+            head: call.head,
+            positional,
+            named,
+            input: input.into_value(call.head),
+        };
+
+        (self.closure)(args).map(|value| value.into_pipeline_data())
+    }
+}
@@ -0,0 +1,207 @@
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use nu_engine::{convert_env_values, eval_block};
+use nu_parser::parse;
+use nu_protocol::engine::{Command, EngineState, Permissions, Stack, StateWorkingSet};
+use nu_protocol::{PipelineData, Record, ShellError, Signature, Span, Value, CONFIG_VARIABLE_ID};
+
+use crate::{ClosureCommand, CommandArgs, EvalError};
+
+/// Sets up an `Engine` from a set of commands, such as the one made by
+/// `nu_command::create_default_context`, and the environment and config to run with
+pub struct EngineBuilder {
+    engine_state: EngineState,
+    commands: Vec<Box<dyn Command>>,
+    env_vars: Vec<(String, Value)>,
+    inherit_env: bool,
+    config: Option<Value>,
+}
+
+impl EngineBuilder {
+    pub fn new(engine_state: EngineState) -> Self {
+        Self {
+            engine_state,
+            commands: vec![],
+            env_vars: vec![],
+            inherit_env: false,
+            config: None,
+        }
+    }
+
+    /// Add a command, replacing the one with the same name if there is one
+    pub fn command(mut self, command: impl Command + 'static) -> Self {
+        self.commands.push(Box::new(command));
+        self
+    }
+
+    /// Add a command that runs `closure`, with the name and parameters in `signature`
+    pub fn closure_command(
+        self,
+        signature: Signature,
+        closure: impl Fn(CommandArgs) -> Result<Value, ShellError> + Send + Sync + 'static,
+    ) -> Self {
+        self.command(ClosureCommand::new(signature, closure))
+    }
+
+    /// Start with the environment variables of the host process, before the ones set with
+    /// `env_var`
+    pub fn inherit_env(mut self) -> Self {
+        self.inherit_env = true;
+        self
+    }
+
+    pub fn env_var(mut self, name: impl Into<String>, value: Value) -> Self {
+        self.env_vars.push((name.into(), value));
+        self
+    }
+
+    /// The record `$config` starts as. An empty one is used when none is given
+    pub fn config(mut self, config: Value) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// What the commands are allowed to do. Everything is allowed unless this is given
+    pub fn permissions(mut self, permissions: Permissions) -> Self {
+        self.engine_state.permissions = permissions;
+        self
+    }
+
+    /// Set to stop what's being evaluated, like ctrl-c does in the shell
+    pub fn ctrlc(mut self, ctrlc: Arc<AtomicBool>) -> Self {
+        self.engine_state.ctrlc = Some(ctrlc);
+        self
+    }
+
+    /// Fails if the config isn't a valid one, or if an environment variable can't be converted
+    /// with the conversions it sets
+    pub fn build(self) -> Result<Engine, ShellError> {
+        let mut engine_state = self.engine_state;
+        add_commands(&mut engine_state, self.commands)?;
+
+        let mut stack = Stack::new();
+        let span = Span::new(0, 0);
+
+        if self.inherit_env {
+            for (name, value) in std::env::vars() {
+                stack.add_env_var(name, Value::string(value, span));
+            }
+        }
+        for (name, value) in self.env_vars {
+            stack.add_env_var(name, value);
+        }
+
+        stack.vars.insert(
+            CONFIG_VARIABLE_ID,
+            self.config
+                .unwrap_or_else(|| Value::record(Record::new(), span)),
+        );
+        let config = stack.get_config()?;
+
+        if let Some(err) = convert_env_values(&engine_state, &mut stack, &config) {
+            return Err(err);
+        }
+
+        Ok(Engine {
+            engine_state,
+            stack,
+        })
+    }
+}
+
+/// An engine that evaluates sources one after the other, like the lines of the REPL
+///
+/// The definitions, variables and environment changes a source makes are kept for the ones
+/// evaluated after it.
+pub struct Engine {
+    engine_state: EngineState,
+    stack: Stack,
+}
+
+impl Engine {
+    pub fn eval(&mut self, source: &str) -> Result<Value, EvalError> {
+        self.eval_with_input(source, PipelineData::new(Span::new(0, 0)))
+    }
+
+    /// Evaluate `source` with `input` piped into it
+    pub fn eval_with_input(
+        &mut self,
+        source: &str,
+        input: PipelineData,
+    ) -> Result<Value, EvalError> {
+        let parsed = {
+            let mut working_set = StateWorkingSet::new(&self.engine_state);
+            let (block, err) = parse(&mut working_set, Some("source"), source.as_bytes(), false);
+            match err {
+                Some(err) => Err(err),
+                None => Ok((block, working_set.render())),
+            }
+        };
+
+        let (block, delta) = match parsed {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                // Nothing the source defined is kept, only the source itself, which lands where
+                // the parser put it, for the spans in the error to point into
+                self.engine_state
+                    .add_file("source".into(), source.as_bytes().to_vec());
+                return Err(err.into());
+            }
+        };
+
+        self.engine_state.merge_delta(delta)?;
+
+        let span = Span::new(0, 0);
+        match eval_block(&self.engine_state, &mut self.stack, &block, input)?.into_value(span) {
+            Value::Error { error } => Err(error.into()),
+            value => Ok(value),
+        }
+    }
+
+    /// Add a command after the engine is built, replacing the one with the same name if there is
+    /// one
+    pub fn add_command(&mut self, command: impl Command + 'static) -> Result<(), ShellError> {
+        add_commands(&mut self.engine_state, vec![Box::new(command)])
+    }
+
+    pub fn add_closure_command(
+        &mut self,
+        signature: Signature,
+        closure: impl Fn(CommandArgs) -> Result<Value, ShellError> + Send + Sync + 'static,
+    ) -> Result<(), ShellError> {
+        self.add_command(ClosureCommand::new(signature, closure))
+    }
+
+    /// The value of an environment variable, as the sources evaluated so far left it
+    pub fn env_var(&self, name: &str) -> Option<Value> {
+        self.stack.get_env_var(name)
+    }
+
+    pub fn engine_state(&self) -> &EngineState {
+        &self.engine_state
+    }
+
+    pub fn stack(&self) -> &Stack {
+        &self.stack
+    }
+}
+
+fn add_commands(
+    engine_state: &mut EngineState,
+    commands: Vec<Box<dyn Command>>,
+) -> Result<(), ShellError> {
+    if commands.is_empty() {
+        return Ok(());
+    }
+
+    let delta = {
+        let mut working_set = StateWorkingSet::new(engine_state);
+        for command in commands {
+            working_set.add_decl(command);
+        }
+        working_set.render()
+    };
+
+    engine_state.merge_delta(delta)
+}
@@ -0,0 +1,69 @@
+use std::fmt;
+
+use miette::{Diagnostic, LabeledSpan, Severity};
+use nu_parser::ParseError;
+use nu_protocol::ShellError;
+
+/// Why a source given to `Engine::eval` didn't produce a value
+///
+/// The spans in it point into the engine's files, which keep every source given to it, so a
+/// `StateWorkingSet` of the engine's state can be used as the source code to render it with.
+#[derive(Debug, Clone)]
+pub enum EvalError {
+    Parse(ParseError),
+    Shell(ShellError),
+}
+
+impl EvalError {
+    fn diagnostic(&self) -> &dyn Diagnostic {
+        match self {
+            EvalError::Parse(err) => err,
+            EvalError::Shell(err) => err,
+        }
+    }
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalError::Parse(err) => err.fmt(f),
+            EvalError::Shell(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for EvalError {}
+
+impl Diagnostic for EvalError {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.diagnostic().code()
+    }
+
+    fn severity(&self) -> Option<Severity> {
+        self.diagnostic().severity()
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.diagnostic().help()
+    }
+
+    fn url<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.diagnostic().url()
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        self.diagnostic().labels()
+    }
+}
+
+impl From<ParseError> for EvalError {
+    fn from(err: ParseError) -> Self {
+        EvalError::Parse(err)
+    }
+}
+
+impl From<ShellError> for EvalError {
+    fn from(err: ShellError) -> Self {
+        EvalError::Shell(err)
+    }
+}
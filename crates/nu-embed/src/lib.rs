@@ -0,0 +1,7 @@
+mod closure_command;
+mod engine;
+mod error;
+
+pub use closure_command::{ClosureCommand, CommandArgs};
+pub use engine::{Engine, EngineBuilder};
+pub use error::EvalError;
@@ -0,0 +1,157 @@
+use miette::Diagnostic;
+use nu_command::create_default_context;
+use nu_embed::{Engine, EngineBuilder, EvalError};
+use nu_protocol::{ShellError, Signature, Span, SyntaxShape, Value};
+
+fn engine() -> Engine {
+    EngineBuilder::new(create_default_context())
+        .build()
+        .expect("the default engine should build")
+}
+
+#[test]
+fn evaluates_a_source() {
+    let mut engine = engine();
+
+    let value = engine.eval("[1 2 3] | math sum").unwrap();
+
+    assert_eq!(value.as_integer().unwrap(), 6);
+}
+
+#[test]
+fn keeps_definitions_and_variables_between_evals() {
+    let mut engine = engine();
+
+    engine.eval("let x = 20").unwrap();
+    engine.eval("def double [n] { $n * 2 }").unwrap();
+    let value = engine.eval("$x + (double 11)").unwrap();
+
+    assert_eq!(value.as_integer().unwrap(), 42);
+}
+
+#[test]
+fn keeps_environment_changes_between_evals() {
+    let mut engine = EngineBuilder::new(create_default_context())
+        .env_var("GREETING", Value::test_string("hello"))
+        .build()
+        .unwrap();
+
+    engine.eval("let-env NAME = 'nu'").unwrap();
+    let value = engine
+        .eval("$\"($nu.env.GREETING) ($nu.env.NAME)\"")
+        .unwrap();
+
+    assert_eq!(value.as_string().unwrap(), "hello nu");
+    assert_eq!(engine.env_var("NAME").unwrap().as_string().unwrap(), "nu");
+}
+
+#[test]
+fn runs_closure_commands() {
+    let mut engine = EngineBuilder::new(create_default_context())
+        .closure_command(
+            Signature::build("repeat")
+                .desc("Repeat a string")
+                .required("times", SyntaxShape::Int, "how many times")
+                .switch("shout", "make it uppercase", Some('s')),
+            |args| {
+                let times = args.req(0)?.as_integer()?;
+                let mut text = args.input.as_string()?.repeat(times as usize);
+                if args.has_flag("shout") {
+                    text = text.to_uppercase();
+                }
+
+                Ok(Value::string(text, args.head))
+            },
+        )
+        .build()
+        .unwrap();
+
+    let value = engine.eval("'ab' | repeat 3 --shout").unwrap();
+
+    assert_eq!(value.as_string().unwrap(), "ABABAB");
+}
+
+#[test]
+fn adds_closure_commands_after_building() {
+    let mut engine = engine();
+
+    engine
+        .add_closure_command(Signature::build("answer"), |args| {
+            Ok(Value::int(42, args.head))
+        })
+        .unwrap();
+
+    assert_eq!(engine.eval("answer").unwrap().as_integer().unwrap(), 42);
+}
+
+#[test]
+fn returns_errors_from_closure_commands() {
+    let mut engine = engine();
+    engine
+        .add_closure_command(Signature::build("broken"), |args| {
+            Err(ShellError::UnsupportedInput("not today".into(), args.head))
+        })
+        .unwrap();
+
+    match engine.eval("broken") {
+        Err(EvalError::Shell(ShellError::UnsupportedInput(msg, _))) => assert_eq!(msg, "not today"),
+        other => panic!("expected the command's error, got {:?}", other),
+    }
+}
+
+#[test]
+fn returns_errors_from_inside_custom_commands_with_their_calls() {
+    let mut engine = engine();
+    engine
+        .add_closure_command(Signature::build("broken"), |args| {
+            Err(ShellError::UnsupportedInput("not today".into(), args.head))
+        })
+        .unwrap();
+    engine.eval("def outer [] { broken }").unwrap();
+
+    match engine.eval("outer") {
+        Err(EvalError::Shell(err)) => {
+            assert_eq!(err.trace().1.len(), 1);
+            match err.into_inner() {
+                ShellError::UnsupportedInput(msg, _) => assert_eq!(msg, "not today"),
+                other => panic!("expected the command's error, got {:?}", other),
+            }
+        }
+        other => panic!("expected the command's error, got {:?}", other),
+    }
+}
+
+#[test]
+fn returns_parse_errors_and_keeps_working() {
+    let mut engine = engine();
+
+    assert!(matches!(
+        engine.eval("def broken ["),
+        Err(EvalError::Parse(_))
+    ));
+    assert_eq!(engine.eval("1 + 1").unwrap().as_integer().unwrap(), 2);
+}
+
+#[test]
+fn parse_errors_point_into_the_source() {
+    let mut engine = engine();
+    engine.eval("let x = 1").unwrap();
+
+    let err = engine.eval("$x + $nope").unwrap_err();
+    let label = err
+        .labels()
+        .and_then(|mut labels| labels.next())
+        .expect("a parse error has a label");
+    let span = Span::new(label.offset(), label.offset() + label.len());
+
+    assert_eq!(engine.engine_state().get_span_contents(&span), b"$nope");
+}
+
+#[test]
+fn rejects_an_invalid_config() {
+    let result = EngineBuilder::new(create_default_context())
+        .config(Value::test_string("not a record"))
+        .build();
+
+    assert!(result.is_err());
+}
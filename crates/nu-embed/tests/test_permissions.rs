@@ -0,0 +1,234 @@
+use std::sync::{Arc, Mutex};
+
+use nu_command::create_default_context;
+use nu_embed::{ClosureCommand, Engine, EngineBuilder, EvalError};
+use nu_protocol::engine::{Capability, Permissions};
+use nu_protocol::{ShellError, Signature, SyntaxShape, Value};
+use nu_test_support::playground::{Playground, Stub::EmptyFile};
+
+fn engine(permissions: Permissions) -> Engine {
+    EngineBuilder::new(create_default_context())
+        .permissions(permissions)
+        .build()
+        .expect("the engine should build")
+}
+
+fn not_allowed(result: Result<Value, EvalError>) -> (String, String) {
+    match result {
+        Err(EvalError::Shell(ShellError::NotAllowed {
+            command,
+            capability,
+            ..
+        })) => (command, capability),
+        other => panic!("expected the call not to be allowed, got {:?}", other),
+    }
+}
+
+#[test]
+fn rejects_externals() {
+    let mut engine = engine(Permissions::none());
+
+    let (command, capability) = not_allowed(engine.eval("^echo hello"));
+
+    assert_eq!(command, "run_external");
+    assert_eq!(capability, "externals");
+}
+
+#[test]
+fn allows_everything_by_default() {
+    Playground::setup("permissions_test_1", |dirs, _| {
+        let mut engine = engine(Permissions::default());
+
+        engine
+            .eval(&format!("touch '{}'", dirs.test().join("a.txt").display()))
+            .unwrap();
+
+        assert!(dirs.test().join("a.txt").exists());
+    })
+}
+
+#[test]
+fn writes_under_the_write_roots_only() {
+    Playground::setup("permissions_test_2", |dirs, sandbox| {
+        sandbox.mkdir("allowed").mkdir("denied");
+        let mut engine = engine(Permissions::none().allow_write(dirs.test().join("allowed")));
+
+        engine
+            .eval(&format!(
+                "touch '{}'",
+                dirs.test().join("allowed/a.txt").display()
+            ))
+            .unwrap();
+        let (command, capability) = not_allowed(engine.eval(&format!(
+            "touch '{}'",
+            dirs.test().join("denied/a.txt").display()
+        )));
+
+        assert!(dirs.test().join("allowed/a.txt").exists());
+        assert!(!dirs.test().join("denied/a.txt").exists());
+        assert_eq!(command, "touch");
+        assert_eq!(capability, "filesystem write");
+    })
+}
+
+#[test]
+fn rejects_paths_leaving_the_roots() {
+    Playground::setup("permissions_test_3", |dirs, sandbox| {
+        sandbox.mkdir("allowed");
+        let mut engine = engine(Permissions::none().allow_write(dirs.test().join("allowed")));
+
+        not_allowed(engine.eval(&format!(
+            "touch '{}'",
+            dirs.test().join("allowed/../escaped.txt").display()
+        )));
+
+        assert!(!dirs.test().join("escaped.txt").exists());
+    })
+}
+
+#[test]
+fn runs_the_command_with_the_paths_it_checked() {
+    Playground::setup("permissions_test_6", |dirs, sandbox| {
+        sandbox.mkdir("allowed").mkdir("denied");
+        let paths = Arc::new(Mutex::new(vec![
+            dirs.test().join("denied/b.txt"),
+            dirs.test().join("allowed/a.txt"),
+        ]));
+
+        // A different path each time it runs, so a second evaluation would escape the check
+        let next_path = ClosureCommand::new(Signature::build("next-path"), move |args| {
+            let path = paths.lock().unwrap().pop().unwrap_or_default();
+            Ok(Value::string(path.to_string_lossy(), args.head))
+        });
+        let mut engine = EngineBuilder::new(create_default_context())
+            .command(next_path)
+            .permissions(Permissions::none().allow_write(dirs.test().join("allowed")))
+            .build()
+            .unwrap();
+
+        engine.eval("touch (next-path)").unwrap();
+
+        assert!(dirs.test().join("allowed/a.txt").exists());
+        assert!(!dirs.test().join("denied/b.txt").exists());
+    })
+}
+
+#[test]
+fn reads_under_the_read_roots_only() {
+    Playground::setup("permissions_test_4", |dirs, sandbox| {
+        sandbox
+            .mkdir("public")
+            .mkdir("private")
+            .with_files(vec![EmptyFile("public/a.txt"), EmptyFile("private/b.txt")]);
+        let mut engine = engine(Permissions::none().allow_read(dirs.test().join("public")));
+
+        let listed = engine
+            .eval(&format!(
+                "ls '{}' | length",
+                dirs.test().join("public").display()
+            ))
+            .unwrap();
+        let (command, capability) =
+            not_allowed(engine.eval(&format!("ls '{}'", dirs.test().join("private/*").display())));
+
+        assert_eq!(listed.as_integer().unwrap(), 1);
+        assert_eq!(command, "ls");
+        assert_eq!(capability, "filesystem read");
+    })
+}
+
+#[test]
+fn checks_paths_given_through_flags() {
+    Playground::setup("permissions_test_7", |dirs, sandbox| {
+        sandbox.mkdir("allowed").mkdir("denied");
+        let write = ClosureCommand::new(
+            Signature::build("write").named("out", SyntaxShape::Filepath, "where to", None),
+            |args| Ok(Value::string("written", args.head)),
+        )
+        .with_capabilities(vec![Capability::FilesystemWrite]);
+        let mut engine = EngineBuilder::new(create_default_context())
+            .command(write)
+            .permissions(Permissions::none().allow_write(dirs.test().join("allowed")))
+            .build()
+            .unwrap();
+
+        engine
+            .eval(&format!(
+                "write --out '{}'",
+                dirs.test().join("allowed/a.txt").display()
+            ))
+            .unwrap();
+        let (command, capability) = not_allowed(engine.eval(&format!(
+            "let out = '{}'; write --out $out",
+            dirs.test().join("denied/a.txt").display()
+        )));
+
+        assert_eq!(command, "write");
+        assert_eq!(capability, "filesystem write");
+    })
+}
+
+#[test]
+fn checks_paths_given_as_input() {
+    Playground::setup("permissions_test_8", |dirs, sandbox| {
+        sandbox
+            .mkdir("public")
+            .mkdir("private")
+            .with_files(vec![EmptyFile("public/a.txt"), EmptyFile("private/b.txt")]);
+        let mut engine = engine(Permissions::none().allow_read(dirs.test().join("public")));
+
+        let public = engine
+            .eval(&format!(
+                "'{}' | path type",
+                dirs.test().join("public/a.txt").display()
+            ))
+            .unwrap();
+        let private = engine.eval(&format!(
+            "'{}' | path exists",
+            dirs.test().join("private/b.txt").display()
+        ));
+
+        assert_eq!(public.as_string().unwrap(), "File");
+        let (command, capability) = not_allowed(private);
+        assert_eq!(command, "path exists");
+        assert_eq!(capability, "filesystem read");
+    })
+}
+
+#[test]
+fn reading_doesnt_allow_writing() {
+    Playground::setup("permissions_test_5", |dirs, _| {
+        let mut engine = engine(Permissions::none().allow_read(dirs.test()));
+
+        let (_, capability) =
+            not_allowed(engine.eval(&format!("mkdir '{}'", dirs.test().join("new").display())));
+
+        assert_eq!(capability, "filesystem write");
+    })
+}
+
+#[test]
+fn checks_the_capabilities_of_closure_commands() {
+    let fetch = ClosureCommand::new(Signature::build("fetch"), |args| {
+        Ok(Value::string("fetched", args.head))
+    })
+    .with_capabilities(vec![Capability::Network]);
+
+    let mut allowed = EngineBuilder::new(create_default_context())
+        .command(fetch.clone())
+        .build()
+        .unwrap();
+    let mut denied = EngineBuilder::new(create_default_context())
+        .command(fetch)
+        .permissions(Permissions::none())
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        allowed.eval("fetch").unwrap().as_string().unwrap(),
+        "fetched"
+    );
+    let (command, capability) = not_allowed(denied.eval("fetch"));
+    assert_eq!(command, "fetch");
+    assert_eq!(capability, "network");
+}
@@ -0,0 +1,511 @@
+use std::collections::{HashMap, HashSet};
+
+use nu_protocol::ast::{Block, Call, Expr, Expression, Pipeline, Statement};
+use nu_protocol::engine::StateWorkingSet;
+use nu_protocol::ir::{Instruction, IrBlock, Operand, RegId, SlotId, INPUT_REGISTER};
+use nu_protocol::{Span, Value, VarId, NU_VARIABLE_ID, SCOPE_VARIABLE_ID};
+
+use crate::eval::compute;
+
+/// Lower the blocks added in the working set to IR, so that evaluating them runs the IR
+/// interpreter instead of walking their AST
+pub fn compile_blocks(working_set: &mut StateWorkingSet) {
+    for block_id in working_set.permanent_state.num_blocks()..working_set.num_blocks() {
+        let ir = compile_block(working_set, working_set.get_block(block_id));
+        working_set.get_block_mut(block_id).ir = Some(ir);
+    }
+}
+
+/// Lower a block to IR. The IR evaluates to the same results as the AST of the block
+pub fn compile_block(working_set: &StateWorkingSet, block: &Block) -> IrBlock {
+    let mut compiler = Compiler {
+        working_set,
+        instructions: vec![],
+        register_count: 1,
+        slots: vec![],
+        slot_ids: HashMap::new(),
+        read_by_ast: HashSet::new(),
+    };
+
+    // Like in `eval_block`, the output of each statement is the input of the next one
+    let mut input = INPUT_REGISTER;
+    for stmt in block.stmts.iter() {
+        if let Statement::Pipeline(pipeline) = stmt {
+            input = compiler.pipeline(pipeline, input);
+        }
+    }
+
+    // Which variables the AST evaluator reads is only known once the whole block is compiled
+    let Compiler {
+        mut instructions,
+        register_count,
+        slots,
+        read_by_ast,
+        ..
+    } = compiler;
+    for instruction in instructions.iter_mut() {
+        if let Instruction::StoreSlot { slot, on_stack, .. } = instruction {
+            *on_stack = read_by_ast.contains(&slots[*slot]);
+        }
+    }
+
+    IrBlock {
+        instructions,
+        register_count,
+        slots,
+        output: input,
+    }
+}
+
+struct Compiler<'a> {
+    working_set: &'a StateWorkingSet<'a>,
+    instructions: Vec<Instruction>,
+    register_count: usize,
+    slots: Vec<VarId>,
+    slot_ids: HashMap<VarId, SlotId>,
+    /// The variables read by the code left to the AST evaluator, which has to find them on the
+    /// stack
+    read_by_ast: HashSet<VarId>,
+}
+
+impl Compiler<'_> {
+    fn next_register(&mut self) -> RegId {
+        self.register_count += 1;
+        self.register_count - 1
+    }
+
+    fn slot(&mut self, var_id: VarId) -> SlotId {
+        match self.slot_ids.get(&var_id) {
+            Some(slot) => *slot,
+            None => {
+                self.slots.push(var_id);
+                self.slot_ids.insert(var_id, self.slots.len() - 1);
+                self.slots.len() - 1
+            }
+        }
+    }
+
+    fn pipeline(&mut self, pipeline: &Pipeline, mut input: RegId) -> RegId {
+        let expressions = &pipeline.expressions;
+
+        for (idx, expr) in expressions.iter().enumerate() {
+            input = match &expr.expr {
+                Expr::Call(call) => self.call(call, input),
+                Expr::ExternalCall(..) => {
+                    self.read_by_ast(expr);
+
+                    let dst = self.next_register();
+                    self.instructions.push(Instruction::External {
+                        dst,
+                        input,
+                        expr: Box::new(expr.clone()),
+                        last_expression: idx == expressions.len() - 1,
+                        redirect_stderr: self.redirect_stderr(expressions, idx),
+                    });
+                    dst
+                }
+                // An expression in a pipeline ignores its input
+                _ => self.expression(expr),
+            };
+        }
+
+        input
+    }
+
+    fn call(&mut self, call: &Call, input: RegId) -> RegId {
+        let dst = self.next_register();
+
+        if let Some((var_id, rhs)) = self.let_binding(call) {
+            let src = self.expression(rhs);
+            let slot = self.slot(var_id);
+            self.instructions.push(Instruction::StoreSlot {
+                dst,
+                slot,
+                src,
+                on_stack: false,
+                span: call.head,
+            });
+
+            return dst;
+        }
+
+        for arg in &call.positional {
+            self.read_by_ast(arg);
+        }
+        for arg in call.named.iter().filter_map(|(_, arg)| arg.as_ref()) {
+            self.read_by_ast(arg);
+        }
+        self.instructions.push(Instruction::Call {
+            dst,
+            input,
+            call: Box::new(call.clone()),
+        });
+        dst
+    }
+
+    // `let` only sets a variable, which the IR does itself
+    fn let_binding<'c>(&self, call: &'c Call) -> Option<(VarId, &'c Expression)> {
+        if self.working_set.get_decl(call.decl_id).name() != "let" || !call.named.is_empty() {
+            return None;
+        }
+
+        match (call.positional.first(), call.positional.get(1)) {
+            (
+                Some(Expression {
+                    expr: Expr::VarDecl(var_id),
+                    ..
+                }),
+                Some(Expression {
+                    expr: Expr::Keyword(_, _, rhs),
+                    ..
+                }),
+            ) => Some((*var_id, rhs)),
+            _ => None,
+        }
+    }
+
+    // Like in `eval_pipeline`, an external hands its stderr over to the next command only when
+    // that one collects it
+    fn redirect_stderr(&self, expressions: &[Expression], idx: usize) -> bool {
+        match expressions.get(idx + 1) {
+            Some(Expression {
+                expr: Expr::Call(call),
+                ..
+            }) => self.working_set.get_decl(call.decl_id).collects_stderr(),
+            _ => false,
+        }
+    }
+
+    fn expression(&mut self, expr: &Expression) -> RegId {
+        let span = expr.span;
+
+        if let Some(value) = self.constant(expr) {
+            let dst = self.next_register();
+            self.instructions
+                .push(Instruction::LoadLiteral { dst, value });
+
+            return dst;
+        }
+
+        match &expr.expr {
+            Expr::Var(var_id) if built_when_asked_for(*var_id) => {
+                let dst = self.next_register();
+                self.instructions.push(Instruction::LoadVariable {
+                    dst,
+                    var_id: *var_id,
+                    span,
+                });
+                dst
+            }
+            Expr::Var(var_id) => {
+                let slot = self.slot(*var_id);
+
+                let dst = self.next_register();
+                self.instructions
+                    .push(Instruction::LoadSlot { dst, slot, span });
+                dst
+            }
+            Expr::FullCellPath(cell_path) => {
+                let src = self.expression(&cell_path.head);
+                if cell_path.tail.is_empty() {
+                    return src;
+                }
+
+                let dst = self.next_register();
+                self.instructions.push(Instruction::FollowCellPath {
+                    dst,
+                    src,
+                    path: cell_path.tail.clone(),
+                });
+                dst
+            }
+            Expr::BinaryOp(lhs, op, rhs) => match &op.expr {
+                Expr::Operator(operator) => {
+                    let lhs = self.operand(lhs);
+                    let rhs = self.operand(rhs);
+
+                    let dst = self.next_register();
+                    self.instructions.push(Instruction::BinaryOp {
+                        dst,
+                        lhs,
+                        op: operator.clone(),
+                        op_span: op.span,
+                        rhs,
+                    });
+                    dst
+                }
+                _ => self.fallback(expr),
+            },
+            Expr::List(items) => {
+                let items = items.iter().map(|item| self.expression(item)).collect();
+
+                let dst = self.next_register();
+                self.instructions
+                    .push(Instruction::BuildList { dst, items, span });
+                dst
+            }
+            Expr::Record(fields) => {
+                let mut cols = vec![];
+                let mut vals = vec![];
+                for (col, val) in fields {
+                    cols.push(self.expression(col));
+                    vals.push(self.expression(val));
+                }
+
+                let dst = self.next_register();
+                self.instructions.push(Instruction::BuildRecord {
+                    dst,
+                    cols,
+                    vals,
+                    span,
+                });
+                dst
+            }
+            Expr::Table(headers, rows) => {
+                let headers = headers
+                    .iter()
+                    .map(|header| (self.expression(header), header.span))
+                    .collect();
+                let rows = rows
+                    .iter()
+                    .map(|row| row.iter().map(|cell| self.expression(cell)).collect())
+                    .collect();
+
+                let dst = self.next_register();
+                self.instructions.push(Instruction::BuildTable {
+                    dst,
+                    headers,
+                    rows,
+                    span,
+                });
+                dst
+            }
+            Expr::Range(from, next, to, operator) => {
+                let from = from.as_ref().map(|from| self.expression(from));
+                let next = next.as_ref().map(|next| self.expression(next));
+                let to = to.as_ref().map(|to| self.expression(to));
+
+                let dst = self.next_register();
+                self.instructions.push(Instruction::BuildRange {
+                    dst,
+                    from,
+                    next,
+                    to,
+                    operator: *operator,
+                    span,
+                });
+                dst
+            }
+            Expr::Call(call) => {
+                let input = self.next_register();
+                self.instructions.push(Instruction::LoadLiteral {
+                    dst: input,
+                    value: Value::nothing(call.head),
+                });
+
+                let src = self.call(call, input);
+                self.collect(src, call.head)
+            }
+            // A subexpression shares the stack of the block it's in, so its pipelines can be
+            // compiled in with the rest. The output of an external at the end of one is collected
+            // into a string there instead of streaming, which only the AST evaluator does
+            Expr::Subexpression(block_id) => {
+                let block = self.working_set.get_block(*block_id);
+                if has_externals(block) {
+                    return self.fallback(expr);
+                }
+
+                let mut input = self.next_register();
+                self.instructions.push(Instruction::LoadLiteral {
+                    dst: input,
+                    value: Value::nothing(span),
+                });
+                for stmt in block.stmts.iter() {
+                    if let Statement::Pipeline(pipeline) = stmt {
+                        input = self.pipeline(pipeline, input);
+                    }
+                }
+                self.collect(input, span)
+            }
+            Expr::Keyword(_, _, expr) => self.expression(expr),
+            _ => self.fallback(expr),
+        }
+    }
+
+    fn collect(&mut self, src: RegId, span: Span) -> RegId {
+        let dst = self.next_register();
+        self.instructions
+            .push(Instruction::Collect { dst, src, span });
+        dst
+    }
+
+    // Variables are read from their slot where they're used, except for the ones that are built
+    // when asked for
+    fn operand(&mut self, expr: &Expression) -> Operand {
+        let var_id = match &expr.expr {
+            Expr::Var(var_id) => Some(*var_id),
+            Expr::FullCellPath(cell_path) if cell_path.tail.is_empty() => match cell_path.head.expr
+            {
+                Expr::Var(var_id) => Some(var_id),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        match var_id {
+            Some(var_id) if !built_when_asked_for(var_id) => {
+                Operand::Slot(self.slot(var_id), expr.span)
+            }
+            _ => match self.constant(expr) {
+                Some(value) => Operand::Literal(value),
+                None => Operand::Register(self.expression(expr)),
+            },
+        }
+    }
+
+    // A block is a constant too, but the variables in it are read by the AST evaluator, or by the
+    // IR of the block, from the stack
+    fn constant(&mut self, expr: &Expression) -> Option<Value> {
+        let value = literal(expr)?;
+        if let Value::Block { .. } = value {
+            self.read_by_ast(expr);
+        }
+
+        Some(value)
+    }
+
+    fn fallback(&mut self, expr: &Expression) -> RegId {
+        self.read_by_ast(expr);
+
+        let dst = self.next_register();
+        self.instructions.push(Instruction::Eval {
+            dst,
+            expr: Box::new(expr.clone()),
+        });
+        dst
+    }
+
+    fn read_by_ast(&mut self, expr: &Expression) {
+        vars_in_expression(self.working_set, expr, &mut self.read_by_ast);
+    }
+}
+
+fn built_when_asked_for(var_id: VarId) -> bool {
+    var_id == NU_VARIABLE_ID || var_id == SCOPE_VARIABLE_ID
+}
+
+fn has_externals(block: &Block) -> bool {
+    block.stmts.iter().any(|stmt| match stmt {
+        Statement::Pipeline(pipeline) => pipeline
+            .expressions
+            .iter()
+            .any(|expr| matches!(expr.expr, Expr::ExternalCall(..))),
+        _ => false,
+    })
+}
+
+fn literal(expr: &Expression) -> Option<Value> {
+    let span = expr.span;
+
+    match &expr.expr {
+        Expr::Bool(val) => Some(Value::Bool { val: *val, span }),
+        Expr::Int(val) => Some(Value::Int { val: *val, span }),
+        Expr::Float(val) => Some(Value::Float { val: *val, span }),
+        Expr::String(val) | Expr::Filepath(val) | Expr::GlobPattern(val) => {
+            Some(Value::string(val.clone(), span))
+        }
+        Expr::ValueWithUnit(size, unit) => match size.expr {
+            Expr::Int(size) => Some(compute(size, unit.item, unit.span)),
+            _ => None,
+        },
+        Expr::CellPath(cell_path) => Some(Value::CellPath {
+            val: cell_path.clone(),
+            span,
+        }),
+        Expr::Nothing | Expr::Garbage | Expr::VarDecl(_) | Expr::Operator(_) => {
+            Some(Value::Nothing { span })
+        }
+        Expr::Block(block_id) => Some(Value::Block {
+            val: *block_id,
+            span,
+        }),
+        _ => None,
+    }
+}
+
+// The variables an expression reads, in the blocks nested in it too
+fn vars_in_expression(working_set: &StateWorkingSet, expr: &Expression, vars: &mut HashSet<VarId>) {
+    match &expr.expr {
+        Expr::Var(var_id) => {
+            vars.insert(*var_id);
+        }
+        Expr::Call(call) => {
+            for arg in call
+                .positional
+                .iter()
+                .chain(call.named.iter().filter_map(|(_, arg)| arg.as_ref()))
+            {
+                vars_in_expression(working_set, arg, vars);
+            }
+        }
+        Expr::ExternalCall(_, _, args, redirections, _) => {
+            for arg in args
+                .iter()
+                .chain(redirections.iter().filter_map(|r| r.target.as_ref()))
+            {
+                vars_in_expression(working_set, arg, vars);
+            }
+        }
+        Expr::Block(block_id) | Expr::RowCondition(block_id) | Expr::Subexpression(block_id) => {
+            for stmt in working_set.get_block(*block_id).stmts.iter() {
+                if let Statement::Pipeline(pipeline) = stmt {
+                    for expr in pipeline.expressions.iter() {
+                        vars_in_expression(working_set, expr, vars);
+                    }
+                }
+            }
+        }
+        Expr::BinaryOp(lhs, _, rhs) => {
+            vars_in_expression(working_set, lhs, vars);
+            vars_in_expression(working_set, rhs, vars);
+        }
+        Expr::FullCellPath(cell_path) => vars_in_expression(working_set, &cell_path.head, vars),
+        Expr::Keyword(_, _, expr) | Expr::ValueWithUnit(expr, _) => {
+            vars_in_expression(working_set, expr, vars)
+        }
+        Expr::List(items) => {
+            for item in items {
+                vars_in_expression(working_set, item, vars);
+            }
+        }
+        Expr::Range(from, next, to, _) => {
+            for expr in from.iter().chain(next.iter()).chain(to.iter()) {
+                vars_in_expression(working_set, expr, vars);
+            }
+        }
+        Expr::Record(fields) => {
+            for (col, val) in fields {
+                vars_in_expression(working_set, col, vars);
+                vars_in_expression(working_set, val, vars);
+            }
+        }
+        Expr::Table(headers, rows) => {
+            for expr in headers.iter().chain(rows.iter().flatten()) {
+                vars_in_expression(working_set, expr, vars);
+            }
+        }
+        Expr::Bool(_)
+        | Expr::Int(_)
+        | Expr::Float(_)
+        | Expr::VarDecl(_)
+        | Expr::Operator(_)
+        | Expr::Filepath(_)
+        | Expr::GlobPattern(_)
+        | Expr::String(_)
+        | Expr::CellPath(_)
+        | Expr::ImportPattern(_)
+        | Expr::Signature(_)
+        | Expr::Nothing
+        | Expr::Garbage => {}
+    }
+}
@@ -0,0 +1,116 @@
+use std::sync::atomic::Ordering;
+use std::sync::{mpsc, Arc};
+use std::thread::JoinHandle;
+
+use nu_protocol::ast::Expression;
+use nu_protocol::engine::{EngineState, Stack};
+use nu_protocol::{PipelineData, ShellError, ValueStream, EVAL_STACK_SIZE};
+
+use crate::eval::eval_pipeline_element;
+
+// How many values a stage can get ahead of the stage after it
+const CHANNEL_BOUND: usize = 128;
+
+/// Start every stage of a pipeline but the last on a thread of its own, and return the input for
+/// the last stage, which is left to the caller
+///
+/// A stage waits for the output of the stage before it, then hands its own output on. Streams are
+/// pulled by the stage that produced them and pushed through a bounded channel, so the work of
+/// every stage overlaps with the others while a fast producer can't run away from a slow consumer.
+/// The stages run with a copy of the caller's stack, so their changes to the environment don't
+/// last. Callers keep pipelines with a stage that changes the environment, one whose command says
+/// so with `changes_env`, out of here.
+///
+/// A stage stops early on ctrl-c, or once the stage after it is gone. A stage that fails hands
+/// nothing on, which stops the stages after it, and its error is the one returned.
+pub(crate) fn start_stages_concurrently(
+    engine_state: &EngineState,
+    stack: &Stack,
+    expressions: &[Expression],
+    input: PipelineData,
+) -> Result<PipelineData, ShellError> {
+    let last = expressions.len() - 1;
+    let shared = Arc::new((engine_state.clone(), expressions.to_vec()));
+
+    let (first_tx, mut input_rx) = mpsc::channel();
+    let _ = first_tx.send(input);
+
+    let mut stages: Vec<JoinHandle<Result<(), ShellError>>> = vec![];
+
+    for idx in 0..last {
+        let (output_tx, output_rx) = mpsc::channel();
+        let shared = shared.clone();
+        let mut stack = stack.clone();
+
+        let stage = std::thread::Builder::new()
+            .stack_size(EVAL_STACK_SIZE)
+            .spawn(move || {
+                let (engine_state, expressions) = &*shared;
+
+                // The stage before failed, and has an error of its own to report
+                let input = match input_rx.recv() {
+                    Ok(input) => input,
+                    Err(_) => return Ok(()),
+                };
+
+                let output =
+                    eval_pipeline_element(engine_state, &mut stack, expressions, idx, input)?;
+
+                hand_off(engine_state, output, output_tx);
+
+                Ok(())
+            });
+        stages.push(stage.map_err(|err| ShellError::NushellFailed(format!("{}", err)))?);
+
+        input_rx = output_rx;
+    }
+
+    match input_rx.recv() {
+        Ok(input) => Ok(input),
+        Err(_) => {
+            for stage in stages {
+                stage
+                    .join()
+                    .map_err(|_| ShellError::NushellFailed("pipeline stage panicked".into()))??;
+            }
+
+            Err(ShellError::NushellFailed(
+                "pipeline stage stopped without output".into(),
+            ))
+        }
+    }
+}
+
+fn hand_off(engine_state: &EngineState, output: PipelineData, tx: mpsc::Sender<PipelineData>) {
+    match output {
+        PipelineData::Stream(stream, metadata) => {
+            let (values_tx, values_rx) = mpsc::sync_channel(CHANNEL_BOUND);
+
+            let ctrlc = engine_state.ctrlc.clone();
+            let stream_to_next = ValueStream::from_stream(values_rx.into_iter(), ctrlc.clone());
+
+            if tx
+                .send(PipelineData::Stream(stream_to_next, metadata))
+                .is_err()
+            {
+                return;
+            }
+
+            for value in stream {
+                if let Some(ctrlc) = &ctrlc {
+                    if ctrlc.load(Ordering::SeqCst) {
+                        break;
+                    }
+                }
+
+                if values_tx.send(value).is_err() {
+                    break;
+                }
+            }
+        }
+        // Values are there already, and externals are read by threads of their own
+        output => {
+            let _ = tx.send(output);
+        }
+    }
+}
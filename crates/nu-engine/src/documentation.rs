@@ -1,6 +1,6 @@
 use itertools::Itertools;
 use nu_protocol::{
-    engine::EngineState, Example, PositionalArg, Signature, Span, SyntaxShape, Value,
+    engine::EngineState, Example, PositionalArg, Record, Signature, Span, SyntaxShape, Value,
 };
 use std::collections::HashMap;
 
@@ -15,51 +15,41 @@ pub struct DocumentationConfig {
     brief: bool,
 }
 
-fn generate_doc(name: &str, engine_state: &EngineState, head: Span) -> (Vec<String>, Vec<Value>) {
-    let mut cols = vec![];
-    let mut vals = vec![];
+fn generate_doc(name: &str, engine_state: &EngineState, head: Span) -> Record {
+    let mut record = Record::new();
 
     let command = engine_state
         .find_decl(name.as_bytes())
         .map(|decl_id| engine_state.get_decl(decl_id))
         .unwrap_or_else(|| panic!("Expected command '{}' from names to be in registry", name));
 
-    cols.push("name".to_string());
-    vals.push(Value::String {
-        val: name.into(),
-        span: head,
-    });
+    record.insert("name", Value::string(name, head));
 
-    cols.push("usage".to_string());
-    vals.push(Value::String {
-        val: command.usage().to_owned(),
-        span: head,
-    });
+    record.insert("usage", Value::string(command.usage().to_owned(), head));
 
     if let Some(link) = retrieve_doc_link(name) {
-        cols.push("doc_link".into());
-        vals.push(Value::String {
-            val: link,
-            span: head,
-        });
+        record.insert("doc_link", Value::string(link, head));
     }
 
-    cols.push("documentation".to_owned());
-    vals.push(Value::String {
-        val: get_documentation(
-            &command.signature(),
-            &command.examples(),
-            engine_state,
-            &DocumentationConfig {
-                no_subcommands: true,
-                no_color: true,
-                brief: false,
-            },
-        ),
-        span: head,
-    });
-
-    (cols, vals)
+    record.insert(
+        "documentation",
+        Value::String {
+            val: get_documentation(
+                &command.signature(),
+                &command.examples(),
+                engine_state,
+                &DocumentationConfig {
+                    no_subcommands: true,
+                    no_color: true,
+                    brief: false,
+                },
+            )
+            .into(),
+            span: head,
+        },
+    );
+
+    record
 }
 
 // generate_docs gets the documentation from each command and returns a Table as output
@@ -94,31 +84,18 @@ pub fn generate_docs(engine_state: &EngineState, head: Span) -> Value {
         // Iterate over all the subcommands of the parent command
         let mut sub_table = Vec::new();
         for sub_name in cmap.get(&sig.name).unwrap_or(&Vec::new()) {
-            let (cols, vals) = generate_doc(sub_name, engine_state, head);
-            sub_table.push(Value::Record {
-                cols,
-                vals,
-                span: head,
-            });
+            sub_table.push(Value::record(
+                generate_doc(sub_name, engine_state, head),
+                head,
+            ));
         }
 
         if !sub_table.is_empty() {
-            row_entries.0.push("subcommands".into());
-            row_entries.1.push(Value::List {
-                vals: sub_table,
-                span: head,
-            });
+            row_entries.insert("subcommands", Value::list(sub_table, head));
         }
-        table.push(Value::Record {
-            cols: row_entries.0,
-            vals: row_entries.1,
-            span: head,
-        });
-    }
-    Value::List {
-        vals: table,
-        span: head,
+        table.push(Value::record(row_entries, head));
     }
+    Value::list(table, head)
 }
 
 fn retrieve_doc_link(name: &str) -> Option<String> {
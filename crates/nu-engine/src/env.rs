@@ -27,7 +27,7 @@ pub fn convert_env_values(
     for scope in &stack.env_vars {
         let mut new_scope = HashMap::new();
 
-        for (name, val) in scope {
+        for (name, val) in scope.iter() {
             if let Some(env_conv) = config.env_conversions.get(name) {
                 if let Some((block_id, from_span)) = env_conv.from_string {
                     let val_span = match val.span() {
@@ -76,7 +76,7 @@ pub fn convert_env_values(
             }
         }
 
-        new_env_vars.push(new_scope);
+        new_env_vars.push(new_scope.into());
     }
 
     stack.env_vars = new_env_vars;
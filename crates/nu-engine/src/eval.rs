@@ -1,12 +1,22 @@
+use std::borrow::Cow;
 use std::cmp::Ordering;
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use nu_protocol::ast::{Block, Call, Expr, Expression, Operator, Statement};
-use nu_protocol::engine::{EngineState, Stack};
+use nu_protocol::ast::{
+    Block, Call, Expr, Expression, Operator, Redirection, RedirectionSource, Statement,
+};
+use nu_protocol::engine::{EngineState, Profiler, Stack};
 use nu_protocol::{
-    IntoPipelineData, PipelineData, Range, ShellError, Span, Spanned, Type, Unit, Value, VarId,
+    IntoPipelineData, PipelineData, Range, Record, ShellError, Span, Spanned, Type, Unit, Value,
+    VarId, CONFIG_VARIABLE_ID,
 };
 
+use crate::concurrent::start_stages_concurrently;
+use crate::eval_ir::eval_ir_block;
 use crate::get_full_help;
+use crate::permissions::check_permissions;
 
 pub fn eval_operator(op: &Expression) -> Result<Operator, ShellError> {
     match op {
@@ -20,26 +30,45 @@ pub fn eval_operator(op: &Expression) -> Result<Operator, ShellError> {
     }
 }
 
-fn eval_call(
+pub(crate) fn eval_call(
     engine_state: &EngineState,
     stack: &mut Stack,
     call: &Call,
     input: PipelineData,
 ) -> Result<PipelineData, ShellError> {
     let decl = engine_state.get_decl(call.decl_id);
+    let wants_help = call.named.iter().any(|(flag, _)| flag.item == "help");
 
-    if call.named.iter().any(|(flag, _)| flag.item == "help") {
-        let full_help = get_full_help(&decl.signature(), &decl.examples(), engine_state);
-        Ok(Value::String {
-            val: full_help,
-            span: call.head,
+    let call = if wants_help {
+        Cow::Borrowed(call)
+    } else {
+        // Commands that don't stream stop here when ctrl-c is pressed, or a timeout runs out
+        if let Some(ctrlc) = &engine_state.ctrlc {
+            if ctrlc.load(AtomicOrdering::SeqCst) {
+                return Err(ShellError::Interrupted(call.head));
+            }
         }
-        .into_pipeline_data())
+
+        check_permissions(engine_state, stack, call, decl.as_ref())?
+    };
+
+    if wants_help {
+        let full_help = get_full_help(&decl.signature(), &decl.examples(), engine_state);
+        Ok(Value::string(full_help, call.head).into_pipeline_data())
     } else if let Some(block_id) = decl.get_block_id() {
         let block = engine_state.get_block(block_id);
 
         let mut stack = stack.collect_captures(&block.captures);
 
+        stack.recursion_depth += 1;
+        let limit = recursion_limit(&stack);
+        if stack.recursion_depth > limit {
+            return Err(ShellError::RecursionLimitReached {
+                limit,
+                span: call.head,
+            });
+        }
+
         for (param_idx, param) in decl
             .signature()
             .required_positional
@@ -80,10 +109,7 @@ fn eval_call(
                 rest_positional
                     .var_id
                     .expect("Internal error: rest positional parameter lacks var_id"),
-                Value::List {
-                    vals: rest_items,
-                    span,
-                },
+                Value::list(rest_items, span),
             )
         }
 
@@ -121,19 +147,24 @@ fn eval_call(
             }
         }
         eval_block(engine_state, &mut stack, block, input)
+            .map_err(|error| error.called_from(call.head))
     } else {
-        decl.run(engine_state, stack, call, input)
+        decl.run(engine_state, stack, &call, input)
     }
 }
 
-fn eval_external(
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn eval_external(
     engine_state: &EngineState,
     stack: &mut Stack,
     name: &str,
     name_span: &Span,
     args: &[Expression],
+    redirections: &[Redirection],
     input: PipelineData,
     last_expression: bool,
+    redirect_stderr: bool,
+    background: bool,
 ) -> Result<PipelineData, ShellError> {
     let decl_id = engine_state
         .find_decl("run_external".as_bytes())
@@ -142,6 +173,7 @@ fn eval_external(
     let command = engine_state.get_decl(decl_id);
 
     let mut call = Call::new();
+    call.head = *name_span;
 
     call.positional.push(Expression {
         expr: Expr::String(name.trim_start_matches('^').to_string()),
@@ -154,6 +186,25 @@ fn eval_external(
         call.positional.push(arg.clone())
     }
 
+    for redirection in redirections {
+        let flag = match (redirection.source, &redirection.target) {
+            (RedirectionSource::Stdout, Some(_)) => "stdout_file",
+            (RedirectionSource::Stderr, Some(_)) => "stderr_file",
+            (RedirectionSource::StdoutAndStderr, Some(_)) => "stdout_and_stderr_file",
+            // Stdout already goes into the pipeline
+            (RedirectionSource::Stdout, None) => continue,
+            (_, None) => "stderr_to_pipeline",
+        };
+
+        call.named.push((
+            Spanned {
+                item: flag.into(),
+                span: redirection.span,
+            },
+            redirection.target.clone(),
+        ))
+    }
+
     if last_expression {
         call.named.push((
             Spanned {
@@ -164,6 +215,28 @@ fn eval_external(
         ))
     }
 
+    if redirect_stderr {
+        call.named.push((
+            Spanned {
+                item: "redirect_stderr".into(),
+                span: *name_span,
+            },
+            None,
+        ))
+    }
+
+    if background {
+        call.named.push((
+            Spanned {
+                item: "background".into(),
+                span: *name_span,
+            },
+            None,
+        ))
+    }
+
+    let call = check_permissions(engine_state, stack, &call, command.as_ref())?;
+
     command.run(engine_state, stack, &call, input)
 }
 
@@ -236,7 +309,7 @@ pub fn eval_expression(
                     .into_value(call.head),
             )
         }
-        Expr::ExternalCall(name, span, args) => {
+        Expr::ExternalCall(name, span, args, redirections, background) => {
             // FIXME: protect this collect with ctrl-c
             Ok(eval_external(
                 engine_state,
@@ -244,8 +317,11 @@ pub fn eval_expression(
                 name,
                 span,
                 args,
+                redirections,
                 PipelineData::new(*span),
                 true,
+                false,
+                *background,
             )?
             .into_value(*span))
         }
@@ -256,26 +332,7 @@ pub fn eval_expression(
             let op = eval_operator(op)?;
             let rhs = eval_expression(engine_state, stack, rhs)?;
 
-            match op {
-                Operator::Plus => lhs.add(op_span, &rhs),
-                Operator::Minus => lhs.sub(op_span, &rhs),
-                Operator::Multiply => lhs.mul(op_span, &rhs),
-                Operator::Divide => lhs.div(op_span, &rhs),
-                Operator::LessThan => lhs.lt(op_span, &rhs),
-                Operator::LessThanOrEqual => lhs.lte(op_span, &rhs),
-                Operator::GreaterThan => lhs.gt(op_span, &rhs),
-                Operator::GreaterThanOrEqual => lhs.gte(op_span, &rhs),
-                Operator::Equal => lhs.eq(op_span, &rhs),
-                Operator::NotEqual => lhs.ne(op_span, &rhs),
-                Operator::In => lhs.r#in(op_span, &rhs),
-                Operator::NotIn => lhs.not_in(op_span, &rhs),
-                Operator::Contains => lhs.contains(op_span, &rhs),
-                Operator::NotContains => lhs.not_contains(op_span, &rhs),
-                Operator::Modulo => lhs.modulo(op_span, &rhs),
-                Operator::And => lhs.and(op_span, &rhs),
-                Operator::Or => lhs.or(op_span, &rhs),
-                Operator::Pow => lhs.pow(op_span, &rhs),
-            }
+            eval_binary_op(&lhs, op, op_span, &rhs)
         }
         Expr::RowCondition(block_id) | Expr::Subexpression(block_id) => {
             let block = engine_state.get_block(*block_id);
@@ -295,109 +352,273 @@ pub fn eval_expression(
             for expr in x {
                 output.push(eval_expression(engine_state, stack, expr)?);
             }
-            Ok(Value::List {
-                vals: output,
-                span: expr.span,
-            })
+            Ok(Value::list(output, expr.span))
         }
         Expr::Record(fields) => {
-            let mut cols = vec![];
-            let mut vals = vec![];
+            let mut record = Record::new();
             for (col, val) in fields {
-                cols.push(eval_expression(engine_state, stack, col)?.as_string()?);
-                vals.push(eval_expression(engine_state, stack, val)?);
+                let col_name = eval_expression(engine_state, stack, col)?.as_string()?;
+                let val = eval_expression(engine_state, stack, val)?;
+                record.try_insert(col_name, val, col.span)?;
             }
 
-            Ok(Value::Record {
-                cols,
-                vals,
-                span: expr.span,
-            })
+            Ok(Value::record(record, expr.span))
         }
         Expr::Table(headers, vals) => {
             let mut output_headers = vec![];
-            for expr in headers {
-                output_headers.push(eval_expression(engine_state, stack, expr)?.as_string()?);
+            for header in headers {
+                let name = eval_expression(engine_state, stack, header)?.as_string()?;
+                if output_headers.contains(&name) {
+                    return Err(ShellError::ColumnDefinedTwice(name, header.span));
+                }
+                output_headers.push(name);
             }
 
             let mut output_rows = vec![];
             for val in vals {
-                let mut row = vec![];
-                for expr in val {
-                    row.push(eval_expression(engine_state, stack, expr)?);
+                let mut row = Record::with_capacity(output_headers.len());
+                for (header, expr) in output_headers.iter().zip(val) {
+                    row.insert(header.clone(), eval_expression(engine_state, stack, expr)?);
                 }
-                output_rows.push(Value::Record {
-                    cols: output_headers.clone(),
-                    vals: row,
-                    span: expr.span,
-                });
+                output_rows.push(Value::record(row, expr.span));
             }
-            Ok(Value::List {
-                vals: output_rows,
-                span: expr.span,
-            })
+            Ok(Value::list(output_rows, expr.span))
         }
         Expr::Keyword(_, _, expr) => eval_expression(engine_state, stack, expr),
-        Expr::String(s) => Ok(Value::String {
-            val: s.clone(),
-            span: expr.span,
-        }),
-        Expr::Filepath(s) => Ok(Value::String {
-            val: s.clone(),
-            span: expr.span,
-        }),
-        Expr::GlobPattern(s) => Ok(Value::String {
-            val: s.clone(),
-            span: expr.span,
-        }),
+        Expr::String(s) => Ok(Value::string(s.clone(), expr.span)),
+        Expr::Filepath(s) => Ok(Value::string(s.clone(), expr.span)),
+        Expr::GlobPattern(s) => Ok(Value::string(s.clone(), expr.span)),
         Expr::Signature(_) => Ok(Value::Nothing { span: expr.span }),
         Expr::Garbage => Ok(Value::Nothing { span: expr.span }),
         Expr::Nothing => Ok(Value::Nothing { span: expr.span }),
     }
 }
 
+pub(crate) fn eval_binary_op(
+    lhs: &Value,
+    op: Operator,
+    op_span: Span,
+    rhs: &Value,
+) -> Result<Value, ShellError> {
+    match op {
+        Operator::Plus => lhs.add(op_span, rhs),
+        Operator::Minus => lhs.sub(op_span, rhs),
+        Operator::Multiply => lhs.mul(op_span, rhs),
+        Operator::Divide => lhs.div(op_span, rhs),
+        Operator::LessThan => lhs.lt(op_span, rhs),
+        Operator::LessThanOrEqual => lhs.lte(op_span, rhs),
+        Operator::GreaterThan => lhs.gt(op_span, rhs),
+        Operator::GreaterThanOrEqual => lhs.gte(op_span, rhs),
+        Operator::Equal => lhs.eq(op_span, rhs),
+        Operator::NotEqual => lhs.ne(op_span, rhs),
+        Operator::In => lhs.r#in(op_span, rhs),
+        Operator::NotIn => lhs.not_in(op_span, rhs),
+        Operator::Contains => lhs.contains(op_span, rhs),
+        Operator::NotContains => lhs.not_contains(op_span, rhs),
+        Operator::Modulo => lhs.modulo(op_span, rhs),
+        Operator::And => lhs.and(op_span, rhs),
+        Operator::Or => lhs.or(op_span, rhs),
+        Operator::Pow => lhs.pow(op_span, rhs),
+    }
+}
+
+// An external hands its stderr over to the pipeline, instead of the terminal, only when the
+// next command collects it, like `complete`
+fn redirect_stderr(engine_state: &EngineState, expressions: &[Expression], idx: usize) -> bool {
+    match expressions.get(idx + 1) {
+        Some(Expression {
+            expr: Expr::Call(call),
+            ..
+        }) => engine_state.get_decl(call.decl_id).collects_stderr(),
+        _ => false,
+    }
+}
+
 pub fn eval_block(
     engine_state: &EngineState,
     stack: &mut Stack,
     block: &Block,
     mut input: PipelineData,
 ) -> Result<PipelineData, ShellError> {
+    // The IR doesn't keep the pipeline elements apart, so a profiled block walks its AST instead
+    if let (Some(ir), None) = (&block.ir, &stack.profiler) {
+        return eval_ir_block(engine_state, stack, ir, input);
+    }
+
     for stmt in block.stmts.iter() {
         if let Statement::Pipeline(pipeline) = stmt {
-            for (i, elem) in pipeline.expressions.iter().enumerate() {
-                match elem {
-                    Expression {
-                        expr: Expr::Call(call),
-                        ..
-                    } => {
-                        input = eval_call(engine_state, stack, call, input)?;
-                    }
-                    Expression {
-                        expr: Expr::ExternalCall(name, name_span, args),
-                        ..
-                    } => {
-                        input = eval_external(
-                            engine_state,
-                            stack,
-                            name,
-                            name_span,
-                            args,
-                            input,
-                            i == pipeline.expressions.len() - 1,
-                        )?;
-                    }
+            input = eval_pipeline(engine_state, stack, &pipeline.expressions, input)?;
+        }
+    }
 
-                    elem => {
-                        input = eval_expression(engine_state, stack, elem)?.into_pipeline_data();
-                    }
-                }
+    Ok(input)
+}
+
+fn eval_pipeline(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    expressions: &[Expression],
+    mut input: PipelineData,
+) -> Result<PipelineData, ShellError> {
+    // Stages running at the same time would count each other's time, so profiling runs them in turn
+    let mut start = 0;
+    if expressions.len() > 1
+        && stack.profiler.is_none()
+        && concurrent_pipelines(stack)
+        && can_run_concurrently(engine_state, expressions)
+    {
+        input = start_stages_concurrently(engine_state, stack, expressions, input)?;
+        start = expressions.len() - 1;
+    }
+
+    for i in start..expressions.len() {
+        input = match stack.profiler.clone() {
+            Some(profiler) => {
+                profile_pipeline_element(engine_state, stack, profiler, expressions, i, input)?
             }
-        }
+            None => eval_pipeline_element(engine_state, stack, expressions, i, input)?,
+        };
     }
 
     Ok(input)
 }
 
+// Evaluate a pipeline element with the profiler for it on the stack, so that the elements that
+// run inside it are nested under it in the report
+fn profile_pipeline_element(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    profiler: Profiler,
+    expressions: &[Expression],
+    idx: usize,
+    input: PipelineData,
+) -> Result<PipelineData, ShellError> {
+    let element_profiler = profiler.enter(expressions[idx].span);
+    stack.profiler = Some(element_profiler.clone());
+
+    // The time spent pulling items out of the input is the previous element's, so it's kept
+    // apart and left out of this one's
+    let pulling = Arc::new(AtomicU64::new(0));
+    let input = time_stream(input, {
+        let pulling = pulling.clone();
+        move |elapsed| {
+            pulling.fetch_add(elapsed.as_nanos() as u64, AtomicOrdering::Relaxed);
+        }
+    });
+
+    let start_time = Instant::now();
+    let result = eval_pipeline_element(engine_state, stack, expressions, idx, input);
+    let mut pulled = pulling.load(AtomicOrdering::Relaxed);
+    element_profiler.exit(
+        start_time
+            .elapsed()
+            .saturating_sub(Duration::from_nanos(pulled)),
+    );
+
+    stack.profiler = Some(profiler);
+
+    // A stream does most of its work once it's drained, after this returns, so the time each of
+    // its items takes to come out is counted too
+    result.map(|output| {
+        time_stream(output, move |elapsed| {
+            let now = pulling.load(AtomicOrdering::Relaxed);
+            let upstream = Duration::from_nanos(now - pulled);
+            pulled = now;
+
+            element_profiler.exit(elapsed.saturating_sub(upstream));
+        })
+    })
+}
+
+// Hand the time each item of a stream takes to come out to `timed`
+fn time_stream(data: PipelineData, timed: impl FnMut(Duration) + Send + 'static) -> PipelineData {
+    match data {
+        PipelineData::Stream(mut stream, metadata) => {
+            stream.stream = Box::new(Timed {
+                iter: stream.stream,
+                timed,
+            });
+            PipelineData::Stream(stream, metadata)
+        }
+        PipelineData::RawStream(mut stream, metadata) => {
+            stream.stream = Box::new(Timed {
+                iter: stream.stream,
+                timed,
+            });
+            PipelineData::RawStream(stream, metadata)
+        }
+        data => data,
+    }
+}
+
+struct Timed<I, F> {
+    iter: I,
+    timed: F,
+}
+
+impl<I: Iterator, F: FnMut(Duration)> Iterator for Timed<I, F> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let start_time = Instant::now();
+        let item = self.iter.next();
+        (self.timed)(start_time.elapsed());
+
+        item
+    }
+}
+
+/// Evaluate the element at `idx` of a pipeline, with the output of the element before it as input
+pub(crate) fn eval_pipeline_element(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    expressions: &[Expression],
+    idx: usize,
+    input: PipelineData,
+) -> Result<PipelineData, ShellError> {
+    match &expressions[idx] {
+        Expression {
+            expr: Expr::Call(call),
+            ..
+        } => eval_call(engine_state, stack, call, input),
+        Expression {
+            expr: Expr::ExternalCall(name, name_span, args, redirections, background),
+            ..
+        } => eval_external(
+            engine_state,
+            stack,
+            name,
+            name_span,
+            args,
+            redirections,
+            input,
+            idx == expressions.len() - 1,
+            redirect_stderr(engine_state, expressions, idx),
+            *background,
+        ),
+        elem => Ok(eval_expression(engine_state, stack, elem)?.into_pipeline_data()),
+    }
+}
+
+fn concurrent_pipelines(stack: &Stack) -> bool {
+    nu_protocol::concurrent_pipelines(stack.vars.get(&CONFIG_VARIABLE_ID))
+}
+
+// Every stage but the last runs with a copy of the stack when the stages run concurrently, so a
+// pipeline where one of those changes the environment runs its stages in turn instead
+fn can_run_concurrently(engine_state: &EngineState, expressions: &[Expression]) -> bool {
+    expressions[..expressions.len() - 1]
+        .iter()
+        .all(|expr| match &expr.expr {
+            Expr::Call(call) => !engine_state.get_decl(call.decl_id).changes_env(),
+            _ => true,
+        })
+}
+
+fn recursion_limit(stack: &Stack) -> i64 {
+    nu_protocol::recursion_limit(stack.vars.get(&CONFIG_VARIABLE_ID))
+}
+
 pub fn eval_subexpression(
     engine_state: &EngineState,
     stack: &mut Stack,
@@ -406,7 +627,17 @@ pub fn eval_subexpression(
 ) -> Result<PipelineData, ShellError> {
     for stmt in block.stmts.iter() {
         if let Statement::Pipeline(pipeline) = stmt {
-            for (i, elem) in pipeline.expressions.iter().enumerate() {
+            let mut start = 0;
+            if pipeline.expressions.len() > 1
+                && concurrent_pipelines(stack)
+                && can_run_concurrently(engine_state, &pipeline.expressions)
+            {
+                input =
+                    start_stages_concurrently(engine_state, stack, &pipeline.expressions, input)?;
+                start = pipeline.expressions.len() - 1;
+            }
+
+            for (i, elem) in pipeline.expressions.iter().enumerate().skip(start) {
                 match elem {
                     Expression {
                         expr: Expr::Call(call),
@@ -415,7 +646,7 @@ pub fn eval_subexpression(
                         input = eval_call(engine_state, stack, call, input)?;
                     }
                     Expression {
-                        expr: Expr::ExternalCall(name, name_span, args),
+                        expr: Expr::ExternalCall(name, name_span, args, redirections, background),
                         ..
                     } => {
                         input = eval_external(
@@ -424,26 +655,26 @@ pub fn eval_subexpression(
                             name,
                             name_span,
                             args,
+                            redirections,
                             input,
                             false,
+                            redirect_stderr(engine_state, &pipeline.expressions, i),
+                            *background,
                         )?;
 
-                        if i == pipeline.expressions.len() - 1 {
+                        // A job in the background has no output to drain, only its id
+                        if i == pipeline.expressions.len() - 1 && !*background {
                             // We're at the end, so drain as a string for the value
                             // to be used later
                             // FIXME: the trimming of the end probably needs to live in a better place
 
                             let config = stack.get_config().unwrap_or_default();
 
-                            let mut s = input.collect_string("", &config);
+                            let mut s = input.collect_string("", &config)?;
                             if s.ends_with('\n') {
                                 s.pop();
                             }
-                            input = Value::String {
-                                val: s.to_string(),
-                                span: *name_span,
-                            }
-                            .into_pipeline_data()
+                            input = Value::string(s.to_string(), *name_span).into_pipeline_data()
                         }
                     }
 
@@ -466,8 +697,7 @@ pub fn eval_variable(
 ) -> Result<Value, ShellError> {
     if var_id == nu_protocol::NU_VARIABLE_ID {
         // $nu
-        let mut output_cols = vec![];
-        let mut output_vals = vec![];
+        let mut output = Record::new();
 
         let env_vars = stack.get_env_vars();
         let env_columns = env_vars.keys();
@@ -480,14 +710,7 @@ pub fn eval_variable(
 
         pairs.sort_by(|a, b| a.0.cmp(&b.0));
 
-        let (env_columns, env_values) = pairs.into_iter().unzip();
-
-        output_cols.push("env".into());
-        output_vals.push(Value::Record {
-            cols: env_columns,
-            vals: env_values,
-            span,
-        });
+        output.insert("env", Value::record(pairs.into_iter().collect(), span));
 
         if let Some(mut config_path) = nu_path::config_dir() {
             config_path.push("nushell");
@@ -497,38 +720,31 @@ pub fn eval_variable(
 
             history_path.push("history.txt");
 
-            output_cols.push("history-path".into());
-            output_vals.push(Value::String {
-                val: history_path.to_string_lossy().to_string(),
-                span,
-            });
+            output.insert(
+                "history-path",
+                Value::string(history_path.to_string_lossy().to_string(), span),
+            );
 
             config_path.push("config.nu");
 
-            output_cols.push("config-path".into());
-            output_vals.push(Value::String {
-                val: config_path.to_string_lossy().to_string(),
-                span,
-            });
+            output.insert(
+                "config-path",
+                Value::string(config_path.to_string_lossy().to_string(), span),
+            );
 
             // TODO: keybindings don't exist yet but lets add a file
             // path for them to be stored in. It doesn't have to be yml.
             keybinding_path.push("keybindings.yml");
-            output_cols.push("keybinding-path".into());
-            output_vals.push(Value::String {
-                val: keybinding_path.to_string_lossy().to_string(),
-                span,
-            })
+            output.insert(
+                "keybinding-path",
+                Value::string(keybinding_path.to_string_lossy().to_string(), span),
+            );
         }
 
         #[cfg(feature = "plugin")]
         if let Some(path) = &engine_state.plugin_signatures {
             if let Some(path_str) = path.to_str() {
-                output_cols.push("plugin-path".into());
-                output_vals.push(Value::String {
-                    val: path_str.into(),
-                    span,
-                });
+                output.insert("plugin-path", Value::string(path_str, span));
             }
         }
 
@@ -536,41 +752,24 @@ pub fn eval_variable(
         // lets just get the current directory
         if let Ok(current_dir) = std::env::current_dir() {
             if let Some(cwd) = current_dir.to_str() {
-                output_cols.push("cwd".into());
-                output_vals.push(Value::String {
-                    val: cwd.into(),
-                    span,
-                })
+                output.insert("cwd", Value::string(cwd, span));
             }
         }
 
         if let Some(home_path) = nu_path::home_dir() {
             if let Some(home_path_str) = home_path.to_str() {
-                output_cols.push("home-path".into());
-                output_vals.push(Value::String {
-                    val: home_path_str.into(),
-                    span,
-                })
+                output.insert("home-path", Value::string(home_path_str, span));
             }
         }
 
         let temp = std::env::temp_dir();
         if let Some(temp_path) = temp.to_str() {
-            output_cols.push("temp-path".into());
-            output_vals.push(Value::String {
-                val: temp_path.into(),
-                span,
-            })
+            output.insert("temp-path", Value::string(temp_path, span));
         }
 
-        Ok(Value::Record {
-            cols: output_cols,
-            vals: output_vals,
-            span,
-        })
+        Ok(Value::record(output, span))
     } else if var_id == nu_protocol::SCOPE_VARIABLE_ID {
-        let mut output_cols = vec![];
-        let mut output_vals = vec![];
+        let mut output_record = Record::new();
 
         let mut var_names = vec![];
         let mut var_types = vec![];
@@ -587,34 +786,31 @@ pub fn eval_variable(
             }
 
             for command in &frame.decls {
-                let mut cols = vec![];
-                let mut vals = vec![];
+                let mut record = Record::new();
 
-                cols.push("command".into());
-                vals.push(Value::String {
-                    val: String::from_utf8_lossy(command.0).to_string(),
-                    span,
-                });
+                record.insert(
+                    "command",
+                    Value::string(String::from_utf8_lossy(command.0).to_string(), span),
+                );
 
                 let decl = engine_state.get_decl(*command.1);
                 let signature = decl.signature();
-                cols.push("category".to_string());
-                vals.push(Value::String {
-                    val: signature.category.to_string(),
-                    span,
-                });
+                record.insert(
+                    "category",
+                    Value::string(signature.category.to_string(), span),
+                );
 
                 // signature
                 let mut sig_records = vec![];
                 {
                     let sig_cols = vec![
-                        "command".to_string(),
-                        "parameter_name".to_string(),
-                        "parameter_type".to_string(),
-                        "syntax_shape".to_string(),
-                        "is_optional".to_string(),
-                        "short_flag".to_string(),
-                        "description".to_string(),
+                        "command",
+                        "parameter_name",
+                        "parameter_type",
+                        "syntax_shape",
+                        "is_optional",
+                        "short_flag",
+                        "description",
                     ];
 
                     // required_positional
@@ -629,11 +825,10 @@ pub fn eval_variable(
                             Value::string(req.desc, span),
                         ];
 
-                        sig_records.push(Value::Record {
-                            cols: sig_cols.clone(),
-                            vals: sig_vals,
+                        sig_records.push(Value::record(
+                            sig_cols.iter().copied().zip(sig_vals).collect(),
                             span,
-                        });
+                        ));
                     }
 
                     // optional_positional
@@ -648,11 +843,10 @@ pub fn eval_variable(
                             Value::string(opt.desc, span),
                         ];
 
-                        sig_records.push(Value::Record {
-                            cols: sig_cols.clone(),
-                            vals: sig_vals,
+                        sig_records.push(Value::record(
+                            sig_cols.iter().copied().zip(sig_vals).collect(),
                             span,
-                        });
+                        ));
                     }
 
                     {
@@ -668,11 +862,10 @@ pub fn eval_variable(
                                 Value::string(rest.desc, span),
                             ];
 
-                            sig_records.push(Value::Record {
-                                cols: sig_cols.clone(),
-                                vals: sig_vals,
+                            sig_records.push(Value::record(
+                                sig_cols.iter().copied().zip(sig_vals).collect(),
                                 span,
-                            });
+                            ));
                         }
                     }
 
@@ -709,105 +902,103 @@ pub fn eval_variable(
                             Value::string(named.desc, span),
                         ];
 
-                        sig_records.push(Value::Record {
-                            cols: sig_cols.clone(),
-                            vals: sig_vals,
+                        sig_records.push(Value::record(
+                            sig_cols.iter().copied().zip(sig_vals).collect(),
                             span,
-                        });
+                        ));
                     }
                 }
 
-                cols.push("signature".to_string());
-                vals.push(Value::List {
-                    vals: sig_records,
-                    span,
-                });
+                record.insert("signature", Value::list(sig_records, span));
 
-                cols.push("usage".to_string());
-                vals.push(Value::String {
-                    val: decl.usage().into(),
-                    span,
-                });
+                record.insert("usage", Value::string(decl.usage(), span));
 
-                cols.push("is_binary".to_string());
-                vals.push(Value::Bool {
-                    val: decl.is_binary(),
-                    span,
-                });
+                record.insert(
+                    "is_binary",
+                    Value::Bool {
+                        val: decl.is_binary(),
+                        span,
+                    },
+                );
 
-                cols.push("is_private".to_string());
-                vals.push(Value::Bool {
-                    val: decl.is_private(),
-                    span,
-                });
+                record.insert(
+                    "is_private",
+                    Value::Bool {
+                        val: decl.is_private(),
+                        span,
+                    },
+                );
 
-                cols.push("is_builtin".to_string());
-                vals.push(Value::Bool {
-                    val: decl.is_builtin(),
-                    span,
-                });
+                record.insert(
+                    "is_builtin",
+                    Value::Bool {
+                        val: decl.is_builtin(),
+                        span,
+                    },
+                );
 
-                cols.push("is_sub".to_string());
-                vals.push(Value::Bool {
-                    val: decl.is_sub(),
-                    span,
-                });
+                record.insert(
+                    "is_sub",
+                    Value::Bool {
+                        val: decl.is_sub(),
+                        span,
+                    },
+                );
 
-                cols.push("is_plugin".to_string());
-                vals.push(Value::Bool {
-                    val: decl.is_plugin().is_some(),
-                    span,
-                });
+                record.insert(
+                    "is_plugin",
+                    Value::Bool {
+                        val: decl.is_plugin().is_some(),
+                        span,
+                    },
+                );
 
-                cols.push("is_custom".to_string());
-                vals.push(Value::Bool {
-                    val: decl.get_block_id().is_some(),
-                    span,
-                });
+                record.insert(
+                    "is_custom",
+                    Value::Bool {
+                        val: decl.get_block_id().is_some(),
+                        span,
+                    },
+                );
 
-                cols.push("creates_scope".to_string());
-                vals.push(Value::Bool {
-                    val: signature.creates_scope,
-                    span,
-                });
+                record.insert(
+                    "creates_scope",
+                    Value::Bool {
+                        val: signature.creates_scope,
+                        span,
+                    },
+                );
 
-                cols.push("extra_usage".to_string());
-                vals.push(Value::String {
-                    val: decl.extra_usage().into(),
-                    span,
-                });
+                record.insert("extra_usage", Value::string(decl.extra_usage(), span));
 
-                commands.push(Value::Record { cols, vals, span })
+                commands.push(Value::record(record, span))
             }
 
             for alias in &frame.aliases {
-                aliases.push(Value::String {
-                    val: String::from_utf8_lossy(alias.0).to_string(),
+                aliases.push(Value::string(
+                    String::from_utf8_lossy(alias.0).to_string(),
                     span,
-                });
+                ));
             }
 
             for overlay in &frame.overlays {
-                overlays.push(Value::String {
-                    val: String::from_utf8_lossy(overlay.0).to_string(),
+                overlays.push(Value::string(
+                    String::from_utf8_lossy(overlay.0).to_string(),
                     span,
-                });
+                ));
             }
         }
 
-        output_cols.push("vars".to_string());
-        output_vals.push(Value::Record {
-            cols: var_names,
-            vals: var_types,
-            span,
-        });
+        output_record.insert(
+            "vars",
+            Value::record(var_names.into_iter().zip(var_types).collect(), span),
+        );
 
         commands.sort_by(|a, b| match (a, b) {
-            (Value::Record { vals: rec_a, .. }, Value::Record { vals: rec_b, .. }) => {
-                // Comparing the first value from the record
-                // It is expected that the first value is the name of the column
+            (Value::Record { val: rec_a, .. }, Value::Record { val: rec_b, .. }) => {
+                // Comparing the command names of the records
                 // The names of the commands should be a value string
-                match (rec_a.get(0), rec_b.get(0)) {
+                match (rec_a.get("command"), rec_b.get("command")) {
                     (Some(val_a), Some(val_b)) => match (val_a, val_b) {
                         (Value::String { val: str_a, .. }, Value::String { val: str_b, .. }) => {
                             str_a.cmp(str_b)
@@ -819,31 +1010,15 @@ pub fn eval_variable(
             }
             _ => Ordering::Equal,
         });
-        output_cols.push("commands".to_string());
-        output_vals.push(Value::List {
-            vals: commands,
-            span,
-        });
+        output_record.insert("commands", Value::list(commands, span));
 
         aliases.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
-        output_cols.push("aliases".to_string());
-        output_vals.push(Value::List {
-            vals: aliases,
-            span,
-        });
+        output_record.insert("aliases", Value::list(aliases, span));
 
         overlays.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
-        output_cols.push("overlays".to_string());
-        output_vals.push(Value::List {
-            vals: overlays,
-            span,
-        });
+        output_record.insert("overlays", Value::list(overlays, span));
 
-        Ok(Value::Record {
-            cols: output_cols,
-            vals: output_vals,
-            span,
-        })
+        Ok(Value::record(output_record, span))
     } else {
         stack
             .get_var(var_id)
@@ -851,7 +1026,7 @@ pub fn eval_variable(
     }
 }
 
-fn compute(size: i64, unit: Unit, span: Span) -> Value {
+pub(crate) fn compute(size: i64, unit: Unit, span: Span) -> Value {
     match unit {
         Unit::Byte => Value::Filesize { val: size, span },
         Unit::Kilobyte => Value::Filesize {
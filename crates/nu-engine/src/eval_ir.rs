@@ -0,0 +1,268 @@
+use nu_protocol::ast::Expr;
+use nu_protocol::engine::{EngineState, Stack};
+use nu_protocol::ir::{Instruction, IrBlock, Operand, RegId, SlotId, INPUT_REGISTER};
+use nu_protocol::{IntoPipelineData, PipelineData, Range, Record, ShellError, Span, Value, VarId};
+
+use crate::eval::{eval_binary_op, eval_call, eval_expression, eval_external, eval_variable};
+
+/// Evaluate a block lowered to IR. This gives the same results as `eval_block` on the AST.
+///
+/// Variables live in the slots of the block's frame, and only the ones that the AST evaluator
+/// reads as well are added to the stack. The stages of a pipeline run one after another here, even
+/// with `concurrent_pipelines` on
+pub fn eval_ir_block(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    block: &IrBlock,
+    input: PipelineData,
+) -> Result<PipelineData, ShellError> {
+    let mut registers = Registers {
+        registers: (0..block.register_count).map(|_| None).collect(),
+    };
+    registers.put(INPUT_REGISTER, input);
+    let mut slots = Slots {
+        vars: &block.slots,
+        slots: block.slots.iter().map(|_| None).collect(),
+    };
+
+    for instruction in block.instructions.iter() {
+        match instruction {
+            Instruction::LoadLiteral { dst, value } => {
+                registers.put(*dst, value.clone().into_pipeline_data());
+            }
+            Instruction::LoadVariable { dst, var_id, span } => {
+                let value = eval_variable(engine_state, stack, *var_id, *span)?;
+                registers.put(*dst, value.into_pipeline_data());
+            }
+            Instruction::LoadSlot { dst, slot, span } => {
+                let value = slots.get(stack, *slot, *span)?.clone();
+                registers.put(*dst, value.into_pipeline_data());
+            }
+            Instruction::StoreSlot {
+                dst,
+                slot,
+                src,
+                on_stack,
+                span,
+            } => {
+                let value = registers.take_value(*src, *span)?;
+                if *on_stack {
+                    stack.add_var(block.slots[*slot], value.clone());
+                }
+                slots.slots[*slot] = Some(value);
+
+                registers.put(*dst, PipelineData::new(*span));
+            }
+            Instruction::BinaryOp {
+                dst,
+                lhs,
+                op,
+                op_span,
+                rhs,
+            } => {
+                let lhs_value;
+                let lhs = match lhs {
+                    Operand::Register(reg) => {
+                        lhs_value = registers.take_value(*reg, *op_span)?;
+                        &lhs_value
+                    }
+                    Operand::Slot(slot, span) => slots.get(stack, *slot, *span)?,
+                    Operand::Literal(value) => value,
+                };
+
+                let rhs_value;
+                let rhs = match rhs {
+                    Operand::Register(reg) => {
+                        rhs_value = registers.take_value(*reg, *op_span)?;
+                        &rhs_value
+                    }
+                    Operand::Slot(slot, span) => slots.get(stack, *slot, *span)?,
+                    Operand::Literal(value) => value,
+                };
+
+                let value = eval_binary_op(lhs, op.clone(), *op_span, rhs)?;
+                registers.put(*dst, value.into_pipeline_data());
+            }
+            Instruction::FollowCellPath { dst, src, path } => {
+                let value = registers
+                    .take_value(*src, Span::new(0, 0))?
+                    .follow_cell_path(path)?;
+                registers.put(*dst, value.into_pipeline_data());
+            }
+            Instruction::BuildList { dst, items, span } => {
+                let mut vals = vec![];
+                for item in items {
+                    vals.push(registers.take_value(*item, *span)?);
+                }
+
+                registers.put(*dst, Value::list(vals, *span).into_pipeline_data());
+            }
+            Instruction::BuildRecord {
+                dst,
+                cols,
+                vals,
+                span,
+            } => {
+                let mut record = Record::new();
+                for (col, val) in cols.iter().zip(vals) {
+                    let col = registers.take_value(*col, *span)?;
+                    let col_span = col.span()?;
+                    let val = registers.take_value(*val, *span)?;
+                    record.try_insert(col.as_string()?, val, col_span)?;
+                }
+
+                registers.put(*dst, Value::record(record, *span).into_pipeline_data());
+            }
+            Instruction::BuildTable {
+                dst,
+                headers,
+                rows,
+                span,
+            } => {
+                let mut names = vec![];
+                for (header, header_span) in headers {
+                    let name = registers.take_value(*header, *span)?.as_string()?;
+                    if names.contains(&name) {
+                        return Err(ShellError::ColumnDefinedTwice(name, *header_span));
+                    }
+                    names.push(name);
+                }
+
+                let mut vals = vec![];
+                for row in rows {
+                    let mut record = Record::with_capacity(names.len());
+                    for (name, cell) in names.iter().zip(row) {
+                        record.insert(name.clone(), registers.take_value(*cell, *span)?);
+                    }
+                    vals.push(Value::record(record, *span));
+                }
+
+                registers.put(*dst, Value::list(vals, *span).into_pipeline_data());
+            }
+            Instruction::BuildRange {
+                dst,
+                from,
+                next,
+                to,
+                operator,
+                span,
+            } => {
+                let mut bound = |reg: &Option<RegId>| match reg {
+                    Some(reg) => registers.take_value(*reg, *span),
+                    None => Ok(Value::Nothing { span: *span }),
+                };
+                let from = bound(from)?;
+                let next = bound(next)?;
+                let to = bound(to)?;
+
+                let value = Value::Range {
+                    val: Box::new(Range::new(*span, from, next, to, operator)?),
+                    span: *span,
+                };
+                registers.put(*dst, value.into_pipeline_data());
+            }
+            Instruction::Collect { dst, src, span } => {
+                let value = registers.take_value(*src, *span)?;
+                registers.put(*dst, value.into_pipeline_data());
+            }
+            Instruction::Call { dst, input, call } => {
+                let input = registers.take(*input)?;
+                let output = eval_call(engine_state, stack, call, input)?;
+                registers.put(*dst, output);
+            }
+            Instruction::External {
+                dst,
+                input,
+                expr,
+                last_expression,
+                redirect_stderr,
+            } => {
+                let (name, name_span, args, redirections, background) = match &expr.expr {
+                    Expr::ExternalCall(name, name_span, args, redirections, background) => {
+                        (name, name_span, args, redirections, background)
+                    }
+                    _ => {
+                        return Err(ShellError::NushellFailed(
+                            "external instruction without an external".into(),
+                        ))
+                    }
+                };
+
+                let input = registers.take(*input)?;
+                let output = eval_external(
+                    engine_state,
+                    stack,
+                    name,
+                    name_span,
+                    args,
+                    redirections,
+                    input,
+                    *last_expression,
+                    *redirect_stderr,
+                    *background,
+                )?;
+                registers.put(*dst, output);
+            }
+            Instruction::Eval { dst, expr } => {
+                let value = eval_expression(engine_state, stack, expr)?;
+                registers.put(*dst, value.into_pipeline_data());
+            }
+        }
+    }
+
+    registers.take(block.output)
+}
+
+struct Slots<'a> {
+    vars: &'a [VarId],
+    slots: Vec<Option<Value>>,
+}
+
+impl Slots<'_> {
+    // A variable that isn't declared in the block, like a parameter, is read from the stack where
+    // the caller put it, without taking a copy
+    fn get<'s>(
+        &'s self,
+        stack: &'s Stack,
+        slot: SlotId,
+        span: Span,
+    ) -> Result<&'s Value, ShellError> {
+        match &self.slots[slot] {
+            Some(value) => Ok(value),
+            None => stack
+                .vars
+                .get(&self.vars[slot])
+                .ok_or(ShellError::VariableNotFoundAtRuntime(span)),
+        }
+    }
+}
+
+struct Registers {
+    registers: Vec<Option<PipelineData>>,
+}
+
+impl Registers {
+    fn put(&mut self, reg: RegId, data: PipelineData) {
+        self.registers[reg] = Some(data);
+    }
+
+    // Each register is read once, so its contents are moved out rather than cloned
+    fn take(&mut self, reg: RegId) -> Result<PipelineData, ShellError> {
+        self.registers[reg]
+            .take()
+            .ok_or_else(|| unwritten_register(reg))
+    }
+
+    // Values keep their own span, which `into_value` would replace for nothing
+    fn take_value(&mut self, reg: RegId, span: Span) -> Result<Value, ShellError> {
+        match self.registers[reg].take() {
+            Some(PipelineData::Value(value, ..)) => Ok(value),
+            Some(data) => Ok(data.into_value(span)),
+            None => Err(unwritten_register(reg)),
+        }
+    }
+}
+
+fn unwritten_register(reg: RegId) -> ShellError {
+    ShellError::NushellFailed(format!("register {} read before it was written", reg))
+}
@@ -1,9 +1,16 @@
 mod call_ext;
+mod compile;
+mod concurrent;
 mod documentation;
 mod env;
 mod eval;
+mod eval_ir;
+mod permissions;
 
 pub use call_ext::CallExt;
+pub use compile::{compile_block, compile_blocks};
 pub use documentation::{generate_docs, get_brief_help, get_documentation, get_full_help};
 pub use env::*;
 pub use eval::{eval_block, eval_expression, eval_operator};
+pub use eval_ir::eval_ir_block;
+pub use permissions::check_path;
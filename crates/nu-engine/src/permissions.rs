@@ -0,0 +1,232 @@
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+
+use nu_protocol::ast::{Call, Expr, Expression};
+use nu_protocol::engine::{Capability, Command, EngineState, Permissions, Stack};
+use nu_protocol::{ShellError, Span, SyntaxShape, Type};
+
+use crate::eval_expression;
+
+/// Fail with `ShellError::NotAllowed` if the engine's permissions don't cover what the command
+/// declares it does. The paths given to filesystem commands are checked against the roots, or
+/// the current directory when they take paths but aren't given any
+///
+/// Returns the call to run the command with. A path that isn't a literal has to be evaluated to
+/// be checked, so it's replaced in the call by the value checked, which keeps it from being
+/// evaluated a second time by the command, and maybe to a different path.
+pub(crate) fn check_permissions<'call>(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &'call Call,
+    decl: &dyn Command,
+) -> Result<Cow<'call, Call>, ShellError> {
+    let permissions = &engine_state.permissions;
+    let mut call = Cow::Borrowed(call);
+    let mut paths = None;
+
+    for capability in decl.capabilities() {
+        if !permissions.allows(capability) {
+            return Err(ShellError::NotAllowed {
+                command: decl.name().to_string(),
+                capability: capability.to_string(),
+                reason: format!("{} isn't allowed here", capability),
+                span: call.head,
+            });
+        }
+
+        if !limits_paths(permissions, capability) {
+            continue;
+        }
+
+        let paths = match &mut paths {
+            Some(paths) => paths,
+            None => {
+                // A command that takes paths but wasn't given any works in the current directory.
+                // One that doesn't take any checks the paths it gets some other way itself
+                let mut arguments = path_arguments(engine_state, stack, &mut call, decl)?;
+                if arguments.is_empty() && takes_paths(decl) {
+                    let cwd = std::env::current_dir().unwrap_or_default();
+                    arguments.push((cwd.to_string_lossy().to_string(), call.head));
+                }
+                paths.insert(arguments)
+            }
+        };
+
+        for (path, span) in paths.iter() {
+            check_path(permissions, decl.name(), capability, path, *span)?;
+        }
+    }
+
+    Ok(call)
+}
+
+/// Fail with `ShellError::NotAllowed` if the permissions don't let the command use the path with
+/// the capability
+///
+/// The path and glob arguments of a command are checked before it runs. Commands call this for
+/// the paths they get any other way, like from their input or from a string that can be a name
+/// as well as a path.
+pub fn check_path(
+    permissions: &Permissions,
+    command: &str,
+    capability: Capability,
+    path: &str,
+    span: Span,
+) -> Result<(), ShellError> {
+    let allowed = if limits_paths(permissions, capability) {
+        permissions.allows_path(capability, &resolve(path))
+    } else {
+        permissions.allows(capability)
+    };
+
+    if allowed {
+        Ok(())
+    } else {
+        Err(ShellError::NotAllowed {
+            command: command.to_string(),
+            capability: capability.to_string(),
+            reason: format!("{} is outside of the allowed directories", path),
+            span,
+        })
+    }
+}
+
+fn limits_paths(permissions: &Permissions, capability: Capability) -> bool {
+    match capability {
+        Capability::FilesystemRead => permissions.read_roots.is_some(),
+        Capability::FilesystemWrite => permissions.write_roots.is_some(),
+        _ => false,
+    }
+}
+
+// The arguments given for the path and glob parameters and flags of the command, as strings.
+// Those that aren't literals are evaluated, and replaced in the call by what they evaluated to
+fn path_arguments(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    call: &mut Cow<Call>,
+    decl: &dyn Command,
+) -> Result<Vec<(String, Span)>, ShellError> {
+    let signature = decl.signature();
+    let shapes = signature
+        .required_positional
+        .iter()
+        .chain(signature.optional_positional.iter())
+        .map(|param| &param.shape)
+        .collect::<Vec<_>>();
+    let rest_shape = signature.rest_positional.as_ref().map(|param| &param.shape);
+
+    let mut paths = vec![];
+    for idx in 0..call.positional.len() {
+        let shape = shapes.get(idx).copied().or(rest_shape);
+        if !is_path_shape(shape) {
+            continue;
+        }
+
+        let (path, evaluated) = path_argument(engine_state, stack, &call.positional[idx])?;
+        paths.push((path, call.positional[idx].span));
+        if let Some(evaluated) = evaluated {
+            call.to_mut().positional[idx] = evaluated;
+        }
+    }
+
+    for idx in 0..call.named.len() {
+        let (name, arg) = &call.named[idx];
+        let shape = signature
+            .named
+            .iter()
+            .find(|flag| flag.long == name.item)
+            .and_then(|flag| flag.arg.as_ref());
+        let arg = match arg {
+            Some(arg) if is_path_shape(shape) => arg,
+            _ => continue,
+        };
+
+        let span = arg.span;
+        let (path, evaluated) = path_argument(engine_state, stack, arg)?;
+        paths.push((path, span));
+        if let Some(evaluated) = evaluated {
+            call.to_mut().named[idx].1 = Some(evaluated);
+        }
+    }
+
+    Ok(paths)
+}
+
+fn takes_paths(decl: &dyn Command) -> bool {
+    let signature = decl.signature();
+
+    signature
+        .required_positional
+        .iter()
+        .chain(signature.optional_positional.iter())
+        .chain(signature.rest_positional.iter())
+        .map(|param| Some(&param.shape))
+        .chain(signature.named.iter().map(|flag| flag.arg.as_ref()))
+        .any(is_path_shape)
+}
+
+fn is_path_shape(shape: Option<&SyntaxShape>) -> bool {
+    matches!(
+        shape,
+        Some(SyntaxShape::Filepath) | Some(SyntaxShape::GlobPattern)
+    )
+}
+
+// The path an argument gives, and what to replace the argument with when it had to be evaluated
+// to get there
+fn path_argument(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    arg: &Expression,
+) -> Result<(String, Option<Expression>), ShellError> {
+    match &arg.expr {
+        Expr::Filepath(path) | Expr::GlobPattern(path) | Expr::String(path) => {
+            Ok((path.clone(), None))
+        }
+        _ => {
+            let path = eval_expression(engine_state, stack, arg)?.as_string()?;
+            let evaluated = Expression {
+                expr: Expr::String(path.clone()),
+                span: arg.span,
+                ty: Type::String,
+                custom_completion: None,
+            };
+            Ok((path, Some(evaluated)))
+        }
+    }
+}
+
+// The absolute path with its dots and symlinks resolved, as far as it exists. Only the directories
+// before the first glob in a pattern are kept, as the files it matches are under them
+fn resolve(path: &str) -> PathBuf {
+    let path = Path::new(path)
+        .components()
+        .take_while(|component| {
+            let component = component.as_os_str().to_string_lossy();
+            !component.contains(&['*', '?', '['][..])
+        })
+        .collect::<PathBuf>();
+
+    let cwd = std::env::current_dir().unwrap_or_default();
+    let path = nu_path::expand_path_with(path, cwd);
+
+    let mut existing = path.as_path();
+    let mut missing = vec![];
+    loop {
+        if let Ok(resolved) = nu_path::canonicalize(existing) {
+            return missing
+                .iter()
+                .rev()
+                .fold(resolved, |resolved, name| resolved.join(name));
+        }
+
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                missing.push(name);
+                existing = parent;
+            }
+            _ => return path.clone(),
+        }
+    }
+}
@@ -417,7 +417,10 @@ impl<'de> de::Deserialize<'de> for Value {
                 A: de::MapAccess<'de>,
             {
                 let mut values = map_with_capacity(map.size_hint());
-                while let Some((k, v)) = map.next_entry()? {
+                while let Some((k, v)) = map.next_entry::<String, Value>()? {
+                    if values.contains_key(&k) {
+                        return Err(de::Error::custom(format!("duplicate key `{}`", k)));
+                    }
                     values.insert(k, v);
                 }
                 Ok(Value::Object(values))
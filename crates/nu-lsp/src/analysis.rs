@@ -0,0 +1,220 @@
+use miette::Diagnostic;
+use nu_engine::get_full_help;
+use nu_parser::{parse, visit_expressions};
+use nu_protocol::{
+    ast::{Block, Expr},
+    engine::{EngineState, StateWorkingSet},
+    BlockId, DeclId, Signature, Span, VarId,
+};
+
+/// An error or warning the parser has about a script, with its span counted from the start of
+/// the script
+pub struct Problem {
+    pub span: Span,
+    pub message: String,
+    pub code: Option<String>,
+    pub is_warning: bool,
+}
+
+/// The errors and warnings from parsing the script
+pub fn problems(engine_state: &EngineState, text: &str) -> Vec<Problem> {
+    let mut working_set = StateWorkingSet::new(engine_state);
+    let (start, _, error) = parse_text(&mut working_set, text);
+
+    let mut problems = vec![];
+    if let Some(error) = &error {
+        problems.push(problem(error, start, text.len(), false));
+    }
+    for warning in working_set.warnings() {
+        problems.push(problem(warning, start, text.len(), true));
+    }
+
+    problems
+}
+
+/// The help of the command called at `offset`, and the span of its name
+pub fn hover(engine_state: &EngineState, text: &str, offset: usize) -> Option<(String, Span)> {
+    let mut working_set = StateWorkingSet::new(engine_state);
+    let (start, block, _) = parse_text(&mut working_set, text);
+    let offset = start + offset;
+
+    let mut found = None;
+    visit_expressions(&working_set, &block, &mut |expr| {
+        if let Expr::Call(call) = &expr.expr {
+            if contains(call.head, offset) {
+                found = Some((call.decl_id, call.head));
+            }
+        }
+    });
+
+    let (decl_id, head) = found?;
+    let decl = working_set.get_decl(decl_id);
+    let mut signature = decl.signature();
+    signature.usage = decl.usage().to_string();
+    signature.extra_usage = decl.extra_usage().to_string();
+    let help = get_full_help(&signature, &decl.examples(), engine_state);
+
+    Some((help, relative(head, start, text.len())?))
+}
+
+/// Where the variable or custom command at `offset` is declared in the script
+pub fn definition(engine_state: &EngineState, text: &str, offset: usize) -> Option<Span> {
+    let mut working_set = StateWorkingSet::new(engine_state);
+    let (start, block, _) = parse_text(&mut working_set, text);
+    let offset = start + offset;
+
+    let mut target = None;
+    visit_expressions(&working_set, &block, &mut |expr| match &expr.expr {
+        Expr::Var(var_id) if contains(expr.span, offset) => target = Some(Target::Var(*var_id)),
+        Expr::Call(call) if contains(call.head, offset) => {
+            target = Some(Target::Decl(call.decl_id))
+        }
+        _ => {}
+    });
+
+    let span = match target? {
+        Target::Var(var_id) => var_declaration(&working_set, &block, var_id),
+        Target::Decl(decl_id) => {
+            let block_id = working_set.get_decl(decl_id).get_block_id()?;
+            def_name(&working_set, &block, block_id)
+        }
+    }?;
+
+    relative(span, start, text.len())
+}
+
+enum Target {
+    Var(VarId),
+    Decl(DeclId),
+}
+
+// Parse the script into the working set, returning where its spans start
+fn parse_text(
+    working_set: &mut StateWorkingSet,
+    text: &str,
+) -> (usize, Block, Option<nu_parser::ParseError>) {
+    let start = working_set.next_span_start();
+    let (block, error) = parse(working_set, Some("source"), text.as_bytes(), false);
+
+    (start, block, error)
+}
+
+fn problem(diagnostic: &dyn Diagnostic, start: usize, len: usize, is_warning: bool) -> Problem {
+    let span = diagnostic
+        .labels()
+        .and_then(|mut labels| labels.next())
+        .map(|label| {
+            let from = label.offset().saturating_sub(start).min(len);
+            Span::new(from, (from + label.len()).min(len))
+        })
+        .unwrap_or_else(|| Span::new(0, 0));
+
+    let mut message = diagnostic.to_string();
+    if let Some(help) = diagnostic.help() {
+        message.push('\n');
+        message.push_str(&help.to_string());
+    }
+
+    Problem {
+        span,
+        message,
+        code: diagnostic.code().map(|code| code.to_string()),
+        is_warning,
+    }
+}
+
+// The span where the variable gets its name: the `let` or `for` that declares it, or the
+// parameter of the signature or block it's from
+fn var_declaration(working_set: &StateWorkingSet, block: &Block, var_id: VarId) -> Option<Span> {
+    let mut found = None;
+    visit_expressions(working_set, block, &mut |expr| {
+        if found.is_some() {
+            return;
+        }
+        found = match &expr.expr {
+            Expr::VarDecl(id) if *id == var_id => Some(expr.span),
+            Expr::Signature(signature) => param_span(working_set, expr.span, signature, var_id),
+            Expr::Block(block_id) => {
+                let signature = &working_set.get_block(*block_id).signature;
+                param_span(working_set, expr.span, signature, var_id)
+            }
+            _ => None,
+        };
+    });
+
+    found
+}
+
+// Parameters don't keep their spans, so they're found by name in the code they're declared in
+fn param_span(
+    working_set: &StateWorkingSet,
+    span: Span,
+    signature: &Signature,
+    var_id: VarId,
+) -> Option<Span> {
+    let positional = signature
+        .required_positional
+        .iter()
+        .chain(signature.optional_positional.iter())
+        .chain(signature.rest_positional.iter())
+        .find(|arg| arg.var_id == Some(var_id))
+        .map(|arg| (arg.name.clone(), 0));
+    let flag = || {
+        signature
+            .named
+            .iter()
+            .find(|flag| flag.var_id == Some(var_id))
+            .map(|flag| (format!("--{}", flag.long), 2))
+    };
+    let (name, prefix) = positional.or_else(flag)?;
+
+    let idx = find_word(working_set.get_span_contents(span), name.as_bytes())?;
+    Some(Span::new(
+        span.start + idx + prefix,
+        span.start + idx + name.len(),
+    ))
+}
+
+// The span of the name in the `def` of the custom command that runs the block
+fn def_name(working_set: &StateWorkingSet, block: &Block, block_id: BlockId) -> Option<Span> {
+    let mut found = None;
+    visit_expressions(working_set, block, &mut |expr| match &expr.expr {
+        Expr::Call(call) if working_set.get_decl(call.decl_id).name() == "def" => {
+            if let (Some(name), Some(body)) = (call.positional.first(), call.positional.get(2)) {
+                if body.as_block() == Some(block_id) {
+                    found = Some(name.span);
+                }
+            }
+        }
+        _ => {}
+    });
+
+    found
+}
+
+// The first place `word` is on its own, and not part of a longer name or a variable
+fn find_word(contents: &[u8], word: &[u8]) -> Option<usize> {
+    let is_name = |c: &u8| c.is_ascii_alphanumeric() || matches!(c, b'_' | b'-' | b'$');
+
+    (0..(contents.len() + 1).saturating_sub(word.len())).find(|idx| {
+        contents[*idx..].starts_with(word)
+            && !idx
+                .checked_sub(1)
+                .and_then(|before| contents.get(before))
+                .map(is_name)
+                .unwrap_or(false)
+            && !contents.get(idx + word.len()).map(is_name).unwrap_or(false)
+    })
+}
+
+fn contains(span: Span, offset: usize) -> bool {
+    span.start <= offset && offset < span.end
+}
+
+fn relative(span: Span, start: usize, len: usize) -> Option<Span> {
+    if span.start >= start && span.end <= start + len {
+        Some(Span::new(span.start - start, span.end - start))
+    } else {
+        None
+    }
+}
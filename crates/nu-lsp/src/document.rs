@@ -0,0 +1,62 @@
+use nu_protocol::Span;
+use serde_json::{json, Value};
+
+/// The text of a script open in the editor
+///
+/// Positions in the protocol are a line and a column counted in UTF-16 code units, while spans
+/// count bytes, so this converts between the two.
+pub struct Document {
+    pub text: String,
+}
+
+impl Document {
+    pub fn new(text: String) -> Self {
+        Self { text }
+    }
+
+    /// The byte offset of a position. Columns past the end of the line are at its end, and lines
+    /// past the end of the text at the end of the text
+    pub fn offset(&self, line: usize, character: usize) -> usize {
+        let mut line_start = 0;
+        for _ in 0..line {
+            match self.text[line_start..].find('\n') {
+                Some(idx) => line_start += idx + 1,
+                None => return self.text.len(),
+            }
+        }
+
+        let mut offset = line_start;
+        let mut column = 0;
+        for c in self.text[line_start..].chars() {
+            if column >= character || c == '\n' || c == '\r' {
+                break;
+            }
+            column += c.len_utf16();
+            offset += c.len_utf8();
+        }
+
+        offset
+    }
+
+    pub fn position(&self, offset: usize) -> Value {
+        let mut offset = offset.min(self.text.len());
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let before = &self.text[..offset];
+        let line = before.matches('\n').count();
+        let line_start = before.rfind('\n').map(|idx| idx + 1).unwrap_or(0);
+        let character = before[line_start..].encode_utf16().count();
+
+        json!({ "line": line, "character": character })
+    }
+
+    /// The range of a span of the text, counted from its start
+    pub fn range(&self, span: Span) -> Value {
+        json!({
+            "start": self.position(span.start),
+            "end": self.position(span.end),
+        })
+    }
+}
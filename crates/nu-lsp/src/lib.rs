@@ -0,0 +1,6 @@
+mod analysis;
+mod document;
+mod server;
+mod transport;
+
+pub use server::LanguageServer;
@@ -0,0 +1,289 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+
+use nu_cli::NuCompleter;
+use nu_protocol::{engine::EngineState, Span};
+use reedline::Completer;
+use serde_json::{json, Value};
+
+use crate::analysis;
+use crate::document::Document;
+use crate::transport::{read_message, write_message};
+
+// Error codes from the JSON-RPC specification
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+// How the client sends changes to documents: all of the text each time
+const FULL_SYNC: u64 = 1;
+
+const ERROR_SEVERITY: u64 = 1;
+const WARNING_SEVERITY: u64 = 2;
+
+/// A server for the Language Server Protocol, which lets an editor show the problems in nu
+/// scripts as they're written, and the help, definitions and completions of what's in them
+///
+/// The commands and variables it knows about, besides the ones in the scripts themselves, are the
+/// ones of the engine state it starts with.
+pub struct LanguageServer {
+    engine_state: EngineState,
+    completer: NuCompleter,
+    documents: HashMap<String, Document>,
+    shutting_down: bool,
+}
+
+struct ResponseError {
+    code: i64,
+    message: String,
+}
+
+impl ResponseError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl LanguageServer {
+    pub fn new(engine_state: EngineState) -> Self {
+        Self {
+            completer: NuCompleter::new(engine_state.clone()),
+            engine_state,
+            documents: HashMap::new(),
+            shutting_down: false,
+        }
+    }
+
+    /// Answer the messages from `input` on `output`, until the client sends `exit` or the input
+    /// ends. Returns whether the client asked the server to shut down before that, which the
+    /// protocol has the exit code say
+    pub fn run(&mut self, mut input: impl BufRead, mut output: impl Write) -> io::Result<bool> {
+        while let Some(body) = read_message(&mut input)? {
+            let message = match serde_json::from_slice::<Value>(&body) {
+                Ok(message) => message,
+                Err(err) => {
+                    let error = ResponseError::new(PARSE_ERROR, err.to_string());
+                    write_message(&mut output, &response(&Value::Null, Err(error)))?;
+                    continue;
+                }
+            };
+
+            if message.get("method").and_then(Value::as_str) == Some("exit") {
+                return Ok(self.shutting_down);
+            }
+
+            for reply in self.handle(&message) {
+                write_message(&mut output, &reply)?;
+            }
+        }
+
+        Ok(false)
+    }
+
+    // The response to a request, or the notifications to send about a notification
+    fn handle(&mut self, message: &Value) -> Vec<Value> {
+        // Without a method it's a response, and the server doesn't send requests to get any
+        let method = match message.get("method").and_then(Value::as_str) {
+            Some(method) => method,
+            None => return vec![],
+        };
+        let params = message.get("params").unwrap_or(&Value::Null);
+
+        match message.get("id") {
+            Some(id) if self.shutting_down => vec![response(
+                id,
+                Err(ResponseError::new(
+                    INVALID_REQUEST,
+                    "The server is shutting down",
+                )),
+            )],
+            Some(id) => vec![response(id, self.request(method, params))],
+            None => self.notification(method, params),
+        }
+    }
+
+    fn request(&mut self, method: &str, params: &Value) -> Result<Value, ResponseError> {
+        match method {
+            "initialize" => Ok(json!({
+                "capabilities": {
+                    "textDocumentSync": FULL_SYNC,
+                    "hoverProvider": true,
+                    "definitionProvider": true,
+                    "completionProvider": {},
+                },
+                "serverInfo": {
+                    "name": "nu",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+            })),
+            "shutdown" => {
+                self.shutting_down = true;
+                Ok(Value::Null)
+            }
+            "textDocument/hover" => self.hover(params),
+            "textDocument/definition" => self.definition(params),
+            "textDocument/completion" => self.completion(params),
+            _ => Err(ResponseError::new(
+                METHOD_NOT_FOUND,
+                format!("Unknown method: {}", method),
+            )),
+        }
+    }
+
+    fn notification(&mut self, method: &str, params: &Value) -> Vec<Value> {
+        let uri = match params.pointer("/textDocument/uri").and_then(Value::as_str) {
+            Some(uri) => uri.to_string(),
+            None => return vec![],
+        };
+
+        let text = match method {
+            "textDocument/didOpen" => params.pointer("/textDocument/text"),
+            "textDocument/didChange" => params
+                .get("contentChanges")
+                .and_then(Value::as_array)
+                .and_then(|changes| changes.last())
+                .and_then(|change| change.get("text")),
+            "textDocument/didClose" => {
+                self.documents.remove(&uri);
+                return vec![publish_diagnostics(&uri, vec![])];
+            }
+            _ => None,
+        };
+
+        match text.and_then(Value::as_str) {
+            Some(text) => {
+                let document = Document::new(text.to_string());
+                let diagnostics = self.diagnostics(&document);
+                self.documents.insert(uri.clone(), document);
+
+                vec![publish_diagnostics(&uri, diagnostics)]
+            }
+            None => vec![],
+        }
+    }
+
+    fn diagnostics(&self, document: &Document) -> Vec<Value> {
+        analysis::problems(&self.engine_state, &document.text)
+            .into_iter()
+            .map(|problem| {
+                let severity = if problem.is_warning {
+                    WARNING_SEVERITY
+                } else {
+                    ERROR_SEVERITY
+                };
+
+                json!({
+                    "range": document.range(problem.span),
+                    "severity": severity,
+                    "code": problem.code,
+                    "source": "nu",
+                    "message": problem.message,
+                })
+            })
+            .collect()
+    }
+
+    fn hover(&self, params: &Value) -> Result<Value, ResponseError> {
+        let (_, document, offset) = match self.document_position(params)? {
+            Some(found) => found,
+            None => return Ok(Value::Null),
+        };
+
+        Ok(
+            match analysis::hover(&self.engine_state, &document.text, offset) {
+                Some((help, span)) => json!({
+                    "contents": { "kind": "plaintext", "value": help },
+                    "range": document.range(span),
+                }),
+                None => Value::Null,
+            },
+        )
+    }
+
+    fn definition(&self, params: &Value) -> Result<Value, ResponseError> {
+        let (uri, document, offset) = match self.document_position(params)? {
+            Some(found) => found,
+            None => return Ok(Value::Null),
+        };
+
+        Ok(
+            match analysis::definition(&self.engine_state, &document.text, offset) {
+                Some(span) => json!({ "uri": uri, "range": document.range(span) }),
+                None => Value::Null,
+            },
+        )
+    }
+
+    fn completion(&self, params: &Value) -> Result<Value, ResponseError> {
+        let (_, document, offset) = match self.document_position(params)? {
+            Some(found) => found,
+            None => return Ok(json!([])),
+        };
+
+        let items = self
+            .completer
+            .complete(&document.text, offset)
+            .into_iter()
+            .map(|(span, name)| {
+                let range = document.range(Span::new(span.start, span.end));
+                json!({
+                    "label": name,
+                    "textEdit": { "range": range, "newText": name },
+                })
+            })
+            .collect::<Vec<_>>();
+
+        Ok(Value::Array(items))
+    }
+
+    // The document and the byte offset a request is about, or `None` if the document isn't open
+    fn document_position<'a>(
+        &'a self,
+        params: &'a Value,
+    ) -> Result<Option<(&'a str, &'a Document, usize)>, ResponseError> {
+        let uri = params.pointer("/textDocument/uri").and_then(Value::as_str);
+        let line = params.pointer("/position/line").and_then(Value::as_u64);
+        let character = params
+            .pointer("/position/character")
+            .and_then(Value::as_u64);
+
+        match (uri, line, character) {
+            (Some(uri), Some(line), Some(character)) => {
+                Ok(self.documents.get(uri).map(|document| {
+                    (
+                        uri,
+                        document,
+                        document.offset(line as usize, character as usize),
+                    )
+                }))
+            }
+            _ => Err(ResponseError::new(
+                INVALID_PARAMS,
+                "Expected a textDocument uri and a position",
+            )),
+        }
+    }
+}
+
+fn response(id: &Value, result: Result<Value, ResponseError>) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": error.code, "message": error.message },
+        }),
+    }
+}
+
+fn publish_diagnostics(uri: &str, diagnostics: Vec<Value>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": { "uri": uri, "diagnostics": diagnostics },
+    })
+}
@@ -0,0 +1,352 @@
+use std::io::{BufRead, Cursor, Read};
+
+use nu_command::create_default_context;
+use nu_lsp::LanguageServer;
+use serde_json::{json, Value};
+
+const URI: &str = "file:///script.nu";
+
+// Send the messages to a new server, one after the other, and return what it sent back and
+// whether it exited cleanly
+fn session(messages: &[Value]) -> (Vec<Value>, bool) {
+    let mut input = vec![];
+    for message in messages {
+        let body = message.to_string();
+        input.extend(format!("Content-Length: {}\r\n\r\n{}", body.len(), body).into_bytes());
+    }
+
+    let mut output = vec![];
+    let shut_down = LanguageServer::new(create_default_context())
+        .run(Cursor::new(input), &mut output)
+        .expect("the server should read and write its messages");
+
+    (read_messages(&output), shut_down)
+}
+
+fn read_messages(output: &[u8]) -> Vec<Value> {
+    let mut output = Cursor::new(output);
+    let mut messages = vec![];
+
+    loop {
+        let mut header = String::new();
+        if output.read_line(&mut header).unwrap() == 0 {
+            return messages;
+        }
+        let length = header
+            .trim()
+            .strip_prefix("Content-Length: ")
+            .expect("every message should start with its length")
+            .parse::<usize>()
+            .unwrap();
+
+        let mut blank = String::new();
+        output.read_line(&mut blank).unwrap();
+        assert_eq!(blank, "\r\n");
+
+        let mut body = vec![0; length];
+        output.read_exact(&mut body).unwrap();
+        messages.push(serde_json::from_slice(&body).unwrap());
+    }
+}
+
+fn request(id: u64, method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+}
+
+fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+fn open(text: &str) -> Value {
+    notification(
+        "textDocument/didOpen",
+        json!({
+            "textDocument": { "uri": URI, "languageId": "nushell", "version": 1, "text": text }
+        }),
+    )
+}
+
+fn at(line: u64, character: u64) -> Value {
+    json!({
+        "textDocument": { "uri": URI },
+        "position": { "line": line, "character": character },
+    })
+}
+
+fn range(start: (u64, u64), end: (u64, u64)) -> Value {
+    json!({
+        "start": { "line": start.0, "character": start.1 },
+        "end": { "line": end.0, "character": end.1 },
+    })
+}
+
+// The response to the request with the id, from a session that opened `text`
+fn answer(text: &str, method: &str, params: Value) -> Value {
+    let (messages, _) = session(&[open(text), request(1, method, params)]);
+
+    messages
+        .into_iter()
+        .find(|message| message["id"] == 1)
+        .expect("the request should be answered")
+}
+
+fn diagnostics(messages: &[Value]) -> Vec<Value> {
+    messages
+        .iter()
+        .filter(|message| message["method"] == "textDocument/publishDiagnostics")
+        .map(|message| message["params"]["diagnostics"].clone())
+        .collect()
+}
+
+#[test]
+fn initializes_and_shuts_down() {
+    let (messages, shut_down) = session(&[
+        request(1, "initialize", json!({ "capabilities": {} })),
+        notification("initialized", json!({})),
+        request(2, "shutdown", Value::Null),
+        notification("exit", Value::Null),
+    ]);
+
+    assert!(shut_down);
+    assert_eq!(messages.len(), 2);
+
+    let capabilities = &messages[0]["result"]["capabilities"];
+    assert_eq!(capabilities["textDocumentSync"], 1);
+    assert_eq!(capabilities["hoverProvider"], true);
+    assert_eq!(capabilities["definitionProvider"], true);
+    assert!(capabilities["completionProvider"].is_object());
+
+    assert_eq!(
+        messages[1],
+        json!({ "jsonrpc": "2.0", "id": 2, "result": null })
+    );
+}
+
+#[test]
+fn exiting_without_shutting_down_is_an_error() {
+    let (_, shut_down) = session(&[
+        request(1, "initialize", json!({ "capabilities": {} })),
+        notification("exit", Value::Null),
+    ]);
+
+    assert!(!shut_down);
+}
+
+#[test]
+fn refuses_requests_after_shutting_down() {
+    let (messages, _) = session(&[
+        request(1, "shutdown", Value::Null),
+        request(2, "textDocument/hover", at(0, 0)),
+    ]);
+
+    assert_eq!(messages[1]["error"]["code"], -32600);
+}
+
+#[test]
+fn reports_unknown_methods() {
+    let (messages, _) = session(&[request(1, "textDocument/rename", at(0, 0))]);
+
+    assert_eq!(messages[0]["error"]["code"], -32601);
+    assert_eq!(messages[0]["id"], 1);
+}
+
+#[test]
+fn reports_messages_that_arent_json() {
+    let input = b"Content-Length: 5\r\n\r\n{oops".to_vec();
+    let mut output = vec![];
+    LanguageServer::new(create_default_context())
+        .run(Cursor::new(input), &mut output)
+        .unwrap();
+
+    let messages = read_messages(&output);
+    assert_eq!(messages[0]["error"]["code"], -32700);
+    assert_eq!(messages[0]["id"], Value::Null);
+}
+
+#[test]
+fn publishes_parse_errors() {
+    let (messages, _) = session(&[open("ls\nls --nope\n")]);
+
+    let diagnostics = diagnostics(&messages);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].as_array().unwrap().len(), 1);
+
+    let error = &diagnostics[0][0];
+    assert_eq!(error["severity"], 1);
+    assert_eq!(error["source"], "nu");
+    assert_eq!(error["range"], range((1, 3), (1, 9)));
+}
+
+#[test]
+fn publishes_parse_warnings() {
+    let (messages, _) = session(&[open("def foo [] {\n    let unused = 1\n    2\n}")]);
+
+    let warning = &diagnostics(&messages)[0][0];
+    assert_eq!(warning["severity"], 2);
+    assert_eq!(warning["code"], "nu::parser::unused_variable");
+    assert_eq!(warning["range"], range((1, 8), (1, 14)));
+}
+
+#[test]
+fn publishes_diagnostics_again_when_the_document_changes() {
+    let (messages, _) = session(&[
+        open("ls"),
+        notification(
+            "textDocument/didChange",
+            json!({
+                "textDocument": { "uri": URI, "version": 2 },
+                "contentChanges": [{ "text": "ls | where" }],
+            }),
+        ),
+        notification(
+            "textDocument/didClose",
+            json!({ "textDocument": { "uri": URI } }),
+        ),
+    ]);
+
+    let diagnostics = diagnostics(&messages);
+    assert_eq!(diagnostics.len(), 3);
+    assert_eq!(diagnostics[0], json!([]));
+    assert_eq!(diagnostics[1].as_array().unwrap().len(), 1);
+    assert_eq!(diagnostics[2], json!([]));
+}
+
+#[test]
+fn hovers_with_the_help_of_a_command() {
+    let response = answer("ls | length", "textDocument/hover", at(0, 7));
+
+    let contents = response["result"]["contents"]["value"].as_str().unwrap();
+    assert!(contents.contains("Count the number of elements"));
+    assert!(contents.contains("Usage:"));
+    assert_eq!(response["result"]["range"], range((0, 5), (0, 11)));
+}
+
+#[test]
+fn hovers_with_the_help_of_a_custom_command() {
+    let response = answer(
+        "def greet [name: string] { $name }\ngreet nu",
+        "textDocument/hover",
+        at(1, 2),
+    );
+
+    let contents = response["result"]["contents"]["value"].as_str().unwrap();
+    assert!(contents.contains("greet <name>"));
+}
+
+#[test]
+fn hovers_with_nothing_away_from_commands() {
+    let response = answer("ls | length", "textDocument/hover", at(0, 3));
+
+    assert_eq!(response["result"], Value::Null);
+}
+
+#[test]
+fn goes_to_the_definition_of_a_custom_command() {
+    let response = answer(
+        "def greet [] { 'hello' }\n\ngreet",
+        "textDocument/definition",
+        at(2, 1),
+    );
+
+    assert_eq!(response["result"]["uri"], URI);
+    assert_eq!(response["result"]["range"], range((0, 4), (0, 9)));
+}
+
+#[test]
+fn goes_to_the_definition_of_a_variable() {
+    let response = answer(
+        "let total = 10\n$total + 1",
+        "textDocument/definition",
+        at(1, 3),
+    );
+
+    assert_eq!(response["result"]["range"], range((0, 4), (0, 9)));
+}
+
+#[test]
+fn goes_to_the_definition_of_a_parameter() {
+    let response = answer(
+        "def greet [name: string, --loud] {\n    if $loud { $name } else { $name }\n}",
+        "textDocument/definition",
+        at(1, 17),
+    );
+    assert_eq!(response["result"]["range"], range((0, 11), (0, 15)));
+
+    let response = answer(
+        "def greet [name: string, --loud] {\n    if $loud { $name } else { $name }\n}",
+        "textDocument/definition",
+        at(1, 8),
+    );
+    assert_eq!(response["result"]["range"], range((0, 27), (0, 31)));
+}
+
+#[test]
+fn goes_to_the_definition_of_a_block_parameter() {
+    let response = answer(
+        "[1 2] | each {|value| $value + 1 }",
+        "textDocument/definition",
+        at(0, 25),
+    );
+
+    assert_eq!(response["result"]["range"], range((0, 15), (0, 20)));
+}
+
+#[test]
+fn has_no_definition_for_builtin_commands() {
+    let response = answer("ls", "textDocument/definition", at(0, 1));
+
+    assert_eq!(response["result"], Value::Null);
+}
+
+#[test]
+fn counts_columns_in_utf16() {
+    let response = answer(
+        "let s = 'é😀'; let total = 10\n$total",
+        "textDocument/definition",
+        at(1, 1),
+    );
+
+    // `é` is one code unit and `😀` two, though they're five bytes together
+    assert_eq!(response["result"]["range"], range((0, 19), (0, 24)));
+}
+
+#[test]
+fn completes_commands() {
+    let response = answer(
+        "def greeting [] { 1 }\ngree",
+        "textDocument/completion",
+        at(1, 4),
+    );
+
+    let items = response["result"].as_array().unwrap();
+    let item = items
+        .iter()
+        .find(|item| item["label"] == "greeting")
+        .expect("the custom command should be completed");
+    assert_eq!(item["textEdit"]["newText"], "greeting");
+    assert_eq!(item["textEdit"]["range"], range((1, 0), (1, 4)));
+}
+
+#[test]
+fn completes_variables() {
+    let response = answer("let count = 1\n$cou", "textDocument/completion", at(1, 4));
+
+    let labels = response["result"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["label"].as_str().unwrap().to_string())
+        .collect::<Vec<_>>();
+    assert!(labels.contains(&"$count".to_string()));
+}
+
+#[test]
+fn answers_about_unopened_documents_with_nothing() {
+    let (messages, _) = session(&[
+        request(1, "textDocument/hover", at(0, 0)),
+        request(2, "textDocument/completion", at(0, 0)),
+    ]);
+
+    assert_eq!(messages[0]["result"], Value::Null);
+    assert_eq!(messages[1]["result"], json!([]));
+}
@@ -195,6 +195,14 @@ pub enum ParseError {
     #[diagnostic(code(nu::parser::file_not_found), url(docsrs))]
     FileNotFound(String, #[label("File not found: {0}")] Span),
 
+    #[error("Only a single external command can run in the background.")]
+    #[diagnostic(
+        code(nu::parser::background_pipeline),
+        url(docsrs),
+        help("to run a whole pipeline in the background, run it in another shell, like `^sh -c 'a | b' &`")
+    )]
+    BackgroundPipeline(#[label = "part of a pipeline"] Span),
+
     #[error("{0}")]
     #[diagnostic()]
     LabeledError(String, String, #[label("{1}")] Span),
@@ -105,7 +105,7 @@ pub fn flatten_expression(
             output.extend(args);
             output
         }
-        Expr::ExternalCall(_, name_span, args) => {
+        Expr::ExternalCall(_, name_span, args, redirections, _) => {
             let mut output = vec![(*name_span, FlatShape::External)];
 
             for arg in args {
@@ -124,6 +124,15 @@ pub fn flatten_expression(
                 }
             }
 
+            for redirection in redirections {
+                output.push((redirection.span, FlatShape::Operator));
+                if let Some(target) = &redirection.target {
+                    output.extend(flatten_expression(working_set, target));
+                }
+            }
+            // sort these since redirections and args can be intermixed
+            output.sort();
+
             output
         }
         Expr::Garbage => {
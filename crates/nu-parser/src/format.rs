@@ -0,0 +1,247 @@
+use crate::{lex, ParseError, TokenContents};
+
+const INDENT: &str = "    ";
+
+/// Format nu source the canonical way: one level of four spaces of indentation for each block,
+/// list or subexpression that spans lines, single spaces between the parts of a pipeline, padded
+/// blocks like `{ ls }` and double quotes where they mean the same as single ones
+///
+/// Comments and the lines the code is split across are kept as they are, except that blank lines
+/// are collapsed into one and removed at the start and end of a block.
+pub fn format_source(contents: &[u8]) -> Result<String, ParseError> {
+    let lines = format_block(contents, 0)?;
+
+    if lines.is_empty() {
+        Ok(String::new())
+    } else {
+        Ok(lines.join("\n") + "\n")
+    }
+}
+
+// The lines of a block, indented `depth` levels, with blank lines as empty strings
+fn format_block(contents: &[u8], depth: usize) -> Result<Vec<String>, ParseError> {
+    let (tokens, err) = lex(contents, 0, &[], &[], false);
+    if let Some(err) = err {
+        return Err(err);
+    }
+
+    let mut lines = vec![];
+    let mut line = String::new();
+    let mut line_depth = depth;
+    let mut blank_line = false;
+
+    for token in tokens {
+        let text = &contents[token.span.start..token.span.end];
+
+        match token.contents {
+            // `\r\n` ends one line, not two
+            TokenContents::Eol if text == b"\r" => {}
+            TokenContents::Eol => {
+                if line.is_empty() {
+                    blank_line = !lines.is_empty();
+                    continue;
+                }
+                if blank_line {
+                    lines.push(String::new());
+                    blank_line = false;
+                }
+
+                // A pipeline continued on the next line is indented one more level
+                let continued = line.ends_with('|');
+                lines.push(INDENT.repeat(line_depth) + &line);
+                line.clear();
+                line_depth = depth + continued as usize;
+            }
+            TokenContents::Comment => {
+                push_part(&mut line, String::from_utf8_lossy(text).trim_end());
+            }
+            TokenContents::Pipe => {
+                if line.is_empty() {
+                    line_depth = depth + 1;
+                }
+                push_part(&mut line, "|");
+            }
+            TokenContents::Semicolon => line.push(';'),
+            TokenContents::Item => push_part(&mut line, &format_item(text, line_depth)?),
+        }
+    }
+
+    if !line.is_empty() {
+        if blank_line {
+            lines.push(String::new());
+        }
+        lines.push(INDENT.repeat(line_depth) + &line);
+    }
+
+    Ok(lines)
+}
+
+fn push_part(line: &mut String, part: &str) {
+    if !line.is_empty() {
+        line.push(' ');
+    }
+    line.push_str(part);
+}
+
+fn format_item(item: &[u8], depth: usize) -> Result<String, ParseError> {
+    if is_delimited(item) {
+        format_group(item[0], &item[1..item.len() - 1], depth)
+    } else if is_plain_single_quoted(item) {
+        Ok(format!(
+            "\"{}\"",
+            String::from_utf8_lossy(&item[1..item.len() - 1])
+        ))
+    } else {
+        Ok(String::from_utf8_lossy(item).to_string())
+    }
+}
+
+// A block, record, list or subexpression, formatted with its contents
+fn format_group(open: u8, contents: &[u8], depth: usize) -> Result<String, ParseError> {
+    let (params, contents) = if open == b'{' {
+        split_params(contents)
+    } else {
+        (None, contents)
+    };
+
+    let (tokens, err) = lex(contents, 0, &[], &[], false);
+    if let Some(err) = err {
+        return Err(err);
+    }
+    let multiline = tokens
+        .iter()
+        .any(|token| token.contents == TokenContents::Eol);
+    let is_record = match tokens.first() {
+        Some(token) if open == b'{' && params.is_none() => {
+            contains_unquoted(&contents[token.span.start..token.span.end], b':')
+        }
+        _ => false,
+    };
+
+    // Contents kept on one line are part of the line the group is on, so they're indented like it
+    let lines = format_block(contents, if multiline { depth + 1 } else { depth })?;
+
+    let (close, pad) = match open {
+        b'{' => ("}", !is_record),
+        b'[' => ("]", false),
+        _ => (")", false),
+    };
+    let open = match &params {
+        Some(params) => format!("{{{}", params),
+        None => (open as char).to_string(),
+    };
+
+    if lines.is_empty() {
+        return Ok(match params {
+            Some(_) => format!("{} {}", open, close),
+            None => format!("{}{}", open, close),
+        });
+    }
+
+    if multiline {
+        Ok(format!(
+            "{}\n{}\n{}{}",
+            open,
+            lines.join("\n"),
+            INDENT.repeat(depth),
+            close
+        ))
+    } else {
+        let contents = lines.join(" ");
+        let contents = contents.trim_start();
+        if pad {
+            Ok(format!("{} {} {}", open, contents, close))
+        } else {
+            Ok(format!("{}{}{}", open, contents, close))
+        }
+    }
+}
+
+// The parameters at the start of a block, like `|x, y|`, with their whitespace collapsed, and the
+// rest of the block
+fn split_params(contents: &[u8]) -> (Option<String>, &[u8]) {
+    let start = match contents.iter().position(|c| !c.is_ascii_whitespace()) {
+        Some(start) if contents[start] == b'|' => start,
+        _ => return (None, contents),
+    };
+
+    match contents[start + 1..].iter().position(|c| *c == b'|') {
+        Some(len) => {
+            let end = start + 1 + len;
+            let params = String::from_utf8_lossy(&contents[start + 1..end])
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            (Some(format!("|{}|", params)), &contents[end + 1..])
+        }
+        None => (None, contents),
+    }
+}
+
+// Whether the item is one pair of delimiters around the rest, like a block or a list, and not
+// something that only starts with one, like `(ls).name`. Strings and comments are skipped the way
+// the lexer skips them
+fn is_delimited(item: &[u8]) -> bool {
+    if !matches!(item.first(), Some(b'{') | Some(b'[') | Some(b'(')) {
+        return false;
+    }
+
+    let mut closing = vec![];
+    let mut quote = None;
+    let mut in_comment = false;
+
+    for (idx, c) in item.iter().enumerate() {
+        if let Some(q) = quote {
+            if *c == q {
+                quote = None;
+            }
+            continue;
+        }
+
+        match c {
+            b'\n' | b'\r' => in_comment = false,
+            _ if in_comment => {}
+            b'#' => in_comment = true,
+            b'\'' | b'"' => quote = Some(*c),
+            b'{' => closing.push(b'}'),
+            b'[' => closing.push(b']'),
+            b'(' => closing.push(b')'),
+            b'}' | b']' | b')' if closing.last() == Some(c) => {
+                closing.pop();
+                if closing.is_empty() {
+                    return idx == item.len() - 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    false
+}
+
+// Single quoted strings without quotes in them, which mean the same in double quotes
+fn is_plain_single_quoted(item: &[u8]) -> bool {
+    item.len() >= 2
+        && item.starts_with(b"'")
+        && item.ends_with(b"'")
+        && !item[1..item.len() - 1]
+            .iter()
+            .any(|c| *c == b'\'' || *c == b'"')
+}
+
+fn contains_unquoted(item: &[u8], needle: u8) -> bool {
+    let mut quote = None;
+
+    for c in item {
+        match quote {
+            Some(q) if *c == q => quote = None,
+            Some(_) => {}
+            None if *c == b'\'' || *c == b'"' => quote = Some(*c),
+            None if *c == needle => return true,
+            None => {}
+        }
+    }
+
+    false
+}
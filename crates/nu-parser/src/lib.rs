@@ -1,21 +1,28 @@
 mod errors;
 mod flatten;
+mod format;
 mod lex;
+mod lint;
 mod lite_parse;
 mod parse_keywords;
 mod parser;
 mod type_check;
+mod visit;
+mod warnings;
 
 pub use errors::ParseError;
 pub use flatten::{
     flatten_block, flatten_expression, flatten_pipeline, flatten_statement, FlatShape,
 };
+pub use format::format_source;
 pub use lex::{lex, Token, TokenContents};
+pub use lint::{lint_style, StyleWarning};
 pub use lite_parse::{lite_parse, LiteBlock};
 pub use parse_keywords::{
     parse_alias, parse_def, parse_def_predecl, parse_let, parse_module, parse_use,
 };
 pub use parser::{find_captures_in_expr, parse, trim_quotes, Import};
+pub use visit::{visit_block, visit_expression, visit_expressions, Visitor};
 
 #[cfg(feature = "plugin")]
 pub use parse_keywords::parse_register;
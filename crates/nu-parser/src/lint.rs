@@ -0,0 +1,179 @@
+use miette::Diagnostic;
+use nu_protocol::Span;
+use thiserror::Error;
+
+use crate::format_source;
+
+/// A problem with how a script is written, which the parser doesn't care about
+#[derive(Clone, Debug, PartialEq, Error, Diagnostic)]
+pub enum StyleWarning {
+    #[error("Trailing whitespace.")]
+    #[diagnostic(
+        code(nu::lint::trailing_whitespace),
+        url(docsrs),
+        severity(Warning),
+        help("Remove it.")
+    )]
+    TrailingWhitespace(#[label = "trailing whitespace"] Span),
+
+    #[error("Indented with tabs.")]
+    #[diagnostic(
+        code(nu::lint::tab_indentation),
+        url(docsrs),
+        severity(Warning),
+        help("Indent with four spaces for each level instead.")
+    )]
+    TabIndentation(#[label = "tab"] Span),
+
+    #[error("Not formatted.")]
+    #[diagnostic(
+        code(nu::lint::not_formatted),
+        url(docsrs),
+        severity(Warning),
+        help("Run `nu --fmt` on the script to format it.")
+    )]
+    NotFormatted(#[label = "the formatted script differs from here"] Span),
+}
+
+/// Check the style of a script, which starts at `span_offset` in the working set it was parsed
+/// into. Scripts that can't be lexed are left to the parser to report
+pub fn lint_style(contents: &[u8], span_offset: usize) -> Vec<StyleWarning> {
+    let mut warnings = vec![];
+    let strings = string_spans(contents);
+    let in_string = |offset: usize| {
+        strings
+            .iter()
+            .any(|(start, end)| *start < offset && offset < *end)
+    };
+
+    let mut line_start = 0;
+    for raw_line in contents.split(|c| *c == b'\n') {
+        let line = raw_line.strip_suffix(b"\r").unwrap_or(raw_line);
+        let span = |start: usize, end: usize| {
+            Span::new(
+                span_offset + line_start + start,
+                span_offset + line_start + end,
+            )
+        };
+
+        let indent = line
+            .iter()
+            .take_while(|c| **c == b' ' || **c == b'\t')
+            .count();
+        if let Some(tab) = line[..indent]
+            .iter()
+            .position(|c| *c == b'\t')
+            .filter(|tab| !in_string(line_start + tab))
+        {
+            warnings.push(StyleWarning::TabIndentation(span(tab, tab + 1)));
+        }
+
+        let content_end = match line.iter().rposition(|c| *c != b' ' && *c != b'\t') {
+            Some(last) => last + 1,
+            None => 0,
+        };
+        if content_end < line.len() && !in_string(line_start + content_end) {
+            warnings.push(StyleWarning::TrailingWhitespace(span(
+                content_end,
+                line.len(),
+            )));
+        }
+
+        line_start += raw_line.len() + 1;
+    }
+
+    if let Ok(formatted) = format_source(contents) {
+        // The formatter ends lines with `\n`, so `\r\n` isn't counted as a difference
+        let difference = first_difference(
+            &without_carriage_returns(contents),
+            &without_carriage_returns(formatted.as_bytes()),
+        );
+        if let Some(offset) = difference.map(|offset| original_offset(contents, offset)) {
+            // Point at the line the difference is on
+            let start = contents[..offset]
+                .iter()
+                .rposition(|c| *c == b'\n')
+                .map(|idx| idx + 1)
+                .unwrap_or(0);
+            let end = contents[offset..]
+                .iter()
+                .position(|c| *c == b'\n' || *c == b'\r')
+                .map(|len| offset + len)
+                .unwrap_or(contents.len());
+
+            warnings.push(StyleWarning::NotFormatted(Span::new(
+                span_offset + start,
+                span_offset + end,
+            )));
+        }
+    }
+
+    warnings
+}
+
+// The offset into `original` of the first byte that's different in `formatted`
+fn first_difference(original: &[u8], formatted: &[u8]) -> Option<usize> {
+    match original.iter().zip(formatted).position(|(a, b)| a != b) {
+        Some(offset) => Some(offset),
+        None if original.len() != formatted.len() => Some(original.len().min(formatted.len())),
+        None => None,
+    }
+}
+
+// The start and end of each string literal, found the way the lexer finds them
+fn string_spans(contents: &[u8]) -> Vec<(usize, usize)> {
+    let mut spans = vec![];
+    let mut quote = None;
+    let mut in_comment = false;
+
+    for (idx, c) in contents.iter().enumerate() {
+        if let Some((q, start)) = quote {
+            if *c == q {
+                spans.push((start, idx + 1));
+                quote = None;
+            }
+            continue;
+        }
+
+        match c {
+            b'\n' | b'\r' => in_comment = false,
+            _ if in_comment => {}
+            b'#' => in_comment = true,
+            b'\'' | b'"' => quote = Some((*c, idx)),
+            _ => {}
+        }
+    }
+
+    if let Some((_, start)) = quote {
+        spans.push((start, contents.len()));
+    }
+
+    spans
+}
+
+fn without_carriage_returns(text: &[u8]) -> Vec<u8> {
+    let mut output = Vec::with_capacity(text.len());
+    for (idx, c) in text.iter().enumerate() {
+        if *c != b'\r' || text.get(idx + 1) != Some(&b'\n') {
+            output.push(*c);
+        }
+    }
+
+    output
+}
+
+// The offset into `original` of `offset` into it once its `\r\n`s are `\n`s
+fn original_offset(original: &[u8], offset: usize) -> usize {
+    let mut remaining = offset;
+    for (idx, c) in original.iter().enumerate() {
+        if *c == b'\r' && original.get(idx + 1) == Some(&b'\n') {
+            continue;
+        }
+        if remaining == 0 {
+            return idx;
+        }
+        remaining -= 1;
+    }
+
+    original.len()
+}
@@ -5,7 +5,7 @@ use nu_protocol::{
         Pipeline, Statement,
     },
     engine::StateWorkingSet,
-    span, Exportable, Overlay, Span, SyntaxShape, Type, CONFIG_VARIABLE_ID,
+    span, Exportable, Overlay, ParseWarning, Span, SyntaxShape, Type, CONFIG_VARIABLE_ID,
 };
 use std::collections::{HashMap, HashSet};
 
@@ -45,12 +45,25 @@ pub fn parse_def_predecl(working_set: &mut StateWorkingSet, spans: &[Span]) -> O
         working_set.exit_scope();
 
         if let (Some(name), Some(mut signature)) = (name, signature) {
-            signature.name = name;
+            let shadowed = working_set.find_decl(name.as_bytes());
+
+            signature.name = name.clone();
             let decl = signature.predeclare();
 
             if working_set.add_predecl(decl).is_some() {
                 return Some(ParseError::DuplicateCommandDef(spans[1]));
             }
+
+            // Redefining a custom command from an earlier parse, like another line of the REPL,
+            // replaces it on purpose
+            if let Some(decl_id) = shadowed {
+                let earlier_custom_command = decl_id < working_set.permanent_state.num_decls()
+                    && working_set.get_decl(decl_id).get_block_id().is_some();
+
+                if !earlier_custom_command {
+                    working_set.add_warning(ParseWarning::ShadowedDefinition(name, spans[1]));
+                }
+            }
         }
     }
 
@@ -114,6 +127,8 @@ pub fn parse_def(
                             signature.name = name.clone();
 
                             *declaration = signature.into_block_command(block_id);
+
+                            working_set.get_block_mut(block_id).span = Some(*block_span);
                         } else {
                             error = error.or_else(|| {
                                 Some(ParseError::InternalError(
@@ -2,6 +2,7 @@ use crate::{
     lex, lite_parse,
     parse_keywords::parse_source,
     type_check::{math_result_type, type_compatible},
+    warnings::find_warnings,
     LiteBlock, ParseError, Token, TokenContents,
 };
 
@@ -9,11 +10,11 @@ use nu_protocol::{
     ast::{
         Block, Call, CellPath, Expr, Expression, FullCellPath, ImportPattern, ImportPatternHead,
         ImportPatternMember, Operator, PathMember, Pipeline, RangeInclusion, RangeOperator,
-        Statement,
+        Redirection, RedirectionSource, Statement,
     },
     engine::StateWorkingSet,
-    span, Flag, PositionalArg, Signature, Span, Spanned, SyntaxShape, Type, Unit, VarId,
-    CONFIG_VARIABLE_ID,
+    span, Flag, ParseWarning, PositionalArg, Signature, Span, Spanned, SyntaxShape, Type, Unit,
+    VarId, CONFIG_VARIABLE_ID,
 };
 
 use crate::parse_keywords::{
@@ -144,14 +145,72 @@ pub fn parse_external_call(
     spans: &[Span],
 ) -> (Expression, Option<ParseError>) {
     let mut args = vec![];
+    let mut redirections = vec![];
     let name_span = spans[0];
     let name = String::from_utf8_lossy(working_set.get_span_contents(name_span)).to_string();
     let mut error = None;
+    let full_span = span(spans);
+
+    // A trailing `&` leaves the command running in the background
+    let (spans, background) = match spans.split_last() {
+        Some((last, rest)) if !rest.is_empty() && working_set.get_span_contents(*last) == b"&" => {
+            (rest, true)
+        }
+        _ => (spans, false),
+    };
+
+    let mut idx = 1;
+    while idx < spans.len() {
+        let span = &spans[idx];
+        idx += 1;
 
-    for span in &spans[1..] {
         let contents = working_set.get_span_contents(*span);
 
-        if contents.starts_with(b"$") || contents.starts_with(b"(") {
+        if let Some((source, operator_len)) = redirection_operator(contents) {
+            let operator_span = Span {
+                start: span.start,
+                end: span.start + operator_len,
+            };
+
+            // The target is either glued to the operator, as in `err>errors.log`, or the next
+            // argument. Without one, the output goes into the next pipeline stage
+            let target_span = if operator_len < contents.len() {
+                Some(Span {
+                    start: operator_span.end,
+                    end: span.end,
+                })
+            } else if idx < spans.len()
+                && redirection_operator(working_set.get_span_contents(spans[idx])).is_none()
+            {
+                idx += 1;
+                Some(spans[idx - 1])
+            } else {
+                None
+            };
+
+            let target = match target_span {
+                Some(target_span) => {
+                    let target_contents = working_set.get_span_contents(target_span);
+
+                    let (target, err) =
+                        if target_contents.starts_with(b"$") || target_contents.starts_with(b"(") {
+                            parse_expression(working_set, &[target_span], true)
+                        } else {
+                            parse_filepath(working_set, target_span)
+                        };
+                    error = error.or(err);
+
+                    Some(target)
+                }
+                None => None,
+            };
+
+            redirections.push(Redirection {
+                source,
+                span: operator_span,
+                target,
+            });
+        } else if contents.starts_with(b"$") || contents.starts_with(b"(") {
             let (arg, err) = parse_expression(working_set, &[*span], true);
             error = error.or(err);
             args.push(arg);
@@ -166,8 +225,8 @@ pub fn parse_external_call(
     }
     (
         Expression {
-            expr: Expr::ExternalCall(name, name_span, args),
-            span: span(spans),
+            expr: Expr::ExternalCall(name, name_span, args, redirections, background),
+            span: full_span,
             ty: Type::Unknown,
             custom_completion: None,
         },
@@ -175,6 +234,18 @@ pub fn parse_external_call(
     )
 }
 
+fn redirection_operator(contents: &[u8]) -> Option<(RedirectionSource, usize)> {
+    if contents.starts_with(b"out+err>") {
+        Some((RedirectionSource::StdoutAndStderr, 8))
+    } else if contents.starts_with(b"out>") {
+        Some((RedirectionSource::Stdout, 4))
+    } else if contents.starts_with(b"err>") {
+        Some((RedirectionSource::Stderr, 4))
+    } else {
+        None
+    }
+}
+
 fn parse_long_flag(
     working_set: &mut StateWorkingSet,
     spans: &[Span],
@@ -613,6 +684,27 @@ pub fn parse_internal_call(
     let err = check_call(command_span, &signature, &call);
     error = error.or(err);
 
+    if let Some(reason) = &signature.deprecated {
+        working_set.add_warning(ParseWarning::DeprecatedCommand(
+            signature.name.clone(),
+            reason.clone(),
+            command_span,
+        ));
+    }
+    for (name, _) in &call.named {
+        if let Some(reason) = signature
+            .get_long_flag(&name.item)
+            .and_then(|flag| flag.deprecated)
+        {
+            working_set.add_warning(ParseWarning::DeprecatedFlag(
+                signature.name.clone(),
+                name.item.clone(),
+                reason,
+                name.span,
+            ));
+        }
+    }
+
     if signature.creates_scope {
         working_set.exit_scope();
     }
@@ -2155,6 +2247,7 @@ pub fn parse_signature_helper(
                                         short: None,
                                         required: false,
                                         var_id: Some(var_id),
+                                        deprecated: None,
                                     }));
                                 } else {
                                     let short_flag = &flags[1];
@@ -2185,6 +2278,7 @@ pub fn parse_signature_helper(
                                             short: Some(chars[0]),
                                             required: false,
                                             var_id: Some(var_id),
+                                            deprecated: None,
                                         }));
                                     } else {
                                         error = error.or_else(|| {
@@ -2211,6 +2305,7 @@ pub fn parse_signature_helper(
                                         short: None,
                                         required: false,
                                         var_id: None,
+                                        deprecated: None,
                                     }));
                                 } else {
                                     let mut encoded_var_name = vec![0u8; 4];
@@ -2226,6 +2321,7 @@ pub fn parse_signature_helper(
                                         short: Some(chars[0]),
                                         required: false,
                                         var_id: Some(var_id),
+                                        deprecated: None,
                                     }));
                                 }
                             } else if contents.starts_with(b"(-") {
@@ -2885,6 +2981,13 @@ pub fn parse_value(
                 //parse_value(working_set, span, &SyntaxShape::Table)
                 parse_full_cell_path(working_set, None, span)
             } else {
+                // Braces holding `name: value` pairs are a record, anything else in them a block
+                if bytes.starts_with(b"{") {
+                    if let (record, None) = parse_record(working_set, span) {
+                        return (record, None);
+                    }
+                }
+
                 let shapes = [
                     SyntaxShape::Int,
                     SyntaxShape::Number,
@@ -3135,7 +3238,10 @@ pub fn parse_expression(
 
     let bytes = working_set.get_span_contents(spans[pos]);
 
-    let (output, err) = if is_math_expression_byte(bytes[0]) {
+    // A lone `-` is no math, but the previous directory to auto-cd into
+    let lone_dash = bytes == b"-" && pos + 1 == spans.len();
+
+    let (output, err) = if is_math_expression_byte(bytes[0]) && !lone_dash {
         parse_math_expression(working_set, &spans[pos..], None)
     } else {
         parse_call(working_set, &spans[pos..], expand_aliases, spans[0])
@@ -3367,6 +3473,16 @@ pub fn parse_block(
                     }
                 }
 
+                // A job is a single external, with nothing to take its output or give it input
+                if let Some(expr) = output
+                    .iter()
+                    .find(|expr| matches!(expr.expr, Expr::ExternalCall(.., true)))
+                {
+                    if error.is_none() {
+                        error = Some(ParseError::BackgroundPipeline(expr.span));
+                    }
+                }
+
                 Statement::Pipeline(Pipeline {
                     expressions: output,
                 })
@@ -3479,11 +3595,18 @@ pub fn find_captures_in_expr(
             }
         }
         Expr::CellPath(_) => {}
-        Expr::ExternalCall(_, _, exprs) => {
+        Expr::ExternalCall(_, _, exprs, redirections, _) => {
             for expr in exprs {
                 let result = find_captures_in_expr(working_set, expr, seen);
                 output.extend(&result);
             }
+
+            for redirection in redirections {
+                if let Some(target) = &redirection.target {
+                    let result = find_captures_in_expr(working_set, target, seen);
+                    output.extend(&result);
+                }
+            }
         }
         Expr::Filepath(_) => {}
         Expr::Float(_) => {}
@@ -3647,5 +3770,9 @@ pub fn parse(
     let (output, err) = parse_block(working_set, &output, scoped);
     error = error.or(err);
 
+    if error.is_none() {
+        find_warnings(working_set, &output);
+    }
+
     (output, error)
 }
@@ -0,0 +1,120 @@
+use nu_protocol::ast::{Block, Expr, Expression, Statement};
+use nu_protocol::engine::StateWorkingSet;
+
+/// What to do with the blocks and expressions of parsed code, as `visit_block` walks through them
+///
+/// Blocks are entered before the expressions in them are visited, and expressions are visited
+/// before the ones inside of them, so outer code always comes first.
+pub trait Visitor {
+    fn enter_block(&mut self, _working_set: &StateWorkingSet, _block: &Block) {}
+
+    fn leave_block(&mut self, _working_set: &StateWorkingSet, _block: &Block) {}
+
+    fn visit_expression(&mut self, _working_set: &StateWorkingSet, _expr: &Expression) {}
+}
+
+/// Walk through the pipelines of a block, and the blocks in them, with the visitor
+pub fn visit_block(working_set: &StateWorkingSet, block: &Block, visitor: &mut dyn Visitor) {
+    visitor.enter_block(working_set, block);
+
+    for stmt in &block.stmts {
+        if let Statement::Pipeline(pipeline) = stmt {
+            for expr in &pipeline.expressions {
+                visit_expression(working_set, expr, visitor);
+            }
+        }
+    }
+
+    visitor.leave_block(working_set, block);
+}
+
+pub fn visit_expression(
+    working_set: &StateWorkingSet,
+    expr: &Expression,
+    visitor: &mut dyn Visitor,
+) {
+    visitor.visit_expression(working_set, expr);
+
+    match &expr.expr {
+        Expr::Call(call) => {
+            for positional in &call.positional {
+                visit_expression(working_set, positional, visitor);
+            }
+            for arg in call.named.iter().filter_map(|(_, arg)| arg.as_ref()) {
+                visit_expression(working_set, arg, visitor);
+            }
+        }
+        Expr::Block(block_id) | Expr::RowCondition(block_id) | Expr::Subexpression(block_id) => {
+            visit_block(working_set, working_set.get_block(*block_id), visitor);
+        }
+        Expr::BinaryOp(lhs, op, rhs) => {
+            visit_expression(working_set, lhs, visitor);
+            visit_expression(working_set, op, visitor);
+            visit_expression(working_set, rhs, visitor);
+        }
+        Expr::ExternalCall(_, _, args, redirections, _) => {
+            for arg in args {
+                visit_expression(working_set, arg, visitor);
+            }
+            for target in redirections.iter().filter_map(|r| r.target.as_ref()) {
+                visit_expression(working_set, target, visitor);
+            }
+        }
+        Expr::FullCellPath(cell_path) => visit_expression(working_set, &cell_path.head, visitor),
+        Expr::Keyword(_, _, expr) | Expr::ValueWithUnit(expr, _) => {
+            visit_expression(working_set, expr, visitor)
+        }
+        Expr::List(exprs) => {
+            for expr in exprs {
+                visit_expression(working_set, expr, visitor);
+            }
+        }
+        Expr::Range(from, next, to, _) => {
+            for expr in from.iter().chain(next.iter()).chain(to.iter()) {
+                visit_expression(working_set, expr, visitor);
+            }
+        }
+        Expr::Record(fields) => {
+            for (name, value) in fields {
+                visit_expression(working_set, name, visitor);
+                visit_expression(working_set, value, visitor);
+            }
+        }
+        Expr::Table(headers, rows) => {
+            for expr in headers.iter().chain(rows.iter().flatten()) {
+                visit_expression(working_set, expr, visitor);
+            }
+        }
+        Expr::Bool(_)
+        | Expr::Int(_)
+        | Expr::Float(_)
+        | Expr::Var(_)
+        | Expr::VarDecl(_)
+        | Expr::Operator(_)
+        | Expr::Filepath(_)
+        | Expr::GlobPattern(_)
+        | Expr::String(_)
+        | Expr::CellPath(_)
+        | Expr::ImportPattern(_)
+        | Expr::Signature(_)
+        | Expr::Nothing
+        | Expr::Garbage => {}
+    }
+}
+
+/// Call `visit` on every expression in the block and the blocks in it, outer ones first
+pub fn visit_expressions(
+    working_set: &StateWorkingSet,
+    block: &Block,
+    visit: &mut dyn FnMut(&Expression),
+) {
+    struct Expressions<'a>(&'a mut dyn FnMut(&Expression));
+
+    impl Visitor for Expressions<'_> {
+        fn visit_expression(&mut self, _working_set: &StateWorkingSet, expr: &Expression) {
+            (self.0)(expr)
+        }
+    }
+
+    visit_block(working_set, block, &mut Expressions(visit));
+}
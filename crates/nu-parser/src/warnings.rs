@@ -0,0 +1,112 @@
+use nu_protocol::{
+    ast::{Block, Expr, Expression, Pipeline, Statement},
+    engine::StateWorkingSet,
+    ParseWarning, Span, VarId, CONFIG_VARIABLE_ID,
+};
+use std::collections::HashSet;
+
+use crate::{visit_block, Visitor};
+
+#[derive(Default)]
+struct Findings {
+    // Variables from `let` inside of blocks, with the span of their name
+    lets: Vec<(VarId, Span)>,
+    used: HashSet<VarId>,
+    warnings: Vec<ParseWarning>,
+    // How many blocks deep the visit is, where the block being checked is 1
+    depth: usize,
+}
+
+/// Look through a parsed block for code that runs but is likely a mistake, and add warnings
+/// about it to the working set
+///
+/// Variables from a `let` at the top of the block aren't reported when they're unused, because
+/// later code, like the next line of the REPL, can still use them.
+pub fn find_warnings(working_set: &mut StateWorkingSet, block: &Block) {
+    let mut findings = Findings::default();
+    visit_block(working_set, block, &mut findings);
+
+    let Findings {
+        lets,
+        used,
+        mut warnings,
+        ..
+    } = findings;
+
+    for (var_id, span) in lets {
+        let name = String::from_utf8_lossy(working_set.get_span_contents(span)).to_string();
+        if !used.contains(&var_id) && !name.starts_with('_') {
+            warnings.push(ParseWarning::UnusedVariable(name, span));
+        }
+    }
+
+    for warning in warnings {
+        working_set.add_warning(warning);
+    }
+}
+
+impl Visitor for Findings {
+    fn enter_block(&mut self, working_set: &StateWorkingSet, block: &Block) {
+        self.depth += 1;
+
+        let pipelines = block
+            .stmts
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Statement::Pipeline(pipeline) => Some(pipeline),
+                Statement::Declaration(_) => None,
+            })
+            .collect::<Vec<_>>();
+
+        for (idx, pipeline) in pipelines.iter().enumerate() {
+            let rest = pipelines[idx + 1..]
+                .iter()
+                .flat_map(|pipeline| pipeline.expressions.iter())
+                .map(|expr| expr.span)
+                .collect::<Vec<_>>();
+            if let (Some(exit), false) = (exit_span(working_set, pipeline), rest.is_empty()) {
+                self.warnings.push(ParseWarning::UnreachableCode(
+                    nu_protocol::span(&rest),
+                    exit,
+                ));
+            }
+        }
+    }
+
+    fn leave_block(&mut self, _working_set: &StateWorkingSet, _block: &Block) {
+        self.depth -= 1;
+    }
+
+    fn visit_expression(&mut self, working_set: &StateWorkingSet, expr: &Expression) {
+        match &expr.expr {
+            Expr::Call(call)
+                if self.depth > 1 && working_set.get_decl(call.decl_id).name() == "let" =>
+            {
+                if let Some(Expression {
+                    expr: Expr::VarDecl(var_id),
+                    span,
+                    ..
+                }) = call.positional.first()
+                {
+                    if *var_id != CONFIG_VARIABLE_ID {
+                        self.lets.push((*var_id, *span));
+                    }
+                }
+            }
+            Expr::Var(var_id) => {
+                self.used.insert(*var_id);
+            }
+            _ => {}
+        }
+    }
+}
+
+// The span of the `exit` call the pipeline ends with, if it does
+fn exit_span(working_set: &StateWorkingSet, pipeline: &Pipeline) -> Option<Span> {
+    match pipeline.expressions.last().map(|expr| &expr.expr) {
+        Some(Expr::Call(call)) if working_set.get_decl(call.decl_id).name() == "exit" => {
+            Some(call.head)
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,144 @@
+use nu_parser::{format_source, lint_style, StyleWarning};
+use nu_protocol::Span;
+
+fn formats(input: &str, expected: &str) {
+    let formatted = format_source(input.as_bytes()).expect("the source should lex");
+    assert_eq!(formatted, expected);
+
+    // Formatting what's formatted doesn't change it
+    let again = format_source(formatted.as_bytes()).expect("the formatted source should lex");
+    assert_eq!(again, formatted);
+}
+
+#[test]
+fn collapses_spaces_between_parts() {
+    formats("ls   |  where  size > 10kb", "ls | where size > 10kb\n");
+}
+
+#[test]
+fn keeps_spaces_in_strings() {
+    formats(
+        r#"echo "a   b"  $"c   (1 +  2)""#,
+        "echo \"a   b\" $\"c   (1 +  2)\"\n",
+    );
+}
+
+#[test]
+fn prefers_double_quotes() {
+    formats("echo 'a b'", "echo \"a b\"\n");
+}
+
+#[test]
+fn keeps_single_quotes_around_double_quotes() {
+    formats(r#"echo 'say "hi"'"#, "echo 'say \"hi\"'\n");
+}
+
+#[test]
+fn pads_blocks_on_one_line() {
+    formats("do {ls}", "do { ls }\n");
+}
+
+#[test]
+fn doesnt_pad_records_lists_and_subexpressions() {
+    formats(
+        "echo { a: 1,  b: 2 } [ 1  2 ] ( 1 + 2 )",
+        "echo {a: 1, b: 2} [1 2] (1 + 2)\n",
+    );
+}
+
+#[test]
+fn keeps_block_params_with_the_brace() {
+    formats("each { |it|   $it + 1 }", "each {|it| $it + 1 }\n");
+}
+
+#[test]
+fn indents_blocks_across_lines() {
+    formats(
+        "def foo [] {\n  if $true {\n\techo 1\n  }\n}",
+        "def foo [] {\n    if $true {\n        echo 1\n    }\n}\n",
+    );
+}
+
+#[test]
+fn indents_continued_pipelines() {
+    formats("ls |\n      length", "ls |\n    length\n");
+}
+
+#[test]
+fn keeps_comments() {
+    formats(
+        "# about foo\ndef foo [] {\n  # inside\n  1  # one\n}",
+        "# about foo\ndef foo [] {\n    # inside\n    1 # one\n}\n",
+    );
+}
+
+#[test]
+fn collapses_blank_lines() {
+    formats(
+        "\n\nlet x = 1\n\n\n\nlet y = 2\n\n",
+        "let x = 1\n\nlet y = 2\n",
+    );
+}
+
+#[test]
+fn removes_blank_lines_at_the_edges_of_blocks() {
+    formats("do {\n\n  ls\n\n}", "do {\n    ls\n}\n");
+}
+
+#[test]
+fn keeps_tables() {
+    formats("[[a  b]; [1   2]]", "[[a b]; [1 2]]\n");
+}
+
+#[test]
+fn leaves_cell_paths_of_subexpressions_alone() {
+    formats("(ls).name", "(ls).name\n");
+}
+
+#[test]
+fn fails_on_unclosed_delimiters() {
+    assert!(format_source(b"do { ls").is_err());
+}
+
+#[test]
+fn formatted_source_has_no_style_warnings() {
+    assert!(lint_style(b"def foo [] {\n    1\n}\n", 0).is_empty());
+}
+
+#[test]
+fn warns_about_trailing_whitespace() {
+    let warnings = lint_style(b"ls  \nls\n", 10);
+
+    assert!(warnings.contains(&StyleWarning::TrailingWhitespace(Span::new(12, 14))));
+}
+
+#[test]
+fn warns_about_tabs() {
+    let warnings = lint_style(b"do {\n\tls\n}\n", 0);
+
+    assert!(warnings.contains(&StyleWarning::TabIndentation(Span::new(5, 6))));
+}
+
+#[test]
+fn leaves_whitespace_in_strings_alone() {
+    assert!(lint_style(b"let s = \"a  \n\tb\"\n", 0).is_empty());
+}
+
+#[test]
+fn formatted_source_with_crlf_has_no_style_warnings() {
+    assert!(lint_style(b"def foo [] {\r\n    1\r\n}\r\n", 0).is_empty());
+}
+
+#[test]
+fn warns_about_unformatted_line_with_crlf() {
+    let warnings = lint_style(b"ls\r\nls   |  length\r\n", 0);
+
+    assert_eq!(warnings, vec![StyleWarning::NotFormatted(Span::new(4, 18))]);
+}
+
+#[test]
+fn warns_about_unformatted_line() {
+    let warnings = lint_style(b"ls\nls   |  length\n", 0);
+
+    assert_eq!(warnings, vec![StyleWarning::NotFormatted(Span::new(3, 17))]);
+}
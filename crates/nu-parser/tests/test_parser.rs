@@ -1,7 +1,7 @@
 use nu_parser::ParseError;
 use nu_parser::*;
 use nu_protocol::{
-    ast::{Expr, Expression, Pipeline, Statement},
+    ast::{Expr, Expression, Pipeline, RedirectionSource, Statement},
     engine::{Command, EngineState, Stack, StateWorkingSet},
     Signature, SyntaxShape,
 };
@@ -178,6 +178,79 @@ pub fn parse_call_missing_req_flag() {
     assert!(matches!(err, Some(ParseError::MissingRequiredFlag(..))));
 }
 
+#[test]
+pub fn parse_external_redirections() {
+    let engine_state = EngineState::new();
+    let mut working_set = StateWorkingSet::new(&engine_state);
+
+    let (block, err) = parse(
+        &mut working_set,
+        None,
+        b"^cmd arg out> out.txt err>err.txt out+err>",
+        true,
+    );
+
+    assert!(err.is_none());
+    assert!(block.len() == 1);
+    match &block[0] {
+        Statement::Pipeline(Pipeline { expressions }) => {
+            assert!(expressions.len() == 1);
+            if let Expr::ExternalCall(name, _, args, redirections, _) = &expressions[0].expr {
+                assert_eq!(name, "^cmd");
+                assert_eq!(args.len(), 1);
+                assert_eq!(redirections.len(), 3);
+
+                assert_eq!(redirections[0].source, RedirectionSource::Stdout);
+                assert!(matches!(
+                    &redirections[0].target,
+                    Some(Expression {
+                        expr: Expr::Filepath(path),
+                        ..
+                    }) if path == "out.txt"
+                ));
+
+                assert_eq!(redirections[1].source, RedirectionSource::Stderr);
+                assert!(matches!(
+                    &redirections[1].target,
+                    Some(Expression {
+                        expr: Expr::Filepath(path),
+                        ..
+                    }) if path == "err.txt"
+                ));
+
+                assert_eq!(redirections[2].source, RedirectionSource::StdoutAndStderr);
+                assert!(redirections[2].target.is_none());
+            } else {
+                panic!("not an external call")
+            }
+        }
+        _ => panic!("No match"),
+    }
+}
+
+#[test]
+pub fn parse_external_in_background() {
+    let engine_state = EngineState::new();
+    let mut working_set = StateWorkingSet::new(&engine_state);
+
+    let (block, err) = parse(&mut working_set, None, b"^sleep 10 &", true);
+
+    assert!(err.is_none());
+    assert!(block.len() == 1);
+    match &block[0] {
+        Statement::Pipeline(Pipeline { expressions }) => {
+            assert!(expressions.len() == 1);
+            if let Expr::ExternalCall(_, _, args, _, background) = &expressions[0].expr {
+                assert_eq!(args.len(), 1);
+                assert!(background);
+            } else {
+                panic!("not an external call")
+            }
+        }
+        _ => panic!("No match"),
+    }
+}
+
 #[test]
 fn test_nothing_comparisson_eq() {
     let engine_state = EngineState::new();
@@ -562,3 +635,80 @@ mod range {
         assert!(err.is_some());
     }
 }
+
+mod warnings {
+    use super::*;
+    use nu_protocol::ParseWarning;
+
+    fn working_set_with(engine_state: &EngineState, sig: Signature) -> StateWorkingSet<'_> {
+        let mut working_set = StateWorkingSet::new(engine_state);
+        working_set.add_decl(sig.predeclare());
+        working_set
+    }
+
+    #[test]
+    fn deprecated_command() {
+        let engine_state = EngineState::new();
+        let mut working_set =
+            working_set_with(&engine_state, Signature::build("foo").deprecated("use bar"));
+
+        let (_, err) = parse(&mut working_set, None, b"foo", true);
+
+        assert!(err.is_none());
+        assert!(matches!(
+            working_set.warnings(),
+            [ParseWarning::DeprecatedCommand(name, reason, _)] if name == "foo" && reason == "use bar"
+        ));
+    }
+
+    #[test]
+    fn deprecated_flag() {
+        let engine_state = EngineState::new();
+        let sig = Signature::build("foo")
+            .switch("old", "the old way", Some('o'))
+            .switch("new", "the new way", None)
+            .deprecated_flag("old", "use --new");
+        let mut working_set = working_set_with(&engine_state, sig);
+
+        let (_, err) = parse(&mut working_set, None, b"foo -o", true);
+
+        assert!(err.is_none());
+        assert!(matches!(
+            working_set.warnings(),
+            [ParseWarning::DeprecatedFlag(_, flag, _, _)] if flag == "old"
+        ));
+    }
+
+    #[test]
+    fn no_warnings_without_deprecation() {
+        let engine_state = EngineState::new();
+        let sig = Signature::build("foo")
+            .switch("old", "the old way", Some('o'))
+            .deprecated_flag("old", "use --new");
+        let mut working_set = working_set_with(&engine_state, sig);
+
+        let (_, err) = parse(&mut working_set, None, b"foo", true);
+
+        assert!(err.is_none());
+        assert!(working_set.warnings().is_empty());
+    }
+
+    #[test]
+    fn unused_variable_in_block() {
+        let engine_state = EngineState::new();
+        let mut working_set = working_set_with(&engine_state, Let.signature());
+
+        let (_, err) = parse(
+            &mut working_set,
+            None,
+            b"{ let x = 1; let y = 2; $y }",
+            true,
+        );
+
+        assert!(err.is_none());
+        assert!(matches!(
+            working_set.warnings(),
+            [ParseWarning::UnusedVariable(name, _)] if name == "x"
+        ));
+    }
+}
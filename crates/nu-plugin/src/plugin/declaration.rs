@@ -5,7 +5,7 @@ use crate::protocol::{CallInfo, PluginCall, PluginResponse};
 use std::io::BufReader;
 use std::path::{Path, PathBuf};
 
-use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::engine::{Capability, Command, EngineState, Stack};
 use nu_protocol::{ast::Call, Signature, Value};
 use nu_protocol::{PipelineData, ShellError};
 
@@ -48,6 +48,10 @@ impl Command for PluginDeclaration {
         self.signature.usage.as_str()
     }
 
+    fn capabilities(&self) -> Vec<Capability> {
+        vec![Capability::Plugins]
+    }
+
     fn run(
         &self,
         engine_state: &EngineState,
@@ -75,11 +79,9 @@ impl Command for PluginDeclaration {
             PipelineData::Stream(stream, ..) => {
                 let values = stream.collect::<Vec<Value>>();
 
-                Value::List {
-                    vals: values,
-                    span: call.head,
-                }
+                Value::list(values, call.head)
             }
+            PipelineData::RawStream(stream, ..) => stream.into_value(),
         };
 
         // Create message to plugin to indicate that signature is required and
@@ -72,6 +72,7 @@ impl From<ShellError> for LabeledError {
                 msg,
                 span: None,
             },
+            ShellError::CalledFrom(trace) => (*trace.error).into(),
             err => LabeledError {
                 label: "Error - Add to LabeledError From<ShellError>".into(),
                 msg: err.to_string(),
@@ -235,10 +235,7 @@ mod tests {
                     val: 1.0,
                     span: Span { start: 0, end: 10 },
                 },
-                Value::String {
-                    val: "something".into(),
-                    span: Span { start: 0, end: 10 },
-                },
+                Value::string("something", Span { start: 0, end: 10 }),
             ],
             named: vec![(
                 Spanned {
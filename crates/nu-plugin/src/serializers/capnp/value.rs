@@ -1,5 +1,5 @@
 use crate::plugin_capnp::value;
-use nu_protocol::{ShellError, Span, Value};
+use nu_protocol::{Record, ShellError, Span, Value};
 
 pub(crate) fn serialize_value(value: &Value, mut builder: value::Builder) {
     let value_span = match value {
@@ -23,16 +23,17 @@ pub(crate) fn serialize_value(value: &Value, mut builder: value::Builder) {
             builder.set_string(val);
             *span
         }
-        Value::Record { cols, vals, span } => {
+        Value::Record { val, span } => {
             let mut record_builder = builder.reborrow().init_record();
 
-            let mut cols_builder = record_builder.reborrow().init_cols(cols.len() as u32);
-            cols.iter()
+            let mut cols_builder = record_builder.reborrow().init_cols(val.len() as u32);
+            val.columns()
+                .iter()
                 .enumerate()
                 .for_each(|(index, col)| cols_builder.set(index as u32, col.as_str()));
 
-            let mut values_builder = record_builder.reborrow().init_vals(vals.len() as u32);
-            vals.iter().enumerate().for_each(|(index, value)| {
+            let mut values_builder = record_builder.reborrow().init_vals(val.len() as u32);
+            val.values().iter().enumerate().for_each(|(index, value)| {
                 let inner_builder = values_builder.reborrow().get(index as u32);
                 serialize_value(value, inner_builder);
             });
@@ -81,7 +82,7 @@ pub(crate) fn deserialize_value(reader: value::Reader, head: Span) -> Result<Val
             let string = val
                 .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?
                 .to_string();
-            Ok(Value::String { val: string, span })
+            Ok(Value::string(string, span))
         }
         Ok(value::Record(record)) => {
             let record = record.map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
@@ -103,7 +104,10 @@ pub(crate) fn deserialize_value(reader: value::Reader, head: Span) -> Result<Val
                 .map(move |x| deserialize_value(x, span))
                 .collect::<Result<Vec<Value>, ShellError>>()?;
 
-            Ok(Value::Record { cols, vals, span })
+            let record = Record::from_raw_cols_vals(cols, vals, span)
+                .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
+
+            Ok(Value::record(record, span))
         }
         Ok(value::List(vals)) => {
             let values = vals.map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
@@ -113,10 +117,7 @@ pub(crate) fn deserialize_value(reader: value::Reader, head: Span) -> Result<Val
                 .map(move |x| deserialize_value(x, span))
                 .collect::<Result<Vec<Value>, ShellError>>()?;
 
-            Ok(Value::List {
-                vals: values_list,
-                span,
-            })
+            Ok(Value::list(values_list, span))
         }
         Err(capnp::NotInSchema(_)) => Ok(Value::Nothing { span: head }),
     }
@@ -175,10 +176,7 @@ mod tests {
                 val: 10.0,
                 span: Span { start: 3, end: 40 },
             },
-            Value::String {
-                val: "a string".into(),
-                span: Span { start: 4, end: 50 },
-            },
+            Value::string("a string", Span { start: 4, end: 50 }),
         ];
 
         for value in values {
@@ -225,16 +223,10 @@ mod tests {
                 val: 10.0,
                 span: Span { start: 3, end: 40 },
             },
-            Value::String {
-                val: "a string".into(),
-                span: Span { start: 4, end: 50 },
-            },
+            Value::string("a string", Span { start: 4, end: 50 }),
         ];
 
-        let value = Value::List {
-            vals: values,
-            span: Span { start: 1, end: 10 },
-        };
+        let value = Value::list(values, Span { start: 1, end: 10 });
 
         let mut buffer: Vec<u8> = Vec::new();
         write_buffer(&value, &mut buffer).expect("unable to serialize message");
@@ -262,10 +254,7 @@ mod tests {
                 val: 10.0,
                 span: Span { start: 3, end: 40 },
             },
-            Value::String {
-                val: "inner string".into(),
-                span: Span { start: 4, end: 50 },
-            },
+            Value::string("inner string", Span { start: 4, end: 50 }),
         ];
 
         let values = vec![
@@ -281,20 +270,11 @@ mod tests {
                 val: 66.6,
                 span: Span { start: 3, end: 40 },
             },
-            Value::String {
-                val: "a string".into(),
-                span: Span { start: 4, end: 50 },
-            },
-            Value::List {
-                vals: inner_values,
-                span: Span { start: 5, end: 60 },
-            },
+            Value::string("a string", Span { start: 4, end: 50 }),
+            Value::list(inner_values, Span { start: 5, end: 60 }),
         ];
 
-        let value = Value::List {
-            vals: values,
-            span: Span { start: 1, end: 10 },
-        };
+        let value = Value::list(values, Span { start: 1, end: 10 });
 
         let mut buffer: Vec<u8> = Vec::new();
         write_buffer(&value, &mut buffer).expect("unable to serialize message");
@@ -322,10 +302,7 @@ mod tests {
                 val: 10.0,
                 span: Span { start: 3, end: 40 },
             },
-            Value::String {
-                val: "inner string".into(),
-                span: Span { start: 4, end: 50 },
-            },
+            Value::string("inner string", Span { start: 4, end: 50 }),
         ];
 
         let vals = vec![
@@ -341,29 +318,16 @@ mod tests {
                 val: 66.6,
                 span: Span { start: 3, end: 40 },
             },
-            Value::String {
-                val: "a string".into(),
-                span: Span { start: 4, end: 50 },
-            },
-            Value::List {
-                vals: inner_values,
-                span: Span { start: 5, end: 60 },
-            },
+            Value::string("a string", Span { start: 4, end: 50 }),
+            Value::list(inner_values, Span { start: 5, end: 60 }),
         ];
 
-        let cols = vec![
-            "bool".to_string(),
-            "int".to_string(),
-            "float".to_string(),
-            "string".to_string(),
-            "list".to_string(),
-        ];
+        let cols = vec!["bool", "int", "float", "string", "list"];
 
-        let record = Value::Record {
-            cols,
-            vals,
-            span: Span { start: 1, end: 20 },
-        };
+        let record = Value::record(
+            cols.into_iter().zip(vals).collect(),
+            Span { start: 1, end: 20 },
+        );
 
         let mut buffer: Vec<u8> = Vec::new();
         write_buffer(&record, &mut buffer).expect("unable to serialize message");
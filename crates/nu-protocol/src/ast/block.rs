@@ -1,6 +1,7 @@
 use std::ops::{Index, IndexMut};
 
-use crate::{Signature, VarId};
+use crate::ir::IrBlock;
+use crate::{Signature, Span, VarId};
 
 use super::Statement;
 
@@ -9,6 +10,8 @@ pub struct Block {
     pub signature: Box<Signature>,
     pub stmts: Vec<Statement>,
     pub captures: Vec<VarId>,
+    pub span: Option<Span>,  // where the block was defined, if known
+    pub ir: Option<IrBlock>, // the block lowered to IR, when it has been compiled
 }
 
 impl Block {
@@ -47,6 +50,8 @@ impl Block {
             signature: Box::new(Signature::new("")),
             stmts: vec![],
             captures: vec![],
+            span: None,
+            ir: None,
         }
     }
 }
@@ -60,6 +65,8 @@ where
             signature: Box::new(Signature::new("")),
             stmts: stmts.collect(),
             captures: vec![],
+            span: None,
+            ir: None,
         }
     }
 }
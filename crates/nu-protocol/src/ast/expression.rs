@@ -130,12 +130,19 @@ impl Expression {
                 false
             }
             Expr::CellPath(_) => false,
-            Expr::ExternalCall(_, _, args) => {
+            Expr::ExternalCall(_, _, args, redirections, _) => {
                 for arg in args {
                     if arg.has_in_variable(working_set) {
                         return true;
                     }
                 }
+                for redirection in redirections {
+                    if let Some(target) = &redirection.target {
+                        if target.has_in_variable(working_set) {
+                            return true;
+                        }
+                    }
+                }
                 false
             }
             Expr::ImportPattern(_) => false,
@@ -278,10 +285,15 @@ impl Expression {
                 }
             }
             Expr::CellPath(_) => {}
-            Expr::ExternalCall(_, _, args) => {
+            Expr::ExternalCall(_, _, args, redirections, _) => {
                 for arg in args {
                     arg.replace_in_variable(working_set, new_var_id)
                 }
+                for redirection in redirections {
+                    if let Some(target) = &mut redirection.target {
+                        target.replace_in_variable(working_set, new_var_id)
+                    }
+                }
             }
             Expr::Filepath(_) => {}
             Expr::Float(_) => {}
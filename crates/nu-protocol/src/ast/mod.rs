@@ -6,6 +6,7 @@ mod expression;
 mod import_pattern;
 mod operator;
 mod pipeline;
+mod redirection;
 mod statement;
 
 pub use block::*;
@@ -16,4 +17,5 @@ pub use expression::*;
 pub use import_pattern::*;
 pub use operator::*;
 pub use pipeline::*;
+pub use redirection::*;
 pub use statement::*;
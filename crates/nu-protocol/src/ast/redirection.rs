@@ -0,0 +1,20 @@
+use super::Expression;
+use crate::Span;
+
+/// Which output of an external command a redirection applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedirectionSource {
+    Stdout,
+    Stderr,
+    StdoutAndStderr,
+}
+
+/// A redirection on an external call, like `err> errors.log`
+///
+/// Without a target, the output is sent into the next pipeline stage, as in `^cmd err>| lines`
+#[derive(Debug, Clone)]
+pub struct Redirection {
+    pub source: RedirectionSource,
+    pub span: Span,
+    pub target: Option<Expression>,
+}
@@ -4,6 +4,18 @@ use std::collections::HashMap;
 
 const ANIMATE_PROMPT_DEFAULT: bool = false;
 
+/// The size of the native stack code is evaluated on. Nu runs its main thread, the stages of a
+/// concurrent pipeline and the workers of par-each with it, so that custom commands can call each
+/// other as deeply as the default recursion limit allows
+pub const EVAL_STACK_SIZE: usize = 128 * 1024 * 1024;
+
+// The native stack a custom command call can take up. A call with an `if` in its body takes about
+// 36KB in a debug build, and about 64KB with one more block nested in it, so this leaves room for
+// bodies nesting a few blocks more. Release builds take a fraction of it
+const CALL_STACK_SIZE: usize = 128 * 1024;
+
+const RECURSION_LIMIT_DEFAULT: i64 = (EVAL_STACK_SIZE / CALL_STACK_SIZE) as i64;
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct EnvConversion {
     pub from_string: Option<(BlockId, Span)>,
@@ -16,7 +28,7 @@ impl EnvConversion {
 
         let mut conv_map = HashMap::new();
 
-        for (k, v) in record.0.iter().zip(record.1) {
+        for (k, v) in record {
             if (k == "from_string") || (k == "to_string") {
                 conv_map.insert(k.as_str(), (v.as_block()?, v.span()?));
             } else {
@@ -38,6 +50,32 @@ impl EnvConversion {
     }
 }
 
+/// Blocks the REPL runs at fixed points of its loop
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Hooks {
+    /// Run right before the prompt is drawn
+    pub pre_prompt: Vec<BlockId>,
+    /// Run after a line is entered, before it is evaluated
+    pub pre_execution: Vec<BlockId>,
+    /// Run with the old and new value whenever the named environment variable changes
+    pub env_change: HashMap<String, Vec<BlockId>>,
+}
+
+impl Hooks {
+    /// A hook can be given as a single block or as a list of blocks
+    fn blocks_from_value(value: &Value) -> Result<Vec<BlockId>, ShellError> {
+        match value {
+            Value::Block { val, .. } => Ok(vec![*val]),
+            Value::List { vals, .. } => vals.iter().map(|v| v.as_block()).collect(),
+            x => Err(ShellError::UnsupportedConfigValue(
+                "block or list of blocks".into(),
+                x.get_type().to_string(),
+                x.span()?,
+            )),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Config {
     pub filesize_metric: bool,
@@ -51,6 +89,7 @@ pub struct Config {
     pub filesize_format: String,
     pub use_ansi_coloring: bool,
     pub env_conversions: HashMap<String, EnvConversion>,
+    pub hooks: Hooks,
 }
 
 impl Default for Config {
@@ -67,6 +106,7 @@ impl Default for Config {
             filesize_format: "auto".into(),
             use_ansi_coloring: true,
             env_conversions: HashMap::new(), // TODO: Add default conversoins
+            hooks: Hooks::default(),
         }
     }
 }
@@ -89,7 +129,7 @@ impl Value {
 
         let mut config = Config::default();
 
-        for (key, value) in v.0.iter().zip(v.1) {
+        for (key, value) in v {
             match key.as_str() {
                 "filesize_metric" => {
                     config.filesize_metric = value.as_bool()?;
@@ -101,15 +141,10 @@ impl Value {
                     config.use_ls_colors = value.as_bool()?;
                 }
                 "color_config" => {
-                    let (cols, inner_vals) = value.as_record()?;
                     let mut hm = HashMap::new();
-                    for (k, v) in cols.iter().zip(inner_vals) {
+                    for (k, v) in value.as_record()? {
                         match &v {
-                            Value::Record {
-                                cols: inner_cols,
-                                vals: inner_vals,
-                                span: _,
-                            } => {
+                            Value::Record { val: inner, .. } => {
                                 // make a string from our config.color_config section that
                                 // looks like this: { fg: "#rrggbb" bg: "#rrggbb" attr: "abc", }
                                 // the real key here was to have quotes around the values but not
@@ -118,9 +153,8 @@ impl Value {
                                 // maybe there's a better way to generate this but i'm not sure
                                 // what it is.
                                 let key = k.to_string();
-                                let mut val: String = inner_cols
+                                let mut val: String = inner
                                     .iter()
-                                    .zip(inner_vals)
                                     .map(|(x, y)| {
                                         let clony = y.clone();
                                         format!("{}: \"{}\" ", x, clony.into_string(", ", &config))
@@ -166,16 +200,30 @@ impl Value {
                     config.filesize_format = value.as_string()?.to_lowercase();
                 }
                 "env_conversions" => {
-                    let (env_vars, conversions) = value.as_record()?;
                     let mut env_conversions = HashMap::new();
 
-                    for (env_var, record) in env_vars.iter().zip(conversions) {
+                    for (env_var, record) in value.as_record()? {
                         // println!("{}: {:?}", env_var, record);
                         env_conversions.insert(env_var.into(), EnvConversion::from_record(record)?);
                     }
 
                     config.env_conversions = env_conversions;
                 }
+                "pre_prompt" => {
+                    config.hooks.pre_prompt = Hooks::blocks_from_value(value)?;
+                }
+                "pre_execution" => {
+                    config.hooks.pre_execution = Hooks::blocks_from_value(value)?;
+                }
+                "env_change" => {
+                    let mut env_change = HashMap::new();
+
+                    for (env_var, hook) in value.as_record()? {
+                        env_change.insert(env_var.into(), Hooks::blocks_from_value(hook)?);
+                    }
+
+                    config.hooks.env_change = env_change;
+                }
                 _ => {}
             }
         }
@@ -183,3 +231,29 @@ impl Value {
         Ok(config)
     }
 }
+
+/// Whether `concurrent_pipelines` is on in the config record, so that the stages of a pipeline run
+/// at the same time. This is checked for every pipeline, so it's read from the config record on
+/// its own, instead of converting the whole config into a `Config` each time
+pub fn concurrent_pipelines(config: Option<&Value>) -> bool {
+    match config {
+        Some(Value::Record { val, .. }) => matches!(
+            val.get("concurrent_pipelines"),
+            Some(Value::Bool { val: true, .. })
+        ),
+        _ => false,
+    }
+}
+
+/// The `recursion_limit` in the config record: how deeply custom commands can call each other
+/// before the call fails. It's read on its own for the same reason, as it's checked for every
+/// custom command call
+pub fn recursion_limit(config: Option<&Value>) -> i64 {
+    match config {
+        Some(Value::Record { val, .. }) => match val.get("recursion_limit") {
+            Some(Value::Int { val, .. }) => *val,
+            _ => RECURSION_LIMIT_DEFAULT,
+        },
+        _ => RECURSION_LIMIT_DEFAULT,
+    }
+}
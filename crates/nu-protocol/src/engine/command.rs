@@ -2,7 +2,7 @@ use std::path::PathBuf;
 
 use crate::{ast::Call, BlockId, Example, PipelineData, ShellError, Signature};
 
-use super::{EngineState, Stack};
+use super::{Capability, EngineState, Stack};
 
 pub trait Command: Send + Sync + CommandClone {
     fn name(&self) -> &str;
@@ -54,6 +54,24 @@ pub trait Command: Send + Sync + CommandClone {
         None
     }
 
+    // What the command does outside of its input and arguments, which the engine checks against
+    // its permissions before running it
+    fn capabilities(&self) -> Vec<Capability> {
+        Vec::new()
+    }
+
+    // Whether the command takes in the stderr of an external before it, as well as its stdout,
+    // instead of leaving the stderr on the terminal
+    fn collects_stderr(&self) -> bool {
+        false
+    }
+
+    // Whether the command changes the environment of the caller, like let-env or cd do, which a
+    // pipeline stage running with a copy of the stack would lose
+    fn changes_env(&self) -> bool {
+        false
+    }
+
     // If command is a block i.e. def blah [] { }, get the block id
     fn get_block_id(&self) -> Option<BlockId> {
         None
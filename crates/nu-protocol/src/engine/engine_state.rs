@@ -1,12 +1,12 @@
-use super::Command;
+use super::{Command, JobTable, Permissions};
 use crate::{
-    ast::Block, BlockId, DeclId, Example, Overlay, OverlayId, ShellError, Signature, Span, Type,
-    VarId,
+    ast::Block, BlockId, DeclId, Example, Overlay, OverlayId, ParseWarning, ShellError, Signature,
+    Span, Type, VarId,
 };
 use core::panic;
 use std::{
     collections::HashMap,
-    sync::{atomic::AtomicBool, Arc},
+    sync::{atomic::AtomicBool, Arc, Mutex},
 };
 
 #[cfg(feature = "plugin")]
@@ -140,6 +140,8 @@ pub struct EngineState {
     overlays: im::Vector<Overlay>,
     pub scope: im::Vector<ScopeFrame>,
     pub ctrlc: Option<Arc<AtomicBool>>,
+    pub jobs: Arc<Mutex<JobTable>>,
+    pub permissions: Permissions,
     #[cfg(feature = "plugin")]
     pub plugin_signatures: Option<PathBuf>,
 }
@@ -160,6 +162,8 @@ impl EngineState {
             overlays: im::vector![],
             scope: im::vector![ScopeFrame::new()],
             ctrlc: None,
+            jobs: Arc::new(Mutex::new(JobTable::default())),
+            permissions: Permissions::all(),
             #[cfg(feature = "plugin")]
             plugin_signatures: None,
         }
@@ -329,6 +333,47 @@ impl EngineState {
         None
     }
 
+    /// All the commands with the name, in the order they're looked up: the one `find_decl` finds,
+    /// then the ones it shadows. Built-in commands are included even when a command defined with
+    /// the same name took their place in the scope, unless they were hidden
+    pub fn find_decls(&self, name: &[u8]) -> Vec<DeclId> {
+        let mut visibility: Visibility = Visibility::new();
+        let mut decl_ids = vec![];
+
+        for scope in self.scope.iter().rev() {
+            visibility.append(&scope.visibility);
+
+            if let Some(decl_id) = scope.decls.get(name) {
+                if visibility.is_decl_id_visible(decl_id) && !decl_ids.contains(decl_id) {
+                    decl_ids.push(*decl_id);
+                }
+            }
+        }
+
+        for (decl_id, decl) in self.decls.iter().enumerate() {
+            if decl.name().as_bytes() == name
+                && decl.get_block_id().is_none()
+                && decl.is_plugin().is_none()
+                && visibility.is_decl_id_visible(&decl_id)
+                && !decl_ids.contains(&decl_id)
+            {
+                decl_ids.push(decl_id);
+            }
+        }
+
+        decl_ids
+    }
+
+    pub fn find_alias(&self, name: &[u8]) -> Option<&[Span]> {
+        for scope in self.scope.iter().rev() {
+            if let Some(spans) = scope.aliases.get(name) {
+                return Some(spans);
+            }
+        }
+
+        None
+    }
+
     #[cfg(feature = "plugin")]
     pub fn plugin_decls(&self) -> impl Iterator<Item = &Box<dyn Command + 'static>> {
         let mut unique_plugin_decls = HashMap::new();
@@ -551,6 +596,7 @@ pub struct StateDelta {
     blocks: Vec<Block>,           // indexed by BlockId
     overlays: Vec<Overlay>,       // indexed by OverlayId
     pub scope: Vec<ScopeFrame>,
+    warnings: Vec<ParseWarning>, // found while parsing, not kept when merged
     #[cfg(feature = "plugin")]
     plugins_changed: bool, // marks whether plugin file should be updated
 }
@@ -592,6 +638,7 @@ impl<'a> StateWorkingSet<'a> {
                 blocks: vec![],
                 overlays: vec![],
                 scope: vec![ScopeFrame::new()],
+                warnings: vec![],
                 #[cfg(feature = "plugin")]
                 plugins_changed: false,
             },
@@ -646,6 +693,17 @@ impl<'a> StateWorkingSet<'a> {
         }
     }
 
+    pub fn add_warning(&mut self, warning: ParseWarning) {
+        // The parser can go over the same code more than once, trying out different shapes
+        if !self.delta.warnings.contains(&warning) {
+            self.delta.warnings.push(warning);
+        }
+    }
+
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.delta.warnings
+    }
+
     pub fn add_predecl(&mut self, decl: Box<dyn Command>) -> Option<DeclId> {
         let name = decl.name().as_bytes().to_vec();
 
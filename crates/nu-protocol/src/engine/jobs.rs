@@ -0,0 +1,83 @@
+use std::process::Child;
+
+/// An external command left running in the background, started with a trailing `&`
+#[derive(Debug)]
+pub struct Job {
+    pub id: usize,
+    pub command: String,
+    pub child: Child,
+}
+
+impl Job {
+    /// What the job is doing: `running`, `exited (<code>)` or `killed`
+    pub fn status(&mut self) -> String {
+        match self.child.try_wait() {
+            Ok(None) => "running".to_string(),
+            Ok(Some(status)) => match status.code() {
+                Some(code) => format!("exited ({})", code),
+                None => "killed".to_string(),
+            },
+            Err(err) => format!("unknown ({})", err),
+        }
+    }
+}
+
+/// The jobs running in the background of this shell
+///
+/// Job ids are handed out in order and never reused, so an id keeps pointing to the same job,
+/// or to nothing once it's gone.
+#[derive(Debug, Default)]
+pub struct JobTable {
+    next_id: usize,
+    jobs: Vec<Job>,
+}
+
+impl JobTable {
+    pub fn add(&mut self, command: String, child: Child) -> usize {
+        self.next_id += 1;
+
+        self.jobs.push(Job {
+            id: self.next_id,
+            command,
+            child,
+        });
+
+        self.next_id
+    }
+
+    pub fn remove(&mut self, id: usize) -> Option<Job> {
+        let idx = self.jobs.iter().position(|job| job.id == id)?;
+
+        Some(self.jobs.remove(idx))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Job> {
+        self.jobs.iter_mut()
+    }
+
+    /// Puts a job taken out with `remove` back, under the same id
+    pub fn put_back(&mut self, job: Job) {
+        let idx = self
+            .jobs
+            .iter()
+            .position(|other| other.id > job.id)
+            .unwrap_or(self.jobs.len());
+
+        self.jobs.insert(idx, job);
+    }
+
+    /// Takes the jobs that finished out of the table
+    pub fn take_finished(&mut self) -> Vec<Job> {
+        let mut finished = vec![];
+        let mut idx = 0;
+        while idx < self.jobs.len() {
+            if matches!(self.jobs[idx].child.try_wait(), Ok(None)) {
+                idx += 1;
+            } else {
+                finished.push(self.jobs.remove(idx));
+            }
+        }
+
+        finished
+    }
+}
@@ -1,9 +1,15 @@
 mod call_info;
 mod command;
 mod engine_state;
+mod jobs;
+mod permissions;
+mod profiler;
 mod stack;
 
 pub use call_info::*;
 pub use command::*;
 pub use engine_state::*;
+pub use jobs::*;
+pub use permissions::*;
+pub use profiler::*;
 pub use stack::*;
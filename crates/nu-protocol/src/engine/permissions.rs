@@ -0,0 +1,136 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Something a command can do outside of the values it's given, which the engine can be set up
+/// not to allow
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capability {
+    /// Reading files or directories. The paths a command is given have to be under one of the
+    /// read roots, and so does the current directory when it takes paths but isn't given any
+    FilesystemRead,
+    /// Creating, changing or removing files or directories. The paths a command is given have to
+    /// be under one of the write roots
+    FilesystemWrite,
+    /// Running external commands, or anything else that starts or signals other processes
+    Externals,
+    Network,
+    Plugins,
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Capability::FilesystemRead => "filesystem read",
+            Capability::FilesystemWrite => "filesystem write",
+            Capability::Externals => "externals",
+            Capability::Network => "network",
+            Capability::Plugins => "plugins",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// What the commands run by an engine are allowed to do
+///
+/// Everything is allowed by default. A root of `None` doesn't limit the paths, while an empty list
+/// of roots doesn't allow any. Commands are checked against the capabilities they declare before
+/// they run, so files a script reads while it's parsed, with `source` or `use`, aren't covered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Permissions {
+    pub read_roots: Option<Vec<PathBuf>>,
+    pub write_roots: Option<Vec<PathBuf>>,
+    pub externals: bool,
+    pub network: bool,
+    pub plugins: bool,
+}
+
+impl Default for Permissions {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl Permissions {
+    pub fn all() -> Self {
+        Self {
+            read_roots: None,
+            write_roots: None,
+            externals: true,
+            network: true,
+            plugins: true,
+        }
+    }
+
+    /// Nothing outside of evaluating the script itself. Add to it with the `allow_*` methods
+    pub fn none() -> Self {
+        Self {
+            read_roots: Some(vec![]),
+            write_roots: Some(vec![]),
+            externals: false,
+            network: false,
+            plugins: false,
+        }
+    }
+
+    pub fn allow_read(mut self, root: impl Into<PathBuf>) -> Self {
+        if let Some(roots) = &mut self.read_roots {
+            roots.push(root.into());
+        }
+        self
+    }
+
+    /// Allow writing under `root`, which allows reading under it too
+    pub fn allow_write(mut self, root: impl Into<PathBuf>) -> Self {
+        let root = root.into();
+        if let Some(roots) = &mut self.write_roots {
+            roots.push(root.clone());
+        }
+        self.allow_read(root)
+    }
+
+    pub fn allow_externals(mut self, allow: bool) -> Self {
+        self.externals = allow;
+        self
+    }
+
+    pub fn allow_network(mut self, allow: bool) -> Self {
+        self.network = allow;
+        self
+    }
+
+    pub fn allow_plugins(mut self, allow: bool) -> Self {
+        self.plugins = allow;
+        self
+    }
+
+    /// Whether a command with the capability can run at all, before looking at its paths
+    pub fn allows(&self, capability: Capability) -> bool {
+        let roots = match capability {
+            Capability::FilesystemRead => &self.read_roots,
+            Capability::FilesystemWrite => &self.write_roots,
+            Capability::Externals => return self.externals,
+            Capability::Network => return self.network,
+            Capability::Plugins => return self.plugins,
+        };
+
+        !matches!(roots, Some(roots) if roots.is_empty())
+    }
+
+    /// Whether the capability covers `path`, which is expected to be absolute with its symlinks
+    /// resolved. The roots are resolved here, as far as they exist
+    pub fn allows_path(&self, capability: Capability, path: &Path) -> bool {
+        let roots = match capability {
+            Capability::FilesystemRead => &self.read_roots,
+            Capability::FilesystemWrite => &self.write_roots,
+            _ => return self.allows(capability),
+        };
+
+        match roots {
+            Some(roots) => roots.iter().any(|root| {
+                let root = root.canonicalize().unwrap_or_else(|_| root.clone());
+                path.starts_with(root)
+            }),
+            None => true,
+        }
+    }
+}
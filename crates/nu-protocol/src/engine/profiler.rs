@@ -0,0 +1,145 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::engine::EngineState;
+use crate::{record, Span, Value};
+
+/// Times the pipeline elements evaluated with a stack that carries it
+///
+/// The profiler handed to the stack of a custom command, or of a block run by a command, points
+/// at the element that called it, so the elements evaluated in there show up nested under it.
+/// Stacks cloned for other threads keep pointing at the same report.
+#[derive(Debug, Clone, Default)]
+pub struct Profiler {
+    tree: Arc<Mutex<ProfileTree>>,
+    parent: Option<usize>,
+}
+
+#[derive(Debug, Default)]
+struct ProfileTree {
+    nodes: Vec<ProfileNode>,
+    roots: Vec<usize>,
+}
+
+#[derive(Debug)]
+struct ProfileNode {
+    span: Span,
+    calls: i64,
+    total: Duration,
+    children: Vec<usize>,
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count a run of the element at `span`. The profiler returned is the one to evaluate the
+    /// element with, and to give the time it took to with `exit`
+    pub fn enter(&self, span: Span) -> Profiler {
+        let mut tree = match self.tree.lock() {
+            Ok(tree) => tree,
+            Err(_) => return self.clone(),
+        };
+
+        let siblings = match self.parent {
+            Some(parent) => &tree.nodes[parent].children,
+            None => &tree.roots,
+        };
+        let existing = siblings
+            .iter()
+            .copied()
+            .find(|&idx| tree.nodes[idx].span == span);
+
+        let node = match existing {
+            Some(node) => node,
+            None => {
+                let node = tree.nodes.len();
+                tree.nodes.push(ProfileNode {
+                    span,
+                    calls: 0,
+                    total: Duration::ZERO,
+                    children: vec![],
+                });
+                match self.parent {
+                    Some(parent) => tree.nodes[parent].children.push(node),
+                    None => tree.roots.push(node),
+                }
+                node
+            }
+        };
+        tree.nodes[node].calls += 1;
+
+        Profiler {
+            tree: self.tree.clone(),
+            parent: Some(node),
+        }
+    }
+
+    /// Add the time one run of the element this profiler was entered for took
+    pub fn exit(&self, elapsed: Duration) {
+        if let (Ok(mut tree), Some(node)) = (self.tree.lock(), self.parent) {
+            tree.nodes[node].total += elapsed;
+        }
+    }
+
+    /// The timings as a table with a row for each element, in the order they first ran.
+    /// The elements that ran inside an element are in its `children` column
+    pub fn report(&self, engine_state: &EngineState, span: Span) -> Value {
+        match self.tree.lock() {
+            Ok(tree) => report_rows(&tree, &tree.roots, engine_state, span),
+            Err(_) => Value::list(vec![], span),
+        }
+    }
+}
+
+fn report_rows(
+    tree: &ProfileTree,
+    nodes: &[usize],
+    engine_state: &EngineState,
+    span: Span,
+) -> Value {
+    let rows = nodes
+        .iter()
+        .map(|&idx| {
+            let node = &tree.nodes[idx];
+            let children_total: Duration = node
+                .children
+                .iter()
+                .map(|&child| tree.nodes[child].total)
+                .sum();
+            // The children are timed on their own, so together they can come out a hair over
+            // the element that ran them
+            let self_time = node.total.saturating_sub(children_total);
+
+            let source =
+                String::from_utf8_lossy(engine_state.get_span_contents(&node.span)).to_string();
+
+            Value::record(
+                record! {
+                    "source" => Value::string(source, span),
+                    "span" => Value::record(
+                        record! {
+                            "start" => Value::int(node.span.start as i64, span),
+                            "end" => Value::int(node.span.end as i64, span),
+                        },
+                        span,
+                    ),
+                    "calls" => Value::int(node.calls, span),
+                    "total" => Value::Duration {
+                        val: node.total.as_nanos() as i64,
+                        span,
+                    },
+                    "self" => Value::Duration {
+                        val: self_time.as_nanos() as i64,
+                        span,
+                    },
+                    "children" => report_rows(tree, &node.children, engine_state, span),
+                },
+                span,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    Value::list(rows, span)
+}
@@ -1,6 +1,8 @@
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
-use crate::{Config, ShellError, Value, VarId, CONFIG_VARIABLE_ID};
+use crate::engine::Profiler;
+use crate::{Config, Shared, ShellError, Value, VarId, CONFIG_VARIABLE_ID};
 
 /// A runtime value stack used during evaluation
 ///
@@ -23,10 +25,50 @@ use crate::{Config, ShellError, Value, VarId, CONFIG_VARIABLE_ID};
 pub struct Stack {
     /// Variables
     pub vars: HashMap<VarId, Value>,
-    /// Environment variables arranged as a stack to be able to recover values from parent scopes
-    pub env_vars: Vec<HashMap<String, Value>>,
+    /// Environment variables arranged as a stack to be able to recover values from parent scopes.
+    /// The scopes are shared with the stacks collected from this one until either side changes them
+    pub env_vars: Vec<Shared<HashMap<String, Value>>>,
+    /// Times the pipeline elements evaluated with this stack, when they are being profiled
+    pub profiler: Option<Profiler>,
+    /// How many custom command calls deep the evaluation with this stack is
+    pub recursion_depth: i64,
+    /// The exit code of an external whose output streamed on to the rest of the pipeline. It is
+    /// only known once the output has been read, on the thread reading it, so it's kept here and
+    /// read as `LAST_EXIT_CODE` until the env var is set again. Shared with the stacks collected
+    /// from this one
+    pub streamed_exit_code: Arc<Mutex<StreamedExitCode>>,
 }
 
+/// The exit code of the latest external to stream its output, once it's known
+///
+/// Every external that streams its output, and every change to `LAST_EXIT_CODE`, starts a new
+/// generation. An external only records its exit code if its generation is still the latest one,
+/// so one that finishes late doesn't overwrite the exit code of a command that ran after it.
+#[derive(Debug, Default)]
+pub struct StreamedExitCode {
+    generation: u64,
+    exit_code: Option<Value>,
+}
+
+/// Where an external that streams its output records its exit code, given by
+/// `Stack::streamed_exit_code_slot` when it starts
+pub struct ExitCodeSlot {
+    shared: Arc<Mutex<StreamedExitCode>>,
+    generation: u64,
+}
+
+impl ExitCodeSlot {
+    pub fn record(self, exit_code: Value) {
+        if let Ok(mut streamed) = self.shared.lock() {
+            if streamed.generation == self.generation {
+                streamed.exit_code = Some(exit_code);
+            }
+        }
+    }
+}
+
+const LAST_EXIT_CODE: &str = "LAST_EXIT_CODE";
+
 impl Default for Stack {
     fn default() -> Self {
         Self::new()
@@ -38,6 +80,9 @@ impl Stack {
         Stack {
             vars: HashMap::new(),
             env_vars: vec![],
+            profiler: None,
+            recursion_depth: 0,
+            streamed_exit_code: Arc::new(Mutex::new(StreamedExitCode::default())),
         }
     }
 
@@ -54,10 +99,14 @@ impl Stack {
     }
 
     pub fn add_env_var(&mut self, var: String, value: Value) {
+        if var == LAST_EXIT_CODE {
+            self.take_streamed_exit_code();
+        }
+
         if let Some(scope) = self.env_vars.last_mut() {
             scope.insert(var, value);
         } else {
-            self.env_vars.push(HashMap::from([(var, value)]));
+            self.env_vars.push(HashMap::from([(var, value)]).into());
         }
     }
 
@@ -72,9 +121,11 @@ impl Stack {
             }
         }
 
-        // FIXME: this is probably slow
         output.env_vars = self.env_vars.clone();
-        output.env_vars.push(HashMap::new());
+        output.env_vars.push(Shared::default());
+        output.profiler = self.profiler.clone();
+        output.recursion_depth = self.recursion_depth;
+        output.streamed_exit_code = self.streamed_exit_code.clone();
 
         let config = self
             .get_var(CONFIG_VARIABLE_ID)
@@ -89,13 +140,22 @@ impl Stack {
         let mut result = HashMap::new();
 
         for scope in &self.env_vars {
-            result.extend(scope.clone());
+            result.extend(scope.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+        if let Some(exit_code) = self.streamed_exit_code() {
+            result.insert(LAST_EXIT_CODE.into(), exit_code);
         }
 
         result
     }
 
     pub fn get_env_var(&self, name: &str) -> Option<Value> {
+        if name == LAST_EXIT_CODE {
+            if let Some(exit_code) = self.streamed_exit_code() {
+                return Some(exit_code);
+            }
+        }
+
         for scope in self.env_vars.iter().rev() {
             if let Some(v) = scope.get(name) {
                 return Some(v.clone());
@@ -106,15 +166,62 @@ impl Stack {
     }
 
     pub fn remove_env_var(&mut self, name: &str) -> Option<Value> {
+        if name == LAST_EXIT_CODE {
+            if let Some(exit_code) = self.take_streamed_exit_code() {
+                self.remove_env_var_from_scopes(name);
+                return Some(exit_code);
+            }
+        }
+
+        self.remove_env_var_from_scopes(name)
+    }
+
+    fn remove_env_var_from_scopes(&mut self, name: &str) -> Option<Value> {
         for scope in self.env_vars.iter_mut().rev() {
-            if let Some(v) = scope.remove(name) {
-                return Some(v);
+            // Only take a copy of a shared scope when it has the variable to remove
+            if scope.contains_key(name) {
+                return scope.remove(name);
             }
         }
 
         None
     }
 
+    /// Start the generation of an external that streams its output, which makes the externals
+    /// started before it too late to record theirs
+    pub fn streamed_exit_code_slot(&self) -> ExitCodeSlot {
+        let mut streamed = self
+            .streamed_exit_code
+            .lock()
+            .expect("internal error: exit code lock poisoned");
+        streamed.generation += 1;
+        streamed.exit_code = None;
+
+        ExitCodeSlot {
+            shared: self.streamed_exit_code.clone(),
+            generation: streamed.generation,
+        }
+    }
+
+    fn streamed_exit_code(&self) -> Option<Value> {
+        self.streamed_exit_code
+            .lock()
+            .expect("internal error: exit code lock poisoned")
+            .exit_code
+            .clone()
+    }
+
+    // LAST_EXIT_CODE is about to be set or removed, so the externals still streaming their output
+    // are too late to record theirs
+    fn take_streamed_exit_code(&mut self) -> Option<Value> {
+        let mut streamed = self
+            .streamed_exit_code
+            .lock()
+            .expect("internal error: exit code lock poisoned");
+        streamed.generation += 1;
+        streamed.exit_code.take()
+    }
+
     pub fn get_config(&self) -> Result<Config, ShellError> {
         let config = self.get_var(CONFIG_VARIABLE_ID);
 
@@ -134,7 +241,7 @@ impl Stack {
         }
         for (i, scope) in self.env_vars.iter().rev().enumerate() {
             println!("env vars, scope {} (from the last);", i);
-            for (var, val) in scope {
+            for (var, val) in scope.iter() {
                 println!("  {}: {:?}", var, val.clone().debug_value());
             }
         }
@@ -0,0 +1,130 @@
+//! A compact, linear form of a block, lowered from its AST once so that evaluating it doesn't
+//! have to walk the tree again every time.
+//!
+//! Every instruction writes its result to a register. Registers are written once and read once,
+//! which lets the interpreter move values out of them instead of cloning. The variables a block
+//! reads are given slots in its frame when it's compiled. A slot is filled by the `let` that
+//! declares the variable, while the ones that come from outside of the block, like parameters, are
+//! read from the stack where the caller put them. Operands that name a slot are read in place,
+//! like literal operands are read from the instruction.
+
+use crate::ast::{Call, Expression, Operator, PathMember, RangeOperator};
+use crate::{Span, Value, VarId};
+
+pub type RegId = usize;
+
+/// The place of a variable in the frame of the block
+pub type SlotId = usize;
+
+/// The register holding the input of the block
+pub const INPUT_REGISTER: RegId = 0;
+
+#[derive(Debug, Clone)]
+pub struct IrBlock {
+    pub instructions: Vec<Instruction>,
+    pub register_count: usize,
+    /// The variable kept in each slot
+    pub slots: Vec<VarId>,
+    /// The register holding the output once every instruction has run
+    pub output: RegId,
+}
+
+#[derive(Debug, Clone)]
+pub enum Operand {
+    Register(RegId),
+    /// A variable, read from its slot without taking a copy of it
+    Slot(SlotId, Span),
+    Literal(Value),
+}
+
+#[derive(Debug, Clone)]
+pub enum Instruction {
+    LoadLiteral {
+        dst: RegId,
+        value: Value,
+    },
+    /// Load one of the variables that are built when asked for, like `$nu`
+    LoadVariable {
+        dst: RegId,
+        var_id: VarId,
+        span: Span,
+    },
+    LoadSlot {
+        dst: RegId,
+        slot: SlotId,
+        span: Span,
+    },
+    /// Set a variable declared with `let`, which outputs nothing to `dst`. Variables that the AST
+    /// evaluator reads too, in a command's arguments or a nested block, are added to the stack
+    StoreSlot {
+        dst: RegId,
+        slot: SlotId,
+        src: RegId,
+        on_stack: bool,
+        span: Span,
+    },
+    BinaryOp {
+        dst: RegId,
+        lhs: Operand,
+        op: Operator,
+        op_span: Span,
+        rhs: Operand,
+    },
+    FollowCellPath {
+        dst: RegId,
+        src: RegId,
+        path: Vec<PathMember>,
+    },
+    BuildList {
+        dst: RegId,
+        items: Vec<RegId>,
+        span: Span,
+    },
+    BuildRecord {
+        dst: RegId,
+        cols: Vec<RegId>,
+        vals: Vec<RegId>,
+        span: Span,
+    },
+    BuildTable {
+        dst: RegId,
+        headers: Vec<(RegId, Span)>,
+        rows: Vec<Vec<RegId>>,
+        span: Span,
+    },
+    BuildRange {
+        dst: RegId,
+        from: Option<RegId>,
+        next: Option<RegId>,
+        to: Option<RegId>,
+        operator: RangeOperator,
+        span: Span,
+    },
+    /// Collect the contents of `src` into a value, like the output of a call or a subexpression
+    /// used as a value is
+    Collect {
+        dst: RegId,
+        src: RegId,
+        span: Span,
+    },
+    /// Run a command with the contents of `input` as its input
+    Call {
+        dst: RegId,
+        input: RegId,
+        call: Box<Call>,
+    },
+    /// Run an external with the contents of `input` as its input. Where it is in the pipeline
+    /// decides whether its output is streamed and where its stderr goes
+    External {
+        dst: RegId,
+        input: RegId,
+        expr: Box<Expression>,
+        last_expression: bool,
+        redirect_stderr: bool,
+    },
+    /// Evaluate an expression that has no instructions of its own with the AST evaluator
+    Eval {
+        dst: RegId,
+        expr: Box<Expression>,
+    },
+}
@@ -4,7 +4,9 @@ pub mod engine;
 mod example;
 mod exportable;
 mod id;
+pub mod ir;
 mod overlay;
+mod parse_warning;
 mod pipeline_data;
 mod shell_error;
 mod signature;
@@ -20,6 +22,7 @@ pub use example::*;
 pub use exportable::*;
 pub use id::*;
 pub use overlay::*;
+pub use parse_warning::*;
 pub use pipeline_data::*;
 pub use shell_error::*;
 pub use signature::*;
@@ -0,0 +1,56 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+use crate::Span;
+
+/// Something the parser found suspicious, which doesn't stop the code from running
+#[derive(Clone, Debug, PartialEq, Error, Diagnostic)]
+pub enum ParseWarning {
+    #[error("Unused variable.")]
+    #[diagnostic(
+        code(nu::parser::unused_variable),
+        url(docsrs),
+        severity(Warning),
+        help("Remove it, or start its name with an underscore if it's unused on purpose.")
+    )]
+    UnusedVariable(String, #[label("`{0}` is never used")] Span),
+
+    #[error("Definition shadows another command.")]
+    #[diagnostic(
+        code(nu::parser::shadowed_definition),
+        url(docsrs),
+        severity(Warning),
+        help("Give the definition another name, unless it's meant to replace `{0}` here.")
+    )]
+    ShadowedDefinition(String, #[label("hides the `{0}` command")] Span),
+
+    #[error("Unreachable code.")]
+    #[diagnostic(code(nu::parser::unreachable_code), url(docsrs), severity(Warning))]
+    UnreachableCode(
+        #[label("this code is never run")] Span,
+        #[label("because the shell exits here")] Span,
+    ),
+
+    #[error("Deprecated command.")]
+    #[diagnostic(
+        code(nu::parser::deprecated_command),
+        url(docsrs),
+        severity(Warning),
+        help("{1}")
+    )]
+    DeprecatedCommand(String, String, #[label("`{0}` is deprecated")] Span),
+
+    #[error("Deprecated flag.")]
+    #[diagnostic(
+        code(nu::parser::deprecated_flag),
+        url(docsrs),
+        severity(Warning),
+        help("{2}")
+    )]
+    DeprecatedFlag(
+        String,
+        String,
+        String,
+        #[label("`--{1}` of `{0}` is deprecated")] Span,
+    ),
+}
@@ -1,6 +1,6 @@
 use std::sync::{atomic::AtomicBool, Arc};
 
-use crate::{ast::PathMember, Config, ShellError, Span, Value, ValueStream};
+use crate::{ast::PathMember, Config, RawStream, ShellError, Span, Value, ValueStream};
 
 /// The foundational abstraction for input and output to commands
 ///
@@ -35,6 +35,7 @@ use crate::{ast::PathMember, Config, ShellError, Span, Value, ValueStream};
 pub enum PipelineData {
     Value(Value, Option<PipelineMetadata>),
     Stream(ValueStream, Option<PipelineMetadata>),
+    RawStream(RawStream, Option<PipelineMetadata>),
 }
 
 #[derive(Debug)]
@@ -45,6 +46,16 @@ pub struct PipelineMetadata {
 #[derive(Debug)]
 pub enum DataSource {
     Ls,
+    External(ExternalOutput),
+}
+
+/// The parts of an external command's output that don't travel in the stream of its stdout
+#[derive(Debug)]
+pub struct ExternalOutput {
+    /// The stderr of the command, if it was captured instead of going to the terminal
+    pub stderr: Option<RawStream>,
+    /// Yields the exit code of the command once it finished
+    pub exit_code: ValueStream,
 }
 
 impl PipelineData {
@@ -60,6 +71,7 @@ impl PipelineData {
                 vals: s.collect(),
                 span, // FIXME?
             },
+            PipelineData::RawStream(s, ..) => s.into_value(),
         }
     }
 
@@ -73,10 +85,11 @@ impl PipelineData {
         iter
     }
 
-    pub fn collect_string(self, separator: &str, config: &Config) -> String {
+    pub fn collect_string(self, separator: &str, config: &Config) -> Result<String, ShellError> {
         match self {
-            PipelineData::Value(v, ..) => v.into_string(separator, config),
-            PipelineData::Stream(s, ..) => s.into_string(separator, config),
+            PipelineData::Value(v, ..) => Ok(v.into_string(separator, config)),
+            PipelineData::Stream(s, ..) => Ok(s.into_string(separator, config)),
+            PipelineData::RawStream(s, ..) => s.into_string(),
         }
     }
 
@@ -92,6 +105,7 @@ impl PipelineData {
                 span: head,
             }
             .follow_cell_path(cell_path),
+            PipelineData::RawStream(stream, ..) => stream.into_value().follow_cell_path(cell_path),
             PipelineData::Value(v, ..) => v.follow_cell_path(cell_path),
         }
     }
@@ -109,6 +123,14 @@ impl PipelineData {
                 span: head,
             }
             .update_cell_path(cell_path, callback),
+            PipelineData::RawStream(..) => {
+                if let PipelineData::RawStream(stream, metadata) =
+                    std::mem::replace(self, PipelineData::new(head))
+                {
+                    *self = PipelineData::Value(stream.into_value(), metadata);
+                }
+                self.update_cell_path(cell_path, callback, head)
+            }
             PipelineData::Value(v, ..) => v.update_cell_path(cell_path, callback),
         }
     }
@@ -131,6 +153,11 @@ impl PipelineData {
             PipelineData::Value(Value::Range { val, .. }, ..) => {
                 Ok(val.into_range_iter()?.map(f).into_pipeline_data(ctrlc))
             }
+            // The chunks of a raw stream mean nothing on their own, so it's mapped as a whole
+            PipelineData::RawStream(stream, ..) => match f(stream.into_value()) {
+                Value::Error { error } => Err(error),
+                v => Ok(v.into_pipeline_data()),
+            },
             PipelineData::Value(v, ..) => match f(v) {
                 Value::Error { error } => Err(error),
                 v => Ok(v.into_pipeline_data()),
@@ -161,6 +188,9 @@ impl PipelineData {
                 Ok(iter) => Ok(iter.map(f).flatten().into_pipeline_data(ctrlc)),
                 Err(error) => Err(error),
             },
+            PipelineData::RawStream(stream, ..) => {
+                Ok(f(stream.into_value()).into_iter().into_pipeline_data(ctrlc))
+            }
             PipelineData::Value(v, ..) => Ok(f(v).into_iter().into_pipeline_data(ctrlc)),
         }
     }
@@ -182,6 +212,14 @@ impl PipelineData {
             PipelineData::Value(Value::Range { val, .. }, ..) => {
                 Ok(val.into_range_iter()?.filter(f).into_pipeline_data(ctrlc))
             }
+            PipelineData::RawStream(stream, ..) => {
+                let v = stream.into_value();
+                if f(&v) {
+                    Ok(v.into_pipeline_data())
+                } else {
+                    Ok(Value::Nothing { span: v.span()? }.into_pipeline_data())
+                }
+            }
             PipelineData::Value(v, ..) => {
                 if f(&v) {
                     Ok(v.into_pipeline_data())
@@ -248,6 +286,9 @@ impl Iterator for PipelineIterator {
             PipelineData::Value(Value::Nothing { .. }, ..) => None,
             PipelineData::Value(v, ..) => Some(std::mem::take(v)),
             PipelineData::Stream(stream, ..) => stream.next(),
+            PipelineData::RawStream(stream, ..) => stream
+                .next()
+                .map(|value| value.unwrap_or_else(|error| Value::Error { error })),
         }
     }
 }
@@ -1,8 +1,10 @@
-use miette::Diagnostic;
+use std::fmt;
+
+use miette::{Diagnostic, LabeledSpan, Severity};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::{ast::Operator, Span, Type};
+use crate::{ast::Operator, record, Span, Type, Value};
 
 /// The fundamental error type for the evaluation engine. These cases represent different kinds of errors
 /// the evaluator might face, along with helpful spans to label. An error renderer will take this error value
@@ -137,6 +139,59 @@ pub enum ShellError {
         #[label = "value originates here"] Span,
     ),
 
+    #[error("Column defined twice")]
+    #[diagnostic(code(nu::shell::column_defined_twice), url(docsrs))]
+    ColumnDefinedTwice(
+        String,
+        #[label("column '{0}' is defined more than once")] Span,
+    ),
+
+    #[error("Assertion failed")]
+    #[diagnostic(code(nu::shell::assertion_failed), url(docsrs))]
+    AssertionFailed(String, #[label("{0}")] Span),
+
+    #[error("{message}")]
+    #[diagnostic(code(nu::shell::assertion_not_equal), url(docsrs))]
+    AssertionNotEqual {
+        message: String,
+        left: String,
+        #[label("left is {left}")]
+        left_span: Span,
+        right: String,
+        #[label("right is {right}")]
+        right_span: Span,
+    },
+
+    #[error("`{command}` is not allowed to use {capability}")]
+    #[diagnostic(code(nu::shell::not_allowed), url(docsrs))]
+    NotAllowed {
+        command: String,
+        capability: String,
+        reason: String,
+        #[label("{reason}")]
+        span: Span,
+    },
+
+    #[error("Recursion limit ({limit}) reached")]
+    #[diagnostic(
+        code(nu::shell::recursion_limit_reached),
+        url(docsrs),
+        help("The limit can be changed with recursion_limit in the config.")
+    )]
+    RecursionLimitReached {
+        limit: i64,
+        #[label("this call is nested too deeply")]
+        span: Span,
+    },
+
+    #[error("Timed out")]
+    #[diagnostic(code(nu::shell::timed_out), url(docsrs))]
+    TimedOut(String, #[label("didn't finish within {0}")] Span),
+
+    #[error("Operation interrupted")]
+    #[diagnostic(code(nu::shell::interrupted), url(docsrs))]
+    Interrupted(#[label("interrupted here")] Span),
+
     #[error("Not a list value")]
     #[diagnostic(code(nu::shell::not_a_list), url(docsrs))]
     NotAList(
@@ -148,6 +203,10 @@ pub enum ShellError {
     #[diagnostic(code(nu::shell::external_command), url(docsrs))]
     ExternalCommand(String, #[label("{0}")] Span),
 
+    #[error("Job not found")]
+    #[diagnostic(code(nu::shell::job_not_found), url(docsrs))]
+    JobNotFound(#[label("no job with this id")] Span),
+
     #[error("Unsupported input")]
     #[diagnostic(code(nu::shell::unsupported_input), url(docsrs))]
     UnsupportedInput(String, #[label("{0}")] Span),
@@ -242,6 +301,12 @@ pub enum ShellError {
     #[diagnostic(code(nu::shell::missing_config_value), url(docsrs))]
     MissingConfigValue(String, #[label = "missing {0}"] Span),
 
+    /// An error that came out of custom command calls. It's shown like the error it wraps, with
+    /// the calls labeled as well
+    #[error("{0}")]
+    #[diagnostic(forward(0))]
+    CalledFrom(CallTrace),
+
     #[error("{0}")]
     #[diagnostic()]
     SpannedLabeledError(String, String, #[label("{1}")] Span),
@@ -255,8 +320,186 @@ pub enum ShellError {
     LabeledError(String, String),
 }
 
+impl ShellError {
+    /// Add a custom command call the error came out of
+    pub fn called_from(self, span: Span) -> ShellError {
+        match self {
+            ShellError::CalledFrom(mut trace) => {
+                trace.calls.push(span);
+                ShellError::CalledFrom(trace)
+            }
+            error => ShellError::CalledFrom(CallTrace {
+                error: Box::new(error),
+                calls: vec![span],
+            }),
+        }
+    }
+
+    /// The error that was raised, without the custom command calls it came out of. An error
+    /// coming out of a custom command is a `CalledFrom`, so match on this rather than on the
+    /// error itself
+    pub fn inner(&self) -> &ShellError {
+        match self {
+            ShellError::CalledFrom(trace) => &trace.error,
+            error => error,
+        }
+    }
+
+    /// Like `inner`, taking the error
+    pub fn into_inner(self) -> ShellError {
+        match self {
+            ShellError::CalledFrom(trace) => *trace.error,
+            error => error,
+        }
+    }
+
+    /// The error without the custom command calls it came out of, and the spans of those calls
+    pub fn trace(&self) -> (&ShellError, &[Span]) {
+        match self {
+            ShellError::CalledFrom(trace) => (&trace.error, &trace.calls),
+            error => (error, &[]),
+        }
+    }
+
+    /// The error as a record, for scripts handling it themselves. `code` tells which error it is,
+    /// like `nu::shell::division_by_zero`. The labels are a list of `{text, start, end}` records,
+    /// with the span of the first one in `span` as well, and the custom command calls it came out
+    /// of are a list of `{start, end}` records in `trace`
+    pub fn into_value(self, span: Span) -> Value {
+        let (error, calls) = self.trace();
+
+        let labels = error
+            .labels()
+            .map(|labels| labels.collect::<Vec<_>>())
+            .unwrap_or_default();
+        let error_span = match labels.first() {
+            Some(label) => Value::record(
+                record! {
+                    "start" => Value::int(label.offset() as i64, span),
+                    "end" => Value::int((label.offset() + label.len()) as i64, span),
+                },
+                span,
+            ),
+            None => Value::nothing(span),
+        };
+        let labels = labels
+            .iter()
+            .map(|label| {
+                Value::record(
+                    record! {
+                        "text" => Value::string(label.label().unwrap_or_default(), span),
+                        "start" => Value::int(label.offset() as i64, span),
+                        "end" => Value::int((label.offset() + label.len()) as i64, span),
+                    },
+                    span,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let trace = calls
+            .iter()
+            .map(|call| {
+                Value::record(
+                    record! {
+                        "start" => Value::int(call.start as i64, span),
+                        "end" => Value::int(call.end as i64, span),
+                    },
+                    span,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let code = match error.code() {
+            Some(code) => Value::string(code.to_string(), span),
+            None => Value::nothing(span),
+        };
+        let help = match error.help() {
+            Some(help) => Value::string(help.to_string(), span),
+            None => Value::nothing(span),
+        };
+
+        Value::record(
+            record! {
+                "msg" => Value::string(error.to_string(), span),
+                "code" => code,
+                "labels" => Value::list(labels, span),
+                "span" => error_span,
+                "help" => help,
+                "trace" => Value::list(trace, span),
+            },
+            span,
+        )
+    }
+}
+
+/// An error with the spans of the custom command calls it came out of, from the innermost one out
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallTrace {
+    pub error: Box<ShellError>,
+    pub calls: Vec<Span>,
+}
+
+impl fmt::Display for CallTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl std::error::Error for CallTrace {}
+
+impl Diagnostic for CallTrace {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.error.code()
+    }
+
+    fn severity(&self) -> Option<Severity> {
+        self.error.severity()
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.error.help()
+    }
+
+    fn url<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.error.url()
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        // A recursive command is called from the same place over and over, which is labeled once
+        let mut seen = vec![];
+        let calls = self
+            .calls
+            .iter()
+            .filter(move |call| {
+                let new = !seen.contains(*call);
+                seen.push(**call);
+                new
+            })
+            .map(|call| {
+                LabeledSpan::new(
+                    Some("called from here".into()),
+                    call.start,
+                    call.end - call.start,
+                )
+            });
+
+        match self.error.labels() {
+            Some(labels) => Some(Box::new(labels.chain(calls))),
+            None => Some(Box::new(calls)),
+        }
+    }
+}
+
 impl From<std::io::Error> for ShellError {
     fn from(input: std::io::Error) -> ShellError {
+        // Reading a raw stream fails with the stream's own error inside the I/O error
+        if matches!(input.get_ref(), Some(inner) if inner.is::<ShellError>()) {
+            return *input
+                .into_inner()
+                .and_then(|inner| inner.downcast::<ShellError>().ok())
+                .expect("internal error: the I/O error was checked to wrap a ShellError");
+        }
+
         ShellError::IOError(format!("{:?}", input))
     }
 }
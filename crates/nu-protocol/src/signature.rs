@@ -19,6 +19,9 @@ pub struct Flag {
     pub desc: String,
     // For custom commands
     pub var_id: Option<VarId>,
+    // What to use instead, when the flag is on its way out
+    #[serde(default)]
+    pub deprecated: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -51,6 +54,7 @@ pub enum Category {
     Viewers,
     Hash,
     Generators,
+    Testing,
     Custom(String),
 }
 
@@ -76,6 +80,7 @@ impl std::fmt::Display for Category {
             Category::Viewers => "viewers",
             Category::Hash => "hash",
             Category::Generators => "generators",
+            Category::Testing => "testing",
             Category::Custom(name) => name,
         };
 
@@ -96,6 +101,9 @@ pub struct Signature {
     pub creates_scope: bool,
     // Signature category used to classify commands stored in the list of declarations
     pub category: Category,
+    // What to use instead, when the command is on its way out
+    #[serde(default)]
+    pub deprecated: Option<String>,
 }
 
 impl PartialEq for Signature {
@@ -121,6 +129,7 @@ impl Signature {
             desc: "Display this help message".into(),
             required: false,
             var_id: None,
+            deprecated: None,
         };
 
         Signature {
@@ -134,6 +143,7 @@ impl Signature {
             is_filter: false,
             creates_scope: false,
             category: Category::Default,
+            deprecated: None,
         }
     }
     pub fn build(name: impl Into<String>) -> Signature {
@@ -213,6 +223,7 @@ impl Signature {
             required: false,
             desc: desc.into(),
             var_id: None,
+            deprecated: None,
         });
 
         self
@@ -235,6 +246,7 @@ impl Signature {
             required: true,
             desc: desc.into(),
             var_id: None,
+            deprecated: None,
         });
 
         self
@@ -256,6 +268,7 @@ impl Signature {
             required: false,
             desc: desc.into(),
             var_id: None,
+            deprecated: None,
         });
 
         self
@@ -268,6 +281,28 @@ impl Signature {
         self
     }
 
+    /// Mark the command as deprecated, so that the parser warns about calls to it. The reason
+    /// says what to use instead
+    pub fn deprecated(mut self, reason: impl Into<String>) -> Signature {
+        self.deprecated = Some(reason.into());
+        self
+    }
+
+    /// Mark a flag added before as deprecated, so that the parser warns when it's given
+    pub fn deprecated_flag(mut self, name: &str, reason: impl Into<String>) -> Signature {
+        let flag = self.named.iter_mut().find(|flag| flag.long == name);
+        debug_assert!(
+            flag.is_some(),
+            "There is no flag to deprecate named {}",
+            name
+        );
+
+        if let Some(flag) = flag {
+            flag.deprecated = Some(reason.into());
+        }
+        self
+    }
+
     /// Sets that signature will create a scope as it parses
     pub fn creates_scope(mut self) -> Signature {
         self.creates_scope = true;
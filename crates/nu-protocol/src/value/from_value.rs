@@ -141,7 +141,7 @@ impl FromValue for String {
         // FIXME: we may want to fail a little nicer here
         match v {
             Value::CellPath { val, .. } => Ok(val.into_string()),
-            Value::String { val, .. } => Ok(val.clone()),
+            Value::String { val, .. } => Ok(val.to_string()),
             v => Err(ShellError::CantConvert(
                 "string".into(),
                 v.get_type().to_string(),
@@ -156,7 +156,7 @@ impl FromValue for Spanned<String> {
         Ok(Spanned {
             item: match v {
                 Value::CellPath { val, .. } => val.into_string(),
-                Value::String { val, .. } => val.clone(),
+                Value::String { val, .. } => val.to_string(),
                 v => {
                     return Err(ShellError::CantConvert(
                         "string".into(),
@@ -177,7 +177,7 @@ impl FromValue for Vec<String> {
             Value::List { vals, .. } => vals
                 .iter()
                 .map(|val| match val {
-                    Value::String { val, .. } => Ok(val.clone()),
+                    Value::String { val, .. } => Ok(val.to_string()),
                     c => Err(ShellError::CantConvert(
                         "string".into(),
                         c.get_type().to_string(),
@@ -201,7 +201,7 @@ impl FromValue for CellPath {
             Value::CellPath { val, .. } => Ok(val.clone()),
             Value::String { val, .. } => Ok(CellPath {
                 members: vec![PathMember::String {
-                    val: val.clone(),
+                    val: val.to_string(),
                     span,
                 }],
             }),
@@ -342,7 +342,7 @@ impl FromValue for Vec<Value> {
     fn from_value(v: &Value) -> Result<Self, ShellError> {
         // FIXME: we may want to fail a little nicer here
         match v {
-            Value::List { vals, .. } => Ok(vals.clone()),
+            Value::List { vals, .. } => Ok(vals.to_vec()),
             v => Err(ShellError::CantConvert(
                 "Vector of values".into(),
                 v.get_type().to_string(),
@@ -2,6 +2,8 @@ mod custom_value;
 mod from;
 mod from_value;
 mod range;
+mod record;
+mod shared;
 mod stream;
 mod unit;
 
@@ -12,7 +14,9 @@ pub use from_value::FromValue;
 use indexmap::map::IndexMap;
 use num_format::{Locale, ToFormattedString};
 pub use range::*;
+pub use record::Record;
 use serde::{Deserialize, Serialize};
+pub use shared::Shared;
 pub use stream::*;
 use sys_locale::get_locale;
 pub use unit::*;
@@ -29,6 +33,10 @@ pub use custom_value::CustomValue;
 use crate::ShellError;
 
 /// Core structured values that pass through the pipeline in engine-q
+///
+/// The contents of strings, records and lists are kept in [`Shared`] storage, so copying a value
+/// doesn't copy them. Build these with [`Value::string`], [`Value::record`] and [`Value::list`]
+/// rather than with the variants, which need the contents turned into a `Shared` with `.into()`.
 #[derive(Debug, Serialize, Deserialize)]
 pub enum Value {
     Bool {
@@ -60,16 +68,16 @@ pub enum Value {
         span: Span,
     },
     String {
-        val: String,
+        val: Shared<String>,
         span: Span,
     },
     Record {
-        cols: Vec<String>,
-        vals: Vec<Value>,
+        #[serde(flatten)]
+        val: Shared<Record>,
         span: Span,
     },
     List {
-        vals: Vec<Value>,
+        vals: Shared<Vec<Value>>,
         span: Span,
     },
     Block {
@@ -131,9 +139,8 @@ impl Clone for Value {
                 val: val.clone(),
                 span: *span,
             },
-            Value::Record { cols, vals, span } => Value::Record {
-                cols: cols.clone(),
-                vals: vals.clone(),
+            Value::Record { val, span } => Value::Record {
+                val: val.clone(),
                 span: *span,
             },
             Value::List { vals, span } => Value::List {
@@ -173,6 +180,17 @@ impl Value {
         }
     }
 
+    pub fn as_str(&self) -> Result<&str, ShellError> {
+        match self {
+            Value::String { val, .. } => Ok(val),
+            x => Err(ShellError::CantConvert(
+                "string".into(),
+                x.get_type().to_string(),
+                self.span()?,
+            )),
+        }
+    }
+
     pub fn as_block(&self) -> Result<BlockId, ShellError> {
         match self {
             Value::Block { val, .. } => Ok(*val),
@@ -184,9 +202,9 @@ impl Value {
         }
     }
 
-    pub fn as_record(&self) -> Result<(&[String], &[Value]), ShellError> {
+    pub fn as_record(&self) -> Result<&Record, ShellError> {
         match self {
-            Value::Record { cols, vals, .. } => Ok((cols, vals)),
+            Value::Record { val, .. } => Ok(val),
             x => Err(ShellError::CantConvert(
                 "record".into(),
                 x.get_type().to_string(),
@@ -206,6 +224,30 @@ impl Value {
         }
     }
 
+    /// Take the record out, copying it only if it's shared with another value
+    pub fn into_record(self) -> Result<Record, ShellError> {
+        match self {
+            Value::Record { val, .. } => Ok(val.into_inner()),
+            x => Err(ShellError::CantConvert(
+                "record".into(),
+                x.get_type().to_string(),
+                x.span()?,
+            )),
+        }
+    }
+
+    /// Take the list out, copying it only if it's shared with another value
+    pub fn into_list(self) -> Result<Vec<Value>, ShellError> {
+        match self {
+            Value::List { vals, .. } => Ok(vals.into_inner()),
+            x => Err(ShellError::CantConvert(
+                "list".into(),
+                x.get_type().to_string(),
+                x.span()?,
+            )),
+        }
+    }
+
     pub fn as_bool(&self) -> Result<bool, ShellError> {
         match self {
             Value::Bool { val, .. } => Ok(*val),
@@ -296,12 +338,9 @@ impl Value {
             Value::Date { .. } => Type::Date,
             Value::Range { .. } => Type::Range,
             Value::String { .. } => Type::String,
-            Value::Record { cols, vals, .. } => Type::Record(
-                cols.iter()
-                    .zip(vals.iter())
-                    .map(|(x, y)| (x.clone(), y.get_type()))
-                    .collect(),
-            ),
+            Value::Record { val, .. } => {
+                Type::Record(val.iter().map(|(x, y)| (x.clone(), y.get_type())).collect())
+            }
             Value::List { .. } => Type::List(Box::new(Type::Unknown)), // FIXME
             Value::Nothing { .. } => Type::Nothing,
             Value::Block { .. } => Type::Block,
@@ -314,11 +353,7 @@ impl Value {
 
     pub fn get_data_by_key(&self, name: &str) -> Option<Value> {
         match self {
-            Value::Record { cols, vals, .. } => cols
-                .iter()
-                .zip(vals.iter())
-                .find(|(col, _)| col == &name)
-                .map(|(_, val)| val.clone()),
+            Value::Record { val, .. } => val.get(name).cloned(),
             Value::List { vals, span } => {
                 let mut out = vec![];
                 for item in vals {
@@ -332,10 +367,7 @@ impl Value {
                 }
 
                 if !out.is_empty() {
-                    Some(Value::List {
-                        vals: out,
-                        span: *span,
-                    })
+                    Some(Value::list(out, *span))
                 } else {
                     None
                 }
@@ -360,7 +392,7 @@ impl Value {
                     val.to.into_string(", ", config)
                 )
             }
-            Value::String { val, .. } => val,
+            Value::String { val, .. } => val.into_inner(),
             Value::List { vals: val, .. } => format!(
                 "[{}]",
                 val.into_iter()
@@ -368,10 +400,9 @@ impl Value {
                     .collect::<Vec<_>>()
                     .join(separator)
             ),
-            Value::Record { cols, vals, .. } => format!(
+            Value::Record { val, .. } => format!(
                 "{{{}}}",
-                cols.iter()
-                    .zip(vals.iter())
+                val.iter()
                     .map(|(x, y)| format!("{}: {}", x, y.clone().into_string(", ", config)))
                     .collect::<Vec<_>>()
                     .join(separator)
@@ -414,10 +445,10 @@ impl Value {
                     if vals.len() == 1 { "" } else { "s" }
                 ),
             },
-            Value::Record { cols, .. } => format!(
+            Value::Record { val, .. } => format!(
                 "{{record {} field{}}}",
-                cols.len(),
-                if cols.len() == 1 { "" } else { "s" }
+                val.len(),
+                if val.len() == 1 { "" } else { "s" }
             ),
             Value::Block { val, .. } => format!("<Block {}>", val),
             Value::Nothing { .. } => String::new(),
@@ -449,7 +480,7 @@ impl Value {
                     val.to.into_string(", ", config)
                 )
             }
-            Value::String { val, .. } => val,
+            Value::String { val, .. } => val.into_inner(),
             Value::List { vals: val, .. } => format!(
                 "[{}]",
                 val.into_iter()
@@ -457,10 +488,9 @@ impl Value {
                     .collect::<Vec<_>>()
                     .join(separator)
             ),
-            Value::Record { cols, vals, .. } => format!(
+            Value::Record { val, .. } => format!(
                 "{{{}}}",
-                cols.iter()
-                    .zip(vals.iter())
+                val.iter()
                     .map(|(x, y)| format!("{}: {}", x, y.clone().into_string(", ", config)))
                     .collect::<Vec<_>>()
                     .join(separator)
@@ -481,8 +511,9 @@ impl Value {
             Value::List { vals, .. } => {
                 vals.is_empty() && vals.iter().all(|v| v.clone().is_empty())
             }
-            Value::Record { cols, vals, .. } => {
-                cols.iter().all(|v| v.is_empty()) && vals.iter().all(|v| v.clone().is_empty())
+            Value::Record { val, .. } => {
+                val.columns().iter().all(|v| v.is_empty())
+                    && val.values().iter().all(|v| v.clone().is_empty())
             }
             Value::Nothing { .. } => true,
             _ => false,
@@ -546,15 +577,12 @@ impl Value {
                     val: column_name,
                     span: origin_span,
                 } => match &mut current {
-                    Value::Record { cols, vals, span } => {
-                        let cols = cols.clone();
+                    Value::Record { val, span } => {
                         let span = *span;
 
-                        if let Some(found) =
-                            cols.iter().zip(vals.iter()).find(|x| x.0 == column_name)
-                        {
-                            current = found.1.clone();
-                        } else if let Some(suggestion) = did_you_mean(&cols, column_name) {
+                        if let Some(found) = val.get(column_name) {
+                            current = found.clone();
+                        } else if let Some(suggestion) = did_you_mean(val.columns(), column_name) {
                             return Err(ShellError::DidYouMean(suggestion, *origin_span));
                         } else {
                             return Err(ShellError::CantFindColumn(*origin_span, span));
@@ -576,10 +604,7 @@ impl Value {
                             // }
                         }
 
-                        current = Value::List {
-                            vals: output,
-                            span: *span,
-                        };
+                        current = Value::list(output, *span);
                     }
                     Value::CustomValue { val, .. } => {
                         current = val.follow_path_string(column_name.clone(), *origin_span)?;
@@ -627,26 +652,21 @@ impl Value {
                     Value::List { vals, .. } => {
                         for val in vals.iter_mut() {
                             match val {
-                                Value::Record { cols, vals, .. } => {
-                                    for col in cols.iter().zip(vals) {
-                                        if col.0 == col_name {
-                                            col.1.replace_data_at_cell_path(
-                                                &cell_path[1..],
-                                                new_val.clone(),
-                                            )?
-                                        }
+                                Value::Record { val, .. } => {
+                                    if let Some(val) = val.get_mut(col_name) {
+                                        val.replace_data_at_cell_path(
+                                            &cell_path[1..],
+                                            new_val.clone(),
+                                        )?
                                     }
                                 }
                                 v => return Err(ShellError::CantFindColumn(*span, v.span()?)),
                             }
                         }
                     }
-                    Value::Record { cols, vals, .. } => {
-                        for col in cols.iter().zip(vals) {
-                            if col.0 == col_name {
-                                col.1
-                                    .replace_data_at_cell_path(&cell_path[1..], new_val.clone())?
-                            }
+                    Value::Record { val, .. } => {
+                        if let Some(val) = val.get_mut(col_name) {
+                            val.replace_data_at_cell_path(&cell_path[1..], new_val)?
                         }
                     }
                     v => return Err(ShellError::CantFindColumn(*span, v.span()?)),
@@ -675,14 +695,28 @@ impl Value {
 
     pub fn columns(&self) -> Vec<String> {
         match self {
-            Value::Record { cols, .. } => cols.clone(),
+            Value::Record { val, .. } => val.columns().to_vec(),
             _ => vec![],
         }
     }
 
     pub fn string(val: impl Into<String>, span: Span) -> Value {
         Value::String {
-            val: val.into(),
+            val: Shared::new(val.into()),
+            span,
+        }
+    }
+
+    pub fn record(val: Record, span: Span) -> Value {
+        Value::Record {
+            val: Shared::new(val),
+            span,
+        }
+    }
+
+    pub fn list(vals: Vec<Value>, span: Span) -> Value {
+        Value::List {
+            vals: Shared::new(vals),
             span,
         }
     }
@@ -702,11 +736,19 @@ impl Value {
     // Only use these for test data. Should not be used in user data
     pub fn test_string(s: impl Into<String>) -> Value {
         Value::String {
-            val: s.into(),
+            val: Shared::new(s.into()),
             span: Span::test_data(),
         }
     }
 
+    // Only use these for test data. Should not be used in user data
+    pub fn test_record(cols: Vec<impl Into<String>>, vals: Vec<Value>) -> Value {
+        Value::record(
+            cols.into_iter().map(|col| col.into()).zip(vals).collect(),
+            Span::test_data(),
+        )
+    }
+
     // Only use these for test data. Should not be used in user data
     pub fn test_int(val: i64) -> Value {
         Value::Int {
@@ -782,18 +824,9 @@ impl PartialOrd for Value {
                 Some(Ordering::Equal)
             }
             (Value::List { vals: lhs, .. }, Value::List { vals: rhs, .. }) => lhs.partial_cmp(rhs),
-            (
-                Value::Record {
-                    vals: lhs,
-                    cols: lhs_headers,
-                    ..
-                },
-                Value::Record {
-                    vals: rhs,
-                    cols: rhs_headers,
-                    ..
-                },
-            ) if lhs_headers == rhs_headers && lhs == rhs => Some(Ordering::Equal),
+            (Value::Record { val: lhs, .. }, Value::Record { val: rhs, .. }) if lhs == rhs => {
+                Some(Ordering::Equal)
+            }
             (Value::Binary { val: lhs, .. }, Value::Binary { val: rhs, .. }) => {
                 lhs.partial_cmp(rhs)
             }
@@ -837,10 +870,9 @@ impl Value {
                 val: lhs + rhs,
                 span,
             }),
-            (Value::String { val: lhs, .. }, Value::String { val: rhs, .. }) => Ok(Value::String {
-                val: lhs.to_string() + rhs,
-                span,
-            }),
+            (Value::String { val: lhs, .. }, Value::String { val: rhs, .. }) => {
+                Ok(Value::string(lhs.to_string() + rhs, span))
+            }
             (Value::Date { val: lhs, .. }, Value::Duration { val: rhs, .. }) => {
                 match lhs.checked_add_signed(chrono::Duration::nanoseconds(*rhs)) {
                     Some(val) => Ok(Value::Date { val, span }),
@@ -1202,14 +1234,14 @@ impl Value {
                 span,
             }),
             (Value::String { val: lhs, .. }, Value::String { val: rhs, .. }) => Ok(Value::Bool {
-                val: rhs.contains(lhs),
+                val: rhs.contains(lhs.as_str()),
                 span,
             }),
             (lhs, Value::List { vals: rhs, .. }) => Ok(Value::Bool {
                 val: rhs.contains(lhs),
                 span,
             }),
-            (Value::String { val: lhs, .. }, Value::Record { cols: rhs, .. }) => Ok(Value::Bool {
+            (Value::String { val: lhs, .. }, Value::Record { val: rhs, .. }) => Ok(Value::Bool {
                 val: rhs.contains(lhs),
                 span,
             }),
@@ -1235,14 +1267,14 @@ impl Value {
                 span,
             }),
             (Value::String { val: lhs, .. }, Value::String { val: rhs, .. }) => Ok(Value::Bool {
-                val: !rhs.contains(lhs),
+                val: !rhs.contains(lhs.as_str()),
                 span,
             }),
             (lhs, Value::List { vals: rhs, .. }) => Ok(Value::Bool {
                 val: !rhs.contains(lhs),
                 span,
             }),
-            (Value::String { val: lhs, .. }, Value::Record { cols: rhs, .. }) => Ok(Value::Bool {
+            (Value::String { val: lhs, .. }, Value::Record { val: rhs, .. }) => Ok(Value::Bool {
                 val: !rhs.contains(lhs),
                 span,
             }),
@@ -1264,7 +1296,7 @@ impl Value {
 
         match (self, rhs) {
             (Value::String { val: lhs, .. }, Value::String { val: rhs, .. }) => Ok(Value::Bool {
-                val: lhs.contains(rhs),
+                val: lhs.contains(rhs.as_str()),
                 span,
             }),
             (Value::CustomValue { val: lhs, span }, rhs) => {
@@ -1285,7 +1317,7 @@ impl Value {
 
         match (self, rhs) {
             (Value::String { val: lhs, .. }, Value::String { val: rhs, .. }) => Ok(Value::Bool {
-                val: !lhs.contains(rhs),
+                val: !lhs.contains(rhs.as_str()),
                 span,
             }),
             (Value::CustomValue { val: lhs, span }, rhs) => {
@@ -1445,34 +1477,14 @@ impl Value {
 /// Create a Value::Record from a spanned hashmap
 impl From<Spanned<HashMap<String, Value>>> for Value {
     fn from(input: Spanned<HashMap<String, Value>>) -> Self {
-        let span = input.span;
-        let (cols, vals) = input
-            .item
-            .into_iter()
-            .fold((vec![], vec![]), |mut acc, (k, v)| {
-                acc.0.push(k);
-                acc.1.push(v);
-                acc
-            });
-
-        Value::Record { cols, vals, span }
+        Value::record(input.item.into_iter().collect(), input.span)
     }
 }
 
 /// Create a Value::Record from a spanned indexmap
 impl From<Spanned<IndexMap<String, Value>>> for Value {
     fn from(input: Spanned<IndexMap<String, Value>>) -> Self {
-        let span = input.span;
-        let (cols, vals) = input
-            .item
-            .into_iter()
-            .fold((vec![], vec![]), |mut acc, (k, v)| {
-                acc.0.push(k);
-                acc.1.push(v);
-                acc
-            });
-
-        Value::Record { cols, vals, span }
+        Value::record(input.item.into_iter().collect(), input.span)
     }
 }
 
@@ -0,0 +1,290 @@
+use std::collections::HashMap;
+use std::iter::{FromIterator, Zip};
+use std::{fmt, slice, vec};
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Shared, ShellError, Span, Value};
+
+/// The columns of a record with their values.
+///
+/// Columns keep the order they were added in and are looked up through a hash index, so getting
+/// a column doesn't depend on how wide the record is. A column name is in a record at most once.
+///
+/// Records whose columns come from data, like the headers of a file or the keys a user wrote,
+/// are built with `try_insert` or `from_raw_cols_vals`, so that a column given twice is an error.
+/// Collecting or extending a record is for columns known to be distinct, and `insert` is for
+/// setting a column on purpose, whether the record has it already or not.
+#[derive(Clone, Default)]
+pub struct Record {
+    cols: Vec<String>,
+    vals: Vec<Value>,
+    index: HashMap<String, usize>,
+}
+
+impl Record {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Record {
+            cols: Vec::with_capacity(capacity),
+            vals: Vec::with_capacity(capacity),
+            index: HashMap::with_capacity(capacity),
+        }
+    }
+
+    /// Build a record from its columns and the values that go with them, in order. A column given
+    /// twice is an error pointing at `span`
+    pub fn from_raw_cols_vals(
+        cols: Vec<String>,
+        vals: Vec<Value>,
+        span: Span,
+    ) -> Result<Self, ShellError> {
+        let mut record = Record::with_capacity(cols.len());
+        for (col, val) in cols.into_iter().zip(vals) {
+            record.try_insert(col, val, span)?;
+        }
+
+        Ok(record)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cols.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cols.is_empty()
+    }
+
+    /// Add a column at the end of the record. If the record has the column already, its value is
+    /// replaced where it is and the old value is returned
+    pub fn insert(&mut self, col: impl Into<String>, val: Value) -> Option<Value> {
+        let col = col.into();
+
+        match self.index.get(&col) {
+            Some(&idx) => Some(std::mem::replace(&mut self.vals[idx], val)),
+            None => {
+                self.index.insert(col.clone(), self.cols.len());
+                self.cols.push(col);
+                self.vals.push(val);
+                None
+            }
+        }
+    }
+
+    /// Add a column at the end of the record, failing if the record has it already
+    pub fn try_insert(
+        &mut self,
+        col: impl Into<String>,
+        val: Value,
+        span: Span,
+    ) -> Result<(), ShellError> {
+        let col = col.into();
+
+        if self.contains(&col) {
+            Err(ShellError::ColumnDefinedTwice(col, span))
+        } else {
+            self.insert(col, val);
+            Ok(())
+        }
+    }
+
+    pub fn contains(&self, col: &str) -> bool {
+        self.index.contains_key(col)
+    }
+
+    /// The position of a column in the record
+    pub fn index_of(&self, col: &str) -> Option<usize> {
+        self.index.get(col).copied()
+    }
+
+    pub fn get(&self, col: &str) -> Option<&Value> {
+        self.index_of(col).map(|idx| &self.vals[idx])
+    }
+
+    pub fn get_mut(&mut self, col: &str) -> Option<&mut Value> {
+        let idx = self.index_of(col)?;
+        Some(&mut self.vals[idx])
+    }
+
+    /// Remove a column, moving the ones after it up by one
+    pub fn remove(&mut self, col: &str) -> Option<Value> {
+        let idx = self.index.remove(col)?;
+        self.cols.remove(idx);
+        let val = self.vals.remove(idx);
+
+        for col in &self.cols[idx..] {
+            if let Some(pos) = self.index.get_mut(col) {
+                *pos -= 1;
+            }
+        }
+
+        Some(val)
+    }
+
+    pub fn columns(&self) -> &[String] {
+        &self.cols
+    }
+
+    pub fn values(&self) -> &[Value] {
+        &self.vals
+    }
+
+    pub fn iter(&self) -> Zip<slice::Iter<'_, String>, slice::Iter<'_, Value>> {
+        self.cols.iter().zip(self.vals.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> Zip<slice::Iter<'_, String>, slice::IterMut<'_, Value>> {
+        self.cols.iter().zip(self.vals.iter_mut())
+    }
+}
+
+impl PartialEq for Record {
+    fn eq(&self, other: &Self) -> bool {
+        self.cols == other.cols && self.vals == other.vals
+    }
+}
+
+impl fmt::Debug for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<C: Into<String>> FromIterator<(C, Value)> for Record {
+    fn from_iter<I: IntoIterator<Item = (C, Value)>>(iter: I) -> Self {
+        let mut record = Record::new();
+        record.extend(iter);
+        record
+    }
+}
+
+impl<C: Into<String>> Extend<(C, Value)> for Record {
+    fn extend<I: IntoIterator<Item = (C, Value)>>(&mut self, iter: I) {
+        for (col, val) in iter {
+            let col = col.into();
+            debug_assert!(
+                !self.contains(&col),
+                "column '{}' is given twice to a record built from distinct columns",
+                col
+            );
+            self.insert(col, val);
+        }
+    }
+}
+
+impl IntoIterator for Record {
+    type Item = (String, Value);
+    type IntoIter = Zip<vec::IntoIter<String>, vec::IntoIter<Value>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cols.into_iter().zip(self.vals)
+    }
+}
+
+impl<'a> IntoIterator for &'a Record {
+    type Item = (&'a String, &'a Value);
+    type IntoIter = Zip<slice::Iter<'a, String>, slice::Iter<'a, Value>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Record {
+    type Item = (&'a String, &'a mut Value);
+    type IntoIter = Zip<slice::Iter<'a, String>, slice::IterMut<'a, Value>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+// Records are written out as their columns and values, the way they were before they had an
+// index, so that plugins see the same data
+#[derive(Serialize)]
+struct RawRecordRef<'a> {
+    cols: &'a [String],
+    vals: &'a [Value],
+}
+
+#[derive(Deserialize)]
+struct RawRecord {
+    cols: Vec<String>,
+    vals: Vec<Value>,
+}
+
+impl Serialize for Record {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RawRecordRef {
+            cols: &self.cols,
+            vals: &self.vals,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Record {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawRecord::deserialize(deserializer)?;
+        if raw.cols.len() != raw.vals.len() {
+            return Err(D::Error::custom(
+                "a record needs as many values as it has columns",
+            ));
+        }
+
+        let mut record = Record::with_capacity(raw.cols.len());
+        for (col, val) in raw.cols.into_iter().zip(raw.vals) {
+            if record.contains(&col) {
+                return Err(D::Error::custom(format!(
+                    "column '{}' is defined more than once",
+                    col
+                )));
+            }
+            record.insert(col, val);
+        }
+
+        Ok(record)
+    }
+}
+
+impl IntoIterator for Shared<Record> {
+    type Item = (String, Value);
+    type IntoIter = Zip<vec::IntoIter<String>, vec::IntoIter<Value>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_inner().into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Shared<Record> {
+    type Item = (&'a String, &'a Value);
+    type IntoIter = Zip<slice::Iter<'a, String>, slice::Iter<'a, Value>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Shared<Record> {
+    type Item = (&'a String, &'a mut Value);
+    type IntoIter = Zip<slice::Iter<'a, String>, slice::IterMut<'a, Value>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Build a [`Record`] out of `column => value` pairs, for records whose columns are known up front
+#[macro_export]
+macro_rules! record {
+    ($($col:expr => $val:expr),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut record = $crate::Record::new();
+        $(record.insert($col, $val);)*
+        record
+    }};
+}
@@ -1,6 +1,7 @@
 use crate::*;
 use std::{
     fmt::Debug,
+    io::{self, Read},
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
@@ -57,3 +58,258 @@ impl Iterator for ValueStream {
         }
     }
 }
+
+/// A stream of raw bytes, as they come out of an external command, together with a hint on
+/// whether they should be read as text.
+///
+/// Chunks of the underlying stream have no meaning of their own: a UTF-8 character can be split
+/// over two of them. Iterating the stream takes care of that, yielding strings as long as the
+/// bytes decode as UTF-8 and binary values once they don't.
+pub struct RawStream {
+    pub stream: Box<dyn Iterator<Item = Result<Vec<u8>, ShellError>> + Send + 'static>,
+    pub leftover: Vec<u8>,
+    pub ctrlc: Option<Arc<AtomicBool>>,
+    pub is_binary: bool,
+    pub span: Span,
+}
+
+impl RawStream {
+    pub fn new(
+        stream: Box<dyn Iterator<Item = Result<Vec<u8>, ShellError>> + Send + 'static>,
+        ctrlc: Option<Arc<AtomicBool>>,
+        span: Span,
+    ) -> Self {
+        Self {
+            stream,
+            leftover: vec![],
+            ctrlc,
+            is_binary: false,
+            span,
+        }
+    }
+
+    fn is_interrupted(&self) -> bool {
+        match &self.ctrlc {
+            Some(ctrlc) => ctrlc.load(Ordering::SeqCst),
+            None => false,
+        }
+    }
+
+    /// Reads the rest of the stream into a single buffer
+    pub fn into_bytes(mut self) -> Result<Vec<u8>, ShellError> {
+        let mut output = std::mem::take(&mut self.leftover);
+
+        while !self.is_interrupted() {
+            match self.stream.next() {
+                Some(chunk) => output.extend(chunk?),
+                None => break,
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Reads the rest of the stream as text, replacing what isn't valid UTF-8
+    pub fn into_string(self) -> Result<String, ShellError> {
+        let bytes = self.into_bytes()?;
+
+        Ok(match String::from_utf8(bytes) {
+            Ok(s) => s,
+            Err(err) => String::from_utf8_lossy(err.as_bytes()).to_string(),
+        })
+    }
+
+    /// Reads the rest of the stream into a single value: a string if it's text, a binary otherwise
+    pub fn into_value(self) -> Value {
+        let span = self.span;
+        let is_binary = self.is_binary;
+
+        match self.into_bytes() {
+            Ok(bytes) if is_binary => Value::Binary { val: bytes, span },
+            Ok(bytes) => match String::from_utf8(bytes) {
+                Ok(val) => Value::string(val, span),
+                Err(err) => Value::Binary {
+                    val: err.into_bytes(),
+                    span,
+                },
+            },
+            Err(error) => Value::Error { error },
+        }
+    }
+
+    /// The stream as a string for each line, as it's read, for commands that handle their input
+    /// an item at a time. Output that's known to be binary has no lines, so it's read into a
+    /// single value instead. An error reading the stream becomes an error value
+    pub fn into_line_stream(self) -> ValueStream {
+        let ctrlc = self.ctrlc.clone();
+        if self.is_binary {
+            return ValueStream::from_stream(std::iter::once(self.into_value()), ctrlc);
+        }
+
+        let span = self.span;
+        ValueStream::from_stream(
+            self.lines().map(move |line| match line {
+                Ok(line) => Value::string(line, span),
+                Err(error) => Value::Error { error },
+            }),
+            ctrlc,
+        )
+    }
+
+    /// Splits the stream into lines as it's read, so lines spread over more than one chunk stay
+    /// whole. What isn't valid UTF-8 is replaced
+    pub fn lines(self) -> RawLines {
+        RawLines {
+            stream: self,
+            buffer: String::new(),
+        }
+    }
+}
+
+impl Debug for RawStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RawStream").finish()
+    }
+}
+
+/// Reads the bytes of the stream, for parsers that take their input a piece at a time. An error
+/// reading the stream is returned as an I/O error wrapping it, which turns back into the same
+/// `ShellError`
+impl Read for RawStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.leftover.is_empty() {
+            if self.is_interrupted() {
+                return Ok(0);
+            }
+
+            match self.stream.next() {
+                Some(Ok(chunk)) => self.leftover = chunk,
+                Some(Err(err)) => return Err(io::Error::other(err)),
+                None => return Ok(0),
+            }
+        }
+
+        let len = buf.len().min(self.leftover.len());
+        buf[..len].copy_from_slice(&self.leftover[..len]);
+        self.leftover.drain(..len);
+
+        Ok(len)
+    }
+}
+
+impl Iterator for RawStream {
+    type Item = Result<Value, ShellError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_interrupted() {
+            return None;
+        }
+
+        if self.is_binary {
+            let mut bytes = std::mem::take(&mut self.leftover);
+            match self.stream.next() {
+                Some(Ok(chunk)) => bytes.extend(chunk),
+                Some(Err(err)) => return Some(Err(err)),
+                None if bytes.is_empty() => return None,
+                None => {}
+            }
+
+            return Some(Ok(Value::Binary {
+                val: bytes,
+                span: self.span,
+            }));
+        }
+
+        let mut bytes = std::mem::take(&mut self.leftover);
+        loop {
+            match self.stream.next() {
+                Some(Ok(chunk)) => bytes.extend(chunk),
+                Some(Err(err)) => return Some(Err(err)),
+                None if bytes.is_empty() => return None,
+                None => {
+                    // Whatever didn't decode so far won't anymore
+                    return Some(Ok(match String::from_utf8(bytes) {
+                        Ok(val) => Value::string(val, self.span),
+                        Err(err) => {
+                            self.is_binary = true;
+                            Value::Binary {
+                                val: err.into_bytes(),
+                                span: self.span,
+                            }
+                        }
+                    }));
+                }
+            }
+
+            match String::from_utf8(bytes) {
+                Ok(val) => return Some(Ok(Value::string(val, self.span))),
+                Err(err) => {
+                    let utf8_error = err.utf8_error();
+                    bytes = err.into_bytes();
+
+                    match utf8_error.error_len() {
+                        // The chunk ends in the middle of a character, so hold the start of it
+                        // back until the next chunk comes in
+                        None => {
+                            let valid_up_to = utf8_error.valid_up_to();
+                            self.leftover = bytes.split_off(valid_up_to);
+
+                            if bytes.is_empty() {
+                                bytes = std::mem::take(&mut self.leftover);
+                                continue;
+                            }
+
+                            return Some(Ok(Value::String {
+                                val: String::from_utf8(bytes)
+                                    .expect("internal error: bytes were checked to be UTF-8")
+                                    .into(),
+                                span: self.span,
+                            }));
+                        }
+                        // Not UTF-8, so from now on this stream is binary
+                        Some(_) => {
+                            self.is_binary = true;
+                            return Some(Ok(Value::Binary {
+                                val: bytes,
+                                span: self.span,
+                            }));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The lines of a [`RawStream`], without their line endings
+pub struct RawLines {
+    stream: RawStream,
+    buffer: String,
+}
+
+impl Iterator for RawLines {
+    type Item = Result<String, ShellError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(idx) = self.buffer.find('\n') {
+                let rest = self.buffer.split_off(idx + 1);
+                let mut line = std::mem::replace(&mut self.buffer, rest);
+                line.pop();
+
+                return Some(Ok(line));
+            }
+
+            match self.stream.next() {
+                Some(Ok(Value::String { val, .. })) => self.buffer.push_str(&val),
+                Some(Ok(Value::Binary { val, .. })) => {
+                    self.buffer.push_str(&String::from_utf8_lossy(&val))
+                }
+                Some(Ok(_)) => {}
+                Some(Err(error)) => return Some(Err(error)),
+                None if self.buffer.is_empty() => return None,
+                None => return Some(Ok(std::mem::take(&mut self.buffer))),
+            }
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use nu_protocol::{concurrent_pipelines, recursion_limit, Span, Value};
+
+fn block(val: usize) -> Value {
+    Value::Block {
+        val,
+        span: Span::test_data(),
+    }
+}
+
+#[test]
+fn test_hooks_from_config() {
+    let config = Value::test_record(
+        vec!["pre_prompt", "pre_execution", "env_change"],
+        vec![
+            block(1),
+            Value::list(vec![block(2), block(3)], Span::test_data()),
+            Value::test_record(vec!["PWD"], vec![block(4)]),
+        ],
+    )
+    .into_config()
+    .expect("config should be valid");
+
+    assert_eq!(config.hooks.pre_prompt, vec![1]);
+    assert_eq!(config.hooks.pre_execution, vec![2, 3]);
+    assert_eq!(config.hooks.env_change.get("PWD"), Some(&vec![4]));
+}
+
+#[test]
+fn test_hooks_must_be_blocks() {
+    let config = Value::test_record(vec!["pre_prompt"], vec![Value::test_string("echo hi")]);
+
+    assert!(config.into_config().is_err());
+}
+
+#[test]
+fn test_concurrent_pipelines_from_config() {
+    let config = Value::test_record(
+        vec!["concurrent_pipelines"],
+        vec![Value::Bool {
+            val: true,
+            span: Span::test_data(),
+        }],
+    );
+
+    assert!(concurrent_pipelines(Some(&config)));
+    assert!(!concurrent_pipelines(None));
+}
+
+#[test]
+fn test_recursion_limit_from_config() {
+    let config = Value::test_record(vec!["recursion_limit"], vec![Value::test_int(5)]);
+
+    assert_eq!(recursion_limit(Some(&config)), 5);
+    assert_eq!(recursion_limit(None), 1024);
+}
@@ -0,0 +1,108 @@
+use std::io::Read;
+
+use nu_protocol::{Config, PipelineData, RawStream, ShellError, Span, Value};
+
+fn raw_stream(chunks: Vec<&[u8]>) -> RawStream {
+    let chunks: Vec<Result<Vec<u8>, ShellError>> =
+        chunks.into_iter().map(|chunk| Ok(chunk.to_vec())).collect();
+
+    RawStream::new(Box::new(chunks.into_iter()), None, Span::test_data())
+}
+
+#[test]
+fn raw_stream_keeps_split_characters_whole() {
+    // "é" is 0xc3 0xa9 in UTF-8
+    let stream = raw_stream(vec![b"caf\xc3", b"\xa9 au lait"]);
+
+    let values: Vec<Value> = stream.map(|value| value.unwrap()).collect();
+
+    assert_eq!(
+        values,
+        vec![Value::test_string("caf"), Value::test_string("é au lait")]
+    );
+}
+
+#[test]
+fn raw_stream_turns_binary_on_invalid_utf8() {
+    let stream = raw_stream(vec![b"text", b"\xff\xfe", b"more"]);
+
+    let values: Vec<Value> = stream.map(|value| value.unwrap()).collect();
+
+    assert_eq!(
+        values,
+        vec![
+            Value::test_string("text"),
+            Value::Binary {
+                val: b"\xff\xfe".to_vec(),
+                span: Span::test_data()
+            },
+            Value::Binary {
+                val: b"more".to_vec(),
+                span: Span::test_data()
+            },
+        ]
+    );
+}
+
+#[test]
+fn raw_stream_into_value() {
+    let stream = raw_stream(vec![b"caf\xc3", b"\xa9"]);
+    assert_eq!(stream.into_value(), Value::test_string("café"));
+
+    let stream = raw_stream(vec![b"\x00\xff", b"\x01"]);
+    assert_eq!(
+        stream.into_value(),
+        Value::Binary {
+            val: vec![0x00, 0xff, 0x01],
+            span: Span::test_data()
+        }
+    );
+}
+
+#[test]
+fn raw_stream_read_error_is_returned_when_collected() {
+    let chunks: Vec<Result<Vec<u8>, ShellError>> = vec![
+        Ok(b"partial".to_vec()),
+        Err(ShellError::IOError("the pipe broke".into())),
+    ];
+    let stream = RawStream::new(Box::new(chunks.into_iter()), None, Span::test_data());
+
+    let collected = PipelineData::RawStream(stream, None).collect_string("", &Config::default());
+
+    assert!(matches!(collected, Err(ShellError::IOError(_))));
+}
+
+#[test]
+fn raw_stream_lines_keep_lines_split_over_chunks_whole() {
+    let stream = raw_stream(vec![b"first\nsec", b"ond\n", b"\nlast"]);
+
+    let lines: Vec<String> = stream.lines().map(|line| line.unwrap()).collect();
+
+    assert_eq!(lines, vec!["first", "second", "", "last"]);
+}
+
+#[test]
+fn raw_stream_reads_its_bytes() {
+    let mut stream = raw_stream(vec![b"caf\xc3", b"\xa9", b"", b" au lait"]);
+
+    let mut text = String::new();
+    stream.read_to_string(&mut text).unwrap();
+
+    assert_eq!(text, "café au lait");
+}
+
+#[test]
+fn raw_stream_read_error_turns_back_into_the_shell_error() {
+    let chunks: Vec<Result<Vec<u8>, ShellError>> = vec![
+        Ok(b"partial".to_vec()),
+        Err(ShellError::IOError("the pipe broke".into())),
+    ];
+    let mut stream = RawStream::new(Box::new(chunks.into_iter()), None, Span::test_data());
+
+    let error = stream.read_to_end(&mut vec![]).unwrap_err();
+
+    assert_eq!(
+        ShellError::from(error).to_string(),
+        ShellError::IOError("the pipe broke".into()).to_string()
+    );
+}
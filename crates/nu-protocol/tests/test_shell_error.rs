@@ -0,0 +1,42 @@
+use miette::Diagnostic;
+use nu_protocol::{ShellError, Span};
+
+#[test]
+fn called_from_shows_like_the_error_it_wraps() {
+    let error = ShellError::SpannedLabeledErrorHelp(
+        "Oops".into(),
+        "here".into(),
+        Span::new(10, 14),
+        "try again".into(),
+    );
+    let traced = error.clone().called_from(Span::new(0, 5));
+
+    assert_eq!(traced.to_string(), error.to_string());
+    assert_eq!(
+        traced.help().map(|help| help.to_string()),
+        Some("try again".into())
+    );
+
+    let labels = traced
+        .labels()
+        .expect("the traced error has labels")
+        .map(|label| (label.label().map(String::from), label.offset()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        labels,
+        vec![
+            (Some("here".into()), 10),
+            (Some("called from here".into()), 0)
+        ]
+    );
+}
+
+#[test]
+fn called_from_keeps_the_code_of_the_error_it_wraps() {
+    let traced = ShellError::DivisionByZero(Span::new(2, 3)).called_from(Span::new(0, 5));
+
+    assert_eq!(
+        traced.code().map(|code| code.to_string()),
+        Some("nu::shell::division_by_zero".into())
+    );
+}
@@ -1,4 +1,4 @@
-use nu_protocol::{Span, Value};
+use nu_protocol::{ast::PathMember, Record, Shared, ShellError, Span, Value};
 
 #[test]
 fn test_comparison_nothing() {
@@ -43,3 +43,132 @@ fn test_comparison_nothing() {
         ));
     }
 }
+
+#[test]
+fn test_clone_shares_until_changed() {
+    let original = Value::list(
+        vec![Value::test_int(1), Value::test_int(2)],
+        Span::test_data(),
+    );
+
+    let mut copy = original.clone();
+    match (&original, &copy) {
+        (Value::List { vals: a, .. }, Value::List { vals: b, .. }) => {
+            assert!(Shared::ptr_eq(a, b))
+        }
+        _ => unreachable!(),
+    }
+
+    copy.replace_data_at_cell_path(
+        &[PathMember::Int {
+            val: 0,
+            span: Span::test_data(),
+        }],
+        Value::test_int(10),
+    )
+    .expect("row 0 is in the list");
+
+    assert_eq!(
+        original.as_list().expect("list"),
+        &[Value::test_int(1), Value::test_int(2)]
+    );
+    assert_eq!(
+        copy.as_list().expect("list"),
+        &[Value::test_int(10), Value::test_int(2)]
+    );
+}
+
+#[test]
+fn test_record_keeps_insertion_order() {
+    let mut record = Record::new();
+    record.insert("c", Value::test_int(1));
+    record.insert("a", Value::test_int(2));
+    record.insert("b", Value::test_int(3));
+
+    assert_eq!(record.columns(), &["c", "a", "b"]);
+    assert_eq!(record.get("a"), Some(&Value::test_int(2)));
+
+    let old = record.insert("c", Value::test_int(10));
+    assert_eq!(old, Some(Value::test_int(1)));
+    assert_eq!(record.columns(), &["c", "a", "b"]);
+
+    assert_eq!(record.remove("c"), Some(Value::test_int(10)));
+    assert_eq!(record.columns(), &["a", "b"]);
+    assert_eq!(record.get("b"), Some(&Value::test_int(3)));
+    assert_eq!(record.get("c"), None);
+}
+
+#[test]
+fn test_record_rejects_duplicate_columns() {
+    let result = Record::from_raw_cols_vals(
+        vec!["a".into(), "b".into(), "a".into()],
+        vec![Value::test_int(1), Value::test_int(2), Value::test_int(3)],
+        Span::test_data(),
+    );
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_record_serializes_as_columns_and_values() {
+    let value = Value::test_record(
+        vec!["name", "size"],
+        vec![Value::test_string("foo"), Value::test_int(1)],
+    );
+
+    let json = serde_json::to_value(&value).expect("record serializes");
+    assert_eq!(json["Record"]["cols"], serde_json::json!(["name", "size"]));
+
+    let back: Value = serde_json::from_value(json).expect("record deserializes");
+    assert_eq!(back, value);
+
+    let duplicated = serde_json::json!({
+        "Record": {
+            "cols": ["a", "a"],
+            "vals": [{"Int": {"val": 1, "span": {"start": 0, "end": 0}}},
+                     {"Int": {"val": 2, "span": {"start": 0, "end": 0}}}],
+            "span": {"start": 0, "end": 0}
+        }
+    });
+    assert!(serde_json::from_value::<Value>(duplicated).is_err());
+}
+
+#[test]
+fn test_accessors_give_the_contents_without_shared() -> Result<(), ShellError> {
+    let span = Span::test_data();
+
+    assert_eq!(Value::string("a", span).as_str()?, "a");
+    assert_eq!(
+        Value::list(vec![Value::test_int(1)], span).into_list()?,
+        vec![Value::test_int(1)]
+    );
+    assert_eq!(
+        Value::test_record(vec!["a"], vec![Value::test_int(1)])
+            .into_record()?
+            .get("a"),
+        Some(&Value::test_int(1))
+    );
+    assert!(Value::test_int(1).into_list().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_constructors_build_the_same_values_as_the_variants() {
+    let span = Span::test_data();
+
+    assert_eq!(
+        Value::string("a", span),
+        Value::String {
+            val: "a".into(),
+            span
+        }
+    );
+    assert_eq!(
+        Value::list(vec![Value::test_int(1)], span),
+        Value::List {
+            vals: vec![Value::test_int(1)].into(),
+            span
+        }
+    );
+}
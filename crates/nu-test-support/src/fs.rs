@@ -0,0 +1,15 @@
+use std::path::{Path, PathBuf};
+
+/// The root of the repository
+pub fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .canonicalize()
+        .expect("the repository root can't be found")
+}
+
+/// The directory of the sample files that tests can read
+pub fn fixtures() -> PathBuf {
+    root().join("tests").join("fixtures")
+}
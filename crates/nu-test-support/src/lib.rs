@@ -0,0 +1,26 @@
+pub mod fs;
+pub mod macros;
+pub mod playground;
+
+use std::process::ExitStatus;
+
+pub use playground::{Dirs, Playground, Stub};
+
+/// What running a pipeline with `nu!` printed, and how the shell exited
+#[derive(Debug)]
+pub struct Outcome {
+    pub out: String,
+    pub err: String,
+    pub status: ExitStatus,
+}
+
+/// Join the lines of a pipeline written over several lines, so that tests can lay long
+/// pipelines out the way they read best
+pub fn pipeline(commands: &str) -> String {
+    commands
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
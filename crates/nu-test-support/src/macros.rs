@@ -0,0 +1,68 @@
+/// Run a pipeline with the shell, in the directory given as `cwd`, and get back what it printed
+///
+/// The pipeline can be a format string, with its arguments after it:
+///
+/// ```ignore
+/// let actual = nu!(cwd: dirs.test(), "ls | where name == {} | length", "a.txt");
+/// assert_eq!(actual.out, "1");
+/// ```
+///
+/// It's for the integration tests of the root crate, in `tests/`: cargo builds the `engine-q`
+/// binary before it builds those, and tells them where it is in `CARGO_BIN_EXE_engine-q`
+#[macro_export]
+macro_rules! nu {
+    (cwd: $cwd:expr, $path:expr, $($part:expr),+ $(,)?) => {
+        $crate::macros::run_pipeline(
+            env!("CARGO_BIN_EXE_engine-q"),
+            $cwd,
+            &format!($path, $($part),+),
+        )
+    };
+
+    (cwd: $cwd:expr, $path:expr $(,)?) => {
+        $crate::macros::run_pipeline(env!("CARGO_BIN_EXE_engine-q"), $cwd, $path)
+    };
+
+    ($path:expr $(, $part:expr)* $(,)?) => {
+        $crate::nu!(cwd: ".", $path $(, $part)*)
+    };
+}
+
+use std::io::Write;
+use std::path::Path;
+use std::process::Command;
+
+use crate::{pipeline, Outcome};
+
+/// Run a pipeline the way `nu!` does. The shell reads it from a script, so that it goes through
+/// the same parser as the scripts users write
+pub fn run_pipeline(
+    executable: impl AsRef<Path>,
+    cwd: impl AsRef<Path>,
+    commands: impl AsRef<str>,
+) -> Outcome {
+    let cwd = cwd
+        .as_ref()
+        .canonicalize()
+        .unwrap_or_else(|err| panic!("can't run in {}: {}", cwd.as_ref().display(), err));
+
+    let mut script = tempfile::Builder::new()
+        .suffix(".nu")
+        .tempfile()
+        .expect("can't create the script to run");
+    writeln!(script, "{}", pipeline(commands.as_ref())).expect("can't write the script to run");
+
+    let output = Command::new(executable.as_ref())
+        .arg(script.path())
+        .current_dir(&cwd)
+        // The shell takes its directory from PWD, which would be the one the tests run in
+        .env("PWD", &cwd)
+        .output()
+        .expect("can't run the shell");
+
+    Outcome {
+        out: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        err: String::from_utf8_lossy(&output.stderr).to_string(),
+        status: output.status,
+    }
+}
@@ -0,0 +1,146 @@
+use std::path::{Path, PathBuf};
+
+use tempfile::TempDir;
+
+use crate::fs;
+
+/// A file to create in a playground
+pub enum Stub<'a> {
+    EmptyFile(&'a str),
+    FileWithContent(&'a str, &'a str),
+    /// A file whose content is written indented in the test. The indentation of the first
+    /// line is taken off every line
+    FileWithContentToBeTrimmed(&'a str, &'a str),
+}
+
+/// The directories a test in a playground works with
+pub struct Dirs {
+    root: PathBuf,
+    test: PathBuf,
+}
+
+impl Dirs {
+    /// The temporary directory of the playground, which is removed when the test is done
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The directory of the test, in the root of the playground
+    pub fn test(&self) -> &Path {
+        &self.test
+    }
+
+    /// The sample files of the repository
+    pub fn fixtures(&self) -> PathBuf {
+        fs::fixtures()
+    }
+
+    pub fn formats(&self) -> PathBuf {
+        fs::fixtures().join("formats")
+    }
+}
+
+/// Files and directories for a test to run commands on, made in a temporary directory
+///
+/// ```ignore
+/// Playground::setup("ls_test_1", |dirs, sandbox| {
+///     sandbox.with_files(vec![EmptyFile("a.txt"), EmptyFile("b.txt")]);
+///
+///     let actual = nu!(cwd: dirs.test(), "ls | length");
+///     assert_eq!(actual.out, "2");
+/// })
+/// ```
+pub struct Playground {
+    _root: TempDir,
+    cwd: PathBuf,
+}
+
+impl Playground {
+    pub fn setup(name: &str, block: impl FnOnce(Dirs, &mut Playground)) {
+        let root = tempfile::Builder::new()
+            .prefix("nu-playground-")
+            .tempdir()
+            .expect("can't create the playground");
+
+        // On some systems the temporary directory is behind a symlink, which the shell would
+        // resolve in the paths it shows
+        let root_path = root
+            .path()
+            .canonicalize()
+            .expect("can't find the playground");
+        let test = root_path.join(name);
+        std::fs::create_dir_all(&test).expect("can't create the directory of the test");
+
+        let mut playground = Playground {
+            _root: root,
+            cwd: test.clone(),
+        };
+        let dirs = Dirs {
+            root: root_path,
+            test,
+        };
+
+        block(dirs, &mut playground);
+    }
+
+    /// The directory files and directories are created in
+    pub fn cwd(&self) -> &Path {
+        &self.cwd
+    }
+
+    /// Create the next files and directories in `directory`, which is made if it doesn't exist
+    pub fn within(&mut self, directory: &str) -> &mut Self {
+        self.cwd = self.cwd.join(directory);
+        self.mkdir_all(&self.cwd.clone());
+        self
+    }
+
+    pub fn mkdir(&mut self, directory: &str) -> &mut Self {
+        self.mkdir_all(&self.cwd.join(directory));
+        self
+    }
+
+    pub fn with_files(&mut self, files: Vec<Stub>) -> &mut Self {
+        for file in files {
+            let (name, contents) = match file {
+                Stub::EmptyFile(name) => (name, String::new()),
+                Stub::FileWithContent(name, contents) => (name, contents.to_string()),
+                Stub::FileWithContentToBeTrimmed(name, contents) => (name, trim_indent(contents)),
+            };
+
+            let path = self.cwd.join(name);
+            if let Some(parent) = path.parent() {
+                self.mkdir_all(parent);
+            }
+            std::fs::write(&path, contents)
+                .unwrap_or_else(|err| panic!("can't create {}: {}", path.display(), err));
+        }
+
+        self
+    }
+
+    fn mkdir_all(&self, path: &Path) {
+        std::fs::create_dir_all(path)
+            .unwrap_or_else(|err| panic!("can't create {}: {}", path.display(), err));
+    }
+}
+
+fn trim_indent(contents: &str) -> String {
+    let lines: Vec<&str> = contents
+        .lines()
+        .skip_while(|line| line.trim().is_empty())
+        .collect();
+    let indent = lines
+        .first()
+        .map(|line| line.len() - line.trim_start().len())
+        .unwrap_or(0);
+
+    lines
+        .iter()
+        .map(|line| line.get(indent..).unwrap_or_else(|| line.trim_start()))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+        + "\n"
+}
@@ -57,29 +57,28 @@ impl Example {
     pub fn test2(&self, call: &EvaluatedCall, input: &Value) -> Result<Value, LabeledError> {
         self.print_values(2, call, input)?;
 
-        let cols = vec!["one".to_string(), "two".to_string(), "three".to_string()];
+        let cols = vec!["one", "two", "three"];
 
         let vals = (0..10i64)
             .map(|i| {
-                let vals = (0..3)
-                    .map(|v| Value::Int {
-                        val: v * i,
-                        span: call.head,
+                let record = cols
+                    .iter()
+                    .zip(0..3)
+                    .map(|(col, v)| {
+                        let val = Value::Int {
+                            val: v * i,
+                            span: call.head,
+                        };
+
+                        (*col, val)
                     })
-                    .collect::<Vec<Value>>();
+                    .collect();
 
-                Value::Record {
-                    cols: cols.clone(),
-                    vals,
-                    span: call.head,
-                }
+                Value::record(record, call.head)
             })
             .collect::<Vec<Value>>();
 
-        Ok(Value::List {
-            vals,
-            span: call.head,
-        })
+        Ok(Value::list(vals, call.head))
     }
 
     pub fn test3(&self, call: &EvaluatedCall, input: &Value) -> Result<Value, LabeledError> {
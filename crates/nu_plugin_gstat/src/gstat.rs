@@ -1,6 +1,6 @@
 use git2::{Branch, BranchType, Repository};
 use nu_plugin::LabeledError;
-use nu_protocol::{Span, Spanned, Value};
+use nu_protocol::{Record, Span, Spanned, Value};
 use std::fmt::Write;
 use std::ops::BitAnd;
 use std::path::PathBuf;
@@ -133,207 +133,237 @@ impl GStat {
             }
         };
 
-        let mut cols = vec![];
-        let mut vals = vec![];
+        let mut record = Record::new();
 
-        cols.push("idx_added_staged".into());
-        vals.push(Value::Int {
-            val: stats.idx_added_staged as i64,
-            span: *span,
-        });
-        cols.push("idx_modified_staged".into());
-        vals.push(Value::Int {
-            val: stats.idx_modified_staged as i64,
-            span: *span,
-        });
-        cols.push("idx_deleted_staged".into());
-        vals.push(Value::Int {
-            val: stats.idx_deleted_staged as i64,
-            span: *span,
-        });
-        cols.push("idx_renamed".into());
-        vals.push(Value::Int {
-            val: stats.idx_renamed as i64,
-            span: *span,
-        });
-        cols.push("idx_type_changed".into());
-        vals.push(Value::Int {
-            val: stats.idx_type_changed as i64,
-            span: *span,
-        });
-        cols.push("wt_untracked".into());
-        vals.push(Value::Int {
-            val: stats.wt_untracked as i64,
-            span: *span,
-        });
-        cols.push("wt_modified".into());
-        vals.push(Value::Int {
-            val: stats.wt_modified as i64,
-            span: *span,
-        });
-        cols.push("wt_deleted".into());
-        vals.push(Value::Int {
-            val: stats.wt_deleted as i64,
-            span: *span,
-        });
-        cols.push("wt_type_changed".into());
-        vals.push(Value::Int {
-            val: stats.wt_type_changed as i64,
-            span: *span,
-        });
-        cols.push("wt_renamed".into());
-        vals.push(Value::Int {
-            val: stats.wt_renamed as i64,
-            span: *span,
-        });
-        cols.push("ignored".into());
-        vals.push(Value::Int {
-            val: stats.ignored as i64,
-            span: *span,
-        });
-        cols.push("conflicts".into());
-        vals.push(Value::Int {
-            val: stats.conflicts as i64,
-            span: *span,
-        });
-        cols.push("ahead".into());
-        vals.push(Value::Int {
-            val: stats.ahead as i64,
-            span: *span,
-        });
-        cols.push("behind".into());
-        vals.push(Value::Int {
-            val: stats.behind as i64,
-            span: *span,
-        });
-        cols.push("stashes".into());
-        vals.push(Value::Int {
-            val: stats.stashes as i64,
-            span: *span,
-        });
-        cols.push("branch".into());
-        vals.push(Value::String {
-            val: stats.branch,
-            span: *span,
-        });
-        cols.push("remote".into());
-        vals.push(Value::String {
-            val: stats.remote,
-            span: *span,
-        });
+        record.insert(
+            "idx_added_staged",
+            Value::Int {
+                val: stats.idx_added_staged as i64,
+                span: *span,
+            },
+        );
+        record.insert(
+            "idx_modified_staged",
+            Value::Int {
+                val: stats.idx_modified_staged as i64,
+                span: *span,
+            },
+        );
+        record.insert(
+            "idx_deleted_staged",
+            Value::Int {
+                val: stats.idx_deleted_staged as i64,
+                span: *span,
+            },
+        );
+        record.insert(
+            "idx_renamed",
+            Value::Int {
+                val: stats.idx_renamed as i64,
+                span: *span,
+            },
+        );
+        record.insert(
+            "idx_type_changed",
+            Value::Int {
+                val: stats.idx_type_changed as i64,
+                span: *span,
+            },
+        );
+        record.insert(
+            "wt_untracked",
+            Value::Int {
+                val: stats.wt_untracked as i64,
+                span: *span,
+            },
+        );
+        record.insert(
+            "wt_modified",
+            Value::Int {
+                val: stats.wt_modified as i64,
+                span: *span,
+            },
+        );
+        record.insert(
+            "wt_deleted",
+            Value::Int {
+                val: stats.wt_deleted as i64,
+                span: *span,
+            },
+        );
+        record.insert(
+            "wt_type_changed",
+            Value::Int {
+                val: stats.wt_type_changed as i64,
+                span: *span,
+            },
+        );
+        record.insert(
+            "wt_renamed",
+            Value::Int {
+                val: stats.wt_renamed as i64,
+                span: *span,
+            },
+        );
+        record.insert(
+            "ignored",
+            Value::Int {
+                val: stats.ignored as i64,
+                span: *span,
+            },
+        );
+        record.insert(
+            "conflicts",
+            Value::Int {
+                val: stats.conflicts as i64,
+                span: *span,
+            },
+        );
+        record.insert(
+            "ahead",
+            Value::Int {
+                val: stats.ahead as i64,
+                span: *span,
+            },
+        );
+        record.insert(
+            "behind",
+            Value::Int {
+                val: stats.behind as i64,
+                span: *span,
+            },
+        );
+        record.insert(
+            "stashes",
+            Value::Int {
+                val: stats.stashes as i64,
+                span: *span,
+            },
+        );
+        record.insert("branch", Value::string(stats.branch, *span));
+        record.insert("remote", Value::string(stats.remote, *span));
 
         // Leave this in case we want to turn it into a table instead of a list
         // Ok(Value::List {
-        //     vals: vec![Value::Record {
-        //         cols,
-        //         vals,
-        //         span: *span,
-        //     }],
+        //     vals: vec![Value::record(record, *span)],
         //     span: *span,
         // })
 
-        Ok(Value::Record {
-            cols,
-            vals,
-            span: *span,
-        })
+        Ok(Value::record(record, *span))
     }
 
     fn create_empty_git_status(&self, span: &Span) -> Value {
-        let mut cols = vec![];
-        let mut vals = vec![];
+        let mut record = Record::new();
 
-        cols.push("idx_added_staged".into());
-        vals.push(Value::Int {
-            val: -1,
-            span: *span,
-        });
-        cols.push("idx_modified_staged".into());
-        vals.push(Value::Int {
-            val: -1,
-            span: *span,
-        });
-        cols.push("idx_deleted_staged".into());
-        vals.push(Value::Int {
-            val: -1,
-            span: *span,
-        });
-        cols.push("idx_renamed".into());
-        vals.push(Value::Int {
-            val: -1,
-            span: *span,
-        });
-        cols.push("idx_type_changed".into());
-        vals.push(Value::Int {
-            val: -1,
-            span: *span,
-        });
-        cols.push("wt_untracked".into());
-        vals.push(Value::Int {
-            val: -1,
-            span: *span,
-        });
-        cols.push("wt_modified".into());
-        vals.push(Value::Int {
-            val: -1,
-            span: *span,
-        });
-        cols.push("wt_deleted".into());
-        vals.push(Value::Int {
-            val: -1,
-            span: *span,
-        });
-        cols.push("wt_type_changed".into());
-        vals.push(Value::Int {
-            val: -1,
-            span: *span,
-        });
-        cols.push("wt_renamed".into());
-        vals.push(Value::Int {
-            val: -1,
-            span: *span,
-        });
-        cols.push("ignored".into());
-        vals.push(Value::Int {
-            val: -1,
-            span: *span,
-        });
-        cols.push("conflicts".into());
-        vals.push(Value::Int {
-            val: -1,
-            span: *span,
-        });
-        cols.push("ahead".into());
-        vals.push(Value::Int {
-            val: -1,
-            span: *span,
-        });
-        cols.push("behind".into());
-        vals.push(Value::Int {
-            val: -1,
-            span: *span,
-        });
-        cols.push("stashes".into());
-        vals.push(Value::Int {
-            val: -1,
-            span: *span,
-        });
-        cols.push("branch".into());
-        vals.push(Value::String {
-            val: "no_branch".to_string(),
-            span: *span,
-        });
-        cols.push("remote".into());
-        vals.push(Value::String {
-            val: "no_remote".to_string(),
-            span: *span,
-        });
+        record.insert(
+            "idx_added_staged",
+            Value::Int {
+                val: -1,
+                span: *span,
+            },
+        );
+        record.insert(
+            "idx_modified_staged",
+            Value::Int {
+                val: -1,
+                span: *span,
+            },
+        );
+        record.insert(
+            "idx_deleted_staged",
+            Value::Int {
+                val: -1,
+                span: *span,
+            },
+        );
+        record.insert(
+            "idx_renamed",
+            Value::Int {
+                val: -1,
+                span: *span,
+            },
+        );
+        record.insert(
+            "idx_type_changed",
+            Value::Int {
+                val: -1,
+                span: *span,
+            },
+        );
+        record.insert(
+            "wt_untracked",
+            Value::Int {
+                val: -1,
+                span: *span,
+            },
+        );
+        record.insert(
+            "wt_modified",
+            Value::Int {
+                val: -1,
+                span: *span,
+            },
+        );
+        record.insert(
+            "wt_deleted",
+            Value::Int {
+                val: -1,
+                span: *span,
+            },
+        );
+        record.insert(
+            "wt_type_changed",
+            Value::Int {
+                val: -1,
+                span: *span,
+            },
+        );
+        record.insert(
+            "wt_renamed",
+            Value::Int {
+                val: -1,
+                span: *span,
+            },
+        );
+        record.insert(
+            "ignored",
+            Value::Int {
+                val: -1,
+                span: *span,
+            },
+        );
+        record.insert(
+            "conflicts",
+            Value::Int {
+                val: -1,
+                span: *span,
+            },
+        );
+        record.insert(
+            "ahead",
+            Value::Int {
+                val: -1,
+                span: *span,
+            },
+        );
+        record.insert(
+            "behind",
+            Value::Int {
+                val: -1,
+                span: *span,
+            },
+        );
+        record.insert(
+            "stashes",
+            Value::Int {
+                val: -1,
+                span: *span,
+            },
+        );
+        record.insert("branch", Value::string("no_branch", *span));
+        record.insert("remote", Value::string("no_remote", *span));
 
-        Value::Record {
-            cols,
-            vals,
-            span: *span,
-        }
+        Value::record(record, *span)
     }
 }
 
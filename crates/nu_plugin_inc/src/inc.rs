@@ -30,12 +30,7 @@ impl Inc {
             Some(Action::SemVerAction(act_on)) => {
                 let mut ver = match semver::Version::parse(input) {
                     Ok(parsed_ver) => parsed_ver,
-                    Err(_) => {
-                        return Value::String {
-                            val: input.to_string(),
-                            span: head,
-                        }
-                    }
+                    Err(_) => return Value::string(input, head),
                 };
 
                 match act_on {
@@ -44,20 +39,11 @@ impl Inc {
                     SemVerAction::Patch => ver.increment_patch(),
                 }
 
-                Value::String {
-                    val: ver.to_string(),
-                    span: head,
-                }
+                Value::string(ver.to_string(), head)
             }
             Some(Action::Default) | None => match input.parse::<u64>() {
-                Ok(v) => Value::String {
-                    val: (v + 1).to_string(),
-                    span: head,
-                },
-                Err(_) => Value::String {
-                    val: input.to_string(),
-                    span: head,
-                },
+                Ok(v) => Value::string((v + 1).to_string(), head),
+                Err(_) => Value::string(input, head),
             },
         }
     }
@@ -120,10 +106,7 @@ mod tests {
 
         #[test]
         fn major() {
-            let expected = Value::String {
-                val: "1.0.0".to_string(),
-                span: Span::test_data(),
-            };
+            let expected = Value::string("1.0.0", Span::test_data());
             let mut inc = Inc::new();
             inc.for_semver(SemVerAction::Major);
             assert_eq!(inc.apply("0.1.3", Span::test_data()), expected)
@@ -131,10 +114,7 @@ mod tests {
 
         #[test]
         fn minor() {
-            let expected = Value::String {
-                val: "0.2.0".to_string(),
-                span: Span::test_data(),
-            };
+            let expected = Value::string("0.2.0", Span::test_data());
             let mut inc = Inc::new();
             inc.for_semver(SemVerAction::Minor);
             assert_eq!(inc.apply("0.1.3", Span::test_data()), expected)
@@ -142,10 +122,7 @@ mod tests {
 
         #[test]
         fn patch() {
-            let expected = Value::String {
-                val: "0.1.4".to_string(),
-                span: Span::test_data(),
-            };
+            let expected = Value::string("0.1.4", Span::test_data());
             let mut inc = Inc::new();
             inc.for_semver(SemVerAction::Patch);
             assert_eq!(inc.apply("0.1.3", Span::test_data()), expected)
@@ -8,24 +8,33 @@ use dialoguer::{
 use miette::{IntoDiagnostic, Result};
 use nu_cli::{CliError, NuCompleter, NuHighlighter, NuValidator, NushellPrompt};
 use nu_command::create_default_context;
-use nu_engine::{convert_env_values, eval_block};
-use nu_parser::{lex, parse, trim_quotes, Token, TokenContents};
+use nu_engine::{compile_block, compile_blocks, convert_env_values, eval_block};
+use nu_lsp::LanguageServer;
+use nu_parser::{
+    flatten_block, format_source, lex, lint_style, parse, trim_quotes, FlatShape, Token,
+    TokenContents,
+};
 use nu_protocol::{
-    ast::Call,
-    engine::{EngineState, Stack, StateWorkingSet},
-    Config, PipelineData, ShellError, Span, Value, CONFIG_VARIABLE_ID,
+    ast::{Block, Call, Expr, Expression, Statement},
+    engine::{EngineState, Profiler, Stack, StateWorkingSet},
+    record, BlockId, Config, IntoPipelineData, PipelineData, Record, ShellError, Span, Type, Value,
+    CONFIG_VARIABLE_ID, EVAL_STACK_SIZE,
 };
 use reedline::{
     Completer, CompletionActionHandler, DefaultHinter, DefaultPrompt, LineBuffer, Prompt,
 };
 use std::{
+    collections::HashMap,
     io::Write,
+    path::{Path, PathBuf},
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
     },
+    time::Instant,
 };
 
+mod test_runner;
 #[cfg(test)]
 mod tests;
 
@@ -84,6 +93,17 @@ impl CompletionActionHandler for FuzzyCompletion {
 }
 
 fn main() -> Result<()> {
+    // Code is evaluated on a thread with a native stack of a known size, which the default
+    // recursion limit of custom commands is worked out from
+    std::thread::Builder::new()
+        .stack_size(EVAL_STACK_SIZE)
+        .spawn(run)
+        .into_diagnostic()?
+        .join()
+        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+}
+
+fn run() -> Result<()> {
     // miette::set_panic_hook();
     let miette_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |x| {
@@ -107,17 +127,91 @@ fn main() -> Result<()> {
     engine_state.ctrlc = Some(engine_state_ctrlc);
     // End ctrl-c protection section
 
-    if let Some(path) = std::env::args().nth(1) {
+    let mut args: Vec<String> = std::env::args().collect();
+
+    // `--ir` runs everything through the IR interpreter instead of evaluating the AST,
+    // `--profile` reports the time taken by each pipeline element of the script or of config.nu,
+    // `--test` runs the tests of the script or directory given instead of the script,
+    // `--deny-warnings` makes the warnings of the parser errors, for checking scripts in CI,
+    // `--fmt` formats the scripts given, or only reports the ones that aren't with `--check`,
+    // `--lint` reports the problems in the scripts given without running them, and `--lsp` runs a
+    // language server for editors on stdin and stdout
+    let mut use_ir = false;
+    let mut use_profiler = false;
+    let mut test_mode = false;
+    let mut deny_warnings = false;
+    let mut fmt_mode = false;
+    let mut check_only = false;
+    let mut lint_mode = false;
+    let mut lsp_mode = false;
+    while let Some(flag) = args.get(1) {
+        match flag.as_str() {
+            "--ir" => use_ir = true,
+            "--profile" => use_profiler = true,
+            "--test" => test_mode = true,
+            "--deny-warnings" => deny_warnings = true,
+            "--fmt" => fmt_mode = true,
+            "--check" => check_only = true,
+            "--lint" => lint_mode = true,
+            "--lsp" => lsp_mode = true,
+            _ => break,
+        }
+        args.remove(1);
+    }
+
+    if test_mode {
+        let path = args.get(1).cloned().unwrap_or_else(|| ".".into());
+        let passed = run_test_files(&mut engine_state, Path::new(&path), use_ir);
+
+        std::process::exit(if passed { 0 } else { 1 });
+    }
+
+    if lsp_mode {
+        let stdin = std::io::stdin();
+        let stdout = std::io::stdout();
+        let shut_down = LanguageServer::new(engine_state)
+            .run(stdin.lock(), stdout.lock())
+            .into_diagnostic()?;
+
+        std::process::exit(if shut_down { 0 } else { 1 });
+    }
+
+    if fmt_mode || lint_mode {
+        let paths = match args.get(1..) {
+            Some(paths) if !paths.is_empty() => paths.to_vec(),
+            _ => vec![".".into()],
+        };
+        let files = paths
+            .iter()
+            .flat_map(|path| test_runner::script_files(Path::new(path)))
+            .collect::<Vec<_>>();
+
+        let passed = if fmt_mode {
+            format_files(&engine_state, &files, check_only)
+        } else {
+            lint_files(&engine_state, &files)
+        };
+
+        std::process::exit(if passed { 0 } else { 1 });
+    }
+
+    if let Some(path) = args.get(1).cloned() {
         let file = std::fs::read(&path).into_diagnostic()?;
 
         let (block, delta) = {
             let mut working_set = StateWorkingSet::new(&engine_state);
-            let (output, err) = parse(&mut working_set, Some(&path), &file, false);
+            let (mut output, err) = parse(&mut working_set, Some(&path), &file, false);
             if let Some(err) = err {
                 report_error(&working_set, &err);
 
                 std::process::exit(1);
             }
+            if report_warnings(&working_set, deny_warnings) {
+                std::process::exit(1);
+            }
+            if use_ir {
+                compile_to_ir(&mut working_set, &mut output);
+            }
             (output, working_set.render())
         };
 
@@ -134,11 +228,7 @@ fn main() -> Result<()> {
         // Set up our initial config to start from
         stack.vars.insert(
             CONFIG_VARIABLE_ID,
-            Value::Record {
-                cols: vec![],
-                vals: vec![],
-                span: Span { start: 0, end: 0 },
-            },
+            Value::record(Record::new(), Span { start: 0, end: 0 }),
         );
 
         let config = match stack.get_config() {
@@ -158,6 +248,10 @@ fn main() -> Result<()> {
             std::process::exit(1);
         }
 
+        if use_profiler {
+            stack.profiler = Some(Profiler::new());
+        }
+
         match eval_block(
             &engine_state,
             &mut stack,
@@ -177,9 +271,12 @@ fn main() -> Result<()> {
                 }
 
                 // Next, let's check if there are any flags we want to pass to the main function
-                let args: Vec<String> = std::env::args().skip(2).collect();
+                let args: Vec<String> = args.iter().skip(2).cloned().collect();
 
                 if args.is_empty() && engine_state.find_decl(b"main").is_none() {
+                    print_profile(&engine_state, &mut stack);
+                    exit_with_last_exit_code(&stack, &block);
+
                     return Ok(());
                 }
 
@@ -187,12 +284,19 @@ fn main() -> Result<()> {
 
                 let (block, delta) = {
                     let mut working_set = StateWorkingSet::new(&engine_state);
-                    let (output, err) = parse(&mut working_set, Some("<cmdline>"), &args, false);
+                    let (mut output, err) =
+                        parse(&mut working_set, Some("<cmdline>"), &args, false);
                     if let Some(err) = err {
                         report_error(&working_set, &err);
 
                         std::process::exit(1);
                     }
+                    if report_warnings(&working_set, deny_warnings) {
+                        std::process::exit(1);
+                    }
+                    if use_ir {
+                        compile_to_ir(&mut working_set, &mut output);
+                    }
                     (output, working_set.render())
                 };
 
@@ -218,6 +322,8 @@ fn main() -> Result<()> {
                             }
                             println!("{}", item.into_string("\n", &config));
                         }
+
+                        print_profile(&engine_state, &mut stack);
                     }
                     Err(err) => {
                         let working_set = StateWorkingSet::new(&engine_state);
@@ -253,11 +359,7 @@ fn main() -> Result<()> {
         // Set up our initial config to start from
         stack.vars.insert(
             CONFIG_VARIABLE_ID,
-            Value::Record {
-                cols: vec![],
-                vals: vec![],
-                span: Span::new(0, 0),
-            },
+            Value::record(Record::new(), Span::new(0, 0)),
         );
 
         // Load config startup file
@@ -271,7 +373,21 @@ fn main() -> Result<()> {
                 let config_filename = config_path.to_string_lossy().to_owned();
 
                 if let Ok(contents) = std::fs::read_to_string(&config_path) {
-                    eval_source(&mut engine_state, &mut stack, &contents, &config_filename);
+                    if use_profiler {
+                        stack.profiler = Some(Profiler::new());
+                    }
+
+                    eval_source(
+                        &mut engine_state,
+                        &mut stack,
+                        &contents,
+                        &config_filename,
+                        use_ir,
+                        deny_warnings,
+                        false,
+                    );
+
+                    print_profile(&engine_state, &mut stack);
                 }
             }
         }
@@ -315,11 +431,22 @@ fn main() -> Result<()> {
                 let plugin_filename = plugin_path.to_string_lossy().to_owned();
 
                 if let Ok(contents) = std::fs::read_to_string(&plugin_path) {
-                    eval_source(&mut engine_state, &mut stack, &contents, &plugin_filename);
+                    eval_source(
+                        &mut engine_state,
+                        &mut stack,
+                        &contents,
+                        &plugin_filename,
+                        use_ir,
+                        deny_warnings,
+                        false,
+                    );
                 }
             }
         }
 
+        // Values of the env vars watched by `env_change` hooks as of the last prompt
+        let mut previous_env_vars: HashMap<String, Value> = HashMap::new();
+
         loop {
             let config = match stack.get_config() {
                 Ok(config) => config,
@@ -334,6 +461,23 @@ fn main() -> Result<()> {
             //Reset the ctrl-c handler
             ctrlc.store(false, Ordering::SeqCst);
 
+            // Jobs that finished since the last prompt are reported once, then forgotten
+            if let Ok(mut jobs) = engine_state.jobs.lock() {
+                for mut job in jobs.take_finished() {
+                    let status = job.status();
+                    eprintln!("[{}] {}  {}", job.id, status, job.command);
+                }
+            }
+
+            eval_env_change_hooks(&engine_state, &mut stack, &config, &mut previous_env_vars);
+
+            for block_id in &config.hooks.pre_prompt {
+                if let Err(err) = eval_hook(&engine_state, &mut stack, *block_id, vec![]) {
+                    let working_set = StateWorkingSet::new(&engine_state);
+                    report_error(&working_set, &err);
+                }
+            }
+
             let line_editor = Reedline::create()
                 .into_diagnostic()?
                 .with_completion_action_handler(Box::new(FuzzyCompletion {
@@ -388,11 +532,31 @@ fn main() -> Result<()> {
             let input = line_editor.read_line(prompt);
             match input {
                 Ok(Signal::Success(s)) => {
+                    for block_id in &config.hooks.pre_execution {
+                        if let Err(err) = eval_hook(&engine_state, &mut stack, *block_id, vec![]) {
+                            let working_set = StateWorkingSet::new(&engine_state);
+                            report_error(&working_set, &err);
+                        }
+                    }
+
+                    let start_time = Instant::now();
+
                     eval_source(
                         &mut engine_state,
                         &mut stack,
                         &s,
                         &format!("entry #{}", entry_num),
+                        use_ir,
+                        deny_warnings,
+                        true,
+                    );
+
+                    stack.add_env_var(
+                        "CMD_DURATION_MS".into(),
+                        Value::Int {
+                            val: start_time.elapsed().as_millis() as i64,
+                            span: Span { start: 0, end: 0 },
+                        },
                     );
                 }
                 Ok(Signal::CtrlC) => {
@@ -539,10 +703,7 @@ fn gather_parent_env_vars(engine_state: &mut EngineState, stack: &mut Stack) {
 
                 let bytes = trim_quotes(bytes);
 
-                Value::String {
-                    val: String::from_utf8_lossy(bytes).to_string(),
-                    span: *span,
-                }
+                Value::string(String::from_utf8_lossy(bytes).to_string(), *span)
             } else {
                 report_capture_error(
                     engine_state,
@@ -613,6 +774,210 @@ fn print_pipeline_data(
     Ok(())
 }
 
+// Run the tests found at `path`, reporting the failures and printing a table of the results.
+// Returns whether they all passed
+fn run_test_files(engine_state: &mut EngineState, path: &Path, use_ir: bool) -> bool {
+    let mut stack = Stack::new();
+
+    gather_parent_env_vars(engine_state, &mut stack);
+    stack.vars.insert(
+        CONFIG_VARIABLE_ID,
+        Value::record(Record::new(), Span::new(0, 0)),
+    );
+
+    let config = stack.get_config().unwrap_or_default();
+    if let Some(e) = convert_env_values(engine_state, &mut stack, &config) {
+        let working_set = StateWorkingSet::new(engine_state);
+        report_error(&working_set, &e);
+        return false;
+    }
+
+    let outcomes = test_runner::run_tests(engine_state, &stack, path, use_ir);
+
+    let span = Span::new(0, 0);
+    let mut passed = true;
+    let mut rows = vec![];
+    for outcome in outcomes {
+        if let Err(error) = &outcome.result {
+            let working_set = StateWorkingSet::new(engine_state);
+            report_error(&working_set, error.as_ref());
+            passed = false;
+        }
+
+        let result = if outcome.result.is_ok() {
+            "passed"
+        } else {
+            "failed"
+        };
+        rows.push(Value::record(
+            record! {
+                "file" => Value::string(outcome.file, span),
+                "test" => Value::string(outcome.test, span),
+                "result" => Value::string(result, span),
+            },
+            span,
+        ));
+    }
+
+    let table = Value::list(rows, span);
+    if let Err(err) = print_pipeline_data(table.into_pipeline_data(), engine_state, &mut stack) {
+        let working_set = StateWorkingSet::new(engine_state);
+        report_error(&working_set, &err);
+        return false;
+    }
+
+    passed
+}
+
+// Format the scripts, or only report the ones that aren't formatted when `check_only` is set.
+// Scripts that don't parse are reported and left alone. Returns whether all of them were fine
+fn format_files(engine_state: &EngineState, files: &[PathBuf], check_only: bool) -> bool {
+    let mut passed = true;
+
+    for file in files {
+        let name = file.to_string_lossy().to_string();
+        let contents = match std::fs::read(file) {
+            Ok(contents) => contents,
+            Err(err) => {
+                let working_set = StateWorkingSet::new(engine_state);
+                report_error(&working_set, &ShellError::from(err));
+                passed = false;
+                continue;
+            }
+        };
+
+        let mut working_set = StateWorkingSet::new(engine_state);
+        let (block, err) = parse(&mut working_set, Some(&name), &contents, false);
+        let formatted = match err {
+            Some(err) => Err(err),
+            None => format_source(&contents),
+        };
+        let formatted = match formatted {
+            Ok(formatted) => formatted,
+            Err(err) => {
+                report_error(&working_set, &err);
+                passed = false;
+                continue;
+            }
+        };
+
+        if formatted.as_bytes() == contents.as_slice() {
+            continue;
+        }
+
+        // The formatter only moves code around, so the formatted script has to parse to the same
+        // shapes. If it doesn't, the script is left alone instead of being broken
+        let shapes = flat_shapes(&working_set, &block);
+        if let Err(err) = check_formatted(engine_state, &name, &formatted, &shapes) {
+            report_error(&working_set, &err);
+            passed = false;
+            continue;
+        }
+
+        if check_only {
+            eprintln!("{} isn't formatted", name);
+            passed = false;
+        } else if let Err(err) = std::fs::write(file, formatted) {
+            report_error(&working_set, &ShellError::from(err));
+            passed = false;
+        }
+    }
+
+    passed
+}
+
+// Parse the formatted source of a script, and fail unless it gives the same shapes as the script
+pub(crate) fn check_formatted(
+    engine_state: &EngineState,
+    name: &str,
+    formatted: &str,
+    shapes: &[FlatShape],
+) -> Result<(), ShellError> {
+    let broken = |reason: String| {
+        ShellError::LabeledError(
+            format!("Formatting {} would change it", name),
+            format!(
+                "{}, so it's left as it is. This is a bug in the formatter",
+                reason
+            ),
+        )
+    };
+
+    let mut working_set = StateWorkingSet::new(engine_state);
+    let (block, err) = parse(&mut working_set, Some(name), formatted.as_bytes(), false);
+    if let Some(err) = err {
+        return Err(broken(format!(
+            "the formatted script doesn't parse: {}",
+            err
+        )));
+    }
+
+    if flat_shapes(&working_set, &block) != shapes {
+        return Err(broken("the formatted script parses differently".into()));
+    }
+
+    Ok(())
+}
+
+pub(crate) fn flat_shapes(working_set: &StateWorkingSet, block: &Block) -> Vec<FlatShape> {
+    flatten_block(working_set, block)
+        .into_iter()
+        .map(|(_, shape)| shape)
+        .collect()
+}
+
+// Report the parse errors, the warnings of the parser and the style problems of the scripts,
+// without running them. Returns whether there weren't any
+fn lint_files(engine_state: &EngineState, files: &[PathBuf]) -> bool {
+    let mut passed = true;
+
+    for file in files {
+        let name = file.to_string_lossy().to_string();
+        let contents = match std::fs::read(file) {
+            Ok(contents) => contents,
+            Err(err) => {
+                let working_set = StateWorkingSet::new(engine_state);
+                report_error(&working_set, &ShellError::from(err));
+                passed = false;
+                continue;
+            }
+        };
+
+        let mut working_set = StateWorkingSet::new(engine_state);
+        let span_offset = working_set.next_span_start();
+        let (_, err) = parse(&mut working_set, Some(&name), &contents, false);
+        if let Some(err) = err {
+            report_error(&working_set, &err);
+            passed = false;
+            continue;
+        }
+
+        let style_warnings = lint_style(&contents, span_offset);
+        if !working_set.warnings().is_empty() || !style_warnings.is_empty() {
+            passed = false;
+        }
+
+        report_warnings(&working_set, false);
+        for warning in &style_warnings {
+            eprintln!("Warning: {:?}", CliError(warning, &working_set));
+        }
+    }
+
+    passed
+}
+
+// Print the report of the profiler on the stack, if there is one, and stop profiling
+fn print_profile(engine_state: &EngineState, stack: &mut Stack) {
+    if let Some(profiler) = stack.profiler.take() {
+        let report = profiler.report(engine_state, Span::new(0, 0));
+
+        if let Err(err) = print_pipeline_data(report.into_pipeline_data(), engine_state, stack) {
+            let working_set = StateWorkingSet::new(engine_state);
+            report_error(&working_set, &err);
+        }
+    }
+}
+
 fn update_prompt<'prompt>(
     env_variable: &str,
     engine_state: &EngineState,
@@ -637,11 +1002,12 @@ fn update_prompt<'prompt>(
         &mut stack,
         block,
         PipelineData::new(Span::new(0, 0)), // Don't try this at home, 0 span is ignored
-    ) {
-        Ok(pipeline_data) => {
-            let config = stack.get_config().unwrap_or_default();
-            pipeline_data.collect_string("", &config)
-        }
+    )
+    .and_then(|pipeline_data| {
+        let config = stack.get_config().unwrap_or_default();
+        pipeline_data.collect_string("", &config)
+    }) {
+        Ok(prompt) => prompt,
         Err(..) => {
             // If we can't run the custom prompt, give them the default
             return default_prompt as &dyn Prompt;
@@ -653,15 +1019,137 @@ fn update_prompt<'prompt>(
     nu_prompt as &dyn Prompt
 }
 
+// Run a hook block with the given positional arguments. Hooks are there to set up the
+// environment (eg. loading a toolchain when entering a directory), so env vars the block
+// defines are kept in the caller's stack after it finishes.
+fn eval_hook(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    block_id: BlockId,
+    arguments: Vec<Value>,
+) -> Result<(), ShellError> {
+    let block = engine_state.get_block(block_id);
+    let mut callee_stack = stack.collect_captures(&block.captures);
+
+    for (param, arg) in block
+        .signature
+        .required_positional
+        .iter()
+        .chain(block.signature.optional_positional.iter())
+        .zip(arguments)
+    {
+        if let Some(var_id) = param.var_id {
+            callee_stack.add_var(var_id, arg);
+        }
+    }
+
+    let span = Span::new(0, 0);
+
+    // The output is printed like that of a line entered at the prompt, which also makes sure the
+    // whole block has run before we move on
+    let output = eval_block(
+        engine_state,
+        &mut callee_stack,
+        block,
+        PipelineData::new(span),
+    )?;
+    print_pipeline_data(output, engine_state, &mut callee_stack)?;
+
+    // The env vars the hook set or hid stay that way. Blocks nested in the hook keep theirs to
+    // themselves, as they do anywhere else
+    let before = stack.get_env_vars();
+    let after = callee_stack.get_env_vars();
+    for name in before.keys() {
+        if !after.contains_key(name) {
+            stack.remove_env_var(name);
+        }
+    }
+    for (name, value) in after {
+        if before.get(&name) != Some(&value) {
+            stack.add_env_var(name, value);
+        }
+    }
+
+    Ok(())
+}
+
+// Run the `env_change` hooks of every watched env var whose value differs from the one seen
+// at the last prompt. The blocks get the old and the new value as arguments; a variable seen
+// for the first time has an old value of nothing, so the hooks also fire on startup.
+fn eval_env_change_hooks(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    config: &Config,
+    previous_env_vars: &mut HashMap<String, Value>,
+) {
+    let span = Span::new(0, 0);
+
+    for (env_name, block_ids) in &config.hooks.env_change {
+        let after = stack
+            .get_env_var(env_name)
+            .unwrap_or_else(|| Value::nothing(span));
+        let before = previous_env_vars
+            .insert(env_name.clone(), after.clone())
+            .unwrap_or_else(|| Value::nothing(span));
+
+        if before == after {
+            continue;
+        }
+
+        for block_id in block_ids {
+            if let Err(err) = eval_hook(
+                engine_state,
+                stack,
+                *block_id,
+                vec![before.clone(), after.clone()],
+            ) {
+                let working_set = StateWorkingSet::new(engine_state);
+                report_error(&working_set, &err);
+            }
+        }
+    }
+}
+
+// In script mode, a failing external as the last statement of the script decides the exit status of nu
+fn exit_with_last_exit_code(stack: &Stack, block: &Block) {
+    let ends_with_external = matches!(
+        block.stmts.last(),
+        Some(Statement::Pipeline(pipeline)) if matches!(
+            pipeline.expressions.last(),
+            Some(Expression { expr: Expr::ExternalCall(..), .. })
+        )
+    );
+
+    if !ends_with_external {
+        return;
+    }
+
+    if let Some(Value::Int { val, .. }) = stack.get_env_var("LAST_EXIT_CODE") {
+        if val != 0 {
+            std::process::exit(val as i32);
+        }
+    }
+}
+
+// Lower a freshly parsed block, and the blocks that were added while parsing it, to IR
+fn compile_to_ir(working_set: &mut StateWorkingSet, block: &mut Block) {
+    compile_blocks(working_set);
+    block.ir = Some(compile_block(working_set, block));
+}
+
+// `interactive` is for a line typed at the prompt, where a lone directory path is a `cd` into it
 fn eval_source(
     engine_state: &mut EngineState,
     stack: &mut Stack,
     source: &str,
     fname: &str,
+    use_ir: bool,
+    deny_warnings: bool,
+    interactive: bool,
 ) -> bool {
     let (block, delta) = {
         let mut working_set = StateWorkingSet::new(engine_state);
-        let (output, err) = parse(
+        let (mut output, err) = parse(
             &mut working_set,
             Some(fname), // format!("entry #{}", entry_num)
             source.as_bytes(),
@@ -671,6 +1159,12 @@ fn eval_source(
             report_error(&working_set, &err);
             return false;
         }
+        if report_warnings(&working_set, deny_warnings) {
+            return false;
+        }
+        if use_ir {
+            compile_to_ir(&mut working_set, &mut output);
+        }
 
         (output, working_set.render())
     };
@@ -680,12 +1174,13 @@ fn eval_source(
         report_error(&working_set, &err);
     }
 
-    match eval_block(
-        engine_state,
-        stack,
-        &block,
-        PipelineData::new(Span::new(0, 0)), // Don't try this at home, 0 span is ignored
-    ) {
+    let input = PipelineData::new(Span::new(0, 0)); // Don't try this at home, 0 span is ignored
+    let output = match auto_cd_target(&block) {
+        Some((path, span)) if interactive => eval_auto_cd(engine_state, stack, path, span, input),
+        _ => eval_block(engine_state, stack, &block, input),
+    };
+
+    match output {
         Ok(pipeline_data) => {
             if let Err(err) = print_pipeline_data(pipeline_data, engine_state, stack) {
                 let working_set = StateWorkingSet::new(engine_state);
@@ -713,6 +1208,62 @@ fn eval_source(
     true
 }
 
+// A line made of nothing but a directory path (or `-` for the previous directory) that isn't a
+// command is taken as a `cd` into that directory. Only the prompt does this: in a script or a
+// block, such a path is run as an external like any other
+fn auto_cd_target(block: &Block) -> Option<(&str, Span)> {
+    match &block.stmts[..] {
+        [Statement::Pipeline(pipeline)] => match &pipeline.expressions[..] {
+            [Expression {
+                expr: Expr::ExternalCall(name, name_span, args, redirections, false),
+                ..
+            }] if args.is_empty() && redirections.is_empty() => {
+                let looks_like_path = name == "-"
+                    || name.starts_with('.')
+                    || name.starts_with('~')
+                    || name.contains('/')
+                    || name.contains(std::path::MAIN_SEPARATOR);
+
+                if looks_like_path && (name == "-" || nu_path::expand_path(name).is_dir()) {
+                    Some((name, *name_span))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn eval_auto_cd(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    path: &str,
+    span: Span,
+    input: PipelineData,
+) -> Result<PipelineData, ShellError> {
+    let decl_id = engine_state
+        .find_decl(b"cd")
+        .ok_or(ShellError::CommandNotFound(span))?;
+
+    let call = Call {
+        decl_id,
+        head: span,
+        positional: vec![Expression {
+            expr: Expr::Filepath(path.to_string()),
+            span,
+            ty: Type::String,
+            custom_completion: None,
+        }],
+        named: vec![],
+    };
+
+    engine_state
+        .get_decl(decl_id)
+        .run(engine_state, stack, &call, input)
+}
+
 #[cfg(windows)]
 pub fn enable_vt_processing() -> Result<(), ShellError> {
     pub const ENABLE_PROCESSED_OUTPUT: u32 = 0x0001;
@@ -734,6 +1285,20 @@ pub fn enable_vt_processing() -> Result<(), ShellError> {
     Ok(())
 }
 
+// Show the warnings found while parsing. Denied warnings are reported as errors, and the return
+// value says that the code shouldn't run
+fn report_warnings(working_set: &StateWorkingSet, deny_warnings: bool) -> bool {
+    for warning in working_set.warnings() {
+        if deny_warnings {
+            report_error(working_set, warning);
+        } else {
+            eprintln!("Warning: {:?}", CliError(warning, working_set));
+        }
+    }
+
+    deny_warnings && !working_set.warnings().is_empty()
+}
+
 pub fn report_error(
     working_set: &StateWorkingSet,
     error: &(dyn miette::Diagnostic + Send + Sync + 'static),
@@ -0,0 +1,169 @@
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+use miette::Diagnostic;
+use nu_engine::{compile_block, compile_blocks, eval_block, eval_expression};
+use nu_parser::parse;
+use nu_protocol::{
+    ast::{Call, Expr, Expression},
+    engine::{EngineState, Stack, StateWorkingSet},
+    DeclId, PipelineData, ShellError, Span, Type, Value,
+};
+
+/// A `test ...` command that was run, or a script whose tests couldn't be found because it failed
+pub struct TestOutcome {
+    pub file: String,
+    pub test: String,
+    pub result: Result<(), Box<dyn Diagnostic + Send + Sync>>,
+}
+
+/// Run the commands named `test ...` defined in a script, or in the `.nu` scripts under a
+/// directory, including the ones exported by the modules in them. Each test runs with a fresh copy
+/// of `stack`, so that one test can't change what the next one sees
+pub fn run_tests(
+    engine_state: &mut EngineState,
+    stack: &Stack,
+    path: &Path,
+    use_ir: bool,
+) -> Vec<TestOutcome> {
+    let mut outcomes = vec![];
+
+    for file in script_files(path) {
+        let name = file.to_string_lossy().to_string();
+        let first_decl = engine_state.num_decls();
+
+        if let Err(error) = load_script(engine_state, stack, &file, &name, use_ir) {
+            outcomes.push(TestOutcome {
+                file: name,
+                test: String::new(),
+                result: Err(error),
+            });
+            continue;
+        }
+
+        for decl_id in first_decl..engine_state.num_decls() {
+            let test = engine_state.get_decl(decl_id).name().to_string();
+            if !test.starts_with("test ") {
+                continue;
+            }
+
+            let result = run_test(engine_state, stack.clone(), decl_id);
+            outcomes.push(TestOutcome {
+                file: name.clone(),
+                test,
+                result: result.map_err(failure),
+            });
+        }
+    }
+
+    outcomes
+}
+
+/// The path itself if it's a file, or the `.nu` scripts under it in order if it's a directory
+pub fn script_files(path: &Path) -> Vec<PathBuf> {
+    if !path.is_dir() {
+        return vec![path.to_path_buf()];
+    }
+
+    let mut entries: Vec<PathBuf> = match std::fs::read_dir(path) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .map(|e| e.path())
+            .collect(),
+        Err(_) => return vec![],
+    };
+    entries.sort();
+
+    entries
+        .into_iter()
+        .flat_map(|entry| {
+            if entry.is_dir() {
+                script_files(&entry)
+            } else if entry.extension() == Some(OsStr::new("nu")) {
+                vec![entry]
+            } else {
+                vec![]
+            }
+        })
+        .collect()
+}
+
+fn failure(error: impl Diagnostic + Send + Sync + 'static) -> Box<dyn Diagnostic + Send + Sync> {
+    Box::new(error)
+}
+
+// Parse a script, which defines its tests, and run it to set up what they need
+fn load_script(
+    engine_state: &mut EngineState,
+    stack: &Stack,
+    file: &Path,
+    name: &str,
+    use_ir: bool,
+) -> Result<(), Box<dyn Diagnostic + Send + Sync>> {
+    let contents = std::fs::read(file).map_err(|error| failure(ShellError::from(error)))?;
+
+    let (block, delta, parse_error) = {
+        let mut working_set = StateWorkingSet::new(engine_state);
+        let (mut block, err) = parse(&mut working_set, Some(name), &contents, false);
+        if use_ir && err.is_none() {
+            compile_blocks(&mut working_set);
+            block.ir = Some(compile_block(&working_set, &block));
+        }
+
+        (block, working_set.render(), err)
+    };
+
+    // The script is kept even when it doesn't parse, so that the error can show its source
+    engine_state.merge_delta(delta).map_err(failure)?;
+    if let Some(err) = parse_error {
+        return Err(failure(err));
+    }
+
+    let mut stack = stack.clone();
+    let output = eval_block(
+        engine_state,
+        &mut stack,
+        &block,
+        PipelineData::new(Span::new(0, 0)),
+    )
+    .map_err(failure)?;
+    for value in output {
+        if let Value::Error { error } = value {
+            return Err(failure(error));
+        }
+    }
+
+    Ok(())
+}
+
+// A test passes when calling it doesn't fail, and no error comes out of it
+fn run_test(
+    engine_state: &EngineState,
+    mut stack: Stack,
+    decl_id: DeclId,
+) -> Result<(), ShellError> {
+    let span = Span::new(0, 0);
+    let call = Expression {
+        expr: Expr::Call(Box::new(Call {
+            decl_id,
+            head: span,
+            positional: vec![],
+            named: vec![],
+        })),
+        span,
+        ty: Type::Unknown,
+        custom_completion: None,
+    };
+
+    match eval_expression(engine_state, &mut stack, &call)? {
+        Value::Error { error } => Err(error),
+        Value::List { vals, .. } => vals
+            .iter()
+            .find_map(|value| match value {
+                Value::Error { error } => Some(Err(error.clone())),
+                _ => None,
+            })
+            .unwrap_or(Ok(())),
+        _ => Ok(()),
+    }
+}
@@ -1,32 +1,41 @@
 use assert_cmd::prelude::*;
 use pretty_assertions::assert_eq;
 use std::io::Write;
-use std::process::Command;
+use std::process::{Command, Stdio};
 use tempfile::NamedTempFile;
 
 type TestResult = Result<(), Box<dyn std::error::Error>>;
 
+// Every script is run twice, once evaluating its AST and once through the IR interpreter, and
+// both have to give the same results
+#[cfg(test)]
+const EVALUATORS: [&[&str]; 2] = [&[], &["--ir"]];
+
 #[cfg(test)]
 fn run_test(input: &str, expected: &str) -> TestResult {
     let mut file = NamedTempFile::new()?;
-    let name = file.path();
-
-    let mut cmd = Command::cargo_bin("engine-q")?;
-    cmd.arg(name);
+    let name = file.path().to_path_buf();
 
     writeln!(file, "{}", input)?;
 
-    let output = cmd.output()?;
+    for flags in EVALUATORS {
+        let mut cmd = Command::cargo_bin("engine-q")?;
+        cmd.args(flags);
+        cmd.arg(&name);
 
-    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let output = cmd.output()?;
 
-    println!("stdout: {}", stdout);
-    println!("stderr: {}", stderr);
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
 
-    assert!(output.status.success());
+        println!("flags: {:?}", flags);
+        println!("stdout: {}", stdout);
+        println!("stderr: {}", stderr);
 
-    assert_eq!(stdout.trim(), expected);
+        assert!(output.status.success());
+
+        assert_eq!(stdout.trim(), expected);
+    }
 
     Ok(())
 }
@@ -34,22 +43,82 @@ fn run_test(input: &str, expected: &str) -> TestResult {
 #[cfg(test)]
 fn fail_test(input: &str, expected: &str) -> TestResult {
     let mut file = NamedTempFile::new()?;
-    let name = file.path();
+    let name = file.path().to_path_buf();
+
+    writeln!(file, "{}", input)?;
+
+    for flags in EVALUATORS {
+        let mut cmd = Command::cargo_bin("engine-q")?;
+        cmd.args(flags);
+        cmd.arg(&name);
+
+        let output = cmd.output()?;
+
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+
+        println!("flags: {:?}", flags);
+        println!("stdout: {}", stdout);
+        println!("stderr: {}", stderr);
+
+        assert!(stderr.contains(expected));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+fn exit_code_test(input: &str, expected: i32) -> TestResult {
+    let mut file = NamedTempFile::new()?;
+    let name = file.path().to_path_buf();
+
+    writeln!(file, "{}", input)?;
+
+    for flags in EVALUATORS {
+        let mut cmd = Command::cargo_bin("engine-q")?;
+        cmd.args(flags);
+        cmd.arg(&name);
+
+        let output = cmd.output()?;
 
-    let mut cmd = Command::cargo_bin("engine-q")?;
-    cmd.arg(name);
+        println!("flags: {:?}", flags);
+        println!("stdout: {}", String::from_utf8_lossy(&output.stdout));
+        println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+
+        assert_eq!(output.status.code(), Some(expected));
+    }
+
+    Ok(())
+}
+
+// Runs like `run_test`, and checks that the parser warned about something in the script
+#[cfg(test)]
+fn warn_test(input: &str, expected: &str, warning: &str) -> TestResult {
+    let mut file = NamedTempFile::new()?;
+    let name = file.path().to_path_buf();
 
     writeln!(file, "{}", input)?;
 
-    let output = cmd.output()?;
+    for flags in EVALUATORS {
+        let mut cmd = Command::cargo_bin("engine-q")?;
+        cmd.args(flags);
+        cmd.arg(&name);
 
-    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let output = cmd.output()?;
 
-    println!("stdout: {}", stdout);
-    println!("stderr: {}", stderr);
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
 
-    assert!(stderr.contains(expected));
+        println!("flags: {:?}", flags);
+        println!("stdout: {}", stdout);
+        println!("stderr: {}", stderr);
+
+        assert!(output.status.success());
+
+        assert_eq!(stdout.trim(), expected);
+        assert!(stderr.contains("Warning:"));
+        assert!(stderr.contains(warning));
+    }
 
     Ok(())
 }
@@ -352,6 +421,57 @@ fn cell_path_var2() -> TestResult {
     run_test("let x = [[lang, gems]; [nu, 100]]; $x.lang.0", "nu")
 }
 
+#[test]
+fn cell_path_var3() -> TestResult {
+    run_test("let r = {a: [1 2 3]}; $r.a.1 + $r.a.2 * $r.a.0", "5")
+}
+
+#[test]
+fn let_read_in_a_nested_block() -> TestResult {
+    run_test(
+        "let x = 3; let y = ($x * 2); [1 2] | each { $it + $x + $y } | math sum",
+        "21",
+    )
+}
+
+#[test]
+fn let_read_in_a_def() -> TestResult {
+    run_test("let x = 5; def foo [] { $x + 1 }; foo", "6")
+}
+
+#[test]
+fn let_in_a_loop_body() -> TestResult {
+    run_test(
+        "let x = 5; for i in 1..2 { let y = $i * $x; $y } | math sum",
+        "15",
+    )
+}
+
+#[test]
+fn let_in_a_subexpression() -> TestResult {
+    run_test("let x = 2; let y = (let z = $x + 1; $z * $x); $y", "6")
+}
+
+#[test]
+fn table_with_variables() -> TestResult {
+    run_test("let a = 1; [[x y]; [$a 2] [3 $a]] | get y | math sum", "3")
+}
+
+#[test]
+fn table_with_a_column_twice() -> TestResult {
+    fail_test("[[a a]; [1 2]]", "defined twice")
+}
+
+#[test]
+fn range_with_variables() -> TestResult {
+    run_test("let n = 4; 1..$n | math sum", "10")
+}
+
+#[test]
+fn range_with_a_step() -> TestResult {
+    run_test("let s = 2; 0..$s..8 | length", "5")
+}
+
 #[test]
 fn custom_rest_var() -> TestResult {
     run_test("def foo [...x] { $x.0 + $x.1 }; foo 10 80", "90")
@@ -871,6 +991,59 @@ fn split_column() -> TestResult {
     )
 }
 
+#[test]
+fn split_column_with_a_column_twice() -> TestResult {
+    fail_test(
+        r#""hello world" | split column " " a a"#,
+        "defined more than once",
+    )
+}
+
+#[test]
+fn from_csv_with_a_column_twice() -> TestResult {
+    fail_test(r#"$"a,a(char nl)1,2" | from csv"#, "defined more than once")
+}
+
+#[test]
+fn from_ssv_with_a_column_twice() -> TestResult {
+    fail_test(
+        r#"$"a  a(char nl)1  2" | from ssv"#,
+        "defined more than once",
+    )
+}
+
+#[test]
+fn from_url_with_a_column_twice() -> TestResult {
+    fail_test(r#""a=1&a=2" | from url"#, "defined more than once")
+}
+
+#[test]
+fn from_json_with_a_key_twice() -> TestResult {
+    fail_test(r#"'{"a": 1, "a": 2}' | from json"#, "duplicate key `a`")
+}
+
+#[test]
+fn select_a_column_twice() -> TestResult {
+    fail_test(r#"{a: 1} | select a a"#, "defined more than once")
+}
+
+#[test]
+fn from_eml_keeps_a_header_given_twice() -> TestResult {
+    run_test(
+        r#"$"Received: a(char nl)Received: b(char nl)Subject: s(char nl)(char nl)body" | from eml | get Received | str collect ','"#,
+        "a,b",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn from_csv_of_raw_external_output_with_a_column_twice() -> TestResult {
+    fail_test(
+        r#"^sh -c "echo a,a; echo 1,2" | from csv"#,
+        "defined more than once",
+    )
+}
+
 #[test]
 fn for_loops() -> TestResult {
     run_test(r#"(for x in [1, 2, 3] { $x + 10 }).1"#, "12")
@@ -1074,6 +1247,14 @@ fn update_cell_path_1() -> TestResult {
     )
 }
 
+#[test]
+fn update_leaves_copy_unchanged() -> TestResult {
+    run_test(
+        r#"let a = [[name]; [a]]; let b = ($a | update name b); $a.name.0 + $b.name.0"#,
+        "ab",
+    )
+}
+
 #[test]
 fn range_and_reduction() -> TestResult {
     run_test(r#"1..6..36 | math sum"#, "148")
@@ -1134,6 +1315,16 @@ fn record_2() -> TestResult {
     run_test(r#"{'b': 'c'}.b"#, "c")
 }
 
+#[test]
+fn record_with_duplicate_column() -> TestResult {
+    fail_test(r#"{a: 1, a: 2}"#, "defined more than once")
+}
+
+#[test]
+fn table_with_duplicate_column() -> TestResult {
+    fail_test(r#"[[a, a]; [1, 2]]"#, "defined more than once")
+}
+
 #[test]
 fn multi_word_imports() -> TestResult {
     run_test(
@@ -1332,3 +1523,1099 @@ fn to_json_raw_flag() -> TestResult {
         r#"[{"a":"jim","b":"susie"},{"a":3,"b":4}]"#,
     )
 }
+
+#[test]
+fn exit_with_exit_code() -> TestResult {
+    exit_code_test("echo hello; exit 3", 3)
+}
+
+#[cfg(not(windows))]
+#[test]
+fn last_exit_code_is_recorded() -> TestResult {
+    run_test("^true; $nu.env.LAST_EXIT_CODE", "0")?;
+    run_test("^false; $nu.env.LAST_EXIT_CODE", "1")
+}
+
+#[cfg(not(windows))]
+#[test]
+fn last_exit_code_is_recorded_for_piped_externals() -> TestResult {
+    run_test("^false | lines | length; $nu.env.LAST_EXIT_CODE", "1")?;
+    run_test(
+        r#"^sh -c "echo a; exit 3" | lines | length; $nu.env.LAST_EXIT_CODE"#,
+        "3",
+    )?;
+    run_test(
+        "^false | lines | length; ^true; $nu.env.LAST_EXIT_CODE",
+        "0",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn late_piped_external_keeps_the_exit_code_of_a_later_command() -> TestResult {
+    run_test(
+        r#"^sh -c "echo a; sleep 1; exit 3" | first 1 | length; ^true; sleep 2sec; $nu.env.LAST_EXIT_CODE"#,
+        "0",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn failing_external_sets_script_exit_code() -> TestResult {
+    exit_code_test("^false", 1)
+}
+
+#[cfg(not(windows))]
+#[test]
+fn complete_external() -> TestResult {
+    run_test(
+        r#"^sh -c "echo out; echo err 1>&2; exit 3" | complete | get exit_code"#,
+        "3",
+    )?;
+    run_test(
+        r#"^sh -c "echo out; echo err 1>&2" | complete | get stderr | str trim"#,
+        "err",
+    )
+}
+
+#[test]
+fn complete_collects_stderr_through_an_alias() -> TestResult {
+    run_test(
+        r#"alias done = complete; ^sh -c "echo err 1>&2" | done | get stderr | str trim"#,
+        "err",
+    )
+}
+
+#[test]
+fn complete_needs_external() -> TestResult {
+    fail_test("echo 1 | complete", "external command")
+}
+
+#[cfg(not(windows))]
+#[test]
+fn redirect_stderr_to_file() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let file = dir.path().join("err.txt");
+
+    run_test(
+        &format!(
+            r#"^sh -c "echo out; echo err 1>&2" err> {} | lines | get 0"#,
+            file.display()
+        ),
+        "out",
+    )?;
+
+    assert_eq!(std::fs::read_to_string(file)?, "err\n");
+
+    Ok(())
+}
+
+#[cfg(not(windows))]
+#[test]
+fn redirect_stdout_and_stderr_to_file() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let file = dir.path().join("both.txt");
+
+    run_test(
+        &format!(
+            r#"^sh -c "echo out; echo err 1>&2" out+err> {}"#,
+            file.display()
+        ),
+        "",
+    )?;
+
+    assert_eq!(std::fs::read_to_string(file)?, "out\nerr\n");
+
+    Ok(())
+}
+
+#[cfg(not(windows))]
+#[test]
+fn redirect_stderr_into_pipeline() -> TestResult {
+    run_test(r#"^sh -c "echo err 1>&2" err>| str trim"#, "err")
+}
+
+#[cfg(not(windows))]
+#[test]
+fn save_raw_external_output() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let source = dir.path().join("source.bin");
+    let copy = dir.path().join("copy.bin");
+
+    let bytes: Vec<u8> = (0..100_000).map(|i| (i % 251) as u8).collect();
+    std::fs::write(&source, &bytes)?;
+
+    run_test(
+        &format!("^cat {} | save {}", source.display(), copy.display()),
+        "",
+    )?;
+
+    assert_eq!(std::fs::read(copy)?, bytes);
+
+    Ok(())
+}
+
+#[cfg(not(windows))]
+#[test]
+fn lines_of_raw_external_output() -> TestResult {
+    run_test(
+        r#"^sh -c "echo first; sleep 0.1; echo second" | lines | length"#,
+        "2",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn from_csv_of_raw_external_output() -> TestResult {
+    run_test(
+        r#"^sh -c "echo a,b; echo 1,2; sleep 0.1; echo 3,4" | from csv | get b | math sum"#,
+        "6",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn from_ssv_of_raw_external_output() -> TestResult {
+    run_test(
+        r#"^sh -c "echo 'NAME  SIZE'; echo 'foo   1'; sleep 0.1; echo 'bar   2'" | from ssv | get NAME | str collect ','"#,
+        "foo,bar",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn from_json_objects_of_raw_external_output() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let objects = dir.path().join("objects.json");
+    std::fs::write(&objects, "{\"a\": 1}\n\n{\"a\": 2}\n")?;
+
+    run_test(
+        &format!(
+            "^cat {} | from json --objects | get a | math sum",
+            objects.display()
+        ),
+        "3",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn each_over_raw_external_output() -> TestResult {
+    run_test(
+        r#"^sh -c "echo a; printf b; sleep 0.1; echo c; echo d" | each { $it } | str collect ','"#,
+        "a,bc,d",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn par_each_over_raw_external_output() -> TestResult {
+    run_test(
+        r#"^sh -c "echo a; printf b; sleep 0.1; echo c; echo d" | par-each { $it | str length } | math sum"#,
+        "4",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn background_job_is_listed() -> TestResult {
+    run_test(
+        r#"let id = (^sleep 10 &); let cmd = (jobs | get command.0); job kill $id; $cmd"#,
+        "sleep 10",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn killed_job_is_gone() -> TestResult {
+    run_test(r#"^sleep 10 &; job kill 1; jobs | length"#, "0")
+}
+
+#[cfg(not(windows))]
+#[test]
+fn fg_waits_for_job() -> TestResult {
+    run_test(
+        r#"^sh -c "sleep 0.1; exit 3" &; fg 1; $nu.env.LAST_EXIT_CODE"#,
+        "3",
+    )
+}
+
+#[test]
+fn fg_unknown_job() -> TestResult {
+    fail_test(r#"fg 1"#, "no job with this id")
+}
+
+#[test]
+fn background_pipeline_is_rejected() -> TestResult {
+    fail_test(
+        r#"^echo a | ^sleep 1 &"#,
+        "Only a single external command can run in the background",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn killing_a_job_kills_what_it_started() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let pid_file = dir.path().join("pid");
+
+    run_test(
+        &format!(
+            r#"let id = (^sh -c "sleep 30 & echo $! > {}; wait" &); ^sleep 0.3; job kill $id"#,
+            pid_file.display()
+        ),
+        "",
+    )?;
+
+    // The sleep that the job started is gone along with it
+    assert!(!still_running(&std::fs::read_to_string(&pid_file)?)?);
+
+    Ok(())
+}
+
+#[cfg(not(windows))]
+#[test]
+fn killing_a_job_doesnt_wait_out_the_grace_period() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let pid_file = dir.path().join("pid");
+
+    // The job ignores being asked to terminate, so it's only gone once it's killed
+    run_test(
+        &format!(
+            r#"let id = (^sh -c "trap '' TERM; sleep 30 & echo $! > {}; wait" &); ^sleep 0.3; let took = (benchmark {{ job kill $id }}); sleep 1500ms; $took < 500ms"#,
+            pid_file.display()
+        ),
+        "true",
+    )?;
+
+    assert!(!still_running(&std::fs::read_to_string(&pid_file)?)?);
+
+    Ok(())
+}
+
+// Whether the process is running a second from now. Its new parent may not have reaped it by
+// then, which leaves it a zombie
+#[cfg(not(windows))]
+fn still_running(pid: &str) -> Result<bool, Box<dyn std::error::Error>> {
+    for _ in 0..100 {
+        let output = Command::new("ps")
+            .args(["-o", "stat=", "-p", pid.trim()])
+            .output()?;
+        let state = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if state.is_empty() || state.starts_with('Z') {
+            return Ok(false);
+        }
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    Ok(true)
+}
+
+#[test]
+fn which_builtin() -> TestResult {
+    run_test(r#"which echo | get kind.0"#, "builtin")
+}
+
+#[test]
+fn which_custom_command_shadows_builtin() -> TestResult {
+    run_test(
+        r#"def ls [] { echo hi }; which ls --all | get kind | first 3 | str collect ','"#,
+        "custom,builtin,external",
+    )
+}
+
+#[test]
+fn which_without_all_gives_the_shadowing_command() -> TestResult {
+    run_test(
+        r#"def ls [] { echo hi }; which ls | get kind | str collect ','"#,
+        "custom",
+    )
+}
+
+#[test]
+fn which_alias() -> TestResult {
+    run_test(r#"alias ll = ls -l; which ll | get kind.0"#, "alias")
+}
+
+#[cfg(not(windows))]
+#[test]
+fn which_uses_path_conversion() -> TestResult {
+    run_test(
+        r#"let config = { env_conversions: { PATH: { from_string: {|s| $s | split row ':' }, to_string: {|v| $v | str collect ':' } } } }; let-env PATH = ["/nonexistent" "/bin"]; which sh --all | get path.0"#,
+        "/bin/sh",
+    )
+}
+
+// Only a line typed at the prompt is taken as a `cd`
+#[cfg(not(windows))]
+#[test]
+fn a_directory_in_a_script_is_run_as_an_external() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let dir = dir.path().canonicalize()?;
+    std::fs::create_dir(dir.join("sub"))?;
+
+    fail_test(
+        &format!("cd {}; ./sub; ^pwd", dir.display()),
+        "External command",
+    )
+}
+
+#[test]
+fn cd_into_missing_directory_fails() -> TestResult {
+    fail_test(
+        r#"cd /this/directory/does/not/exist"#,
+        "Cannot change directory",
+    )
+}
+
+#[test]
+fn cd_back_without_previous_directory_fails() -> TestResult {
+    let mut file = NamedTempFile::new()?;
+    writeln!(file, "cd -")?;
+
+    let output = Command::cargo_bin("engine-q")?
+        .arg(file.path())
+        .env_remove("OLDPWD")
+        .output()?;
+
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No previous directory"));
+
+    Ok(())
+}
+
+#[test]
+fn par_each_inside_par_each() -> TestResult {
+    run_test(
+        r#"1..4 | par-each --threads 1 { 1..3 | par-each --threads 1 { $it } | math sum } | math sum"#,
+        "24",
+    )
+}
+
+#[test]
+fn par_each_keeps_order() -> TestResult {
+    run_test(
+        r#"1..50 | par-each { $it * 2 } | str collect ','"#,
+        &(1..=50)
+            .map(|i| (i * 2).to_string())
+            .collect::<Vec<_>>()
+            .join(","),
+    )
+}
+
+#[test]
+fn par_each_keeps_order_past_a_slow_item() -> TestResult {
+    run_test(
+        r#"0..199 | par-each --threads 4 { if $it == 0 { ^sleep 0.3 }; $it } | str collect ','"#,
+        &(0..=199)
+            .map(|i: i32| i.to_string())
+            .collect::<Vec<_>>()
+            .join(","),
+    )
+}
+
+#[test]
+fn par_each_unordered() -> TestResult {
+    run_test(r#"1..50 | par-each --unordered { $it } | math sum"#, "1275")
+}
+
+#[test]
+fn par_each_streams() -> TestResult {
+    run_test(
+        r#"1..1000000 | par-each --threads 2 { $it } | first 3 | str collect ','"#,
+        "1,2,3",
+    )
+}
+
+#[test]
+fn par_each_needs_positive_threads() -> TestResult {
+    fail_test(
+        r#"[1 2] | par-each --threads 0 { $it }"#,
+        "has to be positive",
+    )
+}
+
+// `ls` runs in the shell's own process, so /proc/self is the shell
+#[cfg(target_os = "linux")]
+#[test]
+fn par_each_keeps_one_idle_pool() -> TestResult {
+    run_test(
+        r#"let x = (2..9 | each { |n| [1 2] | par-each --threads $n { $it } | length }); sleep 200ms; (ls /proc/self/task | length) < 30"#,
+        "true",
+    )
+}
+
+#[test]
+fn concurrent_pipeline() -> TestResult {
+    run_test(
+        r#"let config = { concurrent_pipelines: $true }; 1..100 | each { $it * 2 } | where $it > 100 | math sum"#,
+        "7550",
+    )
+}
+
+#[test]
+fn concurrent_pipeline_stops_early() -> TestResult {
+    run_test(
+        r#"let config = { concurrent_pipelines: $true }; 1..1000000 | each { $it } | first 3 | str collect ','"#,
+        "1,2,3",
+    )
+}
+
+#[test]
+fn concurrent_pipeline_keeps_env_changes() -> TestResult {
+    run_test(
+        r#"let config = { concurrent_pipelines: $true }; let parent = ($nu.env.PWD | path dirname); cd $parent | each { $it } | length; $nu.env.PWD == $parent"#,
+        "true",
+    )
+}
+
+#[test]
+fn concurrent_pipeline_reports_error() -> TestResult {
+    fail_test(
+        r#"let config = { concurrent_pipelines: $true }; [1 2] | get foo | each { $it } | length"#,
+        "doesn't support cell paths",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn concurrent_pipeline_with_externals() -> TestResult {
+    run_test(
+        r#"let config = { concurrent_pipelines: $true }; ^sh -c "echo a; echo b" | lines | each { $it } | str collect ','"#,
+        "a,b",
+    )
+}
+
+#[test]
+fn profile_reports_each_pipeline_element() -> TestResult {
+    run_test(
+        r#"profile { [3 1 2] | sort | length } | get source | str collect ','"#,
+        "[3 1 2],sort,length",
+    )
+}
+
+#[test]
+fn profile_counts_calls() -> TestResult {
+    run_test(
+        r#"def double [x] { $x * 2 }; profile { 1..4 | each { double $it } } | get 1.children.0.calls"#,
+        "4",
+    )
+}
+
+#[test]
+fn profile_nests_custom_commands() -> TestResult {
+    run_test(
+        r#"def inner [] { 1 + 2 }; def outer [] { inner }; profile { outer } | get 0.children.0.children.0.source"#,
+        "1 + 2",
+    )
+}
+
+#[test]
+fn profile_times_a_stream_where_it_comes_from() -> TestResult {
+    run_test(
+        r#"let report = (profile { 1..3 | each { sleep 100ms; $it } | length }); [($report.1.total > 250ms) ($report.2.total < 100ms)] | str collect ','"#,
+        "true,true",
+    )
+}
+
+#[test]
+fn benchmark_summarizes_rounds() -> TestResult {
+    run_test(
+        r#"[(benchmark --rounds 3 --warmup 1 { 1..10 | math sum })] | columns | str collect ','"#,
+        "mean,median,min,max,stddev",
+    )
+}
+
+#[test]
+fn benchmark_without_rounds_gives_a_duration() -> TestResult {
+    run_test(r#"benchmark { 1 + 1 } | describe"#, "duration")
+}
+
+#[test]
+fn benchmark_compares_named_blocks() -> TestResult {
+    run_test(
+        r#"benchmark --rounds 2 { small: { 1 + 1 }, big: { 2 + 2 } } | get name | str collect ','"#,
+        "small,big",
+    )
+}
+
+#[test]
+fn benchmark_needs_blocks() -> TestResult {
+    fail_test(r#"benchmark { small: 1 }"#, "block")
+}
+
+#[test]
+fn benchmark_stops_between_rounds_when_interrupted() -> TestResult {
+    fail_test(
+        r#"timeout 200ms { benchmark --rounds 100000000 { 1 + 1 } }"#,
+        "Timed out",
+    )
+}
+
+#[test]
+fn benchmark_needs_a_round() -> TestResult {
+    fail_test(r#"benchmark --rounds 0 { 1 + 1 }"#, "at least one round")
+}
+
+#[test]
+fn assert_passes() -> TestResult {
+    run_test(r#"assert (1 + 1 == 2); "ok""#, "ok")
+}
+
+#[test]
+fn assert_fails_with_message() -> TestResult {
+    fail_test(r#"assert (1 + 1 == 3) "math is broken""#, "math is broken")
+}
+
+#[test]
+fn assert_equal_shows_both_values() -> TestResult {
+    fail_test(r#"assert equal (1 + 1) "2""#, r#"right is "2" (string)"#)
+}
+
+#[test]
+fn assert_error_passes_on_failure() -> TestResult {
+    run_test(r#"assert error { 1 / 0 }; "ok""#, "ok")
+}
+
+#[test]
+fn assert_error_fails_without_error() -> TestResult {
+    fail_test(r#"assert error { 1 + 1 }"#, "didn't fail")
+}
+
+#[cfg(test)]
+fn test_mode_test(
+    script: &str,
+    expected_code: i32,
+    expected_stdout: &str,
+    expected_stderr: &str,
+) -> TestResult {
+    let dir = tempfile::tempdir()?;
+    std::fs::write(dir.path().join("math.nu"), script)?;
+
+    for flags in EVALUATORS {
+        let mut cmd = Command::cargo_bin("engine-q")?;
+        cmd.args(flags);
+        cmd.arg("--test");
+        cmd.arg(dir.path());
+
+        let output = cmd.output()?;
+
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+
+        println!("flags: {:?}", flags);
+        println!("stdout: {}", stdout);
+        println!("stderr: {}", stderr);
+
+        assert_eq!(output.status.code(), Some(expected_code));
+        assert!(stdout.contains(expected_stdout));
+        assert!(stderr.contains(expected_stderr));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_mode_runs_module_tests() -> TestResult {
+    test_mode_test(
+        r#"module math {
+    export def double [x] { $x * 2 }
+    export def "test double" [] { assert equal (double 2) 4 }
+}
+def "test sum" [] { assert equal ([1 2 3] | math sum) 6 }"#,
+        0,
+        "test double",
+        "",
+    )
+}
+
+#[test]
+fn test_mode_reports_failures() -> TestResult {
+    test_mode_test(
+        r#"def "test passes" [] { assert (1 < 2) }
+def "test fails" [] { assert equal (1 + 1) 3 }"#,
+        1,
+        "failed",
+        "right is 3 (int)",
+    )
+}
+
+#[test]
+fn timeout_returns_the_output_in_time() -> TestResult {
+    run_test(r#"timeout 5sec { [1 2 3] | math sum }"#, "6")
+}
+
+#[test]
+fn timeout_stops_a_slow_block() -> TestResult {
+    fail_test(r#"timeout 100ms { sleep 10sec }"#, "Timed out")
+}
+
+#[test]
+fn timeout_stops_a_runaway_each() -> TestResult {
+    fail_test(
+        r#"timeout 100ms { 1..1000000000 | each { $it * 2 } | length }"#,
+        "Timed out",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn timeout_stops_an_external() -> TestResult {
+    let start = std::time::Instant::now();
+
+    fail_test(r#"timeout 200ms { ^sleep 30 }"#, "Timed out")?;
+    fail_test(r#"timeout 200ms { ^sleep 30 | lines }"#, "Timed out")?;
+
+    // A sleep that's left running keeps the output of nu open, so the tests wait for it to end
+    assert!(start.elapsed() < std::time::Duration::from_secs(20));
+
+    Ok(())
+}
+
+#[test]
+fn recursion_limit_stops_infinite_recursion() -> TestResult {
+    fail_test(
+        r#"def forever [n] { forever ($n + 1) }; forever 0"#,
+        "Recursion limit (1024) reached",
+    )
+}
+
+#[test]
+fn recursion_limit_is_configurable() -> TestResult {
+    fail_test(
+        r#"let config = { recursion_limit: 5 }; def down [n] { if $n == 0 { 0 } else { down ($n - 1) } }; down 10"#,
+        "Recursion limit (5) reached",
+    )
+}
+
+#[test]
+fn recursion_within_the_limit() -> TestResult {
+    run_test(
+        r#"def down [n] { if $n == 0 { "done" } else { down ($n - 1) } }; down 1000"#,
+        "done",
+    )
+}
+
+#[test]
+fn error_make_fails_with_the_message() -> TestResult {
+    fail_test(r#"error make {msg: "my custom error"}"#, "my custom error")
+}
+
+#[test]
+fn error_make_shows_the_label_and_help() -> TestResult {
+    fail_test(
+        r#"def check [] { error make {msg: "bad input", label: {text: "caused by this"}, help: "try another input"} }; check"#,
+        "try another input",
+    )
+}
+
+#[test]
+fn error_make_needs_a_message() -> TestResult {
+    fail_test(
+        r#"error make {help: "no message"}"#,
+        "msg in the error record",
+    )
+}
+
+#[test]
+fn try_returns_the_output_without_errors() -> TestResult {
+    run_test(r#"try { 1 + 2 } catch { |err| "failed" }"#, "3")
+}
+
+#[test]
+fn try_catches_error_make() -> TestResult {
+    run_test(
+        r#"try { error make {msg: "oops", help: "retry"} } catch { |err| $"($err.msg), ($err.help)" }"#,
+        "oops, retry",
+    )
+}
+
+#[test]
+fn try_catches_builtin_errors() -> TestResult {
+    run_test(
+        r#"try { [1 2] | get 5 } catch { |err| $err.labels | length }"#,
+        "1",
+    )
+}
+
+#[test]
+fn try_tells_which_error_came_out_of_a_custom_command() -> TestResult {
+    run_test(
+        r#"def divide [x] { 1 / $x }; def outer [] { divide 0 }; try { outer } catch { |err| $"($err.code), ($err.trace | length)" }"#,
+        "nu::shell::division_by_zero, 2",
+    )
+}
+
+#[test]
+fn try_catches_errors_in_a_stream() -> TestResult {
+    run_test(
+        r#"try { [1 0] | each { 1 / $it } } catch { |err| $err.msg } | last"#,
+        "Division by zero.",
+    )
+}
+
+#[test]
+fn try_keeps_the_items_before_an_error_in_a_stream() -> TestResult {
+    run_test(
+        r#"try { [1 2 0 4] | each { 4 / $it } } catch { |err| 0 } | math sum"#,
+        "6",
+    )
+}
+
+#[test]
+fn try_keeps_the_output_streaming() -> TestResult {
+    run_test(r#"try { 1.. | each { $it } } | first 3 | length"#, "3")
+}
+
+#[test]
+fn try_without_catch_ignores_the_error() -> TestResult {
+    run_test(r#"try { 1 / 0 }; "after""#, "after")
+}
+
+#[test]
+fn error_make_points_at_a_caught_span() -> TestResult {
+    run_test(
+        r#"try { try { 1 / 0 } catch { |err| error make {msg: "rethrown", label: {text: "here", start: $err.span.start, end: $err.span.end}} } } catch { |err| $err.labels.0.text }"#,
+        "here",
+    )
+}
+
+#[test]
+fn assert_error_catches_error_make() -> TestResult {
+    run_test(
+        r#"assert error { error make {msg: "expected"} }; "ok""#,
+        "ok",
+    )
+}
+
+#[test]
+fn error_labels_the_custom_command_calls() -> TestResult {
+    fail_test(
+        r#"def inner [] { 1 / 0 }; def outer [] { inner }; outer"#,
+        "called from here",
+    )
+}
+
+#[test]
+fn error_from_custom_command_keeps_its_message() -> TestResult {
+    run_test(
+        r#"def inner [] { 1 / 0 }; def outer [] { inner }; try { outer } catch { |err| $err.msg }"#,
+        "Division by zero.",
+    )
+}
+
+#[test]
+fn error_record_has_the_trace() -> TestResult {
+    run_test(
+        r#"def inner [] { 1 / 0 }; def outer [] { inner }; try { outer } catch { |err| $err.trace | length }"#,
+        "2",
+    )
+}
+
+#[test]
+fn error_record_trace_points_at_the_calls() -> TestResult {
+    run_test(
+        r#"def inner [] { 1 / 0 }; try { inner } catch { |err| ($err.trace.0.end - $err.trace.0.start) }"#,
+        "5",
+    )
+}
+
+#[test]
+fn warns_about_unused_variable() -> TestResult {
+    warn_test("def foo [] { let x = 1; 2 }; foo", "2", "`x` is never used")
+}
+
+#[test]
+fn doesnt_warn_about_used_or_underscore_variables() -> TestResult {
+    let mut file = NamedTempFile::new()?;
+    writeln!(
+        file,
+        "def foo [] {{ let _x = 1; let y = 2; do {{ $y }} }}; foo"
+    )?;
+    let output = Command::cargo_bin("engine-q")?.arg(file.path()).output()?;
+
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).is_empty());
+    Ok(())
+}
+
+#[test]
+fn doesnt_warn_about_top_level_variable() -> TestResult {
+    let mut file = NamedTempFile::new()?;
+    writeln!(file, "let x = 1; 2")?;
+    let output = Command::cargo_bin("engine-q")?.arg(file.path()).output()?;
+
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).is_empty());
+    Ok(())
+}
+
+#[test]
+fn warns_about_shadowed_definition() -> TestResult {
+    warn_test(
+        r#"def length [] { "mine" }; length"#,
+        "mine",
+        "hides the `length` command",
+    )
+}
+
+#[test]
+fn warns_about_definition_shadowing_outer_one() -> TestResult {
+    warn_test(
+        "def foo [] { 1 }; def bar [] { def foo [] { 2 }; foo }; bar",
+        "2",
+        "hides the `foo` command",
+    )
+}
+
+#[test]
+fn warns_about_code_after_exit() -> TestResult {
+    warn_test("def foo [] { exit 0; 1 }; 2", "2", "this code is never run")
+}
+
+#[test]
+fn deny_warnings_fails_before_running() -> TestResult {
+    let mut file = NamedTempFile::new()?;
+    writeln!(file, r#"def foo [] {{ let x = 1; 2 }}; "ran""#)?;
+
+    for flags in EVALUATORS {
+        let output = Command::cargo_bin("engine-q")?
+            .arg("--deny-warnings")
+            .args(flags)
+            .arg(file.path())
+            .output()?;
+
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        println!("stderr: {}", stderr);
+
+        assert_eq!(output.status.code(), Some(1));
+        assert!(stderr.contains("Error:"));
+        assert!(stderr.contains("`x` is never used"));
+        assert!(!String::from_utf8_lossy(&output.stdout).contains("ran"));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn fmt_formats_the_script() -> TestResult {
+    let mut file = NamedTempFile::new()?;
+    write!(file, "def foo [] {{\n  ls   |  length\n}}")?;
+
+    let output = Command::cargo_bin("engine-q")?
+        .arg("--fmt")
+        .arg(file.path())
+        .output()?;
+
+    assert!(output.status.success());
+    assert_eq!(
+        std::fs::read_to_string(file.path())?,
+        "def foo [] {\n    ls | length\n}\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn fmt_check_leaves_the_script_alone() -> TestResult {
+    let mut file = NamedTempFile::new()?;
+    write!(file, "ls   |  length")?;
+
+    let output = Command::cargo_bin("engine-q")?
+        .args(["--fmt", "--check"])
+        .arg(file.path())
+        .output()?;
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("isn't formatted"));
+    assert_eq!(std::fs::read_to_string(file.path())?, "ls   |  length");
+    Ok(())
+}
+
+#[test]
+fn fmt_doesnt_touch_scripts_that_dont_parse() -> TestResult {
+    let mut file = NamedTempFile::new()?;
+    write!(file, "def foo [] {{  $nope }}")?;
+
+    let output = Command::cargo_bin("engine-q")?
+        .arg("--fmt")
+        .arg(file.path())
+        .output()?;
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        std::fs::read_to_string(file.path())?,
+        "def foo [] {  $nope }"
+    );
+    Ok(())
+}
+
+#[test]
+fn fmt_refuses_a_formatting_that_changes_the_script() {
+    let engine_state = nu_command::create_default_context();
+    let mut working_set = nu_protocol::engine::StateWorkingSet::new(&engine_state);
+    let (block, _) = nu_parser::parse(&mut working_set, None, b"ls | length", false);
+    let shapes = crate::flat_shapes(&working_set, &block);
+
+    assert!(crate::check_formatted(&engine_state, "script", "ls | length\n", &shapes).is_ok());
+    assert!(crate::check_formatted(&engine_state, "script", "ls length\n", &shapes).is_err());
+    assert!(crate::check_formatted(&engine_state, "script", "ls | (\n", &shapes).is_err());
+}
+
+#[test]
+fn lint_passes_clean_script() -> TestResult {
+    let mut file = NamedTempFile::new()?;
+    writeln!(file, "def foo [] {{ 1 }}")?;
+
+    let output = Command::cargo_bin("engine-q")?
+        .arg("--lint")
+        .arg(file.path())
+        .output()?;
+
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+    Ok(())
+}
+
+#[test]
+fn lint_reports_warnings_and_style_without_running() -> TestResult {
+    let mut file = NamedTempFile::new()?;
+    writeln!(file, "def foo [] {{ let x = 1; 2 }}  \necho ran")?;
+
+    let output = Command::cargo_bin("engine-q")?
+        .arg("--lint")
+        .arg(file.path())
+        .output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr.contains("`x` is never used"));
+    assert!(stderr.contains("trailing whitespace"));
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("ran"));
+    Ok(())
+}
+
+#[test]
+fn lsp_answers_on_stdin_and_stdout() -> TestResult {
+    let messages = [
+        r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"capabilities":{}}}"#,
+        r#"{"jsonrpc":"2.0","method":"textDocument/didOpen","params":{"textDocument":{"uri":"file:///a.nu","languageId":"nushell","version":1,"text":"ls --nope"}}}"#,
+        r#"{"jsonrpc":"2.0","id":2,"method":"shutdown"}"#,
+        r#"{"jsonrpc":"2.0","method":"exit"}"#,
+    ];
+    let input = messages
+        .iter()
+        .map(|body| format!("Content-Length: {}\r\n\r\n{}", body.len(), body))
+        .collect::<String>();
+
+    let mut child = Command::cargo_bin("engine-q")?
+        .arg("--lsp")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+    child
+        .stdin
+        .take()
+        .ok_or("no stdin")?
+        .write_all(input.as_bytes())?;
+    let output = child.wait_with_output()?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    assert!(stdout.contains(r#""hoverProvider":true"#));
+    assert!(stdout.contains("textDocument/publishDiagnostics"));
+    assert!(stdout.contains(r#""result":null"#));
+    Ok(())
+}
+
+#[test]
+fn let_env_in_if_stays_in_the_branch() -> TestResult {
+    run_test(
+        r#"let-env FOO = 'before'; if $true { let-env FOO = 'then' }; $nu.env.FOO"#,
+        "before",
+    )
+}
+
+// Run the hook block in `source` with the arguments, on a stack with FOO set, and return the stack
+#[cfg(test)]
+fn run_hook(
+    source: &[u8],
+    arguments: Vec<nu_protocol::Value>,
+) -> Result<nu_protocol::engine::Stack, Box<dyn std::error::Error>> {
+    use nu_parser::parse;
+    use nu_protocol::{
+        ast::Statement,
+        engine::{Stack, StateWorkingSet},
+        Record, Span, Value, CONFIG_VARIABLE_ID,
+    };
+
+    let mut engine_state = nu_command::create_default_context();
+    let (block, delta) = {
+        let mut working_set = StateWorkingSet::new(&engine_state);
+        let (block, err) = parse(&mut working_set, None, source, false);
+        assert!(err.is_none());
+        (block, working_set.render())
+    };
+    engine_state.merge_delta(delta)?;
+
+    let block_id = match &block.stmts[0] {
+        Statement::Pipeline(pipeline) => pipeline.expressions[0].as_block(),
+        _ => None,
+    }
+    .ok_or("expected a block")?;
+
+    let span = Span::new(0, 0);
+    let mut stack = Stack::new();
+    stack
+        .vars
+        .insert(CONFIG_VARIABLE_ID, Value::record(Record::new(), span));
+    stack.add_env_var("FOO".into(), Value::string("foo", span));
+
+    crate::eval_hook(&engine_state, &mut stack, block_id, arguments)?;
+
+    Ok(stack)
+}
+
+#[test]
+fn env_change_hook_keeps_the_env_it_sets() -> TestResult {
+    use nu_protocol::{Span, Value};
+
+    let dir = tempfile::tempdir()?;
+    std::fs::write(dir.path().join("rust-toolchain"), "")?;
+    let span = Span::new(0, 0);
+
+    // The example from docs/Environment_Variables.md
+    let stack = run_hook(
+        br#"{|before, after| let-env RUSTUP_TOOLCHAIN = (if ($after | path join rust-toolchain | path exists) { "nightly" } else { "stable" }) }"#,
+        vec![
+            Value::nothing(span),
+            Value::string(dir.path().to_string_lossy(), span),
+        ],
+    )?;
+
+    assert_eq!(
+        stack
+            .get_env_var("RUSTUP_TOOLCHAIN")
+            .ok_or("RUSTUP_TOOLCHAIN wasn't set")?
+            .as_string()?,
+        "nightly"
+    );
+    Ok(())
+}
+
+#[test]
+fn hook_keeps_the_env_it_hides() -> TestResult {
+    let stack = run_hook(b"{ hide FOO }", vec![])?;
+
+    assert!(stack.get_env_var("FOO").is_none());
+    Ok(())
+}
+
+#[test]
+fn hook_leaves_the_env_set_in_nested_blocks() -> TestResult {
+    let stack = run_hook(b"{ if $true { let-env FOO = 'bar' } }", vec![])?;
+
+    assert_eq!(
+        stack.get_env_var("FOO").ok_or("FOO is gone")?.as_string()?,
+        "foo"
+    );
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use nu_test_support::playground::{Playground, Stub::EmptyFile};
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn lists_regular_files() {
+    Playground::setup("ls_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![
+            EmptyFile("yehuda.txt"),
+            EmptyFile("jonathan.txt"),
+            EmptyFile("andres.txt"),
+        ]);
+
+        let actual = nu!(cwd: dirs.test(), "ls | length");
+
+        assert_eq!(actual.out, "3");
+    })
+}
+
+#[test]
+fn lists_files_matching_a_glob() {
+    Playground::setup("ls_test_2", |dirs, sandbox| {
+        sandbox.with_files(vec![
+            EmptyFile("yehuda.10.txt"),
+            EmptyFile("jonathan.10.txt"),
+            EmptyFile("andres.10.csv"),
+        ]);
+
+        let actual = nu!(cwd: dirs.test(), "ls *.txt | length");
+
+        assert_eq!(actual.out, "2");
+    })
+}
+
+#[test]
+fn lists_directories_with_their_type() {
+    Playground::setup("ls_test_3", |dirs, sandbox| {
+        sandbox
+            .mkdir("dir_a")
+            .mkdir("dir_b")
+            .with_files(vec![EmptyFile("file.txt")]);
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                ls
+                | where type == dir
+                | get name
+                | str collect ','
+            "#
+        ));
+
+        assert_eq!(actual.out, "dir_a,dir_b");
+    })
+}
+
+#[test]
+fn lists_files_in_a_subdirectory() {
+    Playground::setup("ls_test_4", |dirs, sandbox| {
+        sandbox
+            .with_files(vec![EmptyFile("outside.txt")])
+            .within("src")
+            .with_files(vec![EmptyFile("lib.rs"), EmptyFile("main.rs")]);
+
+        let actual = nu!(cwd: dirs.test(), "ls src | length");
+
+        assert_eq!(actual.out, "2");
+    })
+}
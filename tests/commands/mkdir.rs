@@ -0,0 +1,20 @@
+use nu_test_support::nu;
+use nu_test_support::playground::Playground;
+
+#[test]
+fn creates_directory() {
+    Playground::setup("mkdir_test_1", |dirs, _| {
+        nu!(cwd: dirs.test(), "mkdir my_new_directory");
+
+        assert!(dirs.test().join("my_new_directory").is_dir());
+    })
+}
+
+#[test]
+fn creates_intermediary_directories() {
+    Playground::setup("mkdir_test_2", |dirs, _| {
+        nu!(cwd: dirs.test(), "mkdir some_folder/another/deeper_one");
+
+        assert!(dirs.test().join("some_folder/another/deeper_one").is_dir());
+    })
+}
@@ -0,0 +1,4 @@
+mod ls;
+mod mkdir;
+mod rm;
+mod touch;
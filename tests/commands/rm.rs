@@ -0,0 +1,28 @@
+use nu_test_support::nu;
+use nu_test_support::playground::{Playground, Stub::EmptyFile};
+
+#[test]
+fn removes_a_file() {
+    Playground::setup("rm_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![EmptyFile("i_will_be_deleted.txt")]);
+
+        nu!(cwd: dirs.test(), "rm i_will_be_deleted.txt");
+
+        assert!(!dirs.test().join("i_will_be_deleted.txt").exists());
+    })
+}
+
+#[test]
+fn removes_files_in_a_subdirectory() {
+    Playground::setup("rm_test_2", |dirs, sandbox| {
+        sandbox
+            .within("src")
+            .with_files(vec![EmptyFile("a.rs"), EmptyFile("b.rs")]);
+
+        nu!(cwd: dirs.test(), "rm src/*.rs");
+
+        assert!(!dirs.test().join("src/a.rs").exists());
+        assert!(!dirs.test().join("src/b.rs").exists());
+        assert!(dirs.test().join("src").is_dir());
+    })
+}
@@ -0,0 +1,11 @@
+use nu_test_support::nu;
+use nu_test_support::playground::Playground;
+
+#[test]
+fn creates_a_file() {
+    Playground::setup("touch_test_1", |dirs, _| {
+        nu!(cwd: dirs.test(), "touch i_will_be_created.txt");
+
+        assert!(dirs.test().join("i_will_be_created.txt").is_file());
+    })
+}
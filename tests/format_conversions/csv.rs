@@ -0,0 +1,64 @@
+use nu_test_support::playground::{Playground, Stub::FileWithContentToBeTrimmed};
+use nu_test_support::{nu, pipeline};
+
+#[cfg(not(windows))]
+#[test]
+fn from_csv_text_to_table() {
+    Playground::setup("filter_from_csv_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "los_tres_caballeros.txt",
+            r#"
+                first_name,last_name,rusty_luck
+                Andrés,Robalino,1
+                Jonathan,Turner,1
+                Yehuda,Katz,1
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                ^cat los_tres_caballeros.txt
+                | from csv
+                | get rusty_luck
+                | length
+            "#
+        ));
+
+        assert_eq!(actual.out, "3");
+    })
+}
+
+#[cfg(not(windows))]
+#[test]
+fn from_csv_fixture() {
+    let actual = nu!(
+        cwd: nu_test_support::fs::fixtures().join("formats"), pipeline(
+        r#"
+            ^cat sample.csv
+            | from csv
+            | where units > 40
+            | get item
+            | str collect ','
+        "#
+    ));
+
+    assert_eq!(actual.out, "pencil,binder");
+}
+
+#[test]
+fn table_to_csv_text() {
+    Playground::setup("filter_to_csv_test_1", |dirs, _| {
+        let actual = nu!(
+            cwd: dirs.test(), pipeline(
+            r#"
+                [[name, luck]; [Andrés, 1], [Yehuda, 2]]
+                | to csv
+                | lines
+                | get 1
+            "#
+        ));
+
+        assert_eq!(actual.out, "Andrés,1");
+    })
+}
@@ -0,0 +1,33 @@
+use nu_test_support::{nu, pipeline};
+
+#[cfg(not(windows))]
+#[test]
+fn from_json_fixture() {
+    let actual = nu!(
+        cwd: nu_test_support::fs::fixtures().join("formats"), pipeline(
+        r#"
+            ^cat sample.json
+            | from json
+            | get orders
+            | where item == pen
+            | get units.0
+        "#
+    ));
+
+    assert_eq!(actual.out, "36");
+}
+
+#[test]
+fn json_roundtrip() {
+    let actual = nu!(
+        cwd: ".", pipeline(
+        r#"
+            {name: nu, tags: [shell, data]}
+            | to json
+            | from json
+            | get tags.1
+        "#
+    ));
+
+    assert_eq!(actual.out, "data");
+}
@@ -0,0 +1,5 @@
+// There is no `open` command yet, so the tests that read sample files get them with `^cat`, and
+// only run where there is one
+mod csv;
+mod json;
+mod xlsx;
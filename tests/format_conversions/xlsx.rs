@@ -0,0 +1,33 @@
+use nu_test_support::{nu, pipeline};
+
+#[cfg(not(windows))]
+#[test]
+fn from_xlsx_fixture() {
+    let actual = nu!(
+        cwd: nu_test_support::fs::fixtures().join("formats"), pipeline(
+        r#"
+            ^cat sample_data.xlsx
+            | from xlsx
+            | get Orders
+            | get 2.Column0
+        "#
+    ));
+
+    assert_eq!(actual.out, "binder");
+}
+
+#[cfg(not(windows))]
+#[test]
+fn from_xlsx_selects_sheets() {
+    let actual = nu!(
+        cwd: nu_test_support::fs::fixtures().join("formats"), pipeline(
+        r#"
+            ^cat sample_data.xlsx
+            | from xlsx -s [Orders]
+            | get Orders
+            | length
+        "#
+    ));
+
+    assert_eq!(actual.out, "4");
+}